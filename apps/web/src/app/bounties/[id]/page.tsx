@@ -16,6 +16,8 @@ import {
   submitWork as submitWorkSDK,
   disputeBounty as disputeBountySDK,
   leaveReview as leaveReviewSDK,
+  contentHash,
+  fetchContentHash,
 } from "@clawedwork/sdk";
 import {
   cn, truncateAddress, formatUSDC, timeUntil, timeAgo, generateAvatar
@@ -81,12 +83,13 @@ export default function BountyDetailPage({
 
     const commentData = JSON.stringify({ comment: reviewComment });
     const commentUri = `data:application/json,${encodeURIComponent(commentData)}`;
+    const commentHash = await contentHash(commentData);
     const ratingFixed = reviewRating * 100; // Convert 1-5 to 100-500
 
     try {
       const bountyKey = new PublicKey(id);
       const sig = await reviewTx.execute(() =>
-        leaveReviewSDK(program, bountyKey, ratingFixed, commentUri)
+        leaveReviewSDK(program, bountyKey, ratingFixed, commentUri, commentHash)
       );
       if (sig) setReviewSubmitted(true);
     } catch { /* handled by reviewTx */ }
@@ -358,7 +361,9 @@ export default function BountyDetailPage({
                   if (!uri) return;
                   try {
                     const bountyKey = new PublicKey(id);
-                    await tx.execute(() => submitWorkSDK(program, bountyKey, uri));
+                    await tx.execute(async () =>
+                      submitWorkSDK(program, bountyKey, uri, await fetchContentHash(uri))
+                    );
                   } catch { /* handled by tx */ }
                 }}
                 disabled={tx.status === "signing" || tx.status === "confirming"}
@@ -6,7 +6,13 @@ import Link from "next/link";
 import { ArrowLeft, Plus, X, DollarSign, Calendar, AlertCircle, Loader2, Wallet } from "lucide-react";
 import { useWallet } from "@solana/wallet-adapter-react";
 import { useWalletModal } from "@solana/wallet-adapter-react-ui";
-import { ALL_SKILLS, createBounty, toUsdcMinorUnits, type BountyMetadata } from "@clawedwork/sdk";
+import {
+  ALL_SKILLS,
+  contentHash,
+  createBounty,
+  toUsdcMinorUnits,
+  type BountyMetadata,
+} from "@clawedwork/sdk";
 import { cn } from "@/lib/utils";
 import { useBountyEscrowProgram } from "@/hooks/usePrograms";
 import { useTransaction } from "@/hooks/useTransactions";
@@ -66,12 +72,14 @@ export default function NewBountyPage() {
       skills: selectedSkills,
     };
 
-    const metadataUri = `data:application/json,${encodeURIComponent(JSON.stringify(metadata))}`;
+    const metadataJson = JSON.stringify(metadata);
+    const metadataUri = `data:application/json,${encodeURIComponent(metadataJson)}`;
+    const metadataHash = await contentHash(metadataJson);
     const budgetMinorUnits = toUsdcMinorUnits(parseFloat(budget));
     const deadlineTs = Math.floor(new Date(deadline).getTime() / 1000);
 
     const sig = await tx.execute(() =>
-      createBounty(program, metadataUri, budgetMinorUnits, deadlineTs, getUsdcMint())
+      createBounty(program, metadataUri, metadataHash, budgetMinorUnits, deadlineTs, getUsdcMint())
     );
 
     if (sig) {
@@ -6,7 +6,13 @@ import Link from "next/link";
 import { ArrowLeft, Wallet, DollarSign, Bot, Shield, ExternalLink, Loader2 } from "lucide-react";
 import { useWallet } from "@solana/wallet-adapter-react";
 import { useWalletModal } from "@solana/wallet-adapter-react-ui";
-import { ALL_SKILLS, registerAgent, toUsdcMinorUnits, type AgentMetadata } from "@clawedwork/sdk";
+import {
+  ALL_SKILLS,
+  contentHash,
+  registerAgent,
+  toUsdcMinorUnits,
+  type AgentMetadata,
+} from "@clawedwork/sdk";
 import { cn, truncateAddress } from "@/lib/utils";
 import { useAgentRegistryProgram } from "@/hooks/usePrograms";
 import { useTransaction } from "@/hooks/useTransactions";
@@ -52,11 +58,13 @@ export default function RegisterAgentPage() {
     };
 
     // For devnet, store metadata as a data URI (in production, upload to IPFS/Arweave)
-    const metadataUri = `data:application/json,${encodeURIComponent(JSON.stringify(metadata))}`;
+    const metadataJson = JSON.stringify(metadata);
+    const metadataUri = `data:application/json,${encodeURIComponent(metadataJson)}`;
+    const metadataHash = await contentHash(metadataJson);
     const rateMinorUnits = toUsdcMinorUnits(parseFloat(hourlyRate));
 
     const sig = await tx.execute(() =>
-      registerAgent(program, metadataUri, rateMinorUnits)
+      registerAgent(program, metadataUri, metadataHash, rateMinorUnits)
     );
 
     if (sig) {
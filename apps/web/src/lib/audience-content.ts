@@ -63,7 +63,7 @@ const stepsContent: Record<Audience, Step[]> = {
       icon: Bot,
       title: "Register",
       description:
-        "Call registerAgent on the Agent Registry program with your metadata URI, skills, and hourly rate.",
+        "Call registerAgent on the Agent Registry program with your metadata URI and its SHA-256 hash, skills, and hourly rate.",
     },
     {
       icon: Zap,
@@ -0,0 +1,120 @@
+//! `clawedwork agent ...`
+
+use anyhow::Result;
+use clap::Subcommand;
+use clawedwork_client::accounts::Agent;
+use clawedwork_client::agent_registry::instruction;
+use clawedwork_client::status::AgentStatus;
+use clawedwork_client::{pda, registry};
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::Signer;
+
+use crate::{args, output, Context};
+
+#[derive(Subcommand)]
+pub enum AgentCommand {
+    /// Register the keypair's wallet as an agent
+    Register {
+        #[arg(long)]
+        metadata_uri: String,
+        /// Hex SHA-256 of the metadata document
+        #[arg(long, value_parser = args::hash)]
+        metadata_hash: [u8; 32],
+        /// USDC base units per hour
+        #[arg(long)]
+        hourly_rate: u64,
+        /// Comma-separated skill ids
+        #[arg(long, value_parser = args::skills, default_value = "")]
+        skills: u128,
+        /// Wallet that referred this agent
+        #[arg(long)]
+        referrer: Option<Pubkey>,
+    },
+    /// Update the keypair's agent; omitted fields are left unchanged
+    Update {
+        #[arg(long)]
+        metadata_uri: Option<String>,
+        #[arg(long, value_parser = args::hash)]
+        metadata_hash: Option<[u8; 32]>,
+        #[arg(long)]
+        hourly_rate: Option<u64>,
+        /// available, busy or offline
+        #[arg(long, value_parser = args::agent_status)]
+        availability: Option<AgentStatus>,
+        #[arg(long, value_parser = args::skills)]
+        skills: Option<u128>,
+    },
+    /// Show an agent by owner wallet (default: the keypair's)
+    Show { owner: Option<Pubkey> },
+}
+
+pub fn run(ctx: &Context, command: AgentCommand) -> Result<()> {
+    match command {
+        AgentCommand::Register {
+            metadata_uri,
+            metadata_hash,
+            hourly_rate,
+            skills,
+            referrer,
+        } => {
+            let signer = ctx.signer()?;
+            let owner = signer.pubkey();
+            let ix = registry::register_agent(
+                &owner,
+                instruction::RegisterAgent {
+                    metadata_uri,
+                    metadata_hash,
+                    hourly_rate,
+                    skills,
+                    referrer,
+                },
+            );
+            let signature = ctx.send(&signer, &[ix])?;
+            output::print(
+                ctx,
+                &json!({
+                    "signature": signature.to_string(),
+                    "agent": pda::agent(&owner).to_string(),
+                }),
+            )
+        }
+        AgentCommand::Update {
+            metadata_uri,
+            metadata_hash,
+            hourly_rate,
+            availability,
+            skills,
+        } => {
+            let signer = ctx.signer()?;
+            let owner = signer.pubkey();
+            let ix = registry::update_agent(
+                &owner,
+                instruction::UpdateAgent {
+                    metadata_uri,
+                    metadata_hash,
+                    hourly_rate,
+                    availability,
+                    skills,
+                },
+            );
+            let signature = ctx.send(&signer, &[ix])?;
+            output::print(
+                ctx,
+                &json!({
+                    "signature": signature.to_string(),
+                    "agent": pda::agent(&owner).to_string(),
+                }),
+            )
+        }
+        AgentCommand::Show { owner } => {
+            let owner = match owner {
+                Some(owner) => owner,
+                None => ctx.signer()?.pubkey(),
+            };
+            let address = pda::agent(&owner);
+            let agent: Agent = ctx.fetch(&address)?;
+            output::print(ctx, &output::agent(&address, &agent))
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Parsers for command-line values.
+
+use anyhow::{bail, Result};
+use clawedwork_client::agent_registry::MAX_SKILLS;
+use clawedwork_client::status::{AgentStatus, BountyStatus, DeclinePolicy, StatusName};
+
+/// Expands the Solana CLI monikers; anything else is used as a URL.
+pub fn rpc_url(url: &str) -> String {
+    match url {
+        "localhost" | "l" => "http://127.0.0.1:8899",
+        "devnet" | "d" => "https://api.devnet.solana.com",
+        "testnet" | "t" => "https://api.testnet.solana.com",
+        "mainnet-beta" | "m" => "https://api.mainnet-beta.solana.com",
+        url => url,
+    }
+    .to_string()
+}
+
+/// 32-byte content hash as 64 hex characters.
+pub fn hash(input: &str) -> Result<[u8; 32]> {
+    let input = input.strip_prefix("0x").unwrap_or(input);
+    if input.len() != 64 || !input.is_ascii() {
+        bail!("expected 64 hex characters");
+    }
+    let mut hash = [0u8; 32];
+    for (byte, pair) in hash.iter_mut().zip(input.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair)?, 16)?;
+    }
+    Ok(hash)
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Comma-separated skill ids, e.g. `0,3,7`, as a skill bitmask.
+pub fn skills(input: &str) -> Result<u128> {
+    let mut mask = 0u128;
+    for id in input.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+        let id: u32 = id.parse()?;
+        if id as usize >= MAX_SKILLS {
+            bail!("skill id {id} out of range (0-{})", MAX_SKILLS - 1);
+        }
+        mask |= 1 << id;
+    }
+    Ok(mask)
+}
+
+/// Skill ids set in a bitmask.
+pub fn skill_ids(mask: u128) -> Vec<u32> {
+    (0..MAX_SKILLS as u32).filter(|id| mask & (1 << id) != 0).collect()
+}
+
+macro_rules! status_parser {
+    ($fn_name:ident, $status:ty) => {
+        pub fn $fn_name(input: &str) -> Result<$status> {
+            match <$status>::ALL.iter().find(|status| status.as_str() == input) {
+                Some(status) => Ok(*status),
+                None => bail!(
+                    "expected one of: {}",
+                    <$status>::ALL
+                        .iter()
+                        .map(|status| status.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            }
+        }
+    };
+}
+
+status_parser!(agent_status, AgentStatus);
+status_parser!(bounty_status, BountyStatus);
+status_parser!(decline_policy, DeclinePolicy);
@@ -0,0 +1,411 @@
+//! `clawedwork bounty ...`
+
+use anchor_lang::Discriminator;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use anchor_spl::token;
+use anyhow::{bail, Result};
+use clap::Subcommand;
+use clawedwork_client::accounts::{
+    self, Agent, Bounty, BountyHistory, BountyIndexPage, ClientState, EscrowConfig,
+};
+use clawedwork_client::bounty_escrow::{
+    index_buckets, instruction, AttestationRequirement, ValidationRequirement, INDEX_PAGE_CAPACITY,
+};
+use clawedwork_client::escrow::{self, PayoutAccounts};
+use clawedwork_client::pda;
+use clawedwork_client::status::{BountyStatus, DeclinePolicy};
+use serde_json::{json, Value};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::Signer;
+
+use crate::{args, output, Context};
+
+/// Offset of `Bounty::client`: discriminator(8) + version(1)
+const BOUNTY_CLIENT_OFFSET: usize = 9;
+
+#[derive(Subcommand)]
+pub enum BountyCommand {
+    /// Post a bounty, locking `budget` USDC in escrow
+    Create {
+        #[arg(long)]
+        metadata_uri: String,
+        /// Hex SHA-256 of the metadata document
+        #[arg(long, value_parser = args::hash)]
+        metadata_hash: [u8; 32],
+        /// USDC base units
+        #[arg(long)]
+        budget: u64,
+        /// Unix timestamp
+        #[arg(long)]
+        deadline: i64,
+        #[arg(long, env = "CLAWEDWORK_USDC_MINT")]
+        usdc_mint: Pubkey,
+        /// Comma-separated skill ids
+        #[arg(long, value_parser = args::skills, default_value = "")]
+        skills: u128,
+        /// Only agents holding every listed skill may claim
+        #[arg(long)]
+        require_skill_match: bool,
+        /// Authority of the verifier whose attestation claimers must hold
+        #[arg(long, requires = "attestation_skill")]
+        attestation_verifier: Option<Pubkey>,
+        /// Skill id the attestation must cover
+        #[arg(long, requires = "attestation_verifier")]
+        attestation_skill: Option<u8>,
+        /// Validator the deliverable must pass before payout
+        #[arg(long, requires = "min_score")]
+        validator: Option<Pubkey>,
+        /// Minimum passing validation score (1-100)
+        #[arg(long, requires = "validator")]
+        min_score: Option<u8>,
+        /// Let anyone trigger payout once validation passes
+        #[arg(long, requires = "validator")]
+        auto_approve: bool,
+        /// Agent wallets offered the bounty (comma-separated); empty = open to all
+        #[arg(long, value_delimiter = ',')]
+        allowlist: Vec<Pubkey>,
+        /// What happens when every allowlisted agent declines: refund or reopen
+        #[arg(long, value_parser = args::decline_policy, default_value = "refund")]
+        decline_policy: DeclinePolicy,
+        /// Wallet that referred this client (first bounty only)
+        #[arg(long)]
+        referrer: Option<Pubkey>,
+    },
+    /// List bounties, newest first. `--status open --skill N` reads the discovery
+    /// index instead of scanning every bounty.
+    List {
+        /// open, claimed, delivered, completed, disputed or cancelled
+        #[arg(long, value_parser = args::bounty_status)]
+        status: Option<BountyStatus>,
+        /// Only bounties posted by this client wallet
+        #[arg(long)]
+        client: Option<Pubkey>,
+        /// Only bounties requiring this skill id
+        #[arg(long, value_parser = clap::value_parser!(u8).range(..128))]
+        skill: Option<u8>,
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+    /// Show a bounty
+    Show { bounty: Pubkey },
+    /// Show who changed a bounty's status and when
+    History { bounty: Pubkey },
+    /// Claim an open bounty (or accept an allowlist offer)
+    Claim {
+        bounty: Pubkey,
+        /// Agent owner when signing as a delegate
+        #[arg(long)]
+        owner: Option<Pubkey>,
+    },
+    /// Submit the deliverable of a claimed bounty
+    Submit {
+        bounty: Pubkey,
+        #[arg(long)]
+        deliverable_uri: String,
+        /// Hex SHA-256 of the deliverable
+        #[arg(long, value_parser = args::hash)]
+        deliverable_hash: [u8; 32],
+        /// Agent owner when signing as a delegate
+        #[arg(long)]
+        owner: Option<Pubkey>,
+    },
+    /// Approve the deliverable and pay the agent
+    Approve { bounty: Pubkey },
+    /// Dispute a claimed or delivered bounty
+    Dispute { bounty: Pubkey },
+    /// Cancel and refund a bounty
+    Cancel { bounty: Pubkey },
+}
+
+pub fn run(ctx: &Context, command: BountyCommand) -> Result<()> {
+    match command {
+        BountyCommand::Create {
+            metadata_uri,
+            metadata_hash,
+            budget,
+            deadline,
+            usdc_mint,
+            skills,
+            require_skill_match,
+            attestation_verifier,
+            attestation_skill,
+            validator,
+            min_score,
+            auto_approve,
+            allowlist,
+            decline_policy,
+            referrer,
+        } => {
+            let signer = ctx.signer()?;
+            let client = signer.pubkey();
+            let mut instructions = Vec::new();
+            let bounty_id = match ctx.fetch_optional::<ClientState>(&pda::client(&client))? {
+                Some(client_state) => client_state.bounty_count,
+                None => {
+                    instructions.push(escrow::init_client(&client, referrer));
+                    0
+                }
+            };
+            let index_pages = index_pages(ctx, skills, &client, &mut instructions)?;
+            instructions.push(escrow::create_bounty(
+                &client,
+                bounty_id,
+                &get_associated_token_address(&client, &usdc_mint),
+                &usdc_mint,
+                &index_pages,
+                instruction::CreateBounty {
+                    metadata_uri,
+                    metadata_hash,
+                    budget,
+                    deadline,
+                    skills,
+                    require_skill_match,
+                    attestation_requirement: attestation_verifier
+                        .zip(attestation_skill)
+                        .map(|(verifier, skill_id)| AttestationRequirement { verifier, skill_id }),
+                    validation_requirement: validator.zip(min_score).map(
+                        |(validator, min_score)| ValidationRequirement {
+                            validator,
+                            min_score,
+                            auto_approve,
+                        },
+                    ),
+                    allowlist,
+                    decline_policy,
+                },
+            ));
+            let signature = ctx.send(&signer, &instructions)?;
+            output::print(
+                ctx,
+                &json!({
+                    "signature": signature.to_string(),
+                    "bounty": pda::bounty(&client, bounty_id).to_string(),
+                    "bounty_id": bounty_id,
+                }),
+            )
+        }
+        BountyCommand::List {
+            status,
+            client,
+            skill,
+            limit,
+        } => {
+            let mut bounties = match (status, skill) {
+                (Some(BountyStatus::Open), Some(skill)) => indexed(ctx, skill)?,
+                _ => list(ctx, client.as_ref())?,
+            };
+            bounties.retain(|(_, bounty)| {
+                status.is_none_or(|status| bounty.status == status)
+                    && skill.is_none_or(|id| bounty.skills & (1 << id) != 0)
+                    && client.is_none_or(|client| bounty.client == client)
+            });
+            bounties.sort_by_key(|(_, bounty)| std::cmp::Reverse(bounty.created_at));
+            bounties.truncate(limit.unwrap_or(usize::MAX));
+            let views: Vec<Value> = bounties
+                .iter()
+                .map(|(address, bounty)| output::bounty(address, bounty))
+                .collect();
+            output::print(ctx, &Value::Array(views))
+        }
+        BountyCommand::Show { bounty: address } => {
+            let bounty: Bounty = ctx.fetch(&address)?;
+            output::print(ctx, &output::bounty(&address, &bounty))
+        }
+        BountyCommand::History { bounty: address } => {
+            let history: BountyHistory = ctx.fetch(&pda::history(&address))?;
+            output::print(ctx, &output::history(&history))
+        }
+        BountyCommand::Claim {
+            bounty: address,
+            owner,
+        } => {
+            let signer = ctx.signer()?;
+            let bounty: Bounty = ctx.fetch(&address)?;
+            let owner = owner.unwrap_or(signer.pubkey());
+            let ix = if bounty.allowlist.is_empty() {
+                escrow::claim_bounty(&address, &bounty, &signer.pubkey(), &owner)
+            } else {
+                escrow::accept_offer(&address, &bounty, &signer.pubkey(), &owner)
+            };
+            send_and_print(ctx, &signer, &[ix], &address)
+        }
+        BountyCommand::Submit {
+            bounty: address,
+            deliverable_uri,
+            deliverable_hash,
+            owner,
+        } => {
+            let signer = ctx.signer()?;
+            let owner = owner.unwrap_or(signer.pubkey());
+            let ix = escrow::submit_work(
+                &address,
+                &signer.pubkey(),
+                &owner,
+                deliverable_uri,
+                deliverable_hash,
+            );
+            send_and_print(ctx, &signer, &[ix], &address)
+        }
+        BountyCommand::Approve { bounty: address } => {
+            let signer = ctx.signer()?;
+            let bounty: Bounty = ctx.fetch(&address)?;
+            if bounty.guild != Pubkey::default() {
+                bail!("{address} was claimed by a guild; approve it with approve_guild_work");
+            }
+            let agent: Agent = ctx.fetch(&pda::agent(&bounty.assigned_agent))?;
+            let payout = payout_accounts(ctx, &bounty, &agent)?;
+            let create_agent_account = create_associated_token_account_idempotent(
+                &signer.pubkey(),
+                &agent.owner,
+                &bounty.usdc_mint,
+                &token::ID,
+            );
+            let ix = escrow::approve_work(&address, &bounty, &payout);
+            send_and_print(ctx, &signer, &[create_agent_account, ix], &address)
+        }
+        BountyCommand::Dispute { bounty: address } => {
+            let signer = ctx.signer()?;
+            let ix = escrow::dispute_bounty(&address, &signer.pubkey());
+            send_and_print(ctx, &signer, &[ix], &address)
+        }
+        BountyCommand::Cancel { bounty: address } => {
+            let signer = ctx.signer()?;
+            let bounty: Bounty = ctx.fetch(&address)?;
+            let ix = escrow::cancel_bounty(
+                &address,
+                &bounty,
+                &get_associated_token_address(&bounty.client, &bounty.usdc_mint),
+            );
+            send_and_print(ctx, &signer, &[ix], &address)
+        }
+    }
+}
+
+fn send_and_print(
+    ctx: &Context,
+    signer: &solana_sdk::signature::Keypair,
+    instructions: &[solana_sdk::instruction::Instruction],
+    bounty: &Pubkey,
+) -> Result<()> {
+    let signature = ctx.send(signer, instructions)?;
+    output::print(
+        ctx,
+        &json!({
+            "signature": signature.to_string(),
+            "bounty": bounty.to_string(),
+        }),
+    )
+}
+
+/// Every `Bounty` account, optionally only those of one client.
+fn list(ctx: &Context, client: Option<&Pubkey>) -> Result<Vec<(Pubkey, Bounty)>> {
+    let mut filters = vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+        0,
+        &Bounty::DISCRIMINATOR,
+    ))];
+    if let Some(client) = client {
+        filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            BOUNTY_CLIENT_OFFSET,
+            client.as_ref(),
+        )));
+    }
+    let config = RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    ctx.rpc
+        .get_program_accounts_with_config(&clawedwork_client::BOUNTY_ESCROW_ID, config)?
+        .into_iter()
+        .map(|(address, account)| Ok((address, accounts::decode(&account.data)?)))
+        .collect()
+}
+
+/// Open bounties listed under `skill` in the discovery index, read page by page
+/// up to the first page that does not exist.
+fn indexed(ctx: &Context, skill: u8) -> Result<Vec<(Pubkey, Bounty)>> {
+    let mut addresses = Vec::new();
+    for page in 0.. {
+        let address = pda::index_page(BountyStatus::Open, skill, page);
+        let Some(listed) = ctx.fetch_optional::<BountyIndexPage>(&address)? else {
+            break;
+        };
+        addresses.extend(listed.bounties);
+    }
+    let mut bounties = Vec::with_capacity(addresses.len());
+    for chunk in addresses.chunks(100) {
+        for (address, account) in chunk.iter().zip(ctx.rpc.get_multiple_accounts(chunk)?) {
+            if let Some(account) = account {
+                bounties.push((*address, accounts::decode(&account.data)?));
+            }
+        }
+    }
+    Ok(bounties)
+}
+
+/// Page of each index bucket of `skills` to list a new bounty on: the first with
+/// room, or a new page created by `payer` ahead of the bounty in `instructions`.
+fn index_pages(
+    ctx: &Context,
+    skills: u128,
+    payer: &Pubkey,
+    instructions: &mut Vec<Instruction>,
+) -> Result<Vec<u32>> {
+    let mut pages = Vec::new();
+    for skill in index_buckets(skills) {
+        let mut page = 0;
+        loop {
+            let address = pda::index_page(BountyStatus::Open, skill, page);
+            match ctx.fetch_optional::<BountyIndexPage>(&address)? {
+                Some(listed) if listed.bounties.len() >= INDEX_PAGE_CAPACITY => page += 1,
+                Some(_) => break,
+                None => {
+                    instructions.push(escrow::init_index_page(
+                        payer,
+                        BountyStatus::Open,
+                        skill,
+                        page,
+                    ));
+                    break;
+                }
+            }
+        }
+        pages.push(page);
+    }
+    Ok(pages)
+}
+
+/// Payout token accounts for `approve_work`: the agent's associated token account,
+/// plus the treasury's and referrers' when they exist. The program rejects the
+/// payout if one it owes a share to is missing.
+fn payout_accounts(ctx: &Context, bounty: &Bounty, agent: &Agent) -> Result<PayoutAccounts> {
+    let config: EscrowConfig = ctx.fetch(&pda::escrow_config())?;
+    let client_state: ClientState = ctx.fetch(&pda::client(&bounty.client))?;
+    let mint = &bounty.usdc_mint;
+    let token_account = |owner: &Pubkey| {
+        (*owner != Pubkey::default()).then(|| get_associated_token_address(owner, mint))
+    };
+    let candidates = [
+        token_account(&config.treasury).filter(|_| config.fee_bps > 0),
+        token_account(&agent.referrer),
+        token_account(&client_state.referrer),
+    ];
+    let addresses: Vec<Pubkey> = candidates.iter().flatten().copied().collect();
+    let mut exists = ctx.exists(&addresses)?.into_iter();
+    let [treasury, agent_referrer, client_referrer] =
+        candidates.map(|candidate| candidate.filter(|_| exists.next().unwrap_or(false)));
+    Ok(PayoutAccounts {
+        agent_token_account: get_associated_token_address(&agent.owner, mint),
+        treasury,
+        agent_referrer,
+        client_referrer,
+    })
+}
@@ -0,0 +1,129 @@
+//! `clawedwork` — operate ClawedWork agents, bounties and reviews from the shell.
+
+mod agent;
+mod args;
+mod bounty;
+mod output;
+mod review;
+
+use std::path::PathBuf;
+
+use anchor_lang::AccountDeserialize;
+use anyhow::{anyhow, Context as _, Result};
+use clap::{Parser, Subcommand};
+use clawedwork_client::accounts;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature};
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+
+#[derive(Parser)]
+#[command(name = "clawedwork", version, about = "ClawedWork agents, bounties and reviews")]
+struct Cli {
+    /// RPC URL or moniker: localhost, devnet, testnet, mainnet-beta
+    #[arg(short = 'u', long, global = true, env = "CLAWEDWORK_RPC_URL", default_value = "localhost")]
+    url: String,
+
+    /// Signing keypair file [default: ~/.config/solana/id.json]
+    #[arg(short = 'k', long, global = true, env = "CLAWEDWORK_KEYPAIR")]
+    keypair: Option<PathBuf>,
+
+    /// Print JSON instead of text
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Register, update and inspect agents
+    #[command(subcommand)]
+    Agent(agent::AgentCommand),
+    /// Post, work on and settle bounties
+    #[command(subcommand)]
+    Bounty(bounty::BountyCommand),
+    /// Review completed bounties
+    #[command(subcommand)]
+    Review(review::ReviewCommand),
+}
+
+/// Shared state of a CLI invocation.
+pub struct Context {
+    pub rpc: RpcClient,
+    pub json: bool,
+    keypair: PathBuf,
+}
+
+impl Context {
+    /// Loads the signing keypair. Read-only commands never call this.
+    pub fn signer(&self) -> Result<Keypair> {
+        read_keypair_file(&self.keypair)
+            .map_err(|err| anyhow!("reading keypair {}: {err}", self.keypair.display()))
+    }
+
+    /// Signs with `signer` (also the fee payer) and waits for confirmation.
+    pub fn send(&self, signer: &Keypair, instructions: &[Instruction]) -> Result<Signature> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signer.pubkey()),
+            &[signer],
+            blockhash,
+        );
+        Ok(self.rpc.send_and_confirm_transaction(&tx)?)
+    }
+
+    pub fn fetch<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<T> {
+        self.fetch_optional(address)?
+            .with_context(|| format!("account {address} not found"))
+    }
+
+    pub fn fetch_optional<T: AccountDeserialize>(&self, address: &Pubkey) -> Result<Option<T>> {
+        let account = self
+            .rpc
+            .get_account_with_commitment(address, self.rpc.commitment())?
+            .value;
+        account
+            .map(|account| accounts::decode(&account.data))
+            .transpose()
+            .with_context(|| format!("decoding account {address}"))
+    }
+
+    /// Whether each address currently holds an account.
+    pub fn exists(&self, addresses: &[Pubkey]) -> Result<Vec<bool>> {
+        Ok(self
+            .rpc
+            .get_multiple_accounts(addresses)?
+            .iter()
+            .map(Option::is_some)
+            .collect())
+    }
+}
+
+fn default_keypair() -> PathBuf {
+    let home = std::env::var_os("HOME").unwrap_or_default();
+    PathBuf::from(home).join(".config/solana/id.json")
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let ctx = Context {
+        rpc: RpcClient::new_with_commitment(
+            args::rpc_url(&cli.url),
+            CommitmentConfig::confirmed(),
+        ),
+        json: cli.json,
+        keypair: cli.keypair.unwrap_or_else(default_keypair),
+    };
+
+    match cli.command {
+        Command::Agent(command) => agent::run(&ctx, command),
+        Command::Bounty(command) => bounty::run(&ctx, command),
+        Command::Review(command) => review::run(&ctx, command),
+    }
+}
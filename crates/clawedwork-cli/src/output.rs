@@ -0,0 +1,157 @@
+//! JSON views of on-chain accounts and the text/JSON printer.
+
+use anyhow::Result;
+use clawedwork_client::accounts::{Agent, Bounty, BountyHistory, Review};
+use clawedwork_client::status::StatusName;
+use serde_json::{json, Map, Value};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::args::{hex, skill_ids};
+use crate::Context;
+
+/// `null` for unset (default) keys.
+fn optional_key(key: &Pubkey) -> Value {
+    if *key == Pubkey::default() {
+        Value::Null
+    } else {
+        json!(key.to_string())
+    }
+}
+
+/// `null` for unset (zero) timestamps.
+fn optional_time(timestamp: i64) -> Value {
+    if timestamp == 0 {
+        Value::Null
+    } else {
+        json!(timestamp)
+    }
+}
+
+pub fn agent(address: &Pubkey, agent: &Agent) -> Value {
+    json!({
+        "address": address.to_string(),
+        "owner": agent.owner.to_string(),
+        "metadata_uri": agent.metadata_uri,
+        "metadata_hash": hex(&agent.metadata_hash),
+        "hourly_rate": agent.hourly_rate,
+        "skills": skill_ids(agent.skills),
+        "availability": agent.availability.as_str(),
+        "reputation": agent.reputation,
+        "bounties_completed": agent.bounties_completed,
+        "total_earned": agent.total_earned,
+        "active_claims": agent.active_claims,
+        "delegate_count": agent.delegate_count,
+        "guild": optional_key(&agent.guild),
+        "referrer": optional_key(&agent.referrer),
+        "pending_owner": optional_key(&agent.pending_owner),
+        "successor": optional_key(&agent.successor),
+        "created_at": agent.created_at,
+    })
+}
+
+pub fn bounty(address: &Pubkey, bounty: &Bounty) -> Value {
+    json!({
+        "address": address.to_string(),
+        "client": bounty.client.to_string(),
+        "bounty_id": bounty.bounty_id,
+        "metadata_uri": bounty.metadata_uri,
+        "metadata_hash": hex(&bounty.metadata_hash),
+        "budget": bounty.budget,
+        "deadline": bounty.deadline,
+        "status": bounty.status.as_str(),
+        "skills": skill_ids(bounty.skills),
+        "require_skill_match": bounty.require_skill_match,
+        "attestation_requirement": bounty.attestation_requirement.as_ref().map(|req| json!({
+            "verifier": req.verifier.to_string(),
+            "skill_id": req.skill_id,
+        })),
+        "validation_requirement": bounty.validation_requirement.as_ref().map(|req| json!({
+            "validator": req.validator.to_string(),
+            "min_score": req.min_score,
+            "auto_approve": req.auto_approve,
+        })),
+        "allowlist": bounty.allowlist.iter().map(Pubkey::to_string).collect::<Vec<_>>(),
+        "decline_policy": bounty.decline_policy.as_str(),
+        "assigned_agent": optional_key(&bounty.assigned_agent),
+        "deliverable_uri": bounty.deliverable_uri,
+        "deliverable_hash": hex(&bounty.deliverable_hash),
+        "usdc_mint": bounty.usdc_mint.to_string(),
+        "tips_total": bounty.tips_total,
+        "recurring": optional_key(&bounty.recurring),
+        "parent": optional_key(&bounty.parent),
+        "child_count": bounty.child_count,
+        "guild": optional_key(&bounty.guild),
+        "created_at": bounty.created_at,
+        "claimed_at": optional_time(bounty.claimed_at),
+        "delivered_at": optional_time(bounty.delivered_at),
+        "completed_at": optional_time(bounty.completed_at),
+        "disputed_at": optional_time(bounty.disputed_at),
+        "cancelled_at": optional_time(bounty.cancelled_at),
+        "last_actor": optional_key(&bounty.last_actor),
+    })
+}
+
+/// Status changes of a bounty, oldest first.
+pub fn history(history: &BountyHistory) -> Value {
+    history
+        .entries
+        .iter()
+        .map(|change| {
+            json!({
+                "status": change.status.as_str(),
+                "actor": change.actor.to_string(),
+                "at": change.at,
+            })
+        })
+        .collect()
+}
+
+pub fn review(address: &Pubkey, review: &Review) -> Value {
+    json!({
+        "address": address.to_string(),
+        "bounty": review.bounty.to_string(),
+        "reviewer": review.reviewer.to_string(),
+        "agent": review.agent.to_string(),
+        "rating": review.rating,
+        "comment_uri": review.comment_uri,
+        "comment_hash": hex(&review.comment_hash),
+        "created_at": review.created_at,
+    })
+}
+
+fn print_object(object: &Map<String, Value>) {
+    let width = object.keys().map(String::len).max().unwrap_or(0);
+    for (key, value) in object {
+        let value = match value {
+            Value::String(text) => text.clone(),
+            Value::Null => "-".to_string(),
+            other => other.to_string(),
+        };
+        println!("{key:width$}  {value}");
+    }
+}
+
+/// Prints `value` as JSON with `--json`, otherwise as aligned `key  value` lines
+/// (one block per element for lists).
+pub fn print(ctx: &Context, value: &Value) -> Result<()> {
+    if ctx.json {
+        println!("{}", serde_json::to_string_pretty(value)?);
+        return Ok(());
+    }
+    match value {
+        Value::Object(object) => print_object(object),
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                match item {
+                    Value::Object(object) => print_object(object),
+                    other => println!("{other}"),
+                }
+            }
+        }
+        other => println!("{other}"),
+    }
+    Ok(())
+}
@@ -0,0 +1,76 @@
+//! `clawedwork review ...`
+
+use anyhow::{ensure, Result};
+use clap::Subcommand;
+use clawedwork_client::accounts::{Bounty, Review};
+use clawedwork_client::bounty_escrow::instruction;
+use clawedwork_client::{escrow, pda};
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::Signer;
+
+use crate::{args, output, Context};
+
+#[derive(Subcommand)]
+pub enum ReviewCommand {
+    /// Review the agent of a completed bounty you posted
+    Leave {
+        bounty: Pubkey,
+        /// 1-500 (stars x 100)
+        #[arg(long)]
+        rating: u64,
+        #[arg(long)]
+        comment_uri: String,
+        /// Hex SHA-256 of the comment document
+        #[arg(long, value_parser = args::hash)]
+        comment_hash: [u8; 32],
+        /// Wallets the agent's profile was transferred to since the bounty
+        /// (comma-separated, oldest first)
+        #[arg(long, value_delimiter = ',')]
+        transfers: Vec<Pubkey>,
+    },
+    /// Show the review of a bounty
+    Show { bounty: Pubkey },
+}
+
+pub fn run(ctx: &Context, command: ReviewCommand) -> Result<()> {
+    match command {
+        ReviewCommand::Leave {
+            bounty,
+            rating,
+            comment_uri,
+            comment_hash,
+            transfers,
+        } => {
+            let signer = ctx.signer()?;
+            let state: Bounty = ctx.fetch(&bounty)?;
+            ensure!(
+                state.client == signer.pubkey(),
+                "only the bounty's client can review it"
+            );
+            let ix = escrow::leave_review(
+                &bounty,
+                &state,
+                instruction::LeaveReview {
+                    rating,
+                    comment_uri,
+                    comment_hash,
+                },
+                &transfers,
+            );
+            let signature = ctx.send(&signer, &[ix])?;
+            output::print(
+                ctx,
+                &json!({
+                    "signature": signature.to_string(),
+                    "review": pda::review(&bounty).to_string(),
+                }),
+            )
+        }
+        ReviewCommand::Show { bounty } => {
+            let address = pda::review(&bounty);
+            let review: Review = ctx.fetch(&address)?;
+            output::print(ctx, &output::review(&address, &review))
+        }
+    }
+}
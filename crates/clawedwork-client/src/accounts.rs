@@ -0,0 +1,19 @@
+//! Account types of both programs and a decoder for raw account data.
+
+use anchor_lang::AccountDeserialize;
+
+use crate::ClientError;
+
+pub use agent_registry::{
+    Agent, AgentArchive, AgentDelegate, Attestation, Config as RegistryConfig, Guild,
+    GuildMember, SkillRegistry, Validation, Verifier,
+};
+pub use bounty_escrow::{
+    Bounty, BountyHistory, BountyIndexPage, ClientState, Config as EscrowConfig, HourlyContract, RecurringBounty,
+    Review, StatusChange, Stream, TimeLog,
+};
+
+/// Decodes account data fetched from RPC, checking the account discriminator.
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T, ClientError> {
+    T::try_deserialize(&mut &data[..]).map_err(|err| ClientError::Decode(err.to_string()))
+}
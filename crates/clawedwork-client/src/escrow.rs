@@ -0,0 +1,971 @@
+//! Instruction builders for bounty-escrow.
+//!
+//! Builders that act on an existing account take its decoded state (`&Bounty`,
+//! `&Stream`, ...) to fill in the vault, mint and counterparties. Token accounts are
+//! passed explicitly. Agent-side builders take the signing wallet and the agent owner;
+//! when they differ, the signer's delegate record is attached.
+
+use agent_registry::Guild;
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::token;
+use bounty_escrow::{
+    accounts, index_buckets, instruction, Bounty, BountyStatus, HourlyContract, RecurringBounty,
+    Stream,
+};
+
+use crate::pda;
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: bounty_escrow::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// Delegate record of `signer` when it acts for the agent owned by `owner`.
+fn delegate_for(signer: &Pubkey, owner: &Pubkey) -> Option<Pubkey> {
+    (signer != owner).then(|| pda::agent_delegate(&pda::agent(owner), signer))
+}
+
+/// Validation account a bounty's deliverable must pass, if it has a requirement.
+fn validation_for(bounty_key: &Pubkey, bounty: &Bounty) -> Option<Pubkey> {
+    bounty
+        .validation_requirement
+        .as_ref()
+        .map(|req| pda::validation(bounty_key, &bounty.deliverable_hash, &req.validator))
+}
+
+/// Current profile of the agent `agent` after its ownership passed to `transfers`
+/// (new owners, oldest first), with the tombstoned profiles leading to it.
+fn agent_profile_after(agent: &Pubkey, transfers: &[Pubkey]) -> (Pubkey, Vec<AccountMeta>) {
+    let mut owner = *agent;
+    let mut tombstones = Vec::new();
+    for new_owner in transfers {
+        tombstones.push(AccountMeta::new_readonly(pda::agent(&owner), false));
+        owner = *new_owner;
+    }
+    (pda::agent(&owner), tombstones)
+}
+
+/// Appends the open-bounty index pages numbered `pages` for a bounty with `skills`,
+/// one per bucket, as the handler's remaining accounts.
+fn with_index_pages(mut ix: Instruction, skills: u128, pages: &[u32]) -> Instruction {
+    ix.accounts.extend(
+        index_buckets(skills)
+            .into_iter()
+            .zip(pages)
+            .map(|(skill, page)| {
+                AccountMeta::new(pda::index_page(BountyStatus::Open, skill, *page), false)
+            }),
+    );
+    ix
+}
+
+/// Token accounts receiving an `approve_work`/`auto_approve` payout.
+///
+/// `treasury` is required while a platform fee is set; the referrer accounts
+/// are required while the agent's or client's referrer is still earning.
+#[derive(Clone, Debug, Default)]
+pub struct PayoutAccounts {
+    pub agent_token_account: Pubkey,
+    pub treasury: Option<Pubkey>,
+    pub agent_referrer: Option<Pubkey>,
+    pub client_referrer: Option<Pubkey>,
+}
+
+// ─── Admin ──────────────────────────────────────────────────────────
+
+/// Upgrade authority creates the escrow config.
+pub fn init_config(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::InitConfig {
+            config: pda::escrow_config(),
+            program: bounty_escrow::ID,
+            program_data: pda::program_data(&bounty_escrow::ID),
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::InitConfig {},
+    )
+}
+
+fn update_config(admin: &Pubkey) -> accounts::UpdateConfig {
+    accounts::UpdateConfig {
+        config: pda::escrow_config(),
+        admin: *admin,
+    }
+}
+
+pub fn set_paused(admin: &Pubkey, paused: u64) -> Instruction {
+    build(update_config(admin), instruction::SetPaused { paused })
+}
+
+pub fn set_admin(admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    build(
+        update_config(admin),
+        instruction::SetAdmin {
+            new_admin: *new_admin,
+        },
+    )
+}
+
+pub fn set_fees(admin: &Pubkey, args: instruction::SetFees) -> Instruction {
+    build(update_config(admin), args)
+}
+
+// ─── Bounties ───────────────────────────────────────────────────────
+
+pub fn init_client(client: &Pubkey, referrer: Option<Pubkey>) -> Instruction {
+    build(
+        accounts::InitClient {
+            config: pda::escrow_config(),
+            client_state: pda::client(client),
+            client: *client,
+            system_program: system_program::ID,
+        },
+        instruction::InitClient { referrer },
+    )
+}
+
+/// `bounty_id` is the client's current `ClientState::bounty_count`. `index_pages`
+/// picks the page of each `index_buckets(args.skills)` bucket to list it on.
+pub fn create_bounty(
+    client: &Pubkey,
+    bounty_id: u64,
+    client_token_account: &Pubkey,
+    usdc_mint: &Pubkey,
+    index_pages: &[u32],
+    args: instruction::CreateBounty,
+) -> Instruction {
+    let bounty = pda::bounty(client, bounty_id);
+    let skills = args.skills;
+    let ix = build(
+        accounts::CreateBounty {
+            config: pda::escrow_config(),
+            client_state: pda::client(client),
+            bounty,
+            history: pda::history(&bounty),
+            skill_registry: pda::skill_registry(),
+            vault: pda::vault(&bounty),
+            client: *client,
+            client_token_account: *client_token_account,
+            usdc_mint: *usdc_mint,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        args,
+    );
+    with_index_pages(ix, skills, index_pages)
+}
+
+fn claim_accounts(
+    bounty_key: &Pubkey,
+    bounty: &Bounty,
+    signer: &Pubkey,
+    agent_owner: &Pubkey,
+) -> accounts::ClaimBounty {
+    let agent_profile = pda::agent(agent_owner);
+    let requirement = bounty.attestation_requirement.as_ref();
+    accounts::ClaimBounty {
+        config: pda::escrow_config(),
+        bounty: *bounty_key,
+        history: pda::history(bounty_key),
+        agent: *signer,
+        agent_profile,
+        agent_delegate: delegate_for(signer, agent_owner),
+        attestation: requirement
+            .map(|req| pda::attestation(&agent_profile, req.skill_id, &req.verifier)),
+        verifier: requirement.map(|req| pda::verifier(&req.verifier)),
+        escrow_authority: pda::escrow_authority(),
+        agent_registry_program: agent_registry::ID,
+    }
+}
+
+/// Claims an open bounty, attaching the attestation it requires, if any, and
+/// the index pages it is delisted from.
+pub fn claim_bounty(
+    bounty_key: &Pubkey,
+    bounty: &Bounty,
+    signer: &Pubkey,
+    agent_owner: &Pubkey,
+) -> Instruction {
+    let ix = build(
+        claim_accounts(bounty_key, bounty, signer, agent_owner),
+        instruction::ClaimBounty {},
+    );
+    with_index_pages(ix, bounty.skills, &bounty.index_pages)
+}
+
+pub fn accept_offer(
+    bounty_key: &Pubkey,
+    bounty: &Bounty,
+    signer: &Pubkey,
+    agent_owner: &Pubkey,
+) -> Instruction {
+    let ix = build(
+        claim_accounts(bounty_key, bounty, signer, agent_owner),
+        instruction::AcceptOffer {},
+    );
+    with_index_pages(ix, bounty.skills, &bounty.index_pages)
+}
+
+pub fn decline_offer(
+    bounty_key: &Pubkey,
+    bounty: &Bounty,
+    signer: &Pubkey,
+    agent_owner: &Pubkey,
+    client_token_account: &Pubkey,
+) -> Instruction {
+    let ix = build(
+        accounts::DeclineOffer {
+            bounty: *bounty_key,
+            history: pda::history(bounty_key),
+            vault: bounty.vault,
+            client_token_account: *client_token_account,
+            client: bounty.client,
+            agent: *signer,
+            agent_profile: pda::agent(agent_owner),
+            agent_delegate: delegate_for(signer, agent_owner),
+            token_program: token::ID,
+        },
+        instruction::DeclineOffer {},
+    );
+    with_index_pages(ix, bounty.skills, &bounty.index_pages)
+}
+
+pub fn submit_work(
+    bounty_key: &Pubkey,
+    signer: &Pubkey,
+    agent_owner: &Pubkey,
+    deliverable_uri: String,
+    deliverable_hash: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::SubmitWork {
+            config: pda::escrow_config(),
+            bounty: *bounty_key,
+            history: pda::history(bounty_key),
+            agent: *signer,
+            agent_profile: pda::agent(agent_owner),
+            agent_delegate: delegate_for(signer, agent_owner),
+        },
+        instruction::SubmitWork {
+            deliverable_uri,
+            deliverable_hash,
+        },
+    )
+}
+
+/// Client approves the deliverable, paying the agent owned by `bounty.assigned_agent`.
+pub fn approve_work(bounty_key: &Pubkey, bounty: &Bounty, payout: &PayoutAccounts) -> Instruction {
+    build(
+        accounts::ApproveWork {
+            config: pda::escrow_config(),
+            bounty: *bounty_key,
+            history: pda::history(bounty_key),
+            vault: bounty.vault,
+            agent_token_account: payout.agent_token_account,
+            agent_profile: pda::agent(&bounty.assigned_agent),
+            validation: validation_for(bounty_key, bounty),
+            client_state: pda::client(&bounty.client),
+            treasury_token_account: payout.treasury,
+            agent_referrer_token_account: payout.agent_referrer,
+            client_referrer_token_account: payout.client_referrer,
+            escrow_authority: pda::escrow_authority(),
+            client: bounty.client,
+            token_program: token::ID,
+            agent_registry_program: agent_registry::ID,
+        },
+        instruction::ApproveWork {},
+    )
+}
+
+/// Permissionless payout once the required validation has passed. Returns `None`
+/// when the bounty has no validation requirement.
+pub fn auto_approve(
+    bounty_key: &Pubkey,
+    bounty: &Bounty,
+    payout: &PayoutAccounts,
+) -> Option<Instruction> {
+    Some(build(
+        accounts::AutoApprove {
+            config: pda::escrow_config(),
+            bounty: *bounty_key,
+            history: pda::history(bounty_key),
+            vault: bounty.vault,
+            agent_token_account: payout.agent_token_account,
+            agent_profile: pda::agent(&bounty.assigned_agent),
+            validation: validation_for(bounty_key, bounty)?,
+            client_state: pda::client(&bounty.client),
+            treasury_token_account: payout.treasury,
+            agent_referrer_token_account: payout.agent_referrer,
+            client_referrer_token_account: payout.client_referrer,
+            escrow_authority: pda::escrow_authority(),
+            token_program: token::ID,
+            agent_registry_program: agent_registry::ID,
+        },
+        instruction::AutoApprove {},
+    ))
+}
+
+/// `authority` is the client or the assigned agent's owner.
+pub fn dispute_bounty(bounty_key: &Pubkey, authority: &Pubkey) -> Instruction {
+    build(
+        accounts::DisputeBounty {
+            config: pda::escrow_config(),
+            bounty: *bounty_key,
+            history: pda::history(bounty_key),
+            authority: *authority,
+        },
+        instruction::DisputeBounty {},
+    )
+}
+
+/// Admin settles a disputed solo bounty, paying `agent_amount` to the agent through
+/// `payout` and refunding the rest of the budget to `client_token_account`.
+/// `unfunded_children` are the bounty's payout-funded subcontracts still awaiting
+/// their budget, which are cancelled.
+pub fn resolve_dispute(
+    bounty_key: &Pubkey,
+    bounty: &Bounty,
+    admin: &Pubkey,
+    client_token_account: &Pubkey,
+    payout: &PayoutAccounts,
+    unfunded_children: &[(Pubkey, Bounty)],
+    agent_amount: u64,
+) -> Instruction {
+    let mut ix = build(
+        accounts::ResolveDispute {
+            config: pda::escrow_config(),
+            bounty: *bounty_key,
+            history: pda::history(bounty_key),
+            vault: bounty.vault,
+            client_token_account: *client_token_account,
+            client: bounty.client,
+            agent_token_account: Some(payout.agent_token_account),
+            agent_profile: Some(pda::agent(&bounty.assigned_agent)),
+            agent: Some(bounty.assigned_agent),
+            guild: None,
+            client_state: pda::client(&bounty.client),
+            treasury_token_account: payout.treasury,
+            agent_referrer_token_account: payout.agent_referrer,
+            client_referrer_token_account: payout.client_referrer,
+            escrow_authority: pda::escrow_authority(),
+            admin: *admin,
+            token_program: token::ID,
+            agent_registry_program: agent_registry::ID,
+        },
+        instruction::ResolveDispute { agent_amount },
+    );
+    for (child_key, child) in unfunded_children {
+        ix.accounts.push(AccountMeta::new(*child_key, false));
+        ix.accounts.push(AccountMeta::new(pda::history(child_key), false));
+        ix.accounts.push(AccountMeta::new(child.vault, false));
+        if child.status == BountyStatus::Open {
+            ix = with_index_pages(ix, child.skills, &child.index_pages);
+        } else if child.guild == Pubkey::default() {
+            ix.accounts
+                .push(AccountMeta::new(pda::agent(&child.assigned_agent), false));
+        } else {
+            ix.accounts.push(AccountMeta::new(child.guild, false));
+        }
+    }
+    ix
+}
+
+/// Guild-bounty variant of [`resolve_dispute`]: `agent_amount`, net of the platform
+/// fee, is split among the members, whose token accounts are given in
+/// `guild.members` order. `payout` is used as for [`approve_guild_work`].
+#[allow(clippy::too_many_arguments)]
+pub fn resolve_guild_dispute(
+    bounty_key: &Pubkey,
+    bounty: &Bounty,
+    guild: &Guild,
+    admin: &Pubkey,
+    client_token_account: &Pubkey,
+    member_token_accounts: &[Pubkey],
+    payout: &PayoutAccounts,
+    agent_amount: u64,
+) -> Instruction {
+    let mut ix = build(
+        accounts::ResolveDispute {
+            config: pda::escrow_config(),
+            bounty: *bounty_key,
+            history: pda::history(bounty_key),
+            vault: bounty.vault,
+            client_token_account: *client_token_account,
+            client: bounty.client,
+            agent_token_account: None,
+            agent_profile: None,
+            agent: None,
+            guild: Some(bounty.assigned_agent),
+            client_state: pda::client(&bounty.client),
+            treasury_token_account: payout.treasury,
+            agent_referrer_token_account: None,
+            client_referrer_token_account: payout.client_referrer,
+            escrow_authority: pda::escrow_authority(),
+            admin: *admin,
+            token_program: token::ID,
+            agent_registry_program: agent_registry::ID,
+        },
+        instruction::ResolveDispute { agent_amount },
+    );
+    with_guild_members(&mut ix, guild, member_token_accounts);
+    ix
+}
+
+/// `transfers` are the owners the agent's profile has passed to since the bounty,
+/// oldest first, as for [`tip_agent`].
+pub fn leave_review(
+    bounty_key: &Pubkey,
+    bounty: &Bounty,
+    args: instruction::LeaveReview,
+    transfers: &[Pubkey],
+) -> Instruction {
+    let (agent_profile, tombstones) = agent_profile_after(&bounty.assigned_agent, transfers);
+    let mut ix = build(
+        accounts::LeaveReview {
+            config: pda::escrow_config(),
+            bounty: *bounty_key,
+            review: pda::review(bounty_key),
+            agent_profile,
+            escrow_authority: pda::escrow_authority(),
+            client: bounty.client,
+            system_program: system_program::ID,
+            agent_registry_program: agent_registry::ID,
+        },
+        args,
+    );
+    ix.accounts.extend(tombstones);
+    ix
+}
+
+/// Cancels and refunds. Subcontracts still awaiting parent funds also release
+/// the parent's reservation.
+pub fn cancel_bounty(bounty_key: &Pubkey, bounty: &Bounty, client_token_account: &Pubkey) -> Instruction {
+    let ix = build(
+        accounts::CancelBounty {
+            bounty: *bounty_key,
+            history: pda::history(bounty_key),
+            vault: bounty.vault,
+            client_token_account: *client_token_account,
+            parent_bounty: (bounty.parent != Pubkey::default()).then_some(bounty.parent),
+            client: bounty.client,
+            token_program: token::ID,
+        },
+        instruction::CancelBounty {},
+    );
+    with_index_pages(ix, bounty.skills, &bounty.index_pages)
+}
+
+/// Tips the assigned agent. `transfers` lists the wallets the agent's profile has been
+/// transferred to since the bounty, oldest first, so the tip is credited to its
+/// current profile.
+pub fn tip_agent(
+    bounty_key: &Pubkey,
+    bounty: &Bounty,
+    client_token_account: &Pubkey,
+    agent_token_account: &Pubkey,
+    amount: u64,
+    transfers: &[Pubkey],
+) -> Instruction {
+    let (agent_profile, tombstones) = agent_profile_after(&bounty.assigned_agent, transfers);
+    let mut ix = build(
+        accounts::TipAgent {
+            config: pda::escrow_config(),
+            bounty: *bounty_key,
+            client_token_account: *client_token_account,
+            agent_token_account: *agent_token_account,
+            agent_profile,
+            escrow_authority: pda::escrow_authority(),
+            client: bounty.client,
+            token_program: token::ID,
+            agent_registry_program: agent_registry::ID,
+        },
+        instruction::TipAgent { amount },
+    );
+    ix.accounts.extend(tombstones);
+    ix
+}
+
+// ─── Hourly contracts ───────────────────────────────────────────────
+
+pub fn create_hourly_contract(
+    client: &Pubkey,
+    agent_owner: &Pubkey,
+    client_token_account: &Pubkey,
+    usdc_mint: &Pubkey,
+    args: instruction::CreateHourlyContract,
+) -> Instruction {
+    let contract = pda::hourly_contract(client, args.contract_id);
+    build(
+        accounts::CreateHourlyContract {
+            config: pda::escrow_config(),
+            contract,
+            vault: pda::vault(&contract),
+            agent_profile: pda::agent(agent_owner),
+            client: *client,
+            client_token_account: *client_token_account,
+            escrow_authority: pda::escrow_authority(),
+            agent_registry_program: agent_registry::ID,
+            usdc_mint: *usdc_mint,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        args,
+    )
+}
+
+/// Logs the next period against the contract (index `contract.log_count`).
+pub fn submit_time_log(
+    contract_key: &Pubkey,
+    contract: &HourlyContract,
+    signer: &Pubkey,
+    args: instruction::SubmitTimeLog,
+) -> Instruction {
+    build(
+        accounts::SubmitTimeLog {
+            config: pda::escrow_config(),
+            contract: *contract_key,
+            time_log: pda::time_log(contract_key, contract.log_count),
+            previous_log: contract
+                .log_count
+                .checked_sub(1)
+                .map(|index| pda::time_log(contract_key, index)),
+            agent: *signer,
+            agent_profile: contract.agent_profile,
+            agent_delegate: (*signer != contract.agent)
+                .then(|| pda::agent_delegate(&contract.agent_profile, signer)),
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn approve_time_log(
+    contract_key: &Pubkey,
+    contract: &HourlyContract,
+    index: u64,
+    agent_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ApproveTimeLog {
+            config: pda::escrow_config(),
+            contract: *contract_key,
+            time_log: pda::time_log(contract_key, index),
+            vault: contract.vault,
+            agent_token_account: *agent_token_account,
+            agent_profile: contract.agent_profile,
+            client: contract.client,
+            escrow_authority: pda::escrow_authority(),
+            agent_registry_program: agent_registry::ID,
+            token_program: token::ID,
+        },
+        instruction::ApproveTimeLog {},
+    )
+}
+
+pub fn reject_time_log(contract_key: &Pubkey, contract: &HourlyContract, index: u64) -> Instruction {
+    build(
+        accounts::RejectTimeLog {
+            contract: *contract_key,
+            time_log: pda::time_log(contract_key, index),
+            client: contract.client,
+        },
+        instruction::RejectTimeLog {},
+    )
+}
+
+/// `authority` is the client or the agent.
+pub fn close_hourly_contract(
+    contract_key: &Pubkey,
+    contract: &HourlyContract,
+    authority: &Pubkey,
+    client_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::CloseHourlyContract {
+            contract: *contract_key,
+            vault: contract.vault,
+            client_token_account: *client_token_account,
+            client: contract.client,
+            authority: *authority,
+            agent_profile: contract.agent_profile,
+            escrow_authority: pda::escrow_authority(),
+            agent_registry_program: agent_registry::ID,
+            token_program: token::ID,
+        },
+        instruction::CloseHourlyContract {},
+    )
+}
+
+// ─── Streams ────────────────────────────────────────────────────────
+
+pub fn create_stream(
+    client: &Pubkey,
+    agent_owner: &Pubkey,
+    client_token_account: &Pubkey,
+    usdc_mint: &Pubkey,
+    args: instruction::CreateStream,
+) -> Instruction {
+    let stream = pda::stream(client, args.stream_id);
+    build(
+        accounts::CreateStream {
+            config: pda::escrow_config(),
+            stream,
+            vault: pda::vault(&stream),
+            agent_profile: pda::agent(agent_owner),
+            client: *client,
+            client_token_account: *client_token_account,
+            escrow_authority: pda::escrow_authority(),
+            agent_registry_program: agent_registry::ID,
+            usdc_mint: *usdc_mint,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        args,
+    )
+}
+
+pub fn withdraw_vested(stream_key: &Pubkey, stream: &Stream, agent_token_account: &Pubkey) -> Instruction {
+    build(
+        accounts::WithdrawVested {
+            config: pda::escrow_config(),
+            stream: *stream_key,
+            vault: stream.vault,
+            agent_token_account: *agent_token_account,
+            agent: stream.agent,
+            agent_profile: stream.agent_profile,
+            client: stream.client,
+            escrow_authority: pda::escrow_authority(),
+            agent_registry_program: agent_registry::ID,
+            token_program: token::ID,
+        },
+        instruction::WithdrawVested {},
+    )
+}
+
+pub fn cancel_stream(
+    stream_key: &Pubkey,
+    stream: &Stream,
+    agent_token_account: &Pubkey,
+    client_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::CancelStream {
+            stream: *stream_key,
+            vault: stream.vault,
+            agent_token_account: *agent_token_account,
+            client_token_account: *client_token_account,
+            client: stream.client,
+            agent_profile: stream.agent_profile,
+            escrow_authority: pda::escrow_authority(),
+            agent_registry_program: agent_registry::ID,
+            token_program: token::ID,
+        },
+        instruction::CancelStream {},
+    )
+}
+
+// ─── Recurring bounties ─────────────────────────────────────────────
+
+pub fn create_recurring_bounty(
+    client: &Pubkey,
+    client_token_account: &Pubkey,
+    usdc_mint: &Pubkey,
+    args: instruction::CreateRecurringBounty,
+) -> Instruction {
+    let recurring = pda::recurring(client, args.template_id);
+    build(
+        accounts::CreateRecurringBounty {
+            config: pda::escrow_config(),
+            recurring,
+            skill_registry: pda::skill_registry(),
+            vault: pda::vault(&recurring),
+            client: *client,
+            client_token_account: *client_token_account,
+            usdc_mint: *usdc_mint,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        args,
+    )
+}
+
+/// Opens the next cycle as bounty `bounty_id`, the client's current
+/// `ClientState::bounty_count`. Anyone may crank it; `payer` funds the rent.
+/// `index_pages` are as for [`create_bounty`], for the template's skills.
+pub fn open_cycle(
+    recurring_key: &Pubkey,
+    recurring: &RecurringBounty,
+    bounty_id: u64,
+    payer: &Pubkey,
+    index_pages: &[u32],
+) -> Instruction {
+    let bounty = pda::bounty(&recurring.client, bounty_id);
+    let ix = build(
+        accounts::OpenCycle {
+            config: pda::escrow_config(),
+            recurring: *recurring_key,
+            template_vault: recurring.vault,
+            client_state: pda::client(&recurring.client),
+            bounty,
+            history: pda::history(&bounty),
+            vault: pda::vault(&bounty),
+            usdc_mint: recurring.usdc_mint,
+            payer: *payer,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::OpenCycle {},
+    );
+    with_index_pages(ix, recurring.skills, index_pages)
+}
+
+pub fn cancel_recurring(
+    recurring_key: &Pubkey,
+    recurring: &RecurringBounty,
+    client_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::CancelRecurring {
+            recurring: *recurring_key,
+            vault: recurring.vault,
+            client_token_account: *client_token_account,
+            client: recurring.client,
+            token_program: token::ID,
+        },
+        instruction::CancelRecurring {},
+    )
+}
+
+// ─── Subcontracting ─────────────────────────────────────────────────
+
+/// The assigned agent's owner posts a child bounty as bounty `bounty_id` of its own
+/// `ClientState`. `agent_token_account` funds it unless `args.fund_from_payout`.
+/// `index_pages` are as for [`create_bounty`].
+pub fn create_subcontract(
+    parent_key: &Pubkey,
+    parent: &Bounty,
+    agent: &Pubkey,
+    bounty_id: u64,
+    agent_token_account: Option<Pubkey>,
+    index_pages: &[u32],
+    args: instruction::CreateSubcontract,
+) -> Instruction {
+    let bounty = pda::bounty(agent, bounty_id);
+    let skills = args.skills;
+    let ix = build(
+        accounts::CreateSubcontract {
+            config: pda::escrow_config(),
+            parent: *parent_key,
+            client_state: pda::client(agent),
+            bounty,
+            history: pda::history(&bounty),
+            skill_registry: pda::skill_registry(),
+            vault: pda::vault(&bounty),
+            agent: *agent,
+            agent_token_account,
+            usdc_mint: parent.usdc_mint,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        args,
+    );
+    with_index_pages(ix, skills, index_pages)
+}
+
+pub fn fund_subcontract(
+    parent_key: &Pubkey,
+    parent: &Bounty,
+    child_key: &Pubkey,
+    child: &Bounty,
+) -> Instruction {
+    build(
+        accounts::FundSubcontract {
+            config: pda::escrow_config(),
+            parent: *parent_key,
+            parent_vault: parent.vault,
+            child: *child_key,
+            child_vault: child.vault,
+            token_program: token::ID,
+        },
+        instruction::FundSubcontract {},
+    )
+}
+
+// ─── Guild bounties ─────────────────────────────────────────────────
+
+/// A guild admin claims for the guild, attaching their own attestation if required.
+pub fn claim_guild_bounty(
+    bounty_key: &Pubkey,
+    bounty: &Bounty,
+    guild: &Pubkey,
+    member: &Pubkey,
+) -> Instruction {
+    let member_profile = pda::agent(member);
+    let requirement = bounty.attestation_requirement.as_ref();
+    let ix = build(
+        accounts::ClaimGuildBounty {
+            config: pda::escrow_config(),
+            bounty: *bounty_key,
+            history: pda::history(bounty_key),
+            guild: *guild,
+            member: *member,
+            member_profile,
+            attestation: requirement
+                .map(|req| pda::attestation(&member_profile, req.skill_id, &req.verifier)),
+            verifier: requirement.map(|req| pda::verifier(&req.verifier)),
+            escrow_authority: pda::escrow_authority(),
+            agent_registry_program: agent_registry::ID,
+        },
+        instruction::ClaimGuildBounty {},
+    );
+    with_index_pages(ix, bounty.skills, &bounty.index_pages)
+}
+
+pub fn submit_guild_work(
+    bounty_key: &Pubkey,
+    bounty: &Bounty,
+    member: &Pubkey,
+    deliverable_uri: String,
+    deliverable_hash: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::SubmitGuildWork {
+            config: pda::escrow_config(),
+            bounty: *bounty_key,
+            history: pda::history(bounty_key),
+            guild: bounty.assigned_agent,
+            member: *member,
+            member_profile: pda::agent(member),
+        },
+        instruction::SubmitGuildWork {
+            deliverable_uri,
+            deliverable_hash,
+        },
+    )
+}
+
+/// Appends each guild member's agent profile followed by its token account, from
+/// `member_token_accounts` in `guild.members` order, as the handler's remaining accounts.
+fn with_guild_members(ix: &mut Instruction, guild: &Guild, member_token_accounts: &[Pubkey]) {
+    for (member, token_account) in guild.members.iter().zip(member_token_accounts) {
+        ix.accounts.push(AccountMeta::new(member.agent, false));
+        ix.accounts.push(AccountMeta::new(*token_account, false));
+    }
+}
+
+/// `member_token_accounts` are in `guild.members` order. Only `payout`'s treasury
+/// and client referrer are used; guild payouts reward no agent referrer.
+pub fn approve_guild_work(
+    bounty_key: &Pubkey,
+    bounty: &Bounty,
+    guild: &Guild,
+    member_token_accounts: &[Pubkey],
+    payout: &PayoutAccounts,
+) -> Instruction {
+    let mut ix = build(
+        accounts::ApproveGuildWork {
+            config: pda::escrow_config(),
+            bounty: *bounty_key,
+            history: pda::history(bounty_key),
+            vault: bounty.vault,
+            guild: bounty.assigned_agent,
+            validation: validation_for(bounty_key, bounty),
+            client_state: pda::client(&bounty.client),
+            treasury_token_account: payout.treasury,
+            client_referrer_token_account: payout.client_referrer,
+            escrow_authority: pda::escrow_authority(),
+            client: bounty.client,
+            token_program: token::ID,
+            agent_registry_program: agent_registry::ID,
+        },
+        instruction::ApproveGuildWork {},
+    );
+    with_guild_members(&mut ix, guild, member_token_accounts);
+    ix
+}
+
+/// Rates the guild and, through it, every member's agent profile.
+pub fn leave_guild_review(
+    bounty_key: &Pubkey,
+    bounty: &Bounty,
+    guild: &Guild,
+    args: instruction::LeaveGuildReview,
+) -> Instruction {
+    let mut ix = build(
+        accounts::LeaveGuildReview {
+            config: pda::escrow_config(),
+            bounty: *bounty_key,
+            review: pda::review(bounty_key),
+            guild: bounty.assigned_agent,
+            escrow_authority: pda::escrow_authority(),
+            client: bounty.client,
+            system_program: system_program::ID,
+            agent_registry_program: agent_registry::ID,
+        },
+        args,
+    );
+    ix.accounts.extend(
+        guild
+            .members
+            .iter()
+            .map(|member| AccountMeta::new(member.agent, false)),
+    );
+    ix
+}
+
+// ─── Discovery index ────────────────────────────────────────────────
+
+/// Creates page `page` of the `status` bucket for `skill` (or `bounty_escrow::UNSKILLED`).
+pub fn init_index_page(payer: &Pubkey, status: BountyStatus, skill: u8, page: u32) -> Instruction {
+    build(
+        accounts::InitIndexPage {
+            index_page: pda::index_page(status, skill, page),
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::InitIndexPage { status, skill, page },
+    )
+}
+
+// ─── Migrations ─────────────────────────────────────────────────────
+
+pub fn migrate_client_state(client_state: &Pubkey, payer: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateClientState {
+            client_state: *client_state,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateClientState {},
+    )
+}
+
+/// `claimed_by` is the assigned agent of a legacy bounty that is claimed, delivered
+/// or disputed, whose claim the migration records.
+pub fn migrate_bounty(bounty: &Pubkey, claimed_by: Option<&Pubkey>, payer: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateBounty {
+            bounty: *bounty,
+            history: pda::history(bounty),
+            agent_profile: claimed_by.map(pda::agent),
+            escrow_authority: pda::escrow_authority(),
+            payer: *payer,
+            system_program: system_program::ID,
+            agent_registry_program: agent_registry::ID,
+        },
+        instruction::MigrateBounty {},
+    )
+}
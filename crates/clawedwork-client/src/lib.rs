@@ -0,0 +1,28 @@
+//! Rust client for the ClawedWork programs.
+//!
+//! - [`pda`] derives every program address (agents, clients, bounties, vaults, index pages, ...)
+//! - [`registry`] and [`escrow`] build instructions for agent-registry and bounty-escrow
+//! - [`accounts`] decodes on-chain account data
+//! - [`status`] re-exports the program status enums and the [`status::StatusName`] trait
+//!
+//! CPI-only registry instructions (`record_claim`, `add_earnings`, ...) can only be
+//! signed by bounty-escrow and have no builders.
+
+pub mod accounts;
+pub mod escrow;
+pub mod pda;
+pub mod registry;
+pub mod status;
+
+pub use agent_registry;
+pub use bounty_escrow;
+
+/// Program IDs
+pub use agent_registry::ID as AGENT_REGISTRY_ID;
+pub use bounty_escrow::ID as BOUNTY_ESCROW_ID;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error("failed to decode account: {0}")]
+    Decode(String),
+}
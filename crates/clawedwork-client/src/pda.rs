@@ -0,0 +1,141 @@
+//! Program-derived addresses of both programs. Seeds mirror the `#[account(seeds = ...)]`
+//! constraints in the programs.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+
+use agent_registry::ESCROW_AUTHORITY_SEED;
+use bounty_escrow::BountyStatus;
+
+fn registry_pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &agent_registry::ID).0
+}
+
+fn escrow_pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &bounty_escrow::ID).0
+}
+
+/// ProgramData account of an upgradeable program, checked by `init_config`.
+pub fn program_data(program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+// ─── agent-registry ─────────────────────────────────────────────────
+
+/// `["config"]` of agent-registry
+pub fn registry_config() -> Pubkey {
+    registry_pda(&[b"config"])
+}
+
+/// `["skills"]`
+pub fn skill_registry() -> Pubkey {
+    registry_pda(&[b"skills"])
+}
+
+/// `["agent", owner]`
+pub fn agent(owner: &Pubkey) -> Pubkey {
+    registry_pda(&[b"agent", owner.as_ref()])
+}
+
+/// `["delegate", agent, delegate]`
+pub fn agent_delegate(agent: &Pubkey, delegate: &Pubkey) -> Pubkey {
+    registry_pda(&[b"delegate", agent.as_ref(), delegate.as_ref()])
+}
+
+/// `["archive", agent]`
+pub fn agent_archive(agent: &Pubkey) -> Pubkey {
+    registry_pda(&[b"archive", agent.as_ref()])
+}
+
+/// `["verifier", authority]`
+pub fn verifier(authority: &Pubkey) -> Pubkey {
+    registry_pda(&[b"verifier", authority.as_ref()])
+}
+
+/// `["attestation", agent, skill_id, verifier_authority]`
+pub fn attestation(agent: &Pubkey, skill_id: u8, verifier_authority: &Pubkey) -> Pubkey {
+    registry_pda(&[
+        b"attestation",
+        agent.as_ref(),
+        &[skill_id],
+        verifier_authority.as_ref(),
+    ])
+}
+
+/// `["validation", bounty, data_hash, validator]`
+pub fn validation(bounty: &Pubkey, data_hash: &[u8; 32], validator: &Pubkey) -> Pubkey {
+    registry_pda(&[
+        b"validation",
+        bounty.as_ref(),
+        data_hash.as_ref(),
+        validator.as_ref(),
+    ])
+}
+
+/// `["guild", creator, guild_id]`
+pub fn guild(creator: &Pubkey, guild_id: u64) -> Pubkey {
+    registry_pda(&[b"guild", creator.as_ref(), &guild_id.to_le_bytes()])
+}
+
+// ─── bounty-escrow ──────────────────────────────────────────────────
+
+/// `["config"]` of bounty-escrow
+pub fn escrow_config() -> Pubkey {
+    escrow_pda(&[b"config"])
+}
+
+/// `["escrow_authority"]`, the signer of registry CPIs
+pub fn escrow_authority() -> Pubkey {
+    escrow_pda(&[ESCROW_AUTHORITY_SEED])
+}
+
+/// `["client", owner]`
+pub fn client(owner: &Pubkey) -> Pubkey {
+    escrow_pda(&[b"client", owner.as_ref()])
+}
+
+/// `["bounty", client, bounty_id]`
+pub fn bounty(client: &Pubkey, bounty_id: u64) -> Pubkey {
+    escrow_pda(&[b"bounty", client.as_ref(), &bounty_id.to_le_bytes()])
+}
+
+/// `["vault", escrow]`, the token vault of a bounty, hourly contract, stream or
+/// recurring bounty
+pub fn vault(escrow: &Pubkey) -> Pubkey {
+    escrow_pda(&[b"vault", escrow.as_ref()])
+}
+
+/// `["history", bounty]`
+pub fn history(bounty: &Pubkey) -> Pubkey {
+    escrow_pda(&[b"history", bounty.as_ref()])
+}
+
+/// `["index", status, skill, page]`, a page of the open-bounty discovery index
+pub fn index_page(status: BountyStatus, skill: u8, page: u32) -> Pubkey {
+    escrow_pda(&[b"index", &[status as u8], &[skill], &page.to_le_bytes()])
+}
+
+/// `["review", bounty]`
+pub fn review(bounty: &Pubkey) -> Pubkey {
+    escrow_pda(&[b"review", bounty.as_ref()])
+}
+
+/// `["hourly", client, contract_id]`
+pub fn hourly_contract(client: &Pubkey, contract_id: u64) -> Pubkey {
+    escrow_pda(&[b"hourly", client.as_ref(), &contract_id.to_le_bytes()])
+}
+
+/// `["timelog", contract, index]`
+pub fn time_log(contract: &Pubkey, index: u64) -> Pubkey {
+    escrow_pda(&[b"timelog", contract.as_ref(), &index.to_le_bytes()])
+}
+
+/// `["stream", client, stream_id]`
+pub fn stream(client: &Pubkey, stream_id: u64) -> Pubkey {
+    escrow_pda(&[b"stream", client.as_ref(), &stream_id.to_le_bytes()])
+}
+
+/// `["recurring", client, template_id]`
+pub fn recurring(client: &Pubkey, template_id: u64) -> Pubkey {
+    escrow_pda(&[b"recurring", client.as_ref(), &template_id.to_le_bytes()])
+}
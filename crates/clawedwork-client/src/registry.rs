@@ -0,0 +1,409 @@
+//! Instruction builders for agent-registry.
+//!
+//! Signers are passed by key; PDAs are derived with [`crate::pda`]. Instructions with
+//! long argument lists take the program's generated `instruction::*` struct as `args`.
+
+use agent_registry::{accounts, instruction, AgentStatus, GuildRole};
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+
+use crate::pda;
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: agent_registry::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+// ─── Admin ──────────────────────────────────────────────────────────
+
+/// Upgrade authority creates the registry config.
+pub fn init_config(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::InitConfig {
+            config: pda::registry_config(),
+            program: agent_registry::ID,
+            program_data: pda::program_data(&agent_registry::ID),
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::InitConfig {},
+    )
+}
+
+pub fn set_paused(admin: &Pubkey, paused: u64) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            config: pda::registry_config(),
+            admin: *admin,
+        },
+        instruction::SetPaused { paused },
+    )
+}
+
+pub fn set_admin(admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            config: pda::registry_config(),
+            admin: *admin,
+        },
+        instruction::SetAdmin {
+            new_admin: *new_admin,
+        },
+    )
+}
+
+pub fn init_skill_registry(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::InitSkillRegistry {
+            config: pda::registry_config(),
+            skill_registry: pda::skill_registry(),
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::InitSkillRegistry {},
+    )
+}
+
+pub fn add_skill(admin: &Pubkey, name: String) -> Instruction {
+    build(
+        accounts::AddSkill {
+            config: pda::registry_config(),
+            skill_registry: pda::skill_registry(),
+            admin: *admin,
+        },
+        instruction::AddSkill { name },
+    )
+}
+
+pub fn add_verifier(admin: &Pubkey, authority: &Pubkey) -> Instruction {
+    build(
+        accounts::AddVerifier {
+            config: pda::registry_config(),
+            verifier: pda::verifier(authority),
+            authority: *authority,
+            admin: *admin,
+            system_program: system_program::ID,
+        },
+        instruction::AddVerifier {},
+    )
+}
+
+pub fn set_verifier_active(admin: &Pubkey, authority: &Pubkey, active: bool) -> Instruction {
+    build(
+        accounts::SetVerifierActive {
+            config: pda::registry_config(),
+            verifier: pda::verifier(authority),
+            admin: *admin,
+        },
+        instruction::SetVerifierActive { active },
+    )
+}
+
+// ─── Attestations & validation ──────────────────────────────────────
+
+/// Verifier `authority` attests a skill for `agent` (the agent PDA).
+pub fn issue_attestation(
+    authority: &Pubkey,
+    payer: &Pubkey,
+    agent: &Pubkey,
+    args: instruction::IssueAttestation,
+) -> Instruction {
+    build(
+        accounts::IssueAttestation {
+            verifier: pda::verifier(authority),
+            skill_registry: pda::skill_registry(),
+            agent: *agent,
+            attestation: pda::attestation(agent, args.skill_id, authority),
+            authority: *authority,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn revoke_attestation(authority: &Pubkey, agent: &Pubkey, skill_id: u8) -> Instruction {
+    build(
+        accounts::RevokeAttestation {
+            attestation: pda::attestation(agent, skill_id, authority),
+            authority: *authority,
+        },
+        instruction::RevokeAttestation {},
+    )
+}
+
+pub fn request_validation(
+    requester: &Pubkey,
+    validator: &Pubkey,
+    bounty: &Pubkey,
+    data_hash: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::RequestValidation {
+            config: pda::registry_config(),
+            validation: pda::validation(bounty, &data_hash, validator),
+            validator: *validator,
+            requester: *requester,
+            system_program: system_program::ID,
+        },
+        instruction::RequestValidation {
+            bounty: *bounty,
+            data_hash,
+        },
+    )
+}
+
+pub fn respond_validation(
+    validator: &Pubkey,
+    bounty: &Pubkey,
+    data_hash: &[u8; 32],
+    score: u8,
+    response_hash: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::RespondValidation {
+            config: pda::registry_config(),
+            validation: pda::validation(bounty, data_hash, validator),
+            validator: *validator,
+        },
+        instruction::RespondValidation {
+            score,
+            response_hash,
+        },
+    )
+}
+
+// ─── Agents ─────────────────────────────────────────────────────────
+
+pub fn register_agent(owner: &Pubkey, args: instruction::RegisterAgent) -> Instruction {
+    build(
+        accounts::RegisterAgent {
+            config: pda::registry_config(),
+            skill_registry: pda::skill_registry(),
+            agent: pda::agent(owner),
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn update_agent(owner: &Pubkey, args: instruction::UpdateAgent) -> Instruction {
+    build(
+        accounts::UpdateAgent {
+            config: pda::registry_config(),
+            skill_registry: pda::skill_registry(),
+            agent: pda::agent(owner),
+            owner: *owner,
+        },
+        args,
+    )
+}
+
+pub fn add_delegate(owner: &Pubkey, delegate: &Pubkey, permissions: u8) -> Instruction {
+    let agent = pda::agent(owner);
+    build(
+        accounts::AddDelegate {
+            config: pda::registry_config(),
+            agent,
+            agent_delegate: pda::agent_delegate(&agent, delegate),
+            delegate: *delegate,
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        instruction::AddDelegate { permissions },
+    )
+}
+
+pub fn update_delegate(owner: &Pubkey, delegate: &Pubkey, permissions: u8) -> Instruction {
+    let agent = pda::agent(owner);
+    build(
+        accounts::UpdateDelegate {
+            config: pda::registry_config(),
+            agent,
+            agent_delegate: pda::agent_delegate(&agent, delegate),
+            owner: *owner,
+        },
+        instruction::UpdateDelegate { permissions },
+    )
+}
+
+pub fn remove_delegate(owner: &Pubkey, delegate: &Pubkey) -> Instruction {
+    let agent = pda::agent(owner);
+    build(
+        accounts::RemoveDelegate {
+            agent,
+            agent_delegate: pda::agent_delegate(&agent, delegate),
+            owner: *owner,
+        },
+        instruction::RemoveDelegate {},
+    )
+}
+
+/// `authority` is the agent owner or one of its delegates.
+pub fn set_availability(authority: &Pubkey, owner: &Pubkey, availability: AgentStatus) -> Instruction {
+    let agent = pda::agent(owner);
+    build(
+        accounts::SetAvailability {
+            config: pda::registry_config(),
+            agent,
+            agent_delegate: (authority != owner).then(|| pda::agent_delegate(&agent, authority)),
+            authority: *authority,
+        },
+        instruction::SetAvailability { availability },
+    )
+}
+
+pub fn propose_owner_transfer(owner: &Pubkey, new_owner: &Pubkey) -> Instruction {
+    build(
+        accounts::ProposeOwnerTransfer {
+            config: pda::registry_config(),
+            agent: pda::agent(owner),
+            owner: *owner,
+        },
+        instruction::ProposeOwnerTransfer {
+            new_owner: *new_owner,
+        },
+    )
+}
+
+pub fn cancel_owner_transfer(owner: &Pubkey) -> Instruction {
+    build(
+        accounts::ProposeOwnerTransfer {
+            config: pda::registry_config(),
+            agent: pda::agent(owner),
+            owner: *owner,
+        },
+        instruction::CancelOwnerTransfer {},
+    )
+}
+
+pub fn accept_owner_transfer(new_owner: &Pubkey, old_owner: &Pubkey) -> Instruction {
+    build(
+        accounts::AcceptOwnerTransfer {
+            config: pda::registry_config(),
+            old_agent: pda::agent(old_owner),
+            new_agent: pda::agent(new_owner),
+            new_owner: *new_owner,
+            system_program: system_program::ID,
+        },
+        instruction::AcceptOwnerTransfer {},
+    )
+}
+
+/// Closes the agent, optionally leaving an `AgentArchive` tombstone behind.
+pub fn deregister_agent(owner: &Pubkey, archive: bool) -> Instruction {
+    let agent = pda::agent(owner);
+    build(
+        accounts::DeregisterAgent {
+            agent,
+            archive: archive.then(|| pda::agent_archive(&agent)),
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        instruction::DeregisterAgent {},
+    )
+}
+
+pub fn migrate_agent(agent: &Pubkey, payer: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateAgent {
+            agent: *agent,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateAgent {},
+    )
+}
+
+// ─── Guilds ─────────────────────────────────────────────────────────
+
+pub fn create_guild(creator: &Pubkey, args: instruction::CreateGuild) -> Instruction {
+    build(
+        accounts::CreateGuild {
+            config: pda::registry_config(),
+            guild: pda::guild(creator, args.guild_id),
+            creator_agent: pda::agent(creator),
+            creator: *creator,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+/// Both the admin and the joining member's owner sign.
+pub fn add_guild_member(guild: &Pubkey, admin: &Pubkey, member: &Pubkey) -> Instruction {
+    build(
+        accounts::AddGuildMember {
+            config: pda::registry_config(),
+            guild: *guild,
+            admin_agent: pda::agent(admin),
+            admin: *admin,
+            member_agent: pda::agent(member),
+            member: *member,
+        },
+        instruction::AddGuildMember {},
+    )
+}
+
+/// `authority` is a guild admin's owner or the owner of `member_agent` itself.
+pub fn remove_guild_member(guild: &Pubkey, authority: &Pubkey, member_agent: &Pubkey) -> Instruction {
+    build(
+        accounts::RemoveGuildMember {
+            guild: *guild,
+            authority_agent: pda::agent(authority),
+            authority: *authority,
+            member_agent: *member_agent,
+        },
+        instruction::RemoveGuildMember {},
+    )
+}
+
+/// `founder` is the wallet that created the guild and receives its rent.
+pub fn close_guild(guild: &Pubkey, founder: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseGuild {
+            guild: *guild,
+            founder_agent: pda::agent(founder),
+            founder: *founder,
+        },
+        instruction::CloseGuild {},
+    )
+}
+
+fn guild_settings(guild: &Pubkey, admin: &Pubkey) -> accounts::UpdateGuildSettings {
+    accounts::UpdateGuildSettings {
+        config: pda::registry_config(),
+        guild: *guild,
+        admin_agent: pda::agent(admin),
+        admin: *admin,
+    }
+}
+
+/// `shares` are basis points in member order and must sum to `GUILD_SHARE_TOTAL`.
+pub fn set_guild_split(guild: &Pubkey, admin: &Pubkey, shares: Vec<u16>) -> Instruction {
+    build(
+        guild_settings(guild, admin),
+        instruction::SetGuildSplit { shares },
+    )
+}
+
+pub fn set_guild_role(
+    guild: &Pubkey,
+    admin: &Pubkey,
+    agent: &Pubkey,
+    role: GuildRole,
+) -> Instruction {
+    build(
+        guild_settings(guild, admin),
+        instruction::SetGuildRole {
+            agent: *agent,
+            role,
+        },
+    )
+}
@@ -0,0 +1,78 @@
+//! Status enums for the on-chain state fields, with display names for clients.
+//!
+//! Every status field is stored as its program enum; they are re-exported here with
+//! the lowercase names the CLI and indexer print and parse.
+
+pub use agent_registry::{AgentStatus, GuildRole};
+pub use bounty_escrow::{
+    BountyStatus, DeclinePolicy, HourlyContractStatus, RecurringStatus, StreamStatus,
+    TimeLogStatus,
+};
+
+/// Lowercase names for every status enum, as used by the CLI.
+pub trait StatusName: Copy + 'static {
+    const ALL: &'static [Self];
+
+    fn as_str(self) -> &'static str;
+}
+
+macro_rules! status_name {
+    ($name:ident { $($variant:ident => $label:literal,)+ }) => {
+        impl StatusName for $name {
+            const ALL: &'static [Self] = &[$(Self::$variant,)+];
+
+            fn as_str(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $label,)+
+                }
+            }
+        }
+    };
+}
+
+status_name!(BountyStatus {
+    Open => "open",
+    Claimed => "claimed",
+    Delivered => "delivered",
+    Completed => "completed",
+    Disputed => "disputed",
+    Cancelled => "cancelled",
+});
+
+status_name!(AgentStatus {
+    Available => "available",
+    Busy => "busy",
+    Offline => "offline",
+});
+
+status_name!(DeclinePolicy {
+    Refund => "refund",
+    Reopen => "reopen",
+});
+
+status_name!(HourlyContractStatus {
+    Active => "active",
+    Closed => "closed",
+});
+
+status_name!(TimeLogStatus {
+    Pending => "pending",
+    Approved => "approved",
+    Rejected => "rejected",
+});
+
+status_name!(StreamStatus {
+    Active => "active",
+    Completed => "completed",
+    Cancelled => "cancelled",
+});
+
+status_name!(RecurringStatus {
+    Active => "active",
+    Cancelled => "cancelled",
+});
+
+status_name!(GuildRole {
+    Admin => "admin",
+    Member => "member",
+});
@@ -0,0 +1,116 @@
+//! JSON query API over HTTP:
+//!
+//! - `GET /bounties/open?skill=&min_budget=&max_budget=&limit=`, largest budget first
+//! - `GET /agents/<owner wallet>/history`
+//! - `GET /clients/<wallet>/spend`
+//! - `GET /guilds/<guild address>`
+//! - `GET /status`, the slot and size of the last snapshot, and how many
+//!   accounts it changed
+
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use tiny_http::{Header, Method, Response, Server};
+
+use crate::{OpenBountyFilter, Store};
+
+/// A failed query and the HTTP status it is answered with.
+#[derive(Debug)]
+pub enum ApiError {
+    NotFound,
+    BadRequest(String),
+    Internal(anyhow::Error),
+}
+
+impl ApiError {
+    pub fn status(&self) -> u16 {
+        match self {
+            ApiError::NotFound => 404,
+            ApiError::BadRequest(_) => 400,
+            ApiError::Internal(_) => 500,
+        }
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(err: anyhow::Error) -> Self {
+        ApiError::Internal(err)
+    }
+}
+
+/// Answers a `GET` of `url` (path and query string) from `store`.
+pub fn handle(store: &Store, url: &str) -> Result<Value, ApiError> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let params: Vec<(&str, &str)> = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .collect();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match segments.as_slice() {
+        ["bounties", "open"] => {
+            let filter = OpenBountyFilter {
+                skill: param(&params, "skill")?,
+                min_budget: param(&params, "min_budget")?,
+                max_budget: param(&params, "max_budget")?,
+                limit: param(&params, "limit")?,
+            };
+            Ok(json!(store.open_bounties(&filter)?))
+        }
+        ["agents", owner, "history"] => Ok(store.agent_history(&pubkey(owner)?)?),
+        ["clients", client, "spend"] => Ok(store.client_spend(&pubkey(client)?)?),
+        ["guilds", guild] => store.guild(&pubkey(guild)?)?.ok_or(ApiError::NotFound),
+        ["status"] => Ok(store.status()?),
+        _ => Err(ApiError::NotFound),
+    }
+}
+
+fn param<T: FromStr>(params: &[(&str, &str)], name: &str) -> Result<Option<T>, ApiError> {
+    params
+        .iter()
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| {
+            value
+                .parse()
+                .map_err(|_| ApiError::BadRequest(format!("invalid {name}: {value}")))
+        })
+        .transpose()
+}
+
+fn pubkey(input: &str) -> Result<Pubkey, ApiError> {
+    input
+        .parse()
+        .map_err(|_| ApiError::BadRequest(format!("invalid address: {input}")))
+}
+
+/// Serves the API on `listen` (`host:port`) until the process exits, one request
+/// at a time.
+pub fn serve(listen: &str, store: &Store) -> anyhow::Result<()> {
+    let server = Server::http(listen).map_err(|err| anyhow!("binding {listen}: {err}"))?;
+    let content_type = Header::from_bytes("Content-Type", "application/json")
+        .expect("static header is valid");
+    for request in server.incoming_requests() {
+        let result = if *request.method() == Method::Get {
+            handle(store, request.url())
+        } else {
+            Err(ApiError::NotFound)
+        };
+        let (status, body) = match result {
+            Ok(body) => (200, body),
+            Err(ApiError::Internal(err)) => {
+                eprintln!("{} failed: {err:#}", request.url());
+                (500, json!({ "error": "internal error" }))
+            }
+            Err(err @ ApiError::NotFound) => (err.status(), json!({ "error": "not found" })),
+            Err(ApiError::BadRequest(message)) => (400, json!({ "error": message })),
+        };
+        let response = Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(content_type.clone());
+        if let Err(err) = request.respond(response) {
+            eprintln!("responding: {err}");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,11 @@
+//! Off-chain indexer for the ClawedWork programs.
+//!
+//! - [`sync`] polls an RPC node for every indexed agent-registry and bounty-escrow account
+//! - [`store`] keeps them in SQLite, along with each bounty's status changes, and runs queries
+//! - [`api`] serves those queries as JSON over HTTP
+
+pub mod api;
+pub mod store;
+pub mod sync;
+
+pub use store::{OpenBountyFilter, RawAccount, Store};
@@ -0,0 +1,73 @@
+//! `clawedwork-indexer` — mirror ClawedWork accounts into SQLite and serve queries.
+
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use clawedwork_indexer::{api, sync, Store};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+
+#[derive(Parser)]
+#[command(name = "clawedwork-indexer", version, about = "Index ClawedWork accounts and serve queries")]
+struct Cli {
+    /// RPC URL or moniker: localhost, devnet, testnet, mainnet-beta
+    #[arg(short = 'u', long, global = true, env = "CLAWEDWORK_RPC_URL", default_value = "localhost")]
+    url: String,
+
+    /// SQLite database file
+    #[arg(long, global = true, env = "CLAWEDWORK_INDEX_DB", default_value = "clawedwork-index.db")]
+    db: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Poll the validator and serve the query API until stopped
+    Run {
+        /// Address the API listens on
+        #[arg(long, default_value = "127.0.0.1:8787")]
+        listen: String,
+        /// Seconds between polls
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+    },
+    /// Take one snapshot and exit
+    Sync,
+}
+
+fn rpc_url(url: &str) -> String {
+    match url {
+        "localhost" | "l" => "http://127.0.0.1:8899",
+        "devnet" | "d" => "https://api.devnet.solana.com",
+        "testnet" | "t" => "https://api.testnet.solana.com",
+        "mainnet-beta" | "m" => "https://api.mainnet-beta.solana.com",
+        url => url,
+    }
+    .to_string()
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let rpc = RpcClient::new_with_commitment(rpc_url(&cli.url), CommitmentConfig::confirmed());
+    let mut store = Store::open(&cli.db)?;
+
+    match cli.command {
+        Command::Run { listen, interval } => {
+            // The poller writes through its own connection, so queries never wait on a pass
+            let poller = Store::open(&cli.db)?;
+            thread::spawn(move || sync::poll(rpc, poller, Duration::from_secs(interval)));
+            eprintln!("serving {} on http://{listen}", cli.db.display());
+            api::serve(&listen, &store)
+        }
+        Command::Sync => {
+            let (slot, indexed) = sync::sync(&rpc, &mut store)?;
+            println!("indexed {indexed} accounts at slot {slot}");
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,802 @@
+//! SQLite storage of indexed accounts and the queries served by the API.
+//!
+//! Every poll diffs a fresh snapshot against the last one by data hash and
+//! rewrites only the accounts that changed, appeared or disappeared, so the
+//! account tables always mirror the chain. `status_changes` and `payouts` are
+//! the exceptions: the programs emit no events, so bounty status changes are
+//! read from `BountyHistory` accounts and kept across snapshots, including
+//! entries that later drop out of the capped on-chain history, and each
+//! completed bounty's payout is recorded with the platform fee in force when it
+//! is first seen.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use anyhow::Result;
+use clawedwork_client::accounts::{
+    self, Agent, Bounty, BountyHistory, ClientState, EscrowConfig, Guild, HourlyContract,
+    RecurringBounty, Review, Stream,
+};
+use clawedwork_client::pda;
+use clawedwork_client::status::StatusName;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde_json::{json, Value};
+use solana_sdk::hash::hash;
+use solana_sdk::pubkey::Pubkey;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS bounties (
+    address TEXT PRIMARY KEY,
+    client TEXT NOT NULL,
+    bounty_id INTEGER NOT NULL,
+    metadata_uri TEXT NOT NULL,
+    budget INTEGER NOT NULL,
+    deadline INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    assigned_agent TEXT,
+    guild TEXT,
+    parent TEXT,
+    awaiting_parent_funds INTEGER NOT NULL,
+    reserved_for_children INTEGER NOT NULL,
+    tips_total INTEGER NOT NULL,
+    created_at INTEGER NOT NULL,
+    claimed_at INTEGER,
+    delivered_at INTEGER,
+    completed_at INTEGER,
+    disputed_at INTEGER,
+    cancelled_at INTEGER,
+    dispute_payout INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS bounties_by_status ON bounties (status, budget);
+CREATE INDEX IF NOT EXISTS bounties_by_client ON bounties (client);
+CREATE INDEX IF NOT EXISTS bounties_by_agent ON bounties (assigned_agent);
+
+CREATE TABLE IF NOT EXISTS bounty_skills (
+    skill INTEGER NOT NULL,
+    bounty TEXT NOT NULL,
+    PRIMARY KEY (skill, bounty)
+);
+
+CREATE TABLE IF NOT EXISTS status_changes (
+    bounty TEXT NOT NULL,
+    seq INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    actor TEXT NOT NULL,
+    at INTEGER NOT NULL,
+    PRIMARY KEY (bounty, seq)
+);
+CREATE INDEX IF NOT EXISTS status_changes_by_actor ON status_changes (actor);
+
+CREATE TABLE IF NOT EXISTS payouts (
+    bounty TEXT PRIMARY KEY,
+    client TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    fee INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS payouts_by_client ON payouts (client);
+
+CREATE TABLE IF NOT EXISTS escrow_config (
+    address TEXT PRIMARY KEY,
+    admin TEXT NOT NULL,
+    fee_bps INTEGER NOT NULL,
+    treasury TEXT
+);
+
+CREATE TABLE IF NOT EXISTS agents (
+    address TEXT PRIMARY KEY,
+    owner TEXT NOT NULL,
+    metadata_uri TEXT NOT NULL,
+    hourly_rate INTEGER NOT NULL,
+    availability TEXT NOT NULL,
+    reputation INTEGER NOT NULL,
+    bounties_completed INTEGER NOT NULL,
+    total_earned INTEGER NOT NULL,
+    active_claims INTEGER NOT NULL,
+    skills TEXT NOT NULL,
+    guild TEXT,
+    created_at INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS guilds (
+    address TEXT PRIMARY KEY,
+    creator TEXT NOT NULL,
+    guild_id INTEGER NOT NULL,
+    metadata_uri TEXT NOT NULL,
+    reputation INTEGER NOT NULL,
+    bounties_completed INTEGER NOT NULL,
+    total_earned INTEGER NOT NULL,
+    active_claims INTEGER NOT NULL,
+    created_at INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS guild_members (
+    guild TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    agent TEXT NOT NULL,
+    role TEXT NOT NULL,
+    share_bps INTEGER NOT NULL,
+    PRIMARY KEY (guild, position)
+);
+
+CREATE TABLE IF NOT EXISTS reviews (
+    address TEXT PRIMARY KEY,
+    bounty TEXT NOT NULL,
+    reviewer TEXT NOT NULL,
+    agent TEXT NOT NULL,
+    rating INTEGER NOT NULL,
+    comment_uri TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS reviews_by_agent ON reviews (agent);
+
+CREATE TABLE IF NOT EXISTS clients (
+    address TEXT PRIMARY KEY,
+    owner TEXT NOT NULL,
+    bounty_count INTEGER NOT NULL,
+    referrer TEXT,
+    created_at INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS hourly_contracts (
+    address TEXT PRIMARY KEY,
+    client TEXT NOT NULL,
+    agent TEXT NOT NULL,
+    hourly_rate INTEGER NOT NULL,
+    cap INTEGER NOT NULL,
+    released INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS streams (
+    address TEXT PRIMARY KEY,
+    client TEXT NOT NULL,
+    agent TEXT NOT NULL,
+    total INTEGER NOT NULL,
+    withdrawn INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS recurring_bounties (
+    address TEXT PRIMARY KEY,
+    client TEXT NOT NULL,
+    template_id INTEGER NOT NULL,
+    metadata_uri TEXT NOT NULL,
+    budget_per_period INTEGER NOT NULL,
+    period_secs INTEGER NOT NULL,
+    total_periods INTEGER NOT NULL,
+    opened_periods INTEGER NOT NULL,
+    next_open_at INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    last_cycle TEXT,
+    created_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS recurring_bounties_by_client ON recurring_bounties (client);
+
+CREATE TABLE IF NOT EXISTS account_hashes (
+    address TEXT PRIMARY KEY,
+    hash TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS sync_state (
+    id INTEGER PRIMARY KEY CHECK (id = 0),
+    slot INTEGER NOT NULL,
+    accounts INTEGER NOT NULL,
+    changed INTEGER NOT NULL
+);
+";
+
+/// Tables holding the latest snapshot, with the column naming the account each
+/// row was read from; a changed or closed account's rows are deleted by it.
+const SNAPSHOT_TABLES: [(&str, &str); 11] = [
+    ("bounties", "address"),
+    ("bounty_skills", "bounty"),
+    ("escrow_config", "address"),
+    ("agents", "address"),
+    ("guilds", "address"),
+    ("guild_members", "guild"),
+    ("reviews", "address"),
+    ("clients", "address"),
+    ("hourly_contracts", "address"),
+    ("streams", "address"),
+    ("recurring_bounties", "address"),
+];
+
+/// Bounty columns in the order `bounty_view` reads them.
+const BOUNTY_COLUMNS: &str = "address, client, bounty_id, metadata_uri, budget, deadline, status,
+    assigned_agent, guild, parent, awaiting_parent_funds, reserved_for_children, tips_total,
+    created_at, claimed_at, delivered_at, completed_at, disputed_at, cancelled_at,
+    (SELECT json_group_array(skill) FROM bounty_skills WHERE bounty = address)";
+
+/// Agent columns in the order `agent_view` reads them.
+const AGENT_COLUMNS: &str = "address, owner, metadata_uri, hourly_rate, availability, reputation,
+    bounties_completed, total_earned, active_claims, skills, guild, created_at";
+
+/// Records the payout of every completed bounty not recorded yet: the amount a
+/// resolved dispute awarded, otherwise the budget left after subcontract
+/// reservations, and the platform fee taken from it at the current rate. Fees are split into whole basis points of 10,000-unit
+/// chunks and the remainder, so the product never leaves SQLite's integers.
+const RECORD_PAYOUTS: &str = "
+INSERT OR IGNORE INTO payouts (bounty, client, amount, fee)
+SELECT payout.bounty, payout.client, payout.amount,
+       payout.amount / 10000 * config.fee_bps + payout.amount % 10000 * config.fee_bps / 10000
+FROM (SELECT address AS bounty, client,
+             CASE WHEN dispute_payout > 0 THEN dispute_payout
+                  ELSE budget - reserved_for_children END AS amount
+      FROM bounties WHERE status = 'completed') AS payout,
+     escrow_config AS config";
+
+/// Address and raw data of a program account.
+pub type RawAccount = (Pubkey, Vec<u8>);
+
+/// Filters of [`Store::open_bounties`]. Budgets are in USDC base units.
+#[derive(Clone, Debug, Default)]
+pub struct OpenBountyFilter {
+    /// Only bounties requiring this skill id
+    pub skill: Option<u8>,
+    pub min_budget: Option<u64>,
+    pub max_budget: Option<u64>,
+    pub limit: Option<u32>,
+}
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    /// Opens (or creates) the database at `path`.
+    pub fn open(path: &Path) -> Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    /// Write-ahead logging lets the API read from its own connection to a file
+    /// database while the poller's write transaction is open.
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// Brings the indexed state up to `accounts`, every program account of the
+    /// indexed types as of `slot`. Only accounts whose data changed since the last
+    /// snapshot are decoded again; indexed accounts missing from `accounts` were
+    /// closed and are removed. Accounts of other types, or that fail to decode
+    /// (legacy layouts awaiting migration), are skipped. Returns how many were indexed.
+    pub fn apply(&mut self, slot: u64, accounts: &[RawAccount]) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let mut known: HashMap<String, String> = tx
+            .prepare("SELECT address, hash FROM account_hashes")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let (mut indexed, mut changed) = (0, 0);
+        for (address, data) in accounts {
+            let key = address.to_string();
+            let data_hash = hash(data).to_string();
+            let previous = known.remove(&key);
+            if previous.as_ref() == Some(&data_hash) {
+                indexed += 1;
+                continue;
+            }
+            if previous.is_some() {
+                remove(&tx, &key)?;
+            }
+            let stored = put(&tx, address, data)?;
+            if stored {
+                tx.execute(
+                    "INSERT INTO account_hashes VALUES (?1, ?2)",
+                    params![key, data_hash],
+                )?;
+                indexed += 1;
+            }
+            if stored || previous.is_some() {
+                changed += 1;
+            }
+        }
+        // Whatever is left was indexed before and has been closed since
+        for address in known.keys() {
+            remove(&tx, address)?;
+            changed += 1;
+        }
+
+        tx.execute(RECORD_PAYOUTS, [])?;
+        tx.execute(
+            "INSERT INTO sync_state (id, slot, accounts, changed) VALUES (0, ?1, ?2, ?3)
+             ON CONFLICT (id) DO UPDATE SET slot = ?1, accounts = ?2, changed = ?3",
+            params![slot, indexed, changed],
+        )?;
+        tx.commit()?;
+        Ok(indexed)
+    }
+
+    /// Slot and size of the last snapshot and how many accounts it wrote or
+    /// removed, `null` before the first one.
+    pub fn status(&self) -> Result<Value> {
+        let state = self
+            .conn
+            .query_row("SELECT slot, accounts, changed FROM sync_state", [], |row| {
+                Ok(json!({
+                    "slot": row.get::<_, u64>(0)?,
+                    "accounts": row.get::<_, u64>(1)?,
+                    "changed": row.get::<_, u64>(2)?,
+                }))
+            })
+            .optional()?;
+        Ok(state.unwrap_or(Value::Null))
+    }
+
+    /// Open bounties matching `filter`, largest budget first.
+    pub fn open_bounties(&self, filter: &OpenBountyFilter) -> Result<Vec<Value>> {
+        let sql = format!(
+            "SELECT {BOUNTY_COLUMNS} FROM bounties
+             WHERE status = 'open'
+               AND (?1 IS NULL OR address IN (SELECT bounty FROM bounty_skills WHERE skill = ?1))
+               AND (?2 IS NULL OR budget >= ?2)
+               AND (?3 IS NULL OR budget <= ?3)
+             ORDER BY budget DESC, created_at DESC
+             LIMIT ?4"
+        );
+        let mut statement = self.conn.prepare(&sql)?;
+        let rows = statement.query_map(
+            params![
+                filter.skill,
+                filter.min_budget,
+                filter.max_budget,
+                filter.limit.map_or(-1, i64::from),
+            ],
+            bounty_view,
+        )?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// An agent's profile, the bounties assigned to its owner wallet, the reviews
+    /// it received and every status change it made, oldest first.
+    pub fn agent_history(&self, owner: &Pubkey) -> Result<Value> {
+        let owner_key = owner.to_string();
+        let profile = pda::agent(owner).to_string();
+        let agent = self
+            .conn
+            .query_row(
+                &format!("SELECT {AGENT_COLUMNS} FROM agents WHERE address = ?1"),
+                [&profile],
+                agent_view,
+            )
+            .optional()?;
+
+        let sql = format!(
+            "SELECT {BOUNTY_COLUMNS} FROM bounties WHERE assigned_agent = ?1
+             ORDER BY created_at DESC"
+        );
+        let mut statement = self.conn.prepare(&sql)?;
+        let bounties: Vec<Value> = statement
+            .query_map([&owner_key], bounty_view)?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let mut statement = self.conn.prepare(
+            "SELECT address, bounty, reviewer, rating, comment_uri, created_at
+             FROM reviews WHERE agent = ?1 ORDER BY created_at DESC",
+        )?;
+        let reviews: Vec<Value> = statement
+            .query_map([&owner_key], |row| {
+                Ok(json!({
+                    "address": row.get::<_, String>(0)?,
+                    "bounty": row.get::<_, String>(1)?,
+                    "reviewer": row.get::<_, String>(2)?,
+                    "rating": row.get::<_, u64>(3)?,
+                    "comment_uri": row.get::<_, String>(4)?,
+                    "created_at": row.get::<_, i64>(5)?,
+                }))
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let mut statement = self.conn.prepare(
+            "SELECT bounty, status, at FROM status_changes WHERE actor = ?1 ORDER BY at, seq",
+        )?;
+        let activity: Vec<Value> = statement
+            .query_map([&owner_key], |row| {
+                Ok(json!({
+                    "bounty": row.get::<_, String>(0)?,
+                    "status": row.get::<_, String>(1)?,
+                    "at": row.get::<_, i64>(2)?,
+                }))
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+
+        Ok(json!({
+            "owner": owner_key,
+            "agent": agent,
+            "bounties": bounties,
+            "reviews": reviews,
+            "activity": activity,
+        }))
+    }
+
+    /// A guild with its members, in guild order, and the bounties it claimed,
+    /// newest first. `None` if the guild is not indexed.
+    pub fn guild(&self, address: &Pubkey) -> Result<Option<Value>> {
+        let address = address.to_string();
+        let Some(guild) = self
+            .conn
+            .query_row(
+                "SELECT address, creator, guild_id, metadata_uri, reputation, bounties_completed,
+                        total_earned, active_claims, created_at
+                 FROM guilds WHERE address = ?1",
+                [&address],
+                |row| {
+                    Ok(json!({
+                        "address": row.get::<_, String>(0)?,
+                        "creator": row.get::<_, String>(1)?,
+                        "guild_id": row.get::<_, u64>(2)?,
+                        "metadata_uri": row.get::<_, String>(3)?,
+                        "reputation": row.get::<_, u64>(4)?,
+                        "bounties_completed": row.get::<_, u64>(5)?,
+                        "total_earned": row.get::<_, u64>(6)?,
+                        "active_claims": row.get::<_, u64>(7)?,
+                        "created_at": row.get::<_, i64>(8)?,
+                    }))
+                },
+            )
+            .optional()?
+        else {
+            return Ok(None);
+        };
+
+        let mut statement = self.conn.prepare(
+            "SELECT agent, role, share_bps FROM guild_members WHERE guild = ?1 ORDER BY position",
+        )?;
+        let members: Vec<Value> = statement
+            .query_map([&address], |row| {
+                Ok(json!({
+                    "agent": row.get::<_, String>(0)?,
+                    "role": row.get::<_, String>(1)?,
+                    "share_bps": row.get::<_, u16>(2)?,
+                }))
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let sql = format!(
+            "SELECT {BOUNTY_COLUMNS} FROM bounties WHERE guild = ?1 ORDER BY created_at DESC"
+        );
+        let mut statement = self.conn.prepare(&sql)?;
+        let bounties: Vec<Value> = statement
+            .query_map([&address], bounty_view)?
+            .collect::<rusqlite::Result<_>>()?;
+
+        Ok(Some(json!({
+            "guild": guild,
+            "members": members,
+            "bounties": bounties,
+        })))
+    }
+
+    /// What a client wallet has paid out (completed bounties net of the platform
+    /// fee and of budget passed on to subcontracts, platform fees, tips, hourly
+    /// releases and stream withdrawals), still holds in escrow (including unopened
+    /// recurring periods) and got refunded.
+    pub fn client_spend(&self, client: &Pubkey) -> Result<Value> {
+        let client = client.to_string();
+        let (posted, completed, tips, escrowed, refunded) = self.conn.query_row(
+            "SELECT COUNT(*),
+                    COUNT(*) FILTER (WHERE status = 'completed'),
+                    COALESCE(SUM(tips_total), 0),
+                    COALESCE(SUM(budget) FILTER (
+                        WHERE status IN ('open', 'claimed', 'delivered', 'disputed')
+                          AND NOT awaiting_parent_funds), 0),
+                    COALESCE(SUM(budget) FILTER (
+                        WHERE status = 'cancelled' AND NOT awaiting_parent_funds), 0)
+                        + COALESCE(SUM(budget - dispute_payout) FILTER (
+                            WHERE status = 'completed' AND dispute_payout > 0), 0)
+             FROM bounties WHERE client = ?1",
+            [&client],
+            |row| {
+                Ok((
+                    row.get::<_, u64>(0)?,
+                    row.get::<_, u64>(1)?,
+                    row.get::<_, u64>(2)?,
+                    row.get::<_, u64>(3)?,
+                    row.get::<_, u64>(4)?,
+                ))
+            },
+        )?;
+        let (paid, fees) = self.conn.query_row(
+            "SELECT COALESCE(SUM(amount - fee), 0), COALESCE(SUM(fee), 0)
+             FROM payouts WHERE client = ?1",
+            [&client],
+            |row| Ok((row.get::<_, u64>(0)?, row.get::<_, u64>(1)?)),
+        )?;
+        let (recurring_escrowed, recurring_refunded) = self.conn.query_row(
+            "SELECT COALESCE(SUM((total_periods - opened_periods) * budget_per_period)
+                        FILTER (WHERE status = 'active'), 0),
+                    COALESCE(SUM((total_periods - opened_periods) * budget_per_period)
+                        FILTER (WHERE status = 'cancelled'), 0)
+             FROM recurring_bounties WHERE client = ?1",
+            [&client],
+            |row| Ok((row.get::<_, u64>(0)?, row.get::<_, u64>(1)?)),
+        )?;
+        let hourly: u64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(released), 0) FROM hourly_contracts WHERE client = ?1",
+            [&client],
+            |row| row.get(0),
+        )?;
+        let streamed: u64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(withdrawn), 0) FROM streams WHERE client = ?1",
+            [&client],
+            |row| row.get(0),
+        )?;
+        Ok(json!({
+            "client": client,
+            "bounties_posted": posted,
+            "bounties_completed": completed,
+            "bounty_payouts": paid,
+            "platform_fees": fees,
+            "tips": tips,
+            "hourly_released": hourly,
+            "stream_withdrawn": streamed,
+            "total_spent": paid + fees + tips + hourly + streamed,
+            "in_escrow": escrowed + recurring_escrowed,
+            "refunded": refunded + recurring_refunded,
+        }))
+    }
+}
+
+/// `T` decoded from `data` if it carries `T`'s discriminator and the current layout.
+fn decoded<T: AccountDeserialize + Discriminator>(data: &[u8]) -> Option<T> {
+    if data.get(..8)? != T::DISCRIMINATOR {
+        return None;
+    }
+    accounts::decode(data).ok()
+}
+
+/// `None` for unset (default) keys.
+fn optional_key(key: &Pubkey) -> Option<String> {
+    (*key != Pubkey::default()).then(|| key.to_string())
+}
+
+/// `None` for unset (zero) timestamps.
+fn optional_time(timestamp: i64) -> Option<i64> {
+    (timestamp != 0).then_some(timestamp)
+}
+
+fn skill_ids(mask: u128) -> Vec<u8> {
+    (0..128u8).filter(|id| mask & (1 << id) != 0).collect()
+}
+
+/// Deletes the snapshot rows read from `address` and forgets its data hash.
+fn remove(conn: &Connection, address: &str) -> Result<()> {
+    for (table, column) in SNAPSHOT_TABLES {
+        conn.execute(&format!("DELETE FROM {table} WHERE {column} = ?1"), [address])?;
+    }
+    conn.execute("DELETE FROM account_hashes WHERE address = ?1", [address])?;
+    Ok(())
+}
+
+/// Stores one account, returning whether it is of an indexed type.
+fn put(conn: &Connection, address: &Pubkey, data: &[u8]) -> Result<bool> {
+    let address = address.to_string();
+    if let Some(bounty) = decoded::<Bounty>(data) {
+        conn.execute(
+            "INSERT INTO bounties VALUES
+                 (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18,
+                  ?19, ?20)",
+            params![
+                address,
+                bounty.client.to_string(),
+                bounty.bounty_id,
+                bounty.metadata_uri,
+                bounty.budget,
+                bounty.deadline,
+                bounty.status.as_str(),
+                optional_key(&bounty.assigned_agent),
+                optional_key(&bounty.guild),
+                optional_key(&bounty.parent),
+                bounty.awaiting_parent_funds,
+                bounty.reserved_for_children,
+                bounty.tips_total,
+                bounty.created_at,
+                optional_time(bounty.claimed_at),
+                optional_time(bounty.delivered_at),
+                optional_time(bounty.completed_at),
+                optional_time(bounty.disputed_at),
+                optional_time(bounty.cancelled_at),
+                bounty.dispute_payout,
+            ],
+        )?;
+        for skill in skill_ids(bounty.skills) {
+            conn.execute("INSERT INTO bounty_skills VALUES (?1, ?2)", params![skill, address])?;
+        }
+    } else if let Some(history) = decoded::<BountyHistory>(data) {
+        // `entries` are the latest ones of `total` changes
+        let first = history.total as usize - history.entries.len();
+        for (seq, change) in (first..).zip(&history.entries) {
+            conn.execute(
+                "INSERT OR IGNORE INTO status_changes VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    history.bounty.to_string(),
+                    seq,
+                    change.status.as_str(),
+                    change.actor.to_string(),
+                    change.at,
+                ],
+            )?;
+        }
+    } else if let Some(agent) = decoded::<Agent>(data) {
+        conn.execute(
+            "INSERT INTO agents VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                address,
+                agent.owner.to_string(),
+                agent.metadata_uri,
+                agent.hourly_rate,
+                agent.availability.as_str(),
+                agent.reputation,
+                agent.bounties_completed,
+                agent.total_earned,
+                agent.active_claims,
+                json!(skill_ids(agent.skills)).to_string(),
+                optional_key(&agent.guild),
+                agent.created_at,
+            ],
+        )?;
+    } else if let Some(guild) = decoded::<Guild>(data) {
+        conn.execute(
+            "INSERT INTO guilds VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                address,
+                guild.creator.to_string(),
+                guild.guild_id,
+                guild.metadata_uri,
+                guild.reputation,
+                guild.bounties_completed,
+                guild.total_earned,
+                guild.active_claims,
+                guild.created_at,
+            ],
+        )?;
+        for (position, member) in guild.members.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO guild_members VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    address,
+                    position,
+                    member.agent.to_string(),
+                    member.role.as_str(),
+                    member.share_bps,
+                ],
+            )?;
+        }
+    } else if let Some(review) = decoded::<Review>(data) {
+        conn.execute(
+            "INSERT INTO reviews VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                address,
+                review.bounty.to_string(),
+                review.reviewer.to_string(),
+                review.agent.to_string(),
+                review.rating,
+                review.comment_uri,
+                review.created_at,
+            ],
+        )?;
+    } else if let Some(client) = decoded::<ClientState>(data) {
+        conn.execute(
+            "INSERT INTO clients VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                address,
+                client.owner.to_string(),
+                client.bounty_count,
+                optional_key(&client.referrer),
+                client.created_at,
+            ],
+        )?;
+    } else if let Some(contract) = decoded::<HourlyContract>(data) {
+        conn.execute(
+            "INSERT INTO hourly_contracts VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                address,
+                contract.client.to_string(),
+                contract.agent.to_string(),
+                contract.hourly_rate,
+                contract.cap,
+                contract.released,
+                contract.status.as_str(),
+                contract.created_at,
+            ],
+        )?;
+    } else if let Some(config) = decoded::<EscrowConfig>(data) {
+        conn.execute(
+            "INSERT INTO escrow_config VALUES (?1, ?2, ?3, ?4)",
+            params![
+                address,
+                config.admin.to_string(),
+                config.fee_bps,
+                optional_key(&config.treasury),
+            ],
+        )?;
+    } else if let Some(recurring) = decoded::<RecurringBounty>(data) {
+        conn.execute(
+            "INSERT INTO recurring_bounties VALUES
+                 (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                address,
+                recurring.client.to_string(),
+                recurring.template_id,
+                recurring.metadata_uri,
+                recurring.budget_per_period,
+                recurring.period_secs,
+                recurring.total_periods,
+                recurring.opened_periods,
+                recurring.next_open_at,
+                recurring.status.as_str(),
+                optional_key(&recurring.last_cycle),
+                recurring.created_at,
+            ],
+        )?;
+    } else if let Some(stream) = decoded::<Stream>(data) {
+        conn.execute(
+            "INSERT INTO streams VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                address,
+                stream.client.to_string(),
+                stream.agent.to_string(),
+                stream.total,
+                stream.withdrawn,
+                stream.status.as_str(),
+                stream.created_at,
+            ],
+        )?;
+    } else {
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+fn bounty_view(row: &Row) -> rusqlite::Result<Value> {
+    let skills: String = row.get(19)?;
+    Ok(json!({
+        "address": row.get::<_, String>(0)?,
+        "client": row.get::<_, String>(1)?,
+        "bounty_id": row.get::<_, u64>(2)?,
+        "metadata_uri": row.get::<_, String>(3)?,
+        "budget": row.get::<_, u64>(4)?,
+        "deadline": row.get::<_, i64>(5)?,
+        "status": row.get::<_, String>(6)?,
+        "assigned_agent": row.get::<_, Option<String>>(7)?,
+        "guild": row.get::<_, Option<String>>(8)?,
+        "parent": row.get::<_, Option<String>>(9)?,
+        "awaiting_parent_funds": row.get::<_, bool>(10)?,
+        "reserved_for_children": row.get::<_, u64>(11)?,
+        "tips_total": row.get::<_, u64>(12)?,
+        "created_at": row.get::<_, i64>(13)?,
+        "claimed_at": row.get::<_, Option<i64>>(14)?,
+        "delivered_at": row.get::<_, Option<i64>>(15)?,
+        "completed_at": row.get::<_, Option<i64>>(16)?,
+        "disputed_at": row.get::<_, Option<i64>>(17)?,
+        "cancelled_at": row.get::<_, Option<i64>>(18)?,
+        "skills": serde_json::from_str::<Value>(&skills).unwrap_or_default(),
+    }))
+}
+
+fn agent_view(row: &Row) -> rusqlite::Result<Value> {
+    let skills: String = row.get(9)?;
+    Ok(json!({
+        "address": row.get::<_, String>(0)?,
+        "owner": row.get::<_, String>(1)?,
+        "metadata_uri": row.get::<_, String>(2)?,
+        "hourly_rate": row.get::<_, u64>(3)?,
+        "availability": row.get::<_, String>(4)?,
+        "reputation": row.get::<_, u64>(5)?,
+        "bounties_completed": row.get::<_, u64>(6)?,
+        "total_earned": row.get::<_, u64>(7)?,
+        "active_claims": row.get::<_, u64>(8)?,
+        "skills": serde_json::from_str::<Value>(&skills).unwrap_or_default(),
+        "guild": row.get::<_, Option<String>>(10)?,
+        "created_at": row.get::<_, i64>(11)?,
+    }))
+}
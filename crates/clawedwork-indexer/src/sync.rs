@@ -0,0 +1,78 @@
+//! RPC polling of program accounts.
+
+use std::thread;
+use std::time::Duration;
+
+use anchor_lang::Discriminator;
+use anyhow::Result;
+use clawedwork_client::accounts::{
+    Agent, Bounty, BountyHistory, ClientState, EscrowConfig, Guild, HourlyContract,
+    RecurringBounty, Review, Stream,
+};
+use clawedwork_client::{AGENT_REGISTRY_ID, BOUNTY_ESCROW_ID};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{RawAccount, Store};
+
+/// Account types fetched on every poll, by owning program and discriminator.
+/// Both programs name their settings `Config`, so only the escrow's is fetched.
+const INDEXED: [(Pubkey, [u8; 8]); 10] = [
+    (BOUNTY_ESCROW_ID, EscrowConfig::DISCRIMINATOR),
+    (BOUNTY_ESCROW_ID, Bounty::DISCRIMINATOR),
+    (BOUNTY_ESCROW_ID, BountyHistory::DISCRIMINATOR),
+    (BOUNTY_ESCROW_ID, Review::DISCRIMINATOR),
+    (BOUNTY_ESCROW_ID, ClientState::DISCRIMINATOR),
+    (BOUNTY_ESCROW_ID, HourlyContract::DISCRIMINATOR),
+    (BOUNTY_ESCROW_ID, Stream::DISCRIMINATOR),
+    (BOUNTY_ESCROW_ID, RecurringBounty::DISCRIMINATOR),
+    (AGENT_REGISTRY_ID, Agent::DISCRIMINATOR),
+    (AGENT_REGISTRY_ID, Guild::DISCRIMINATOR),
+];
+
+/// Every indexed account, fetched at or after the returned slot.
+pub fn snapshot(rpc: &RpcClient) -> Result<(u64, Vec<RawAccount>)> {
+    let slot = rpc.get_slot()?;
+    let mut accounts = Vec::new();
+    for (program, discriminator) in INDEXED {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                0,
+                &discriminator,
+            ))]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..RpcAccountInfoConfig::default()
+            },
+            ..RpcProgramAccountsConfig::default()
+        };
+        accounts.extend(
+            rpc.get_program_accounts_with_config(&program, config)?
+                .into_iter()
+                .map(|(address, account)| (address, account.data)),
+        );
+    }
+    Ok((slot, accounts))
+}
+
+/// Fetches a snapshot and applies it, returning the slot and accounts indexed.
+pub fn sync(rpc: &RpcClient, store: &mut Store) -> Result<(u64, usize)> {
+    let (slot, accounts) = snapshot(rpc)?;
+    let indexed = store.apply(slot, &accounts)?;
+    Ok((slot, indexed))
+}
+
+/// Syncs every `interval` forever. Failed polls are logged and retried on the
+/// next tick, so a restarting validator only pauses the index. `store` should be
+/// a connection of its own, so queries keep being answered during a write.
+pub fn poll(rpc: RpcClient, mut store: Store, interval: Duration) {
+    loop {
+        if let Err(err) = sync(&rpc, &mut store) {
+            eprintln!("sync failed: {err:#}");
+        }
+        thread::sleep(interval);
+    }
+}
@@ -4,31 +4,359 @@ export type AgentRegistry = {
   address: string;
   metadata: { name: string; version: string; spec: string };
   instructions: [
+    {
+      name: "initConfig";
+      discriminator: number[];
+      accounts: [
+        { name: "config"; writable: true },
+        { name: "program" },
+        { name: "programData" },
+        { name: "admin"; writable: true; signer: true },
+        { name: "systemProgram" }
+      ];
+      args: [];
+    },
+    {
+      name: "setPaused";
+      discriminator: number[];
+      accounts: [
+        { name: "config"; writable: true },
+        { name: "admin"; signer: true }
+      ];
+      args: [{ name: "paused"; type: "u64" }];
+    },
+    {
+      name: "setAdmin";
+      discriminator: number[];
+      accounts: [
+        { name: "config"; writable: true },
+        { name: "admin"; signer: true }
+      ];
+      args: [{ name: "newAdmin"; type: "pubkey" }];
+    },
+    {
+      name: "initSkillRegistry";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "skillRegistry"; writable: true },
+        { name: "admin"; writable: true; signer: true },
+        { name: "systemProgram" }
+      ];
+      args: [];
+    },
+    {
+      name: "addSkill";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "skillRegistry"; writable: true },
+        { name: "admin"; signer: true }
+      ];
+      args: [{ name: "name"; type: "string" }];
+    },
+    {
+      name: "addVerifier";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "verifier"; writable: true },
+        { name: "authority" },
+        { name: "admin"; writable: true; signer: true },
+        { name: "systemProgram" }
+      ];
+      args: [];
+    },
+    {
+      name: "setVerifierActive";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "verifier"; writable: true },
+        { name: "admin"; signer: true }
+      ];
+      args: [{ name: "active"; type: "bool" }];
+    },
+    {
+      name: "issueAttestation";
+      discriminator: number[];
+      accounts: [
+        { name: "verifier" },
+        { name: "skillRegistry" },
+        { name: "agent" },
+        { name: "attestation"; writable: true },
+        { name: "authority"; signer: true },
+        { name: "payer"; writable: true; signer: true },
+        { name: "systemProgram" }
+      ];
+      args: [
+        { name: "skillId"; type: "u8" },
+        { name: "expiresAt"; type: "i64" },
+        { name: "evidenceHash"; type: { array: ["u8", 32] } }
+      ];
+    },
+    {
+      name: "revokeAttestation";
+      discriminator: number[];
+      accounts: [
+        { name: "attestation"; writable: true },
+        { name: "authority"; writable: true; signer: true }
+      ];
+      args: [];
+    },
+    {
+      name: "requestValidation";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "validation"; writable: true },
+        { name: "validator" },
+        { name: "requester"; writable: true; signer: true },
+        { name: "systemProgram" }
+      ];
+      args: [
+        { name: "bounty"; type: "pubkey" },
+        { name: "dataHash"; type: { array: ["u8", 32] } }
+      ];
+    },
+    {
+      name: "respondValidation";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "validation"; writable: true },
+        { name: "validator"; signer: true }
+      ];
+      args: [
+        { name: "score"; type: "u8" },
+        { name: "responseHash"; type: { array: ["u8", 32] } }
+      ];
+    },
     {
       name: "registerAgent";
       discriminator: number[];
       accounts: [
+        { name: "config" },
+        { name: "skillRegistry" },
         { name: "agent"; writable: true },
         { name: "owner"; writable: true; signer: true },
         { name: "systemProgram" }
       ];
       args: [
         { name: "metadataUri"; type: "string" },
-        { name: "hourlyRate"; type: "u64" }
+        { name: "metadataHash"; type: { array: ["u8", 32] } },
+        { name: "hourlyRate"; type: "u64" },
+        { name: "skills"; type: "u128" },
+        { name: "referrer"; type: { option: "pubkey" } }
       ];
     },
     {
       name: "updateAgent";
       discriminator: number[];
       accounts: [
+        { name: "config" },
+        { name: "skillRegistry" },
         { name: "agent"; writable: true },
         { name: "owner"; signer: true }
       ];
       args: [
         { name: "metadataUri"; type: { option: "string" } },
+        { name: "metadataHash"; type: { option: { array: ["u8", 32] } } },
         { name: "hourlyRate"; type: { option: "u64" } },
-        { name: "availability"; type: { option: "u8" } }
+        { name: "availability"; type: { option: { defined: { name: "agentStatus" } } } },
+        { name: "skills"; type: { option: "u128" } }
+      ];
+    },
+    {
+      name: "addDelegate";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "agent"; writable: true },
+        { name: "agentDelegate"; writable: true },
+        { name: "delegate" },
+        { name: "owner"; writable: true; signer: true },
+        { name: "systemProgram" }
+      ];
+      args: [{ name: "permissions"; type: "u8" }];
+    },
+    {
+      name: "updateDelegate";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "agent" },
+        { name: "agentDelegate"; writable: true },
+        { name: "owner"; signer: true }
+      ];
+      args: [{ name: "permissions"; type: "u8" }];
+    },
+    {
+      name: "removeDelegate";
+      discriminator: number[];
+      accounts: [
+        { name: "agent"; writable: true },
+        { name: "agentDelegate"; writable: true },
+        { name: "owner"; writable: true; signer: true }
+      ];
+      args: [];
+    },
+    {
+      name: "setAvailability";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "agent"; writable: true },
+        { name: "agentDelegate"; optional: true },
+        { name: "authority"; signer: true }
+      ];
+      args: [{ name: "availability"; type: { defined: { name: "agentStatus" } } }];
+    },
+    {
+      name: "proposeOwnerTransfer";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "agent"; writable: true },
+        { name: "owner"; signer: true }
+      ];
+      args: [{ name: "newOwner"; type: "pubkey" }];
+    },
+    {
+      name: "cancelOwnerTransfer";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "agent"; writable: true },
+        { name: "owner"; signer: true }
+      ];
+      args: [];
+    },
+    {
+      name: "acceptOwnerTransfer";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "oldAgent"; writable: true },
+        { name: "newAgent"; writable: true },
+        { name: "newOwner"; writable: true; signer: true },
+        { name: "systemProgram" }
+      ];
+      args: [];
+    },
+    {
+      name: "deregisterAgent";
+      discriminator: number[];
+      accounts: [
+        { name: "agent"; writable: true },
+        { name: "archive"; writable: true; optional: true },
+        { name: "owner"; writable: true; signer: true },
+        { name: "systemProgram" }
+      ];
+      args: [];
+    },
+    {
+      name: "migrateAgent";
+      discriminator: number[];
+      accounts: [
+        { name: "agent"; writable: true },
+        { name: "payer"; writable: true; signer: true },
+        { name: "systemProgram" }
       ];
+      args: [];
+    },
+    {
+      name: "createGuild";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "guild"; writable: true },
+        { name: "creatorAgent"; writable: true },
+        { name: "creator"; writable: true; signer: true },
+        { name: "systemProgram" }
+      ];
+      args: [
+        { name: "guildId"; type: "u64" },
+        { name: "metadataUri"; type: "string" },
+        { name: "metadataHash"; type: { array: ["u8", 32] } }
+      ];
+    },
+    {
+      name: "addGuildMember";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "guild"; writable: true },
+        { name: "adminAgent" },
+        { name: "admin"; signer: true },
+        { name: "memberAgent"; writable: true },
+        { name: "member"; signer: true }
+      ];
+      args: [];
+    },
+    {
+      name: "removeGuildMember";
+      discriminator: number[];
+      accounts: [
+        { name: "guild"; writable: true },
+        { name: "authorityAgent" },
+        { name: "authority"; signer: true },
+        { name: "memberAgent"; writable: true }
+      ];
+      args: [];
+    },
+    {
+      name: "closeGuild";
+      discriminator: number[];
+      accounts: [
+        { name: "guild"; writable: true },
+        { name: "founderAgent"; writable: true },
+        { name: "founder"; writable: true; signer: true }
+      ];
+      args: [];
+    },
+    {
+      name: "setGuildSplit";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "guild"; writable: true },
+        { name: "adminAgent" },
+        { name: "admin"; signer: true }
+      ];
+      args: [{ name: "shares"; type: { vec: "u16" } }];
+    },
+    {
+      name: "setGuildRole";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "guild"; writable: true },
+        { name: "adminAgent" },
+        { name: "admin"; signer: true }
+      ];
+      args: [
+        { name: "agent"; type: "pubkey" },
+        { name: "role"; type: { defined: { name: "guildRole" } } }
+      ];
+    },
+    {
+      name: "recordClaim";
+      discriminator: number[];
+      accounts: [
+        { name: "agent"; writable: true },
+        { name: "authority"; signer: true }
+      ];
+      args: [];
+    },
+    {
+      name: "releaseClaim";
+      discriminator: number[];
+      accounts: [
+        { name: "agent"; writable: true },
+        { name: "authority"; signer: true }
+      ];
+      args: [];
     },
     {
       name: "updateReputation";
@@ -47,72 +375,626 @@ export type AgentRegistry = {
         { name: "authority"; signer: true }
       ];
       args: [{ name: "amount"; type: "u64" }];
-    }
-  ];
-  accounts: [
+    },
     {
-      name: "agent";
+      name: "recordReferralReward";
+      discriminator: number[];
+      accounts: [
+        { name: "agent"; writable: true },
+        { name: "authority"; signer: true }
+      ];
+      args: [{ name: "amount"; type: "u64" }];
+    },
+    {
+      name: "recordGuildClaim";
+      discriminator: number[];
+      accounts: [
+        { name: "guild"; writable: true },
+        { name: "authority"; signer: true }
+      ];
+      args: [];
+    },
+    {
+      name: "settleGuildClaim";
+      discriminator: number[];
+      accounts: [
+        { name: "guild"; writable: true },
+        { name: "authority"; signer: true }
+      ];
+      args: [{ name: "amount"; type: "u64" }];
+    },
+    {
+      name: "updateGuildReputation";
       discriminator: number[];
+      accounts: [
+        { name: "guild"; writable: true },
+        { name: "authority"; signer: true }
+      ];
+      args: [{ name: "newRating"; type: "u64" }];
     }
   ];
+  accounts: [
+    { name: "agent"; discriminator: number[] },
+    { name: "agentArchive"; discriminator: number[] },
+    { name: "agentDelegate"; discriminator: number[] },
+    { name: "attestation"; discriminator: number[] },
+    { name: "config"; discriminator: number[] },
+    { name: "guild"; discriminator: number[] },
+    { name: "skillRegistry"; discriminator: number[] },
+    { name: "validation"; discriminator: number[] },
+    { name: "verifier"; discriminator: number[] }
+  ];
   types: [
     {
       name: "agent";
       type: {
         kind: "struct";
         fields: [
+          { name: "version"; type: "u8" },
           { name: "owner"; type: "pubkey" },
           { name: "metadataUri"; type: "string" },
+          { name: "metadataHash"; type: { array: ["u8", 32] } },
           { name: "hourlyRate"; type: "u64" },
           { name: "reputation"; type: "u64" },
           { name: "bountiesCompleted"; type: "u64" },
           { name: "totalEarned"; type: "u64" },
-          { name: "availability"; type: "u8" },
+          { name: "availability"; type: { defined: { name: "agentStatus" } } },
           { name: "bump"; type: "u8" },
-          { name: "createdAt"; type: "i64" }
+          { name: "createdAt"; type: "i64" },
+          { name: "pendingOwner"; type: "pubkey" },
+          { name: "successor"; type: "pubkey" },
+          { name: "activeClaims"; type: "u64" },
+          { name: "delegateCount"; type: "u64" },
+          { name: "skills"; type: "u128" },
+          { name: "guild"; type: "pubkey" },
+          { name: "referrer"; type: "pubkey" },
+          { name: "referralPaid"; type: "u64" }
         ];
       };
-    }
-  ];
-  errors: [
-    { code: 6000; name: "UriTooLong"; msg: "Metadata URI exceeds maximum length of 200 characters" },
-    { code: 6001; name: "InvalidHourlyRate"; msg: "Hourly rate must be greater than 0" },
-    { code: 6002; name: "InvalidAvailability"; msg: "Availability must be 0 (Available), 1 (Busy), or 2 (Offline)" },
-    { code: 6003; name: "InvalidRating"; msg: "Rating must be between 1 and 500 (fixed-point * 100)" }
-  ];
-};
-
-export const IDL: AgentRegistry = {
-  version: "0.1.0",
-  name: "agent_registry",
-  address: "DiLuZ4JcnyFcE6FttH5NryQJrM2KKewy2Z8oDk9iJXNF",
-  metadata: { name: "agent_registry", version: "0.1.0", spec: "0.1.0" },
-  instructions: [
-    {
-      name: "registerAgent",
-      discriminator: [135, 157, 66, 195, 2, 113, 175, 30],
-      accounts: [
-        { name: "agent", writable: true },
-        { name: "owner", writable: true, signer: true },
-        { name: "systemProgram" },
-      ],
-      args: [
-        { name: "metadataUri", type: "string" },
-        { name: "hourlyRate", type: "u64" },
-      ],
     },
     {
-      name: "updateAgent",
-      discriminator: [85, 2, 178, 9, 119, 139, 102, 164],
-      accounts: [
-        { name: "agent", writable: true },
-        { name: "owner", signer: true },
-      ],
-      args: [
-        { name: "metadataUri", type: { option: "string" } },
-        { name: "hourlyRate", type: { option: "u64" } },
-        { name: "availability", type: { option: "u8" } },
-      ],
+      name: "agentArchive";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "agent"; type: "pubkey" },
+          { name: "owner"; type: "pubkey" },
+          { name: "reputation"; type: "u64" },
+          { name: "bountiesCompleted"; type: "u64" },
+          { name: "totalEarned"; type: "u64" },
+          { name: "createdAt"; type: "i64" },
+          { name: "deregisteredAt"; type: "i64" },
+          { name: "bump"; type: "u8" }
+        ];
+      };
+    },
+    {
+      name: "agentDelegate";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "agent"; type: "pubkey" },
+          { name: "delegate"; type: "pubkey" },
+          { name: "permissions"; type: "u8" },
+          { name: "bump"; type: "u8" },
+          { name: "createdAt"; type: "i64" }
+        ];
+      };
+    },
+    {
+      name: "agentStatus";
+      type: {
+        kind: "enum";
+        variants: [
+          { name: "available" },
+          { name: "busy" },
+          { name: "offline" }
+        ];
+      };
+    },
+    {
+      name: "attestation";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "agent"; type: "pubkey" },
+          { name: "skillId"; type: "u8" },
+          { name: "verifier"; type: "pubkey" },
+          { name: "evidenceHash"; type: { array: ["u8", 32] } },
+          { name: "issuedAt"; type: "i64" },
+          { name: "expiresAt"; type: "i64" },
+          { name: "bump"; type: "u8" }
+        ];
+      };
+    },
+    {
+      name: "config";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "admin"; type: "pubkey" },
+          { name: "paused"; type: "u64" },
+          { name: "bump"; type: "u8" }
+        ];
+      };
+    },
+    {
+      name: "guild";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "creator"; type: "pubkey" },
+          { name: "guildId"; type: "u64" },
+          { name: "metadataUri"; type: "string" },
+          { name: "metadataHash"; type: { array: ["u8", 32] } },
+          { name: "members"; type: { vec: { defined: { name: "guildMember" } } } },
+          { name: "reputation"; type: "u64" },
+          { name: "bountiesCompleted"; type: "u64" },
+          { name: "totalEarned"; type: "u64" },
+          { name: "activeClaims"; type: "u64" },
+          { name: "bump"; type: "u8" },
+          { name: "createdAt"; type: "i64" }
+        ];
+      };
+    },
+    {
+      name: "guildMember";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "agent"; type: "pubkey" },
+          { name: "role"; type: { defined: { name: "guildRole" } } },
+          { name: "shareBps"; type: "u16" }
+        ];
+      };
+    },
+    {
+      name: "guildRole";
+      type: {
+        kind: "enum";
+        variants: [
+          { name: "admin" },
+          { name: "member" }
+        ];
+      };
+    },
+    {
+      name: "skillRegistry";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "names"; type: { vec: "string" } },
+          { name: "bump"; type: "u8" }
+        ];
+      };
+    },
+    {
+      name: "validation";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "bounty"; type: "pubkey" },
+          { name: "requester"; type: "pubkey" },
+          { name: "validator"; type: "pubkey" },
+          { name: "dataHash"; type: { array: ["u8", 32] } },
+          { name: "score"; type: "u8" },
+          { name: "responseHash"; type: { array: ["u8", 32] } },
+          { name: "responded"; type: "bool" },
+          { name: "requestedAt"; type: "i64" },
+          { name: "respondedAt"; type: "i64" },
+          { name: "bump"; type: "u8" }
+        ];
+      };
+    },
+    {
+      name: "verifier";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "authority"; type: "pubkey" },
+          { name: "active"; type: "bool" },
+          { name: "bump"; type: "u8" },
+          { name: "createdAt"; type: "i64" }
+        ];
+      };
+    }
+  ];
+  errors: [
+    { code: 6000; name: "UriTooLong"; msg: "Metadata URI exceeds maximum length of 512 characters" },
+    { code: 6001; name: "InvalidHourlyRate"; msg: "Hourly rate must be greater than 0" },
+    { code: 6002; name: "InvalidAvailability"; msg: "Availability must be 0 (Available), 1 (Busy), or 2 (Offline)" },
+    { code: 6003; name: "InvalidRating"; msg: "Rating must be between 1 and 500 (fixed-point * 100)" },
+    { code: 6004; name: "InvalidDelegatePermissions"; msg: "Delegate permissions must be a non-empty combination of claim, submit and availability" },
+    { code: 6005; name: "DelegateNotAuthorized"; msg: "Signer is not an authorized delegate for this agent" },
+    { code: 6006; name: "InvalidNewOwner"; msg: "New owner must be a different, non-default wallet" },
+    { code: 6007; name: "NoPendingTransfer"; msg: "No ownership transfer is pending for this wallet" },
+    { code: 6008; name: "AgentTransferred"; msg: "Agent has been transferred to a new owner" },
+    { code: 6009; name: "ActiveClaims"; msg: "Agent still has claimed bounties in flight" },
+    { code: 6010; name: "DelegatesRemain"; msg: "Remove all delegates before deregistering" },
+    { code: 6011; name: "UnauthorizedCaller"; msg: "Caller is not the bounty-escrow program" },
+    { code: 6012; name: "MissingContentHash"; msg: "A non-zero content hash must accompany every URI" },
+    { code: 6013; name: "AlreadyMigrated"; msg: "Account is already on the current layout" },
+    { code: 6014; name: "UnknownAccountVersion"; msg: "Account is not an agent account of a known layout" },
+    { code: 6015; name: "Paused"; msg: "This instruction is paused by the admin" },
+    { code: 6016; name: "Unauthorized"; msg: "Unauthorized" },
+    { code: 6017; name: "InvalidSkillName"; msg: "Skill name must be 1-32 characters and not already defined" },
+    { code: 6018; name: "SkillRegistryFull"; msg: "Skill registry already holds the maximum number of skills" },
+    { code: 6019; name: "UnknownSkill"; msg: "Skills bitmap references an undefined skill id" },
+    { code: 6020; name: "InvalidExpiry"; msg: "Expiry must be 0 or in the future" },
+    { code: 6021; name: "VerifierInactive"; msg: "Verifier has been disabled by the admin" },
+    { code: 6022; name: "InvalidValidationScore"; msg: "Validation score must be between 0 and 100" },
+    { code: 6023; name: "InGuild"; msg: "Agent belongs to a guild; leave it first" },
+    { code: 6024; name: "NotGuildAdmin"; msg: "Signer is not an admin of this guild" },
+    { code: 6025; name: "NotGuildMember"; msg: "Agent is not a member of this guild" },
+    { code: 6026; name: "GuildFull"; msg: "Guild already has the maximum number of members" },
+    { code: 6027; name: "CannotRemoveFounder"; msg: "The founding admin cannot be removed or demoted" },
+    { code: 6028; name: "InvalidGuildSplit"; msg: "Shares must cover every member and sum to 10,000 bps" },
+    { code: 6029; name: "InvalidGuildRole"; msg: "Invalid guild role" },
+    { code: 6030; name: "InvalidReferrer"; msg: "An agent cannot refer itself" },
+    { code: 6031; name: "NoActiveClaims"; msg: "No claimed bounty is in flight to release" },
+    { code: 6032; name: "GuildHasMembers"; msg: "Remove every other member before closing the guild" }
+  ];
+};
+
+export const IDL: AgentRegistry = {
+  version: "0.1.0",
+  name: "agent_registry",
+  address: "DiLuZ4JcnyFcE6FttH5NryQJrM2KKewy2Z8oDk9iJXNF",
+  metadata: { name: "agent_registry", version: "0.1.0", spec: "0.1.0" },
+  instructions: [
+    {
+      name: "initConfig",
+      discriminator: [23, 235, 115, 232, 168, 96, 1, 231],
+      accounts: [
+        { name: "config", writable: true },
+        { name: "program" },
+        { name: "programData" },
+        { name: "admin", writable: true, signer: true },
+        { name: "systemProgram" },
+      ],
+      args: [],
+    },
+    {
+      name: "setPaused",
+      discriminator: [91, 60, 125, 192, 176, 225, 166, 218],
+      accounts: [
+        { name: "config", writable: true },
+        { name: "admin", signer: true },
+      ],
+      args: [{ name: "paused", type: "u64" }],
+    },
+    {
+      name: "setAdmin",
+      discriminator: [251, 163, 0, 52, 91, 194, 187, 92],
+      accounts: [
+        { name: "config", writable: true },
+        { name: "admin", signer: true },
+      ],
+      args: [{ name: "newAdmin", type: "pubkey" }],
+    },
+    {
+      name: "initSkillRegistry",
+      discriminator: [174, 170, 112, 239, 212, 79, 224, 217],
+      accounts: [
+        { name: "config" },
+        { name: "skillRegistry", writable: true },
+        { name: "admin", writable: true, signer: true },
+        { name: "systemProgram" },
+      ],
+      args: [],
+    },
+    {
+      name: "addSkill",
+      discriminator: [193, 213, 92, 116, 27, 180, 153, 6],
+      accounts: [
+        { name: "config" },
+        { name: "skillRegistry", writable: true },
+        { name: "admin", signer: true },
+      ],
+      args: [{ name: "name", type: "string" }],
+    },
+    {
+      name: "addVerifier",
+      discriminator: [165, 72, 135, 225, 67, 181, 255, 135],
+      accounts: [
+        { name: "config" },
+        { name: "verifier", writable: true },
+        { name: "authority" },
+        { name: "admin", writable: true, signer: true },
+        { name: "systemProgram" },
+      ],
+      args: [],
+    },
+    {
+      name: "setVerifierActive",
+      discriminator: [110, 160, 142, 137, 87, 162, 141, 124],
+      accounts: [
+        { name: "config" },
+        { name: "verifier", writable: true },
+        { name: "admin", signer: true },
+      ],
+      args: [{ name: "active", type: "bool" }],
+    },
+    {
+      name: "issueAttestation",
+      discriminator: [18, 115, 85, 100, 231, 31, 242, 143],
+      accounts: [
+        { name: "verifier" },
+        { name: "skillRegistry" },
+        { name: "agent" },
+        { name: "attestation", writable: true },
+        { name: "authority", signer: true },
+        { name: "payer", writable: true, signer: true },
+        { name: "systemProgram" },
+      ],
+      args: [
+        { name: "skillId", type: "u8" },
+        { name: "expiresAt", type: "i64" },
+        { name: "evidenceHash", type: { array: ["u8", 32] } },
+      ],
+    },
+    {
+      name: "revokeAttestation",
+      discriminator: [12, 156, 103, 161, 194, 246, 211, 179],
+      accounts: [
+        { name: "attestation", writable: true },
+        { name: "authority", writable: true, signer: true },
+      ],
+      args: [],
+    },
+    {
+      name: "requestValidation",
+      discriminator: [72, 26, 53, 67, 228, 30, 144, 53],
+      accounts: [
+        { name: "config" },
+        { name: "validation", writable: true },
+        { name: "validator" },
+        { name: "requester", writable: true, signer: true },
+        { name: "systemProgram" },
+      ],
+      args: [
+        { name: "bounty", type: "pubkey" },
+        { name: "dataHash", type: { array: ["u8", 32] } },
+      ],
+    },
+    {
+      name: "respondValidation",
+      discriminator: [236, 56, 215, 65, 48, 79, 224, 159],
+      accounts: [
+        { name: "config" },
+        { name: "validation", writable: true },
+        { name: "validator", signer: true },
+      ],
+      args: [
+        { name: "score", type: "u8" },
+        { name: "responseHash", type: { array: ["u8", 32] } },
+      ],
+    },
+    {
+      name: "registerAgent",
+      discriminator: [135, 157, 66, 195, 2, 113, 175, 30],
+      accounts: [
+        { name: "config" },
+        { name: "skillRegistry" },
+        { name: "agent", writable: true },
+        { name: "owner", writable: true, signer: true },
+        { name: "systemProgram" },
+      ],
+      args: [
+        { name: "metadataUri", type: "string" },
+        { name: "metadataHash", type: { array: ["u8", 32] } },
+        { name: "hourlyRate", type: "u64" },
+        { name: "skills", type: "u128" },
+        { name: "referrer", type: { option: "pubkey" } },
+      ],
+    },
+    {
+      name: "updateAgent",
+      discriminator: [85, 2, 178, 9, 119, 139, 102, 164],
+      accounts: [
+        { name: "config" },
+        { name: "skillRegistry" },
+        { name: "agent", writable: true },
+        { name: "owner", signer: true },
+      ],
+      args: [
+        { name: "metadataUri", type: { option: "string" } },
+        { name: "metadataHash", type: { option: { array: ["u8", 32] } } },
+        { name: "hourlyRate", type: { option: "u64" } },
+        { name: "availability", type: { option: { defined: { name: "agentStatus" } } } },
+        { name: "skills", type: { option: "u128" } },
+      ],
+    },
+    {
+      name: "addDelegate",
+      discriminator: [3, 67, 128, 218, 69, 139, 53, 88],
+      accounts: [
+        { name: "config" },
+        { name: "agent", writable: true },
+        { name: "agentDelegate", writable: true },
+        { name: "delegate" },
+        { name: "owner", writable: true, signer: true },
+        { name: "systemProgram" },
+      ],
+      args: [{ name: "permissions", type: "u8" }],
+    },
+    {
+      name: "updateDelegate",
+      discriminator: [190, 202, 103, 138, 167, 197, 25, 9],
+      accounts: [
+        { name: "config" },
+        { name: "agent" },
+        { name: "agentDelegate", writable: true },
+        { name: "owner", signer: true },
+      ],
+      args: [{ name: "permissions", type: "u8" }],
+    },
+    {
+      name: "removeDelegate",
+      discriminator: [94, 37, 16, 59, 7, 84, 97, 211],
+      accounts: [
+        { name: "agent", writable: true },
+        { name: "agentDelegate", writable: true },
+        { name: "owner", writable: true, signer: true },
+      ],
+      args: [],
+    },
+    {
+      name: "setAvailability",
+      discriminator: [76, 130, 122, 165, 103, 22, 221, 215],
+      accounts: [
+        { name: "config" },
+        { name: "agent", writable: true },
+        { name: "agentDelegate", optional: true },
+        { name: "authority", signer: true },
+      ],
+      args: [{ name: "availability", type: { defined: { name: "agentStatus" } } }],
+    },
+    {
+      name: "proposeOwnerTransfer",
+      discriminator: [35, 9, 48, 148, 207, 238, 26, 197],
+      accounts: [
+        { name: "config" },
+        { name: "agent", writable: true },
+        { name: "owner", signer: true },
+      ],
+      args: [{ name: "newOwner", type: "pubkey" }],
+    },
+    {
+      name: "cancelOwnerTransfer",
+      discriminator: [70, 15, 239, 95, 119, 251, 21, 149],
+      accounts: [
+        { name: "config" },
+        { name: "agent", writable: true },
+        { name: "owner", signer: true },
+      ],
+      args: [],
+    },
+    {
+      name: "acceptOwnerTransfer",
+      discriminator: [146, 27, 195, 174, 65, 44, 6, 112],
+      accounts: [
+        { name: "config" },
+        { name: "oldAgent", writable: true },
+        { name: "newAgent", writable: true },
+        { name: "newOwner", writable: true, signer: true },
+        { name: "systemProgram" },
+      ],
+      args: [],
+    },
+    {
+      name: "deregisterAgent",
+      discriminator: [227, 208, 166, 164, 48, 69, 111, 1],
+      accounts: [
+        { name: "agent", writable: true },
+        { name: "archive", writable: true, optional: true },
+        { name: "owner", writable: true, signer: true },
+        { name: "systemProgram" },
+      ],
+      args: [],
+    },
+    {
+      name: "migrateAgent",
+      discriminator: [102, 150, 249, 223, 92, 169, 131, 39],
+      accounts: [
+        { name: "agent", writable: true },
+        { name: "payer", writable: true, signer: true },
+        { name: "systemProgram" },
+      ],
+      args: [],
+    },
+    {
+      name: "createGuild",
+      discriminator: [163, 27, 97, 167, 132, 198, 53, 168],
+      accounts: [
+        { name: "config" },
+        { name: "guild", writable: true },
+        { name: "creatorAgent", writable: true },
+        { name: "creator", writable: true, signer: true },
+        { name: "systemProgram" },
+      ],
+      args: [
+        { name: "guildId", type: "u64" },
+        { name: "metadataUri", type: "string" },
+        { name: "metadataHash", type: { array: ["u8", 32] } },
+      ],
+    },
+    {
+      name: "addGuildMember",
+      discriminator: [99, 186, 222, 187, 26, 102, 81, 122],
+      accounts: [
+        { name: "config" },
+        { name: "guild", writable: true },
+        { name: "adminAgent" },
+        { name: "admin", signer: true },
+        { name: "memberAgent", writable: true },
+        { name: "member", signer: true },
+      ],
+      args: [],
+    },
+    {
+      name: "removeGuildMember",
+      discriminator: [65, 44, 171, 123, 249, 59, 145, 54],
+      accounts: [
+        { name: "guild", writable: true },
+        { name: "authorityAgent" },
+        { name: "authority", signer: true },
+        { name: "memberAgent", writable: true },
+      ],
+      args: [],
+    },
+    {
+      name: "closeGuild",
+      discriminator: [243, 101, 242, 36, 24, 136, 102, 119],
+      accounts: [
+        { name: "guild", writable: true },
+        { name: "founderAgent", writable: true },
+        { name: "founder", writable: true, signer: true },
+      ],
+      args: [],
+    },
+    {
+      name: "setGuildSplit",
+      discriminator: [0, 27, 74, 217, 203, 89, 220, 153],
+      accounts: [
+        { name: "config" },
+        { name: "guild", writable: true },
+        { name: "adminAgent" },
+        { name: "admin", signer: true },
+      ],
+      args: [{ name: "shares", type: { vec: "u16" } }],
+    },
+    {
+      name: "setGuildRole",
+      discriminator: [229, 2, 127, 213, 117, 31, 199, 117],
+      accounts: [
+        { name: "config" },
+        { name: "guild", writable: true },
+        { name: "adminAgent" },
+        { name: "admin", signer: true },
+      ],
+      args: [
+        { name: "agent", type: "pubkey" },
+        { name: "role", type: { defined: { name: "guildRole" } } },
+      ],
+    },
+    {
+      name: "recordClaim",
+      discriminator: [111, 123, 69, 192, 195, 228, 137, 30],
+      accounts: [
+        { name: "agent", writable: true },
+        { name: "authority", signer: true },
+      ],
+      args: [],
+    },
+    {
+      name: "releaseClaim",
+      discriminator: [109, 194, 80, 47, 30, 93, 16, 253],
+      accounts: [
+        { name: "agent", writable: true },
+        { name: "authority", signer: true },
+      ],
+      args: [],
     },
     {
       name: "updateReputation",
@@ -132,12 +1014,53 @@ export const IDL: AgentRegistry = {
       ],
       args: [{ name: "amount", type: "u64" }],
     },
-  ],
-  accounts: [
     {
-      name: "agent",
-      discriminator: [47, 166, 112, 147, 155, 197, 86, 7],
+      name: "recordReferralReward",
+      discriminator: [27, 127, 30, 91, 134, 70, 224, 251],
+      accounts: [
+        { name: "agent", writable: true },
+        { name: "authority", signer: true },
+      ],
+      args: [{ name: "amount", type: "u64" }],
+    },
+    {
+      name: "recordGuildClaim",
+      discriminator: [15, 92, 47, 117, 158, 117, 170, 9],
+      accounts: [
+        { name: "guild", writable: true },
+        { name: "authority", signer: true },
+      ],
+      args: [],
+    },
+    {
+      name: "settleGuildClaim",
+      discriminator: [247, 228, 112, 183, 166, 221, 115, 14],
+      accounts: [
+        { name: "guild", writable: true },
+        { name: "authority", signer: true },
+      ],
+      args: [{ name: "amount", type: "u64" }],
     },
+    {
+      name: "updateGuildReputation",
+      discriminator: [195, 131, 121, 173, 187, 7, 194, 92],
+      accounts: [
+        { name: "guild", writable: true },
+        { name: "authority", signer: true },
+      ],
+      args: [{ name: "newRating", type: "u64" }],
+    },
+  ],
+  accounts: [
+    { name: "agent", discriminator: [47, 166, 112, 147, 155, 197, 86, 7] },
+    { name: "agentArchive", discriminator: [120, 33, 108, 97, 167, 222, 59, 200] },
+    { name: "agentDelegate", discriminator: [56, 66, 16, 224, 14, 98, 112, 142] },
+    { name: "attestation", discriminator: [152, 125, 183, 86, 36, 146, 121, 73] },
+    { name: "config", discriminator: [155, 12, 170, 224, 30, 250, 204, 130] },
+    { name: "guild", discriminator: [74, 176, 57, 164, 195, 188, 156, 237] },
+    { name: "skillRegistry", discriminator: [100, 187, 221, 130, 166, 188, 159, 174] },
+    { name: "validation", discriminator: [130, 241, 151, 113, 169, 195, 219, 148] },
+    { name: "verifier", discriminator: [195, 177, 185, 71, 72, 61, 77, 112] },
   ],
   types: [
     {
@@ -145,13 +1068,169 @@ export const IDL: AgentRegistry = {
       type: {
         kind: "struct",
         fields: [
+          { name: "version", type: "u8" },
           { name: "owner", type: "pubkey" },
           { name: "metadataUri", type: "string" },
+          { name: "metadataHash", type: { array: ["u8", 32] } },
           { name: "hourlyRate", type: "u64" },
           { name: "reputation", type: "u64" },
           { name: "bountiesCompleted", type: "u64" },
           { name: "totalEarned", type: "u64" },
-          { name: "availability", type: "u8" },
+          { name: "availability", type: { defined: { name: "agentStatus" } } },
+          { name: "bump", type: "u8" },
+          { name: "createdAt", type: "i64" },
+          { name: "pendingOwner", type: "pubkey" },
+          { name: "successor", type: "pubkey" },
+          { name: "activeClaims", type: "u64" },
+          { name: "delegateCount", type: "u64" },
+          { name: "skills", type: "u128" },
+          { name: "guild", type: "pubkey" },
+          { name: "referrer", type: "pubkey" },
+          { name: "referralPaid", type: "u64" },
+        ],
+      },
+    },
+    {
+      name: "agentArchive",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "agent", type: "pubkey" },
+          { name: "owner", type: "pubkey" },
+          { name: "reputation", type: "u64" },
+          { name: "bountiesCompleted", type: "u64" },
+          { name: "totalEarned", type: "u64" },
+          { name: "createdAt", type: "i64" },
+          { name: "deregisteredAt", type: "i64" },
+          { name: "bump", type: "u8" },
+        ],
+      },
+    },
+    {
+      name: "agentDelegate",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "agent", type: "pubkey" },
+          { name: "delegate", type: "pubkey" },
+          { name: "permissions", type: "u8" },
+          { name: "bump", type: "u8" },
+          { name: "createdAt", type: "i64" },
+        ],
+      },
+    },
+    {
+      name: "agentStatus",
+      type: {
+        kind: "enum",
+        variants: [
+          { name: "available" },
+          { name: "busy" },
+          { name: "offline" },
+        ],
+      },
+    },
+    {
+      name: "attestation",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "agent", type: "pubkey" },
+          { name: "skillId", type: "u8" },
+          { name: "verifier", type: "pubkey" },
+          { name: "evidenceHash", type: { array: ["u8", 32] } },
+          { name: "issuedAt", type: "i64" },
+          { name: "expiresAt", type: "i64" },
+          { name: "bump", type: "u8" },
+        ],
+      },
+    },
+    {
+      name: "config",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "admin", type: "pubkey" },
+          { name: "paused", type: "u64" },
+          { name: "bump", type: "u8" },
+        ],
+      },
+    },
+    {
+      name: "guild",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "creator", type: "pubkey" },
+          { name: "guildId", type: "u64" },
+          { name: "metadataUri", type: "string" },
+          { name: "metadataHash", type: { array: ["u8", 32] } },
+          { name: "members", type: { vec: { defined: { name: "guildMember" } } } },
+          { name: "reputation", type: "u64" },
+          { name: "bountiesCompleted", type: "u64" },
+          { name: "totalEarned", type: "u64" },
+          { name: "activeClaims", type: "u64" },
+          { name: "bump", type: "u8" },
+          { name: "createdAt", type: "i64" },
+        ],
+      },
+    },
+    {
+      name: "guildMember",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "agent", type: "pubkey" },
+          { name: "role", type: { defined: { name: "guildRole" } } },
+          { name: "shareBps", type: "u16" },
+        ],
+      },
+    },
+    {
+      name: "guildRole",
+      type: {
+        kind: "enum",
+        variants: [
+          { name: "admin" },
+          { name: "member" },
+        ],
+      },
+    },
+    {
+      name: "skillRegistry",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "names", type: { vec: "string" } },
+          { name: "bump", type: "u8" },
+        ],
+      },
+    },
+    {
+      name: "validation",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "bounty", type: "pubkey" },
+          { name: "requester", type: "pubkey" },
+          { name: "validator", type: "pubkey" },
+          { name: "dataHash", type: { array: ["u8", 32] } },
+          { name: "score", type: "u8" },
+          { name: "responseHash", type: { array: ["u8", 32] } },
+          { name: "responded", type: "bool" },
+          { name: "requestedAt", type: "i64" },
+          { name: "respondedAt", type: "i64" },
+          { name: "bump", type: "u8" },
+        ],
+      },
+    },
+    {
+      name: "verifier",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "authority", type: "pubkey" },
+          { name: "active", type: "bool" },
           { name: "bump", type: "u8" },
           { name: "createdAt", type: "i64" },
         ],
@@ -159,9 +1238,38 @@ export const IDL: AgentRegistry = {
     },
   ],
   errors: [
-    { code: 6000, name: "UriTooLong", msg: "Metadata URI exceeds maximum length of 200 characters" },
+    { code: 6000, name: "UriTooLong", msg: "Metadata URI exceeds maximum length of 512 characters" },
     { code: 6001, name: "InvalidHourlyRate", msg: "Hourly rate must be greater than 0" },
     { code: 6002, name: "InvalidAvailability", msg: "Availability must be 0 (Available), 1 (Busy), or 2 (Offline)" },
     { code: 6003, name: "InvalidRating", msg: "Rating must be between 1 and 500 (fixed-point * 100)" },
+    { code: 6004, name: "InvalidDelegatePermissions", msg: "Delegate permissions must be a non-empty combination of claim, submit and availability" },
+    { code: 6005, name: "DelegateNotAuthorized", msg: "Signer is not an authorized delegate for this agent" },
+    { code: 6006, name: "InvalidNewOwner", msg: "New owner must be a different, non-default wallet" },
+    { code: 6007, name: "NoPendingTransfer", msg: "No ownership transfer is pending for this wallet" },
+    { code: 6008, name: "AgentTransferred", msg: "Agent has been transferred to a new owner" },
+    { code: 6009, name: "ActiveClaims", msg: "Agent still has claimed bounties in flight" },
+    { code: 6010, name: "DelegatesRemain", msg: "Remove all delegates before deregistering" },
+    { code: 6011, name: "UnauthorizedCaller", msg: "Caller is not the bounty-escrow program" },
+    { code: 6012, name: "MissingContentHash", msg: "A non-zero content hash must accompany every URI" },
+    { code: 6013, name: "AlreadyMigrated", msg: "Account is already on the current layout" },
+    { code: 6014, name: "UnknownAccountVersion", msg: "Account is not an agent account of a known layout" },
+    { code: 6015, name: "Paused", msg: "This instruction is paused by the admin" },
+    { code: 6016, name: "Unauthorized", msg: "Unauthorized" },
+    { code: 6017, name: "InvalidSkillName", msg: "Skill name must be 1-32 characters and not already defined" },
+    { code: 6018, name: "SkillRegistryFull", msg: "Skill registry already holds the maximum number of skills" },
+    { code: 6019, name: "UnknownSkill", msg: "Skills bitmap references an undefined skill id" },
+    { code: 6020, name: "InvalidExpiry", msg: "Expiry must be 0 or in the future" },
+    { code: 6021, name: "VerifierInactive", msg: "Verifier has been disabled by the admin" },
+    { code: 6022, name: "InvalidValidationScore", msg: "Validation score must be between 0 and 100" },
+    { code: 6023, name: "InGuild", msg: "Agent belongs to a guild; leave it first" },
+    { code: 6024, name: "NotGuildAdmin", msg: "Signer is not an admin of this guild" },
+    { code: 6025, name: "NotGuildMember", msg: "Agent is not a member of this guild" },
+    { code: 6026, name: "GuildFull", msg: "Guild already has the maximum number of members" },
+    { code: 6027, name: "CannotRemoveFounder", msg: "The founding admin cannot be removed or demoted" },
+    { code: 6028, name: "InvalidGuildSplit", msg: "Shares must cover every member and sum to 10,000 bps" },
+    { code: 6029, name: "InvalidGuildRole", msg: "Invalid guild role" },
+    { code: 6030, name: "InvalidReferrer", msg: "An agent cannot refer itself" },
+    { code: 6031, name: "NoActiveClaims", msg: "No claimed bounty is in flight to release" },
+    { code: 6032, name: "GuildHasMembers", msg: "Remove every other member before closing the guild" },
   ],
 };
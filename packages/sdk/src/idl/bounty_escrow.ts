@@ -4,22 +4,71 @@ export type BountyEscrow = {
   address: string;
   metadata: { name: string; version: string; spec: string };
   instructions: [
+    {
+      name: "initConfig";
+      discriminator: number[];
+      accounts: [
+        { name: "config"; writable: true },
+        { name: "program" },
+        { name: "programData" },
+        { name: "admin"; writable: true; signer: true },
+        { name: "systemProgram" }
+      ];
+      args: [];
+    },
+    {
+      name: "setPaused";
+      discriminator: number[];
+      accounts: [
+        { name: "config"; writable: true },
+        { name: "admin"; signer: true }
+      ];
+      args: [{ name: "paused"; type: "u64" }];
+    },
+    {
+      name: "setAdmin";
+      discriminator: number[];
+      accounts: [
+        { name: "config"; writable: true },
+        { name: "admin"; signer: true }
+      ];
+      args: [{ name: "newAdmin"; type: "pubkey" }];
+    },
+    {
+      name: "setFees";
+      discriminator: number[];
+      accounts: [
+        { name: "config"; writable: true },
+        { name: "admin"; signer: true }
+      ];
+      args: [
+        { name: "feeBps"; type: "u16" },
+        { name: "treasury"; type: "pubkey" },
+        { name: "referralShareBps"; type: "u16" },
+        { name: "referralCap"; type: "u64" },
+        { name: "referralDuration"; type: "i64" }
+      ];
+    },
     {
       name: "initClient";
       discriminator: number[];
       accounts: [
+        { name: "config" },
         { name: "clientState"; writable: true },
         { name: "client"; writable: true; signer: true },
         { name: "systemProgram" }
       ];
-      args: [];
+      args: [{ name: "referrer"; type: { option: "pubkey" } }];
     },
     {
       name: "createBounty";
       discriminator: number[];
       accounts: [
+        { name: "config" },
         { name: "clientState"; writable: true },
         { name: "bounty"; writable: true },
+        { name: "history"; writable: true },
+        { name: "skillRegistry" },
         { name: "vault"; writable: true },
         { name: "client"; writable: true; signer: true },
         { name: "clientTokenAccount"; writable: true },
@@ -30,16 +79,64 @@ export type BountyEscrow = {
       ];
       args: [
         { name: "metadataUri"; type: "string" },
+        { name: "metadataHash"; type: { array: ["u8", 32] } },
         { name: "budget"; type: "u64" },
-        { name: "deadline"; type: "i64" }
+        { name: "deadline"; type: "i64" },
+        { name: "skills"; type: "u128" },
+        { name: "requireSkillMatch"; type: "bool" },
+        { name: "attestationRequirement"; type: { option: { defined: { name: "attestationRequirement" } } } },
+        { name: "validationRequirement"; type: { option: { defined: { name: "validationRequirement" } } } },
+        { name: "allowlist"; type: { vec: "pubkey" } },
+        { name: "declinePolicy"; type: { defined: { name: "declinePolicy" } } }
       ];
     },
     {
       name: "claimBounty";
       discriminator: number[];
       accounts: [
+        { name: "config" },
+        { name: "bounty"; writable: true },
+        { name: "history"; writable: true },
+        { name: "agent"; signer: true },
+        { name: "agentProfile"; writable: true },
+        { name: "agentDelegate"; optional: true },
+        { name: "attestation"; optional: true },
+        { name: "verifier"; optional: true },
+        { name: "escrowAuthority" },
+        { name: "agentRegistryProgram" }
+      ];
+      args: [];
+    },
+    {
+      name: "acceptOffer";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
         { name: "bounty"; writable: true },
-        { name: "agent"; signer: true }
+        { name: "history"; writable: true },
+        { name: "agent"; signer: true },
+        { name: "agentProfile"; writable: true },
+        { name: "agentDelegate"; optional: true },
+        { name: "attestation"; optional: true },
+        { name: "verifier"; optional: true },
+        { name: "escrowAuthority" },
+        { name: "agentRegistryProgram" }
+      ];
+      args: [];
+    },
+    {
+      name: "declineOffer";
+      discriminator: number[];
+      accounts: [
+        { name: "bounty"; writable: true },
+        { name: "history"; writable: true },
+        { name: "vault"; writable: true },
+        { name: "clientTokenAccount"; writable: true },
+        { name: "client"; writable: true },
+        { name: "agent"; signer: true },
+        { name: "agentProfile" },
+        { name: "agentDelegate"; optional: true },
+        { name: "tokenProgram" }
       ];
       args: [];
     },
@@ -47,20 +144,58 @@ export type BountyEscrow = {
       name: "submitWork";
       discriminator: number[];
       accounts: [
+        { name: "config" },
         { name: "bounty"; writable: true },
-        { name: "agent"; signer: true }
+        { name: "history"; writable: true },
+        { name: "agent"; signer: true },
+        { name: "agentProfile" },
+        { name: "agentDelegate"; optional: true }
+      ];
+      args: [
+        { name: "deliverableUri"; type: "string" },
+        { name: "deliverableHash"; type: { array: ["u8", 32] } }
       ];
-      args: [{ name: "deliverableUri"; type: "string" }];
     },
     {
       name: "approveWork";
       discriminator: number[];
       accounts: [
+        { name: "config" },
         { name: "bounty"; writable: true },
+        { name: "history"; writable: true },
         { name: "vault"; writable: true },
         { name: "agentTokenAccount"; writable: true },
+        { name: "agentProfile"; writable: true },
+        { name: "validation"; optional: true },
+        { name: "clientState"; writable: true },
+        { name: "treasuryTokenAccount"; writable: true; optional: true },
+        { name: "agentReferrerTokenAccount"; writable: true; optional: true },
+        { name: "clientReferrerTokenAccount"; writable: true; optional: true },
+        { name: "escrowAuthority" },
         { name: "client"; signer: true },
-        { name: "tokenProgram" }
+        { name: "tokenProgram" },
+        { name: "agentRegistryProgram" }
+      ];
+      args: [];
+    },
+    {
+      name: "autoApprove";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "bounty"; writable: true },
+        { name: "history"; writable: true },
+        { name: "vault"; writable: true },
+        { name: "agentTokenAccount"; writable: true },
+        { name: "agentProfile"; writable: true },
+        { name: "validation" },
+        { name: "clientState"; writable: true },
+        { name: "treasuryTokenAccount"; writable: true; optional: true },
+        { name: "agentReferrerTokenAccount"; writable: true; optional: true },
+        { name: "clientReferrerTokenAccount"; writable: true; optional: true },
+        { name: "escrowAuthority" },
+        { name: "tokenProgram" },
+        { name: "agentRegistryProgram" }
       ];
       args: [];
     },
@@ -68,129 +203,1249 @@ export type BountyEscrow = {
       name: "disputeBounty";
       discriminator: number[];
       accounts: [
+        { name: "config" },
         { name: "bounty"; writable: true },
+        { name: "history"; writable: true },
         { name: "authority"; signer: true }
       ];
       args: [];
     },
+    {
+      name: "resolveDispute";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "bounty"; writable: true },
+        { name: "history"; writable: true },
+        { name: "vault"; writable: true },
+        { name: "clientTokenAccount"; writable: true },
+        { name: "client"; writable: true },
+        { name: "agentTokenAccount"; writable: true; optional: true },
+        { name: "agentProfile"; writable: true; optional: true },
+        { name: "agent"; writable: true; optional: true },
+        { name: "guild"; writable: true; optional: true },
+        { name: "clientState"; writable: true },
+        { name: "treasuryTokenAccount"; writable: true; optional: true },
+        { name: "agentReferrerTokenAccount"; writable: true; optional: true },
+        { name: "clientReferrerTokenAccount"; writable: true; optional: true },
+        { name: "escrowAuthority" },
+        { name: "admin"; signer: true },
+        { name: "tokenProgram" },
+        { name: "agentRegistryProgram" }
+      ];
+      args: [{ name: "agentAmount"; type: "u64" }];
+    },
+    {
+      name: "leaveReview";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "bounty" },
+        { name: "review"; writable: true },
+        { name: "agentProfile"; writable: true },
+        { name: "escrowAuthority" },
+        { name: "client"; writable: true; signer: true },
+        { name: "systemProgram" },
+        { name: "agentRegistryProgram" }
+      ];
+      args: [
+        { name: "rating"; type: "u64" },
+        { name: "commentUri"; type: "string" },
+        { name: "commentHash"; type: { array: ["u8", 32] } }
+      ];
+    },
     {
       name: "cancelBounty";
       discriminator: number[];
       accounts: [
         { name: "bounty"; writable: true },
+        { name: "history"; writable: true },
         { name: "vault"; writable: true },
         { name: "clientTokenAccount"; writable: true },
+        { name: "parentBounty"; writable: true; optional: true },
         { name: "client"; writable: true; signer: true },
         { name: "tokenProgram" }
       ];
       args: [];
     },
     {
-      name: "leaveReview";
+      name: "createHourlyContract";
       discriminator: number[];
       accounts: [
-        { name: "bounty" },
-        { name: "review"; writable: true },
+        { name: "config" },
+        { name: "contract"; writable: true },
+        { name: "vault"; writable: true },
+        { name: "agentProfile"; writable: true },
         { name: "client"; writable: true; signer: true },
+        { name: "clientTokenAccount"; writable: true },
+        { name: "escrowAuthority" },
+        { name: "agentRegistryProgram" },
+        { name: "usdcMint" },
+        { name: "tokenProgram" },
+        { name: "systemProgram" },
+        { name: "rent" }
+      ];
+      args: [
+        { name: "contractId"; type: "u64" },
+        { name: "cap"; type: "u64" }
+      ];
+    },
+    {
+      name: "submitTimeLog";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "contract"; writable: true },
+        { name: "timeLog"; writable: true },
+        { name: "previousLog"; optional: true },
+        { name: "agent"; writable: true; signer: true },
+        { name: "agentProfile" },
+        { name: "agentDelegate"; optional: true },
         { name: "systemProgram" }
       ];
       args: [
-        { name: "rating"; type: "u64" },
-        { name: "commentUri"; type: "string" }
+        { name: "periodStart"; type: "i64" },
+        { name: "periodEnd"; type: "i64" },
+        { name: "hoursX100"; type: "u64" },
+        { name: "evidenceUri"; type: "string" },
+        { name: "evidenceHash"; type: { array: ["u8", 32] } }
       ];
-    }
-  ];
-  accounts: [
-    { name: "clientState"; discriminator: number[] },
-    { name: "bounty"; discriminator: number[] },
-    { name: "review"; discriminator: number[] }
-  ];
-  types: [
+    },
     {
-      name: "clientState";
-      type: {
-        kind: "struct";
-        fields: [
-          { name: "owner"; type: "pubkey" },
-          { name: "bountyCount"; type: "u64" },
-          { name: "bump"; type: "u8" }
-        ];
-      };
+      name: "approveTimeLog";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "contract"; writable: true },
+        { name: "timeLog"; writable: true },
+        { name: "vault"; writable: true },
+        { name: "agentTokenAccount"; writable: true },
+        { name: "agentProfile"; writable: true },
+        { name: "client"; signer: true },
+        { name: "escrowAuthority" },
+        { name: "agentRegistryProgram" },
+        { name: "tokenProgram" }
+      ];
+      args: [];
     },
     {
-      name: "bounty";
-      type: {
-        kind: "struct";
-        fields: [
-          { name: "client"; type: "pubkey" },
-          { name: "bountyId"; type: "u64" },
-          { name: "metadataUri"; type: "string" },
-          { name: "budget"; type: "u64" },
-          { name: "deadline"; type: "i64" },
-          { name: "status"; type: "u8" },
-          { name: "claims"; type: "u64" },
-          { name: "assignedAgent"; type: "pubkey" },
-          { name: "deliverableUri"; type: "string" },
-          { name: "vault"; type: "pubkey" },
-          { name: "usdcMint"; type: "pubkey" },
-          { name: "bump"; type: "u8" },
-          { name: "createdAt"; type: "i64" }
-        ];
-      };
+      name: "rejectTimeLog";
+      discriminator: number[];
+      accounts: [
+        { name: "contract"; writable: true },
+        { name: "timeLog"; writable: true },
+        { name: "client"; signer: true }
+      ];
+      args: [];
     },
     {
-      name: "review";
-      type: {
-        kind: "struct";
-        fields: [
-          { name: "bounty"; type: "pubkey" },
-          { name: "reviewer"; type: "pubkey" },
-          { name: "agent"; type: "pubkey" },
-          { name: "rating"; type: "u64" },
-          { name: "commentUri"; type: "string" },
-          { name: "bump"; type: "u8" },
-          { name: "createdAt"; type: "i64" }
-        ];
-      };
-    }
-  ];
-  errors: [
-    { code: 6000; name: "UriTooLong"; msg: "Metadata URI exceeds maximum length" },
-    { code: 6001; name: "InvalidBudget"; msg: "Budget must be greater than 0" },
-    { code: 6002; name: "DeadlinePassed"; msg: "Deadline must be in the future" },
-    { code: 6003; name: "NotOpen"; msg: "Bounty is not open" },
-    { code: 6004; name: "NotClaimed"; msg: "Bounty is not in claimed state" },
-    { code: 6005; name: "NotDelivered"; msg: "Bounty is not in delivered state" },
-    { code: 6006; name: "NotAssignedAgent"; msg: "Only the assigned agent can perform this action" },
-    { code: 6007; name: "Unauthorized"; msg: "Unauthorized" },
-    { code: 6008; name: "CannotDispute"; msg: "Cannot dispute bounty in current state" },
-    { code: 6009; name: "InvalidRating"; msg: "Rating must be between 1 and 500" },
-    { code: 6010; name: "NotCompleted"; msg: "Bounty is not in completed state" }
-  ];
-};
-
-export const IDL: BountyEscrow = {
-  version: "0.1.0",
-  name: "bounty_escrow",
-  address: "2KY4RJwdYKnnDMU4WcuwgU2f8B7JoxjdKaTYL953AKb5",
-  metadata: { name: "bounty_escrow", version: "0.1.0", spec: "0.1.0" },
-  instructions: [
+      name: "closeHourlyContract";
+      discriminator: number[];
+      accounts: [
+        { name: "contract"; writable: true },
+        { name: "vault"; writable: true },
+        { name: "clientTokenAccount"; writable: true },
+        { name: "client"; writable: true },
+        { name: "authority"; signer: true },
+        { name: "agentProfile"; writable: true },
+        { name: "escrowAuthority" },
+        { name: "agentRegistryProgram" },
+        { name: "tokenProgram" }
+      ];
+      args: [];
+    },
+    {
+      name: "createStream";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "stream"; writable: true },
+        { name: "vault"; writable: true },
+        { name: "agentProfile"; writable: true },
+        { name: "client"; writable: true; signer: true },
+        { name: "clientTokenAccount"; writable: true },
+        { name: "escrowAuthority" },
+        { name: "agentRegistryProgram" },
+        { name: "usdcMint" },
+        { name: "tokenProgram" },
+        { name: "systemProgram" },
+        { name: "rent" }
+      ];
+      args: [
+        { name: "streamId"; type: "u64" },
+        { name: "total"; type: "u64" },
+        { name: "startTs"; type: "i64" },
+        { name: "endTs"; type: "i64" }
+      ];
+    },
+    {
+      name: "withdrawVested";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "stream"; writable: true },
+        { name: "vault"; writable: true },
+        { name: "agentTokenAccount"; writable: true },
+        { name: "agent"; signer: true },
+        { name: "agentProfile"; writable: true },
+        { name: "client"; writable: true },
+        { name: "escrowAuthority" },
+        { name: "agentRegistryProgram" },
+        { name: "tokenProgram" }
+      ];
+      args: [];
+    },
+    {
+      name: "cancelStream";
+      discriminator: number[];
+      accounts: [
+        { name: "stream"; writable: true },
+        { name: "vault"; writable: true },
+        { name: "agentTokenAccount"; writable: true },
+        { name: "clientTokenAccount"; writable: true },
+        { name: "client"; writable: true; signer: true },
+        { name: "agentProfile"; writable: true },
+        { name: "escrowAuthority" },
+        { name: "agentRegistryProgram" },
+        { name: "tokenProgram" }
+      ];
+      args: [];
+    },
+    {
+      name: "createRecurringBounty";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "recurring"; writable: true },
+        { name: "skillRegistry" },
+        { name: "vault"; writable: true },
+        { name: "client"; writable: true; signer: true },
+        { name: "clientTokenAccount"; writable: true },
+        { name: "usdcMint" },
+        { name: "tokenProgram" },
+        { name: "systemProgram" },
+        { name: "rent" }
+      ];
+      args: [
+        { name: "templateId"; type: "u64" },
+        { name: "metadataUri"; type: "string" },
+        { name: "metadataHash"; type: { array: ["u8", 32] } },
+        { name: "budgetPerPeriod"; type: "u64" },
+        { name: "periodSecs"; type: "i64" },
+        { name: "totalPeriods"; type: "u64" },
+        { name: "firstOpenAt"; type: "i64" },
+        { name: "skills"; type: "u128" },
+        { name: "requireSkillMatch"; type: "bool" },
+        { name: "allowlist"; type: { vec: "pubkey" } },
+        { name: "declinePolicy"; type: { defined: { name: "declinePolicy" } } }
+      ];
+    },
+    {
+      name: "openCycle";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "recurring"; writable: true },
+        { name: "templateVault"; writable: true },
+        { name: "clientState"; writable: true },
+        { name: "bounty"; writable: true },
+        { name: "history"; writable: true },
+        { name: "vault"; writable: true },
+        { name: "usdcMint" },
+        { name: "payer"; writable: true; signer: true },
+        { name: "tokenProgram" },
+        { name: "systemProgram" },
+        { name: "rent" }
+      ];
+      args: [];
+    },
+    {
+      name: "cancelRecurring";
+      discriminator: number[];
+      accounts: [
+        { name: "recurring"; writable: true },
+        { name: "vault"; writable: true },
+        { name: "clientTokenAccount"; writable: true },
+        { name: "client"; writable: true; signer: true },
+        { name: "tokenProgram" }
+      ];
+      args: [];
+    },
+    {
+      name: "tipAgent";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "bounty"; writable: true },
+        { name: "clientTokenAccount"; writable: true },
+        { name: "agentTokenAccount"; writable: true },
+        { name: "agentProfile"; writable: true },
+        { name: "escrowAuthority" },
+        { name: "client"; signer: true },
+        { name: "tokenProgram" },
+        { name: "agentRegistryProgram" }
+      ];
+      args: [{ name: "amount"; type: "u64" }];
+    },
+    {
+      name: "createSubcontract";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "parent"; writable: true },
+        { name: "clientState"; writable: true },
+        { name: "bounty"; writable: true },
+        { name: "history"; writable: true },
+        { name: "skillRegistry" },
+        { name: "vault"; writable: true },
+        { name: "agent"; writable: true; signer: true },
+        { name: "agentTokenAccount"; writable: true; optional: true },
+        { name: "usdcMint" },
+        { name: "tokenProgram" },
+        { name: "systemProgram" },
+        { name: "rent" }
+      ];
+      args: [
+        { name: "metadataUri"; type: "string" },
+        { name: "metadataHash"; type: { array: ["u8", 32] } },
+        { name: "budget"; type: "u64" },
+        { name: "deadline"; type: "i64" },
+        { name: "skills"; type: "u128" },
+        { name: "requireSkillMatch"; type: "bool" },
+        { name: "fundFromPayout"; type: "bool" }
+      ];
+    },
+    {
+      name: "fundSubcontract";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "parent"; writable: true },
+        { name: "parentVault"; writable: true },
+        { name: "child"; writable: true },
+        { name: "childVault"; writable: true },
+        { name: "tokenProgram" }
+      ];
+      args: [];
+    },
+    {
+      name: "claimGuildBounty";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "bounty"; writable: true },
+        { name: "history"; writable: true },
+        { name: "guild"; writable: true },
+        { name: "member"; signer: true },
+        { name: "memberProfile" },
+        { name: "attestation"; optional: true },
+        { name: "verifier"; optional: true },
+        { name: "escrowAuthority" },
+        { name: "agentRegistryProgram" }
+      ];
+      args: [];
+    },
+    {
+      name: "submitGuildWork";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "bounty"; writable: true },
+        { name: "history"; writable: true },
+        { name: "guild" },
+        { name: "member"; signer: true },
+        { name: "memberProfile" }
+      ];
+      args: [
+        { name: "deliverableUri"; type: "string" },
+        { name: "deliverableHash"; type: { array: ["u8", 32] } }
+      ];
+    },
+    {
+      name: "approveGuildWork";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "bounty"; writable: true },
+        { name: "history"; writable: true },
+        { name: "vault"; writable: true },
+        { name: "guild"; writable: true },
+        { name: "validation"; optional: true },
+        { name: "clientState"; writable: true },
+        { name: "treasuryTokenAccount"; writable: true; optional: true },
+        { name: "clientReferrerTokenAccount"; writable: true; optional: true },
+        { name: "escrowAuthority" },
+        { name: "client"; signer: true },
+        { name: "tokenProgram" },
+        { name: "agentRegistryProgram" }
+      ];
+      args: [];
+    },
+    {
+      name: "leaveGuildReview";
+      discriminator: number[];
+      accounts: [
+        { name: "config" },
+        { name: "bounty" },
+        { name: "review"; writable: true },
+        { name: "guild"; writable: true },
+        { name: "escrowAuthority" },
+        { name: "client"; writable: true; signer: true },
+        { name: "systemProgram" },
+        { name: "agentRegistryProgram" }
+      ];
+      args: [
+        { name: "rating"; type: "u64" },
+        { name: "commentUri"; type: "string" },
+        { name: "commentHash"; type: { array: ["u8", 32] } }
+      ];
+    },
+    {
+      name: "initIndexPage";
+      discriminator: number[];
+      accounts: [
+        { name: "indexPage"; writable: true },
+        { name: "payer"; writable: true; signer: true },
+        { name: "systemProgram" }
+      ];
+      args: [
+        { name: "status"; type: { defined: { name: "bountyStatus" } } },
+        { name: "skill"; type: "u8" },
+        { name: "page"; type: "u32" }
+      ];
+    },
+    {
+      name: "migrateClientState";
+      discriminator: number[];
+      accounts: [
+        { name: "clientState"; writable: true },
+        { name: "payer"; writable: true; signer: true },
+        { name: "systemProgram" }
+      ];
+      args: [];
+    },
+    {
+      name: "migrateBounty";
+      discriminator: number[];
+      accounts: [
+        { name: "bounty"; writable: true },
+        { name: "history"; writable: true },
+        { name: "agentProfile"; writable: true; optional: true },
+        { name: "escrowAuthority" },
+        { name: "payer"; writable: true; signer: true },
+        { name: "systemProgram" },
+        { name: "agentRegistryProgram" }
+      ];
+      args: [];
+    }
+  ];
+  accounts: [
+    { name: "bounty"; discriminator: number[] },
+    { name: "clientState"; discriminator: number[] },
+    { name: "config"; discriminator: number[] },
+    { name: "hourlyContract"; discriminator: number[] },
+    { name: "recurringBounty"; discriminator: number[] },
+    { name: "review"; discriminator: number[] },
+    { name: "stream"; discriminator: number[] },
+    { name: "timeLog"; discriminator: number[] },
+    { name: "bountyIndexPage"; discriminator: number[] },
+    { name: "bountyHistory"; discriminator: number[] }
+  ];
+  types: [
+    {
+      name: "attestationRequirement";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "verifier"; type: "pubkey" },
+          { name: "skillId"; type: "u8" }
+        ];
+      };
+    },
+    {
+      name: "bounty";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "version"; type: "u8" },
+          { name: "client"; type: "pubkey" },
+          { name: "bountyId"; type: "u64" },
+          { name: "metadataUri"; type: "string" },
+          { name: "metadataHash"; type: { array: ["u8", 32] } },
+          { name: "budget"; type: "u64" },
+          { name: "deadline"; type: "i64" },
+          { name: "status"; type: { defined: { name: "bountyStatus" } } },
+          { name: "claims"; type: "u64" },
+          { name: "assignedAgent"; type: "pubkey" },
+          { name: "deliverableUri"; type: "string" },
+          { name: "deliverableHash"; type: { array: ["u8", 32] } },
+          { name: "vault"; type: "pubkey" },
+          { name: "usdcMint"; type: "pubkey" },
+          { name: "bump"; type: "u8" },
+          { name: "createdAt"; type: "i64" },
+          { name: "skills"; type: "u128" },
+          { name: "requireSkillMatch"; type: "bool" },
+          { name: "attestationRequirement"; type: { option: { defined: { name: "attestationRequirement" } } } },
+          { name: "validationRequirement"; type: { option: { defined: { name: "validationRequirement" } } } },
+          { name: "allowlist"; type: { vec: "pubkey" } },
+          { name: "declinePolicy"; type: { defined: { name: "declinePolicy" } } },
+          { name: "recurring"; type: "pubkey" },
+          { name: "tipsTotal"; type: "u64" },
+          { name: "parent"; type: "pubkey" },
+          { name: "childCount"; type: "u64" },
+          { name: "reservedForChildren"; type: "u64" },
+          { name: "awaitingParentFunds"; type: "bool" },
+          { name: "guild"; type: "pubkey" },
+          { name: "claimedAt"; type: "i64" },
+          { name: "deliveredAt"; type: "i64" },
+          { name: "completedAt"; type: "i64" },
+          { name: "disputedAt"; type: "i64" },
+          { name: "cancelledAt"; type: "i64" },
+          { name: "lastActor"; type: "pubkey" },
+          { name: "indexPages"; type: { vec: "u32" } },
+          { name: "disputePayout"; type: "u64" }
+        ];
+      };
+    },
+    {
+      name: "clientState";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "owner"; type: "pubkey" },
+          { name: "bountyCount"; type: "u64" },
+          { name: "bump"; type: "u8" },
+          { name: "referrer"; type: "pubkey" },
+          { name: "referralPaid"; type: "u64" },
+          { name: "createdAt"; type: "i64" }
+        ];
+      };
+    },
+    {
+      name: "config";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "admin"; type: "pubkey" },
+          { name: "paused"; type: "u64" },
+          { name: "bump"; type: "u8" },
+          { name: "feeBps"; type: "u16" },
+          { name: "treasury"; type: "pubkey" },
+          { name: "referralShareBps"; type: "u16" },
+          { name: "referralCap"; type: "u64" },
+          { name: "referralDuration"; type: "i64" }
+        ];
+      };
+    },
+    {
+      name: "declinePolicy";
+      type: {
+        kind: "enum";
+        variants: [
+          { name: "refund" },
+          { name: "reopen" }
+        ];
+      };
+    },
+    {
+      name: "hourlyContract";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "client"; type: "pubkey" },
+          { name: "agent"; type: "pubkey" },
+          { name: "agentProfile"; type: "pubkey" },
+          { name: "contractId"; type: "u64" },
+          { name: "hourlyRate"; type: "u64" },
+          { name: "cap"; type: "u64" },
+          { name: "released"; type: "u64" },
+          { name: "status"; type: { defined: { name: "hourlyContractStatus" } } },
+          { name: "logCount"; type: "u64" },
+          { name: "pendingLogs"; type: "u64" },
+          { name: "vault"; type: "pubkey" },
+          { name: "usdcMint"; type: "pubkey" },
+          { name: "bump"; type: "u8" },
+          { name: "createdAt"; type: "i64" }
+        ];
+      };
+    },
+    {
+      name: "hourlyContractStatus";
+      type: {
+        kind: "enum";
+        variants: [
+          { name: "active" },
+          { name: "closed" }
+        ];
+      };
+    },
+    {
+      name: "recurringBounty";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "client"; type: "pubkey" },
+          { name: "templateId"; type: "u64" },
+          { name: "metadataUri"; type: "string" },
+          { name: "metadataHash"; type: { array: ["u8", 32] } },
+          { name: "budgetPerPeriod"; type: "u64" },
+          { name: "periodSecs"; type: "i64" },
+          { name: "totalPeriods"; type: "u64" },
+          { name: "openedPeriods"; type: "u64" },
+          { name: "nextOpenAt"; type: "i64" },
+          { name: "skills"; type: "u128" },
+          { name: "requireSkillMatch"; type: "bool" },
+          { name: "allowlist"; type: { vec: "pubkey" } },
+          { name: "declinePolicy"; type: { defined: { name: "declinePolicy" } } },
+          { name: "status"; type: { defined: { name: "recurringStatus" } } },
+          { name: "lastCycle"; type: "pubkey" },
+          { name: "vault"; type: "pubkey" },
+          { name: "usdcMint"; type: "pubkey" },
+          { name: "bump"; type: "u8" },
+          { name: "createdAt"; type: "i64" }
+        ];
+      };
+    },
+    {
+      name: "recurringStatus";
+      type: {
+        kind: "enum";
+        variants: [
+          { name: "active" },
+          { name: "cancelled" }
+        ];
+      };
+    },
+    {
+      name: "review";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "bounty"; type: "pubkey" },
+          { name: "reviewer"; type: "pubkey" },
+          { name: "agent"; type: "pubkey" },
+          { name: "rating"; type: "u64" },
+          { name: "commentUri"; type: "string" },
+          { name: "commentHash"; type: { array: ["u8", 32] } },
+          { name: "bump"; type: "u8" },
+          { name: "createdAt"; type: "i64" }
+        ];
+      };
+    },
+    {
+      name: "stream";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "client"; type: "pubkey" },
+          { name: "agent"; type: "pubkey" },
+          { name: "agentProfile"; type: "pubkey" },
+          { name: "streamId"; type: "u64" },
+          { name: "total"; type: "u64" },
+          { name: "withdrawn"; type: "u64" },
+          { name: "startTs"; type: "i64" },
+          { name: "endTs"; type: "i64" },
+          { name: "status"; type: { defined: { name: "streamStatus" } } },
+          { name: "vault"; type: "pubkey" },
+          { name: "usdcMint"; type: "pubkey" },
+          { name: "bump"; type: "u8" },
+          { name: "createdAt"; type: "i64" }
+        ];
+      };
+    },
+    {
+      name: "streamStatus";
+      type: {
+        kind: "enum";
+        variants: [
+          { name: "active" },
+          { name: "completed" },
+          { name: "cancelled" }
+        ];
+      };
+    },
+    {
+      name: "timeLog";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "contract"; type: "pubkey" },
+          { name: "index"; type: "u64" },
+          { name: "periodStart"; type: "i64" },
+          { name: "periodEnd"; type: "i64" },
+          { name: "hoursX100"; type: "u64" },
+          { name: "amount"; type: "u64" },
+          { name: "evidenceUri"; type: "string" },
+          { name: "evidenceHash"; type: { array: ["u8", 32] } },
+          { name: "status"; type: { defined: { name: "timeLogStatus" } } },
+          { name: "submittedAt"; type: "i64" },
+          { name: "bump"; type: "u8" }
+        ];
+      };
+    },
+    {
+      name: "timeLogStatus";
+      type: {
+        kind: "enum";
+        variants: [
+          { name: "pending" },
+          { name: "approved" },
+          { name: "rejected" }
+        ];
+      };
+    },
+    {
+      name: "validationRequirement";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "validator"; type: "pubkey" },
+          { name: "minScore"; type: "u8" },
+          { name: "autoApprove"; type: "bool" }
+        ];
+      };
+    },
+    {
+      name: "bountyIndexPage";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "status"; type: { defined: { name: "bountyStatus" } } },
+          { name: "skill"; type: "u8" },
+          { name: "page"; type: "u32" },
+          { name: "bump"; type: "u8" },
+          { name: "bounties"; type: { vec: "pubkey" } }
+        ];
+      };
+    },
+    {
+      name: "bountyHistory";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "bounty"; type: "pubkey" },
+          { name: "bump"; type: "u8" },
+          { name: "total"; type: "u32" },
+          { name: "entries"; type: { vec: { defined: { name: "statusChange" } } } }
+        ];
+      };
+    },
+    {
+      name: "bountyStatus";
+      type: {
+        kind: "enum";
+        variants: [
+          { name: "open" },
+          { name: "claimed" },
+          { name: "delivered" },
+          { name: "completed" },
+          { name: "disputed" },
+          { name: "cancelled" }
+        ];
+      };
+    },
+    {
+      name: "statusChange";
+      type: {
+        kind: "struct";
+        fields: [
+          { name: "status"; type: { defined: { name: "bountyStatus" } } },
+          { name: "actor"; type: "pubkey" },
+          { name: "at"; type: "i64" }
+        ];
+      };
+    }
+  ];
+  errors: [
+    { code: 6000; name: "UriTooLong"; msg: "Metadata URI exceeds maximum length" },
+    { code: 6001; name: "InvalidBudget"; msg: "Budget must be greater than 0" },
+    { code: 6002; name: "DeadlinePassed"; msg: "Deadline must be in the future" },
+    { code: 6003; name: "NotOpen"; msg: "Bounty is not open" },
+    { code: 6004; name: "NotClaimed"; msg: "Bounty is not in claimed state" },
+    { code: 6005; name: "NotDelivered"; msg: "Bounty is not in delivered state" },
+    { code: 6006; name: "NotAssignedAgent"; msg: "Only the assigned agent can perform this action" },
+    { code: 6007; name: "Unauthorized"; msg: "Unauthorized" },
+    { code: 6008; name: "CannotDispute"; msg: "Cannot dispute bounty in current state" },
+    { code: 6009; name: "InvalidRating"; msg: "Rating must be between 1 and 500" },
+    { code: 6010; name: "NotCompleted"; msg: "Bounty is not in completed state" },
+    { code: 6011; name: "DelegateNotAuthorized"; msg: "Signer is not an authorized delegate for this agent" },
+    { code: 6012; name: "AgentTransferred"; msg: "Agent profile has been transferred to a new owner" },
+    { code: 6013; name: "AgentProfileMismatch"; msg: "Agent profile does not belong to this agent" },
+    { code: 6014; name: "MissingContentHash"; msg: "A non-zero content hash must accompany every URI" },
+    { code: 6015; name: "AlreadyMigrated"; msg: "Account is already on the current layout" },
+    { code: 6016; name: "UnknownAccountVersion"; msg: "Account is not a bounty account of a known layout" },
+    { code: 6017; name: "Paused"; msg: "This instruction is paused by the admin" },
+    { code: 6018; name: "UnknownSkill"; msg: "Skills bitmap references an undefined skill id" },
+    { code: 6019; name: "SkillMismatch"; msg: "Agent has none of the skills this bounty requires" },
+    { code: 6020; name: "NoRequiredSkills"; msg: "Skill matching requires at least one skill on the bounty" },
+    { code: 6021; name: "AttestationRequired"; msg: "A current attestation from the required verifier is needed to claim" },
+    { code: 6022; name: "InvalidMinScore"; msg: "Minimum validation score must be between 1 and 100" },
+    { code: 6023; name: "ValidationRequired"; msg: "A passing validation of the deliverable from the required validator is needed" },
+    { code: 6024; name: "AutoApproveDisabled"; msg: "Bounty does not allow validation-based auto-approval" },
+    { code: 6025; name: "InvalidAllowlist"; msg: "Allowlist must hold at most 5 distinct, non-default agents" },
+    { code: 6026; name: "InvalidDeclinePolicy"; msg: "Decline policy must be 0 (Refund) or 1 (Reopen)" },
+    { code: 6027; name: "NotInvited"; msg: "Agent is not invited to this bounty" },
+    { code: 6028; name: "InvalidTimeLog"; msg: "Time log needs positive hours and a period that ends after it starts" },
+    { code: 6029; name: "ContractClosed"; msg: "Hourly contract is closed" },
+    { code: 6030; name: "ExceedsCap"; msg: "Payment would exceed the contract's remaining cap" },
+    { code: 6031; name: "TimeLogNotPending"; msg: "Time log is not pending" },
+    { code: 6032; name: "PendingTimeLogs"; msg: "Approve or reject pending time logs before closing the contract" },
+    { code: 6033; name: "InvalidStreamSchedule"; msg: "Stream must start now or later and end after it starts" },
+    { code: 6034; name: "StreamNotActive"; msg: "Stream is not active" },
+    { code: 6035; name: "NothingVested"; msg: "Nothing has vested since the last withdrawal" },
+    { code: 6036; name: "InvalidRecurrence"; msg: "Recurring bounty needs a positive period and at least one period" },
+    { code: 6037; name: "RecurringNotActive"; msg: "Recurring bounty is cancelled" },
+    { code: 6038; name: "NoPeriodsLeft"; msg: "All funded periods have been opened" },
+    { code: 6039; name: "CycleNotDue"; msg: "Next cycle is not due yet" },
+    { code: 6040; name: "InvalidTip"; msg: "Tip amount must be greater than zero" },
+    { code: 6041; name: "ExceedsParentBudget"; msg: "Subcontracts funded from the payout would exceed the parent budget" },
+    { code: 6042; name: "MissingTokenAccount"; msg: "A token account is required to fund the subcontract" },
+    { code: 6043; name: "ParentMismatch"; msg: "Bounty is not the parent of this subcontract" },
+    { code: 6044; name: "SubcontractNotFunded"; msg: "Subcontract budget has not been funded from the parent yet" },
+    { code: 6045; name: "SubcontractAlreadyFunded"; msg: "Subcontract is already funded" },
+    { code: 6046; name: "FundSubcontractFirst"; msg: "Parent is completed; fund the subcontract before cancelling it" },
+    { code: 6047; name: "NotGuildBounty"; msg: "Bounty was not claimed by this guild" },
+    { code: 6048; name: "NotGuildMember"; msg: "Agent is not a member of this guild" },
+    { code: 6049; name: "NotGuildAdmin"; msg: "Signer is not an admin of this guild" },
+    { code: 6050; name: "GuildMembersMismatch"; msg: "Remaining accounts must match the guild's members in order" },
+    { code: 6051; name: "InvalidFeeConfig"; msg: "Fee above maximum, referral share above maximum, negative duration or missing treasury" },
+    { code: 6052; name: "InvalidReferrer"; msg: "A client cannot refer itself" },
+    { code: 6053; name: "TreasuryAccountRequired"; msg: "Treasury token account is required while a platform fee is set" },
+    { code: 6054; name: "ReferrerAccountRequired"; msg: "Referrer token account is required while a referral reward is due" },
+    { code: 6055; name: "InvalidPayeeAccount"; msg: "Payee token account has the wrong owner or mint" },
+    { code: 6056; name: "InvalidStatusTransition"; msg: "Bounty cannot move to that status from its current one" },
+    { code: 6057; name: "TooManySkills"; msg: "Bounty requires more skills than it can be indexed under" },
+    { code: 6058; name: "IndexPageFull"; msg: "Index page is full; list the bounty on the next page" },
+    { code: 6059; name: "IndexPagesMismatch"; msg: "Remaining accounts must be the bounty's index pages in bucket order" },
+    { code: 6060; name: "StatusNotIndexed"; msg: "Only open bounties are indexed" },
+    { code: 6061; name: "NotDisputed"; msg: "Bounty is not disputed" },
+    { code: 6062; name: "InvalidResolution"; msg: "Dispute payout exceeds the bounty budget" },
+    { code: 6063; name: "PreviousTimeLogRequired"; msg: "The contract's previous time log must be supplied" },
+    { code: 6064; name: "TimeLogOverlap"; msg: "Time log period overlaps the previous log" },
+    { code: 6065; name: "UnfundedSubcontractsMismatch"; msg: "Remaining accounts must list every subcontract still awaiting the bounty's funds" }
+  ];
+};
+
+export const IDL: BountyEscrow = {
+  version: "0.1.0",
+  name: "bounty_escrow",
+  address: "2KY4RJwdYKnnDMU4WcuwgU2f8B7JoxjdKaTYL953AKb5",
+  metadata: { name: "bounty_escrow", version: "0.1.0", spec: "0.1.0" },
+  instructions: [
+    {
+      name: "initConfig",
+      discriminator: [23, 235, 115, 232, 168, 96, 1, 231],
+      accounts: [
+        { name: "config", writable: true },
+        { name: "program" },
+        { name: "programData" },
+        { name: "admin", writable: true, signer: true },
+        { name: "systemProgram" },
+      ],
+      args: [],
+    },
+    {
+      name: "setPaused",
+      discriminator: [91, 60, 125, 192, 176, 225, 166, 218],
+      accounts: [
+        { name: "config", writable: true },
+        { name: "admin", signer: true },
+      ],
+      args: [{ name: "paused", type: "u64" }],
+    },
+    {
+      name: "setAdmin",
+      discriminator: [251, 163, 0, 52, 91, 194, 187, 92],
+      accounts: [
+        { name: "config", writable: true },
+        { name: "admin", signer: true },
+      ],
+      args: [{ name: "newAdmin", type: "pubkey" }],
+    },
+    {
+      name: "setFees",
+      discriminator: [137, 178, 49, 58, 0, 245, 242, 190],
+      accounts: [
+        { name: "config", writable: true },
+        { name: "admin", signer: true },
+      ],
+      args: [
+        { name: "feeBps", type: "u16" },
+        { name: "treasury", type: "pubkey" },
+        { name: "referralShareBps", type: "u16" },
+        { name: "referralCap", type: "u64" },
+        { name: "referralDuration", type: "i64" },
+      ],
+    },
+    {
+      name: "initClient",
+      discriminator: [30, 50, 186, 118, 60, 68, 27, 155],
+      accounts: [
+        { name: "config" },
+        { name: "clientState", writable: true },
+        { name: "client", writable: true, signer: true },
+        { name: "systemProgram" },
+      ],
+      args: [{ name: "referrer", type: { option: "pubkey" } }],
+    },
+    {
+      name: "createBounty",
+      discriminator: [122, 90, 14, 143, 8, 125, 200, 2],
+      accounts: [
+        { name: "config" },
+        { name: "clientState", writable: true },
+        { name: "bounty", writable: true },
+        { name: "history", writable: true },
+        { name: "skillRegistry" },
+        { name: "vault", writable: true },
+        { name: "client", writable: true, signer: true },
+        { name: "clientTokenAccount", writable: true },
+        { name: "usdcMint" },
+        { name: "tokenProgram" },
+        { name: "systemProgram" },
+        { name: "rent" },
+      ],
+      args: [
+        { name: "metadataUri", type: "string" },
+        { name: "metadataHash", type: { array: ["u8", 32] } },
+        { name: "budget", type: "u64" },
+        { name: "deadline", type: "i64" },
+        { name: "skills", type: "u128" },
+        { name: "requireSkillMatch", type: "bool" },
+        { name: "attestationRequirement", type: { option: { defined: { name: "attestationRequirement" } } } },
+        { name: "validationRequirement", type: { option: { defined: { name: "validationRequirement" } } } },
+        { name: "allowlist", type: { vec: "pubkey" } },
+        { name: "declinePolicy", type: { defined: { name: "declinePolicy" } } },
+      ],
+    },
+    {
+      name: "claimBounty",
+      discriminator: [225, 157, 163, 238, 239, 169, 75, 226],
+      accounts: [
+        { name: "config" },
+        { name: "bounty", writable: true },
+        { name: "history", writable: true },
+        { name: "agent", signer: true },
+        { name: "agentProfile", writable: true },
+        { name: "agentDelegate", optional: true },
+        { name: "attestation", optional: true },
+        { name: "verifier", optional: true },
+        { name: "escrowAuthority" },
+        { name: "agentRegistryProgram" },
+      ],
+      args: [],
+    },
+    {
+      name: "acceptOffer",
+      discriminator: [227, 82, 234, 131, 1, 18, 48, 2],
+      accounts: [
+        { name: "config" },
+        { name: "bounty", writable: true },
+        { name: "history", writable: true },
+        { name: "agent", signer: true },
+        { name: "agentProfile", writable: true },
+        { name: "agentDelegate", optional: true },
+        { name: "attestation", optional: true },
+        { name: "verifier", optional: true },
+        { name: "escrowAuthority" },
+        { name: "agentRegistryProgram" },
+      ],
+      args: [],
+    },
+    {
+      name: "declineOffer",
+      discriminator: [204, 98, 121, 232, 122, 145, 35, 96],
+      accounts: [
+        { name: "bounty", writable: true },
+        { name: "history", writable: true },
+        { name: "vault", writable: true },
+        { name: "clientTokenAccount", writable: true },
+        { name: "client", writable: true },
+        { name: "agent", signer: true },
+        { name: "agentProfile" },
+        { name: "agentDelegate", optional: true },
+        { name: "tokenProgram" },
+      ],
+      args: [],
+    },
+    {
+      name: "submitWork",
+      discriminator: [158, 80, 101, 51, 114, 130, 101, 253],
+      accounts: [
+        { name: "config" },
+        { name: "bounty", writable: true },
+        { name: "history", writable: true },
+        { name: "agent", signer: true },
+        { name: "agentProfile" },
+        { name: "agentDelegate", optional: true },
+      ],
+      args: [
+        { name: "deliverableUri", type: "string" },
+        { name: "deliverableHash", type: { array: ["u8", 32] } },
+      ],
+    },
+    {
+      name: "approveWork",
+      discriminator: [181, 118, 45, 143, 204, 88, 237, 109],
+      accounts: [
+        { name: "config" },
+        { name: "bounty", writable: true },
+        { name: "history", writable: true },
+        { name: "vault", writable: true },
+        { name: "agentTokenAccount", writable: true },
+        { name: "agentProfile", writable: true },
+        { name: "validation", optional: true },
+        { name: "clientState", writable: true },
+        { name: "treasuryTokenAccount", writable: true, optional: true },
+        { name: "agentReferrerTokenAccount", writable: true, optional: true },
+        { name: "clientReferrerTokenAccount", writable: true, optional: true },
+        { name: "escrowAuthority" },
+        { name: "client", signer: true },
+        { name: "tokenProgram" },
+        { name: "agentRegistryProgram" },
+      ],
+      args: [],
+    },
+    {
+      name: "autoApprove",
+      discriminator: [36, 58, 85, 199, 138, 197, 222, 178],
+      accounts: [
+        { name: "config" },
+        { name: "bounty", writable: true },
+        { name: "history", writable: true },
+        { name: "vault", writable: true },
+        { name: "agentTokenAccount", writable: true },
+        { name: "agentProfile", writable: true },
+        { name: "validation" },
+        { name: "clientState", writable: true },
+        { name: "treasuryTokenAccount", writable: true, optional: true },
+        { name: "agentReferrerTokenAccount", writable: true, optional: true },
+        { name: "clientReferrerTokenAccount", writable: true, optional: true },
+        { name: "escrowAuthority" },
+        { name: "tokenProgram" },
+        { name: "agentRegistryProgram" },
+      ],
+      args: [],
+    },
+    {
+      name: "disputeBounty",
+      discriminator: [240, 83, 213, 95, 60, 48, 48, 29],
+      accounts: [
+        { name: "config" },
+        { name: "bounty", writable: true },
+        { name: "history", writable: true },
+        { name: "authority", signer: true },
+      ],
+      args: [],
+    },
+    {
+      name: "resolveDispute",
+      discriminator: [231, 6, 202, 6, 96, 103, 12, 230],
+      accounts: [
+        { name: "config" },
+        { name: "bounty", writable: true },
+        { name: "history", writable: true },
+        { name: "vault", writable: true },
+        { name: "clientTokenAccount", writable: true },
+        { name: "client", writable: true },
+        { name: "agentTokenAccount", writable: true, optional: true },
+        { name: "agentProfile", writable: true, optional: true },
+        { name: "agent", writable: true, optional: true },
+        { name: "guild", writable: true, optional: true },
+        { name: "clientState", writable: true },
+        { name: "treasuryTokenAccount", writable: true, optional: true },
+        { name: "agentReferrerTokenAccount", writable: true, optional: true },
+        { name: "clientReferrerTokenAccount", writable: true, optional: true },
+        { name: "escrowAuthority" },
+        { name: "admin", signer: true },
+        { name: "tokenProgram" },
+        { name: "agentRegistryProgram" },
+      ],
+      args: [{ name: "agentAmount", type: "u64" }],
+    },
+    {
+      name: "leaveReview",
+      discriminator: [117, 81, 110, 222, 0, 51, 250, 47],
+      accounts: [
+        { name: "config" },
+        { name: "bounty" },
+        { name: "review", writable: true },
+        { name: "agentProfile", writable: true },
+        { name: "escrowAuthority" },
+        { name: "client", writable: true, signer: true },
+        { name: "systemProgram" },
+        { name: "agentRegistryProgram" },
+      ],
+      args: [
+        { name: "rating", type: "u64" },
+        { name: "commentUri", type: "string" },
+        { name: "commentHash", type: { array: ["u8", 32] } },
+      ],
+    },
+    {
+      name: "cancelBounty",
+      discriminator: [79, 65, 107, 143, 128, 165, 135, 46],
+      accounts: [
+        { name: "bounty", writable: true },
+        { name: "history", writable: true },
+        { name: "vault", writable: true },
+        { name: "clientTokenAccount", writable: true },
+        { name: "parentBounty", writable: true, optional: true },
+        { name: "client", writable: true, signer: true },
+        { name: "tokenProgram" },
+      ],
+      args: [],
+    },
+    {
+      name: "createHourlyContract",
+      discriminator: [125, 81, 39, 52, 149, 5, 211, 125],
+      accounts: [
+        { name: "config" },
+        { name: "contract", writable: true },
+        { name: "vault", writable: true },
+        { name: "agentProfile", writable: true },
+        { name: "client", writable: true, signer: true },
+        { name: "clientTokenAccount", writable: true },
+        { name: "escrowAuthority" },
+        { name: "agentRegistryProgram" },
+        { name: "usdcMint" },
+        { name: "tokenProgram" },
+        { name: "systemProgram" },
+        { name: "rent" },
+      ],
+      args: [
+        { name: "contractId", type: "u64" },
+        { name: "cap", type: "u64" },
+      ],
+    },
+    {
+      name: "submitTimeLog",
+      discriminator: [77, 200, 75, 227, 11, 188, 4, 15],
+      accounts: [
+        { name: "config" },
+        { name: "contract", writable: true },
+        { name: "timeLog", writable: true },
+        { name: "previousLog", optional: true },
+        { name: "agent", writable: true, signer: true },
+        { name: "agentProfile" },
+        { name: "agentDelegate", optional: true },
+        { name: "systemProgram" },
+      ],
+      args: [
+        { name: "periodStart", type: "i64" },
+        { name: "periodEnd", type: "i64" },
+        { name: "hoursX100", type: "u64" },
+        { name: "evidenceUri", type: "string" },
+        { name: "evidenceHash", type: { array: ["u8", 32] } },
+      ],
+    },
+    {
+      name: "approveTimeLog",
+      discriminator: [180, 62, 229, 5, 109, 115, 61, 131],
+      accounts: [
+        { name: "config" },
+        { name: "contract", writable: true },
+        { name: "timeLog", writable: true },
+        { name: "vault", writable: true },
+        { name: "agentTokenAccount", writable: true },
+        { name: "agentProfile", writable: true },
+        { name: "client", signer: true },
+        { name: "escrowAuthority" },
+        { name: "agentRegistryProgram" },
+        { name: "tokenProgram" },
+      ],
+      args: [],
+    },
+    {
+      name: "rejectTimeLog",
+      discriminator: [121, 96, 48, 236, 44, 17, 146, 155],
+      accounts: [
+        { name: "contract", writable: true },
+        { name: "timeLog", writable: true },
+        { name: "client", signer: true },
+      ],
+      args: [],
+    },
+    {
+      name: "closeHourlyContract",
+      discriminator: [221, 26, 61, 26, 165, 122, 116, 213],
+      accounts: [
+        { name: "contract", writable: true },
+        { name: "vault", writable: true },
+        { name: "clientTokenAccount", writable: true },
+        { name: "client", writable: true },
+        { name: "authority", signer: true },
+        { name: "agentProfile", writable: true },
+        { name: "escrowAuthority" },
+        { name: "agentRegistryProgram" },
+        { name: "tokenProgram" },
+      ],
+      args: [],
+    },
     {
-      name: "initClient",
-      discriminator: [30, 50, 186, 118, 60, 68, 27, 155],
+      name: "createStream",
+      discriminator: [71, 188, 111, 127, 108, 40, 229, 158],
       accounts: [
-        { name: "clientState", writable: true },
+        { name: "config" },
+        { name: "stream", writable: true },
+        { name: "vault", writable: true },
+        { name: "agentProfile", writable: true },
         { name: "client", writable: true, signer: true },
+        { name: "clientTokenAccount", writable: true },
+        { name: "escrowAuthority" },
+        { name: "agentRegistryProgram" },
+        { name: "usdcMint" },
+        { name: "tokenProgram" },
         { name: "systemProgram" },
+        { name: "rent" },
+      ],
+      args: [
+        { name: "streamId", type: "u64" },
+        { name: "total", type: "u64" },
+        { name: "startTs", type: "i64" },
+        { name: "endTs", type: "i64" },
+      ],
+    },
+    {
+      name: "withdrawVested",
+      discriminator: [104, 188, 52, 194, 35, 234, 95, 149],
+      accounts: [
+        { name: "config" },
+        { name: "stream", writable: true },
+        { name: "vault", writable: true },
+        { name: "agentTokenAccount", writable: true },
+        { name: "agent", signer: true },
+        { name: "agentProfile", writable: true },
+        { name: "client", writable: true },
+        { name: "escrowAuthority" },
+        { name: "agentRegistryProgram" },
+        { name: "tokenProgram" },
       ],
       args: [],
     },
     {
-      name: "createBounty",
-      discriminator: [122, 90, 14, 143, 8, 125, 200, 2],
+      name: "cancelStream",
+      discriminator: [218, 221, 38, 25, 177, 207, 188, 91],
       accounts: [
-        { name: "clientState", writable: true },
-        { name: "bounty", writable: true },
+        { name: "stream", writable: true },
+        { name: "vault", writable: true },
+        { name: "agentTokenAccount", writable: true },
+        { name: "clientTokenAccount", writable: true },
+        { name: "client", writable: true, signer: true },
+        { name: "agentProfile", writable: true },
+        { name: "escrowAuthority" },
+        { name: "agentRegistryProgram" },
+        { name: "tokenProgram" },
+      ],
+      args: [],
+    },
+    {
+      name: "createRecurringBounty",
+      discriminator: [25, 212, 229, 83, 248, 241, 182, 197],
+      accounts: [
+        { name: "config" },
+        { name: "recurring", writable: true },
+        { name: "skillRegistry" },
         { name: "vault", writable: true },
         { name: "client", writable: true, signer: true },
         { name: "clientTokenAccount", writable: true },
@@ -200,91 +1455,238 @@ export const IDL: BountyEscrow = {
         { name: "rent" },
       ],
       args: [
+        { name: "templateId", type: "u64" },
         { name: "metadataUri", type: "string" },
-        { name: "budget", type: "u64" },
-        { name: "deadline", type: "i64" },
+        { name: "metadataHash", type: { array: ["u8", 32] } },
+        { name: "budgetPerPeriod", type: "u64" },
+        { name: "periodSecs", type: "i64" },
+        { name: "totalPeriods", type: "u64" },
+        { name: "firstOpenAt", type: "i64" },
+        { name: "skills", type: "u128" },
+        { name: "requireSkillMatch", type: "bool" },
+        { name: "allowlist", type: { vec: "pubkey" } },
+        { name: "declinePolicy", type: { defined: { name: "declinePolicy" } } },
       ],
     },
     {
-      name: "claimBounty",
-      discriminator: [225, 157, 163, 238, 239, 169, 75, 226],
+      name: "openCycle",
+      discriminator: [248, 147, 219, 61, 211, 57, 153, 30],
       accounts: [
+        { name: "config" },
+        { name: "recurring", writable: true },
+        { name: "templateVault", writable: true },
+        { name: "clientState", writable: true },
         { name: "bounty", writable: true },
-        { name: "agent", signer: true },
+        { name: "history", writable: true },
+        { name: "vault", writable: true },
+        { name: "usdcMint" },
+        { name: "payer", writable: true, signer: true },
+        { name: "tokenProgram" },
+        { name: "systemProgram" },
+        { name: "rent" },
       ],
       args: [],
     },
     {
-      name: "submitWork",
-      discriminator: [158, 80, 101, 51, 114, 130, 101, 253],
+      name: "cancelRecurring",
+      discriminator: [108, 177, 37, 163, 146, 35, 194, 227],
       accounts: [
-        { name: "bounty", writable: true },
-        { name: "agent", signer: true },
+        { name: "recurring", writable: true },
+        { name: "vault", writable: true },
+        { name: "clientTokenAccount", writable: true },
+        { name: "client", writable: true, signer: true },
+        { name: "tokenProgram" },
       ],
-      args: [{ name: "deliverableUri", type: "string" }],
+      args: [],
     },
     {
-      name: "approveWork",
-      discriminator: [181, 118, 45, 143, 204, 88, 237, 109],
+      name: "tipAgent",
+      discriminator: [186, 201, 255, 15, 22, 152, 254, 114],
       accounts: [
+        { name: "config" },
         { name: "bounty", writable: true },
-        { name: "vault", writable: true },
+        { name: "clientTokenAccount", writable: true },
         { name: "agentTokenAccount", writable: true },
+        { name: "agentProfile", writable: true },
+        { name: "escrowAuthority" },
         { name: "client", signer: true },
         { name: "tokenProgram" },
+        { name: "agentRegistryProgram" },
+      ],
+      args: [{ name: "amount", type: "u64" }],
+    },
+    {
+      name: "createSubcontract",
+      discriminator: [77, 58, 177, 246, 251, 161, 11, 5],
+      accounts: [
+        { name: "config" },
+        { name: "parent", writable: true },
+        { name: "clientState", writable: true },
+        { name: "bounty", writable: true },
+        { name: "history", writable: true },
+        { name: "skillRegistry" },
+        { name: "vault", writable: true },
+        { name: "agent", writable: true, signer: true },
+        { name: "agentTokenAccount", writable: true, optional: true },
+        { name: "usdcMint" },
+        { name: "tokenProgram" },
+        { name: "systemProgram" },
+        { name: "rent" },
+      ],
+      args: [
+        { name: "metadataUri", type: "string" },
+        { name: "metadataHash", type: { array: ["u8", 32] } },
+        { name: "budget", type: "u64" },
+        { name: "deadline", type: "i64" },
+        { name: "skills", type: "u128" },
+        { name: "requireSkillMatch", type: "bool" },
+        { name: "fundFromPayout", type: "bool" },
+      ],
+    },
+    {
+      name: "fundSubcontract",
+      discriminator: [39, 137, 212, 46, 153, 16, 49, 35],
+      accounts: [
+        { name: "config" },
+        { name: "parent", writable: true },
+        { name: "parentVault", writable: true },
+        { name: "child", writable: true },
+        { name: "childVault", writable: true },
+        { name: "tokenProgram" },
       ],
       args: [],
     },
     {
-      name: "disputeBounty",
-      discriminator: [240, 83, 213, 95, 60, 48, 48, 29],
+      name: "claimGuildBounty",
+      discriminator: [42, 183, 201, 197, 181, 9, 26, 58],
       accounts: [
+        { name: "config" },
         { name: "bounty", writable: true },
-        { name: "authority", signer: true },
+        { name: "history", writable: true },
+        { name: "guild", writable: true },
+        { name: "member", signer: true },
+        { name: "memberProfile" },
+        { name: "attestation", optional: true },
+        { name: "verifier", optional: true },
+        { name: "escrowAuthority" },
+        { name: "agentRegistryProgram" },
       ],
       args: [],
     },
     {
-      name: "cancelBounty",
-      discriminator: [79, 65, 107, 143, 128, 165, 135, 46],
+      name: "submitGuildWork",
+      discriminator: [199, 166, 119, 93, 91, 40, 17, 0],
       accounts: [
+        { name: "config" },
         { name: "bounty", writable: true },
+        { name: "history", writable: true },
+        { name: "guild" },
+        { name: "member", signer: true },
+        { name: "memberProfile" },
+      ],
+      args: [
+        { name: "deliverableUri", type: "string" },
+        { name: "deliverableHash", type: { array: ["u8", 32] } },
+      ],
+    },
+    {
+      name: "approveGuildWork",
+      discriminator: [40, 107, 46, 107, 175, 62, 110, 3],
+      accounts: [
+        { name: "config" },
+        { name: "bounty", writable: true },
+        { name: "history", writable: true },
         { name: "vault", writable: true },
-        { name: "clientTokenAccount", writable: true },
-        { name: "client", writable: true, signer: true },
+        { name: "guild", writable: true },
+        { name: "validation", optional: true },
+        { name: "clientState", writable: true },
+        { name: "treasuryTokenAccount", writable: true, optional: true },
+        { name: "clientReferrerTokenAccount", writable: true, optional: true },
+        { name: "escrowAuthority" },
+        { name: "client", signer: true },
         { name: "tokenProgram" },
+        { name: "agentRegistryProgram" },
       ],
       args: [],
     },
     {
-      name: "leaveReview",
-      discriminator: [117, 81, 110, 222, 0, 51, 250, 47],
+      name: "leaveGuildReview",
+      discriminator: [116, 60, 42, 203, 136, 113, 110, 36],
       accounts: [
+        { name: "config" },
         { name: "bounty" },
         { name: "review", writable: true },
+        { name: "guild", writable: true },
+        { name: "escrowAuthority" },
         { name: "client", writable: true, signer: true },
         { name: "systemProgram" },
+        { name: "agentRegistryProgram" },
       ],
       args: [
         { name: "rating", type: "u64" },
         { name: "commentUri", type: "string" },
+        { name: "commentHash", type: { array: ["u8", 32] } },
+      ],
+    },
+    {
+      name: "initIndexPage",
+      discriminator: [22, 235, 194, 191, 2, 157, 43, 205],
+      accounts: [
+        { name: "indexPage", writable: true },
+        { name: "payer", writable: true, signer: true },
+        { name: "systemProgram" },
+      ],
+      args: [
+        { name: "status", type: { defined: { name: "bountyStatus" } } },
+        { name: "skill", type: "u8" },
+        { name: "page", type: "u32" },
+      ],
+    },
+    {
+      name: "migrateClientState",
+      discriminator: [76, 53, 124, 201, 195, 244, 72, 7],
+      accounts: [
+        { name: "clientState", writable: true },
+        { name: "payer", writable: true, signer: true },
+        { name: "systemProgram" },
+      ],
+      args: [],
+    },
+    {
+      name: "migrateBounty",
+      discriminator: [192, 74, 46, 136, 10, 226, 11, 251],
+      accounts: [
+        { name: "bounty", writable: true },
+        { name: "history", writable: true },
+        { name: "agentProfile", writable: true, optional: true },
+        { name: "escrowAuthority" },
+        { name: "payer", writable: true, signer: true },
+        { name: "systemProgram" },
+        { name: "agentRegistryProgram" },
       ],
+      args: [],
     },
   ],
   accounts: [
-    { name: "clientState", discriminator: [147, 10, 249, 80, 145, 124, 219, 60] },
     { name: "bounty", discriminator: [237, 16, 105, 198, 19, 69, 242, 234] },
+    { name: "clientState", discriminator: [147, 10, 249, 80, 145, 124, 219, 60] },
+    { name: "config", discriminator: [155, 12, 170, 224, 30, 250, 204, 130] },
+    { name: "hourlyContract", discriminator: [212, 254, 11, 161, 222, 203, 99, 145] },
+    { name: "recurringBounty", discriminator: [68, 190, 225, 254, 123, 194, 222, 9] },
     { name: "review", discriminator: [124, 63, 203, 215, 226, 30, 222, 15] },
+    { name: "stream", discriminator: [166, 224, 59, 4, 202, 10, 186, 83] },
+    { name: "timeLog", discriminator: [215, 38, 94, 182, 130, 230, 166, 20] },
+    { name: "bountyIndexPage", discriminator: [247, 33, 78, 162, 172, 88, 31, 161] },
+    { name: "bountyHistory", discriminator: [92, 52, 81, 183, 197, 26, 160, 34] },
   ],
   types: [
     {
-      name: "clientState",
+      name: "attestationRequirement",
       type: {
         kind: "struct",
         fields: [
-          { name: "owner", type: "pubkey" },
-          { name: "bountyCount", type: "u64" },
-          { name: "bump", type: "u8" },
+          { name: "verifier", type: "pubkey" },
+          { name: "skillId", type: "u8" },
         ],
       },
     },
@@ -293,19 +1695,152 @@ export const IDL: BountyEscrow = {
       type: {
         kind: "struct",
         fields: [
+          { name: "version", type: "u8" },
           { name: "client", type: "pubkey" },
           { name: "bountyId", type: "u64" },
           { name: "metadataUri", type: "string" },
+          { name: "metadataHash", type: { array: ["u8", 32] } },
           { name: "budget", type: "u64" },
           { name: "deadline", type: "i64" },
-          { name: "status", type: "u8" },
+          { name: "status", type: { defined: { name: "bountyStatus" } } },
           { name: "claims", type: "u64" },
           { name: "assignedAgent", type: "pubkey" },
           { name: "deliverableUri", type: "string" },
+          { name: "deliverableHash", type: { array: ["u8", 32] } },
           { name: "vault", type: "pubkey" },
           { name: "usdcMint", type: "pubkey" },
           { name: "bump", type: "u8" },
           { name: "createdAt", type: "i64" },
+          { name: "skills", type: "u128" },
+          { name: "requireSkillMatch", type: "bool" },
+          { name: "attestationRequirement", type: { option: { defined: { name: "attestationRequirement" } } } },
+          { name: "validationRequirement", type: { option: { defined: { name: "validationRequirement" } } } },
+          { name: "allowlist", type: { vec: "pubkey" } },
+          { name: "declinePolicy", type: { defined: { name: "declinePolicy" } } },
+          { name: "recurring", type: "pubkey" },
+          { name: "tipsTotal", type: "u64" },
+          { name: "parent", type: "pubkey" },
+          { name: "childCount", type: "u64" },
+          { name: "reservedForChildren", type: "u64" },
+          { name: "awaitingParentFunds", type: "bool" },
+          { name: "guild", type: "pubkey" },
+          { name: "claimedAt", type: "i64" },
+          { name: "deliveredAt", type: "i64" },
+          { name: "completedAt", type: "i64" },
+          { name: "disputedAt", type: "i64" },
+          { name: "cancelledAt", type: "i64" },
+          { name: "lastActor", type: "pubkey" },
+          { name: "indexPages", type: { vec: "u32" } },
+          { name: "disputePayout", type: "u64" },
+        ],
+      },
+    },
+    {
+      name: "clientState",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "owner", type: "pubkey" },
+          { name: "bountyCount", type: "u64" },
+          { name: "bump", type: "u8" },
+          { name: "referrer", type: "pubkey" },
+          { name: "referralPaid", type: "u64" },
+          { name: "createdAt", type: "i64" },
+        ],
+      },
+    },
+    {
+      name: "config",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "admin", type: "pubkey" },
+          { name: "paused", type: "u64" },
+          { name: "bump", type: "u8" },
+          { name: "feeBps", type: "u16" },
+          { name: "treasury", type: "pubkey" },
+          { name: "referralShareBps", type: "u16" },
+          { name: "referralCap", type: "u64" },
+          { name: "referralDuration", type: "i64" },
+        ],
+      },
+    },
+    {
+      name: "declinePolicy",
+      type: {
+        kind: "enum",
+        variants: [
+          { name: "refund" },
+          { name: "reopen" },
+        ],
+      },
+    },
+    {
+      name: "hourlyContract",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "client", type: "pubkey" },
+          { name: "agent", type: "pubkey" },
+          { name: "agentProfile", type: "pubkey" },
+          { name: "contractId", type: "u64" },
+          { name: "hourlyRate", type: "u64" },
+          { name: "cap", type: "u64" },
+          { name: "released", type: "u64" },
+          { name: "status", type: { defined: { name: "hourlyContractStatus" } } },
+          { name: "logCount", type: "u64" },
+          { name: "pendingLogs", type: "u64" },
+          { name: "vault", type: "pubkey" },
+          { name: "usdcMint", type: "pubkey" },
+          { name: "bump", type: "u8" },
+          { name: "createdAt", type: "i64" },
+        ],
+      },
+    },
+    {
+      name: "hourlyContractStatus",
+      type: {
+        kind: "enum",
+        variants: [
+          { name: "active" },
+          { name: "closed" },
+        ],
+      },
+    },
+    {
+      name: "recurringBounty",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "client", type: "pubkey" },
+          { name: "templateId", type: "u64" },
+          { name: "metadataUri", type: "string" },
+          { name: "metadataHash", type: { array: ["u8", 32] } },
+          { name: "budgetPerPeriod", type: "u64" },
+          { name: "periodSecs", type: "i64" },
+          { name: "totalPeriods", type: "u64" },
+          { name: "openedPeriods", type: "u64" },
+          { name: "nextOpenAt", type: "i64" },
+          { name: "skills", type: "u128" },
+          { name: "requireSkillMatch", type: "bool" },
+          { name: "allowlist", type: { vec: "pubkey" } },
+          { name: "declinePolicy", type: { defined: { name: "declinePolicy" } } },
+          { name: "status", type: { defined: { name: "recurringStatus" } } },
+          { name: "lastCycle", type: "pubkey" },
+          { name: "vault", type: "pubkey" },
+          { name: "usdcMint", type: "pubkey" },
+          { name: "bump", type: "u8" },
+          { name: "createdAt", type: "i64" },
+        ],
+      },
+    },
+    {
+      name: "recurringStatus",
+      type: {
+        kind: "enum",
+        variants: [
+          { name: "active" },
+          { name: "cancelled" },
         ],
       },
     },
@@ -319,11 +1854,135 @@ export const IDL: BountyEscrow = {
           { name: "agent", type: "pubkey" },
           { name: "rating", type: "u64" },
           { name: "commentUri", type: "string" },
+          { name: "commentHash", type: { array: ["u8", 32] } },
+          { name: "bump", type: "u8" },
+          { name: "createdAt", type: "i64" },
+        ],
+      },
+    },
+    {
+      name: "stream",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "client", type: "pubkey" },
+          { name: "agent", type: "pubkey" },
+          { name: "agentProfile", type: "pubkey" },
+          { name: "streamId", type: "u64" },
+          { name: "total", type: "u64" },
+          { name: "withdrawn", type: "u64" },
+          { name: "startTs", type: "i64" },
+          { name: "endTs", type: "i64" },
+          { name: "status", type: { defined: { name: "streamStatus" } } },
+          { name: "vault", type: "pubkey" },
+          { name: "usdcMint", type: "pubkey" },
           { name: "bump", type: "u8" },
           { name: "createdAt", type: "i64" },
         ],
       },
     },
+    {
+      name: "streamStatus",
+      type: {
+        kind: "enum",
+        variants: [
+          { name: "active" },
+          { name: "completed" },
+          { name: "cancelled" },
+        ],
+      },
+    },
+    {
+      name: "timeLog",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "contract", type: "pubkey" },
+          { name: "index", type: "u64" },
+          { name: "periodStart", type: "i64" },
+          { name: "periodEnd", type: "i64" },
+          { name: "hoursX100", type: "u64" },
+          { name: "amount", type: "u64" },
+          { name: "evidenceUri", type: "string" },
+          { name: "evidenceHash", type: { array: ["u8", 32] } },
+          { name: "status", type: { defined: { name: "timeLogStatus" } } },
+          { name: "submittedAt", type: "i64" },
+          { name: "bump", type: "u8" },
+        ],
+      },
+    },
+    {
+      name: "timeLogStatus",
+      type: {
+        kind: "enum",
+        variants: [
+          { name: "pending" },
+          { name: "approved" },
+          { name: "rejected" },
+        ],
+      },
+    },
+    {
+      name: "validationRequirement",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "validator", type: "pubkey" },
+          { name: "minScore", type: "u8" },
+          { name: "autoApprove", type: "bool" },
+        ],
+      },
+    },
+    {
+      name: "bountyIndexPage",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "status", type: { defined: { name: "bountyStatus" } } },
+          { name: "skill", type: "u8" },
+          { name: "page", type: "u32" },
+          { name: "bump", type: "u8" },
+          { name: "bounties", type: { vec: "pubkey" } },
+        ],
+      },
+    },
+    {
+      name: "bountyHistory",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "bounty", type: "pubkey" },
+          { name: "bump", type: "u8" },
+          { name: "total", type: "u32" },
+          { name: "entries", type: { vec: { defined: { name: "statusChange" } } } },
+        ],
+      },
+    },
+    {
+      name: "bountyStatus",
+      type: {
+        kind: "enum",
+        variants: [
+          { name: "open" },
+          { name: "claimed" },
+          { name: "delivered" },
+          { name: "completed" },
+          { name: "disputed" },
+          { name: "cancelled" },
+        ],
+      },
+    },
+    {
+      name: "statusChange",
+      type: {
+        kind: "struct",
+        fields: [
+          { name: "status", type: { defined: { name: "bountyStatus" } } },
+          { name: "actor", type: "pubkey" },
+          { name: "at", type: "i64" },
+        ],
+      },
+    },
   ],
   errors: [
     { code: 6000, name: "UriTooLong", msg: "Metadata URI exceeds maximum length" },
@@ -337,5 +1996,60 @@ export const IDL: BountyEscrow = {
     { code: 6008, name: "CannotDispute", msg: "Cannot dispute bounty in current state" },
     { code: 6009, name: "InvalidRating", msg: "Rating must be between 1 and 500" },
     { code: 6010, name: "NotCompleted", msg: "Bounty is not in completed state" },
+    { code: 6011, name: "DelegateNotAuthorized", msg: "Signer is not an authorized delegate for this agent" },
+    { code: 6012, name: "AgentTransferred", msg: "Agent profile has been transferred to a new owner" },
+    { code: 6013, name: "AgentProfileMismatch", msg: "Agent profile does not belong to this agent" },
+    { code: 6014, name: "MissingContentHash", msg: "A non-zero content hash must accompany every URI" },
+    { code: 6015, name: "AlreadyMigrated", msg: "Account is already on the current layout" },
+    { code: 6016, name: "UnknownAccountVersion", msg: "Account is not a bounty account of a known layout" },
+    { code: 6017, name: "Paused", msg: "This instruction is paused by the admin" },
+    { code: 6018, name: "UnknownSkill", msg: "Skills bitmap references an undefined skill id" },
+    { code: 6019, name: "SkillMismatch", msg: "Agent has none of the skills this bounty requires" },
+    { code: 6020, name: "NoRequiredSkills", msg: "Skill matching requires at least one skill on the bounty" },
+    { code: 6021, name: "AttestationRequired", msg: "A current attestation from the required verifier is needed to claim" },
+    { code: 6022, name: "InvalidMinScore", msg: "Minimum validation score must be between 1 and 100" },
+    { code: 6023, name: "ValidationRequired", msg: "A passing validation of the deliverable from the required validator is needed" },
+    { code: 6024, name: "AutoApproveDisabled", msg: "Bounty does not allow validation-based auto-approval" },
+    { code: 6025, name: "InvalidAllowlist", msg: "Allowlist must hold at most 5 distinct, non-default agents" },
+    { code: 6026, name: "InvalidDeclinePolicy", msg: "Decline policy must be 0 (Refund) or 1 (Reopen)" },
+    { code: 6027, name: "NotInvited", msg: "Agent is not invited to this bounty" },
+    { code: 6028, name: "InvalidTimeLog", msg: "Time log needs positive hours and a period that ends after it starts" },
+    { code: 6029, name: "ContractClosed", msg: "Hourly contract is closed" },
+    { code: 6030, name: "ExceedsCap", msg: "Payment would exceed the contract's remaining cap" },
+    { code: 6031, name: "TimeLogNotPending", msg: "Time log is not pending" },
+    { code: 6032, name: "PendingTimeLogs", msg: "Approve or reject pending time logs before closing the contract" },
+    { code: 6033, name: "InvalidStreamSchedule", msg: "Stream must start now or later and end after it starts" },
+    { code: 6034, name: "StreamNotActive", msg: "Stream is not active" },
+    { code: 6035, name: "NothingVested", msg: "Nothing has vested since the last withdrawal" },
+    { code: 6036, name: "InvalidRecurrence", msg: "Recurring bounty needs a positive period and at least one period" },
+    { code: 6037, name: "RecurringNotActive", msg: "Recurring bounty is cancelled" },
+    { code: 6038, name: "NoPeriodsLeft", msg: "All funded periods have been opened" },
+    { code: 6039, name: "CycleNotDue", msg: "Next cycle is not due yet" },
+    { code: 6040, name: "InvalidTip", msg: "Tip amount must be greater than zero" },
+    { code: 6041, name: "ExceedsParentBudget", msg: "Subcontracts funded from the payout would exceed the parent budget" },
+    { code: 6042, name: "MissingTokenAccount", msg: "A token account is required to fund the subcontract" },
+    { code: 6043, name: "ParentMismatch", msg: "Bounty is not the parent of this subcontract" },
+    { code: 6044, name: "SubcontractNotFunded", msg: "Subcontract budget has not been funded from the parent yet" },
+    { code: 6045, name: "SubcontractAlreadyFunded", msg: "Subcontract is already funded" },
+    { code: 6046, name: "FundSubcontractFirst", msg: "Parent is completed; fund the subcontract before cancelling it" },
+    { code: 6047, name: "NotGuildBounty", msg: "Bounty was not claimed by this guild" },
+    { code: 6048, name: "NotGuildMember", msg: "Agent is not a member of this guild" },
+    { code: 6049, name: "NotGuildAdmin", msg: "Signer is not an admin of this guild" },
+    { code: 6050, name: "GuildMembersMismatch", msg: "Remaining accounts must match the guild's members in order" },
+    { code: 6051, name: "InvalidFeeConfig", msg: "Fee above maximum, referral share above maximum, negative duration or missing treasury" },
+    { code: 6052, name: "InvalidReferrer", msg: "A client cannot refer itself" },
+    { code: 6053, name: "TreasuryAccountRequired", msg: "Treasury token account is required while a platform fee is set" },
+    { code: 6054, name: "ReferrerAccountRequired", msg: "Referrer token account is required while a referral reward is due" },
+    { code: 6055, name: "InvalidPayeeAccount", msg: "Payee token account has the wrong owner or mint" },
+    { code: 6056, name: "InvalidStatusTransition", msg: "Bounty cannot move to that status from its current one" },
+    { code: 6057, name: "TooManySkills", msg: "Bounty requires more skills than it can be indexed under" },
+    { code: 6058, name: "IndexPageFull", msg: "Index page is full; list the bounty on the next page" },
+    { code: 6059, name: "IndexPagesMismatch", msg: "Remaining accounts must be the bounty's index pages in bucket order" },
+    { code: 6060, name: "StatusNotIndexed", msg: "Only open bounties are indexed" },
+    { code: 6061, name: "NotDisputed", msg: "Bounty is not disputed" },
+    { code: 6062, name: "InvalidResolution", msg: "Dispute payout exceeds the bounty budget" },
+    { code: 6063, name: "PreviousTimeLogRequired", msg: "The contract's previous time log must be supplied" },
+    { code: 6064, name: "TimeLogOverlap", msg: "Time log period overlaps the previous log" },
+    { code: 6065, name: "UnfundedSubcontractsMismatch", msg: "Remaining accounts must list every subcontract still awaiting the bounty's funds" },
   ],
 };
@@ -18,20 +18,34 @@ export { BountyEscrowIDL, type BountyEscrow } from "./idl";
 // Program interactions
 export {
   getAgentRegistryProgram,
+  deriveRegistryConfigPDA,
+  deriveSkillRegistryPDA,
   deriveAgentPDA,
+  deriveVerifierPDA,
+  deriveAttestationPDA,
+  deriveValidationPDA,
   registerAgent,
   updateAgent,
   fetchAgent,
   fetchAllAgents,
   type AgentAccount,
+  type AgentAvailability,
+  type AgentStatusAccount,
 } from "./programs";
 
 export {
   getBountyEscrowProgram,
+  deriveEscrowConfigPDA,
+  deriveEscrowAuthorityPDA,
   deriveClientStatePDA,
   deriveBountyPDA,
   deriveVaultPDA,
+  deriveHistoryPDA,
+  deriveIndexPagePDA,
   deriveReviewPDA,
+  indexBuckets,
+  INDEX_PAGE_CAPACITY,
+  UNSKILLED,
   initClient,
   createBounty,
   claimBounty,
@@ -46,6 +60,10 @@ export {
   fetchReview,
   fetchReviewsForAgent,
   type BountyAccount,
+  type BountyStatusAccount,
+  type BountyStatusName,
+  type CreateBountyOptions,
+  type DeclinePolicyName,
   type ReviewAccount,
 } from "./programs";
 
@@ -60,3 +78,4 @@ export {
   bountyAccountToBounty,
   reviewAccountToReview,
 } from "./utils/converters";
+export { contentHash, fetchContentHash } from "./utils/hash";
@@ -15,24 +15,73 @@ export function getAgentRegistryProgram(provider: AnchorProvider): AgentRegistry
   );
 }
 
+const REGISTRY_PROGRAM = new PublicKey(AGENT_REGISTRY_PROGRAM_ID);
+
+export function deriveRegistryConfigPDA(): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("config")], REGISTRY_PROGRAM);
+}
+
+export function deriveSkillRegistryPDA(): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("skills")], REGISTRY_PROGRAM);
+}
+
 export function deriveAgentPDA(owner: PublicKey): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
     [Buffer.from("agent"), owner.toBuffer()],
-    new PublicKey(AGENT_REGISTRY_PROGRAM_ID)
+    REGISTRY_PROGRAM
   );
 }
 
+export function deriveVerifierPDA(authority: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("verifier"), authority.toBuffer()],
+    REGISTRY_PROGRAM
+  );
+}
+
+export function deriveAttestationPDA(
+  agent: PublicKey,
+  skillId: number,
+  verifierAuthority: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("attestation"), agent.toBuffer(), Buffer.from([skillId]), verifierAuthority.toBuffer()],
+    REGISTRY_PROGRAM
+  );
+}
+
+export function deriveValidationPDA(
+  bounty: PublicKey,
+  dataHash: number[],
+  validator: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("validation"), bounty.toBuffer(), Buffer.from(dataHash), validator.toBuffer()],
+    REGISTRY_PROGRAM
+  );
+}
+
+/**
+ * Registers the connected wallet as an agent. `metadataHash` is the SHA-256 of the
+ * metadata content (see `contentHash`); `skills` is a bitmask of the on-chain skill
+ * registry.
+ */
 export async function registerAgent(
   program: AgentRegistryProgram,
   metadataUri: string,
-  hourlyRate: number
+  metadataHash: number[],
+  hourlyRate: number,
+  skills: BN = new BN(0),
+  referrer: PublicKey | null = null
 ): Promise<string> {
   const owner = program.provider.publicKey!;
   const [agentPda] = deriveAgentPDA(owner);
 
   const tx = await program.methods
-    .registerAgent(metadataUri, new BN(hourlyRate))
+    .registerAgent(metadataUri, metadataHash, new BN(hourlyRate), skills, referrer)
     .accounts({
+      config: deriveRegistryConfigPDA()[0],
+      skillRegistry: deriveSkillRegistryPDA()[0],
       agent: agentPda,
       owner,
       systemProgram: SystemProgram.programId,
@@ -42,11 +91,22 @@ export async function registerAgent(
   return tx;
 }
 
+/** Anchor encoding of the on-chain `AgentStatus` enum, e.g. `{ busy: {} }` */
+export type AgentStatusAccount =
+  | { available: Record<string, never> }
+  | { busy: Record<string, never> }
+  | { offline: Record<string, never> };
+
+export type AgentAvailability = "available" | "busy" | "offline";
+
+/** Fields left null are unchanged. A new `metadataUri` needs its `metadataHash`. */
 export async function updateAgent(
   program: AgentRegistryProgram,
   metadataUri: string | null,
+  metadataHash: number[] | null,
   hourlyRate: number | null,
-  availability: number | null
+  availability: AgentAvailability | null,
+  skills: BN | null = null
 ): Promise<string> {
   const owner = program.provider.publicKey!;
   const [agentPda] = deriveAgentPDA(owner);
@@ -54,10 +114,14 @@ export async function updateAgent(
   const tx = await program.methods
     .updateAgent(
       metadataUri,
+      metadataHash,
       hourlyRate !== null ? new BN(hourlyRate) : null,
-      availability
+      availability !== null ? { [availability]: {} } : null,
+      skills
     )
     .accounts({
+      config: deriveRegistryConfigPDA()[0],
+      skillRegistry: deriveSkillRegistryPDA()[0],
       agent: agentPda,
       owner,
     })
@@ -67,15 +131,25 @@ export async function updateAgent(
 }
 
 export interface AgentAccount {
+  version: number;
   owner: PublicKey;
   metadataUri: string;
+  metadataHash: number[];
   hourlyRate: BN;
   reputation: BN;
   bountiesCompleted: BN;
   totalEarned: BN;
-  availability: number;
+  availability: AgentStatusAccount;
   bump: number;
   createdAt: BN;
+  pendingOwner: PublicKey;
+  successor: PublicKey;
+  activeClaims: BN;
+  delegateCount: BN;
+  skills: BN;
+  guild: PublicKey;
+  referrer: PublicKey;
+  referralPaid: BN;
 }
 
 export async function fetchAgent(
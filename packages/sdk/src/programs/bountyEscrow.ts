@@ -1,8 +1,23 @@
 import { Program, AnchorProvider, BN } from "@coral-xyz/anchor";
-import { PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import {
+  type AccountMeta,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  type TransactionInstruction,
+} from "@solana/web3.js";
 import { TOKEN_PROGRAM_ID, getAssociatedTokenAddress } from "@solana/spl-token";
 import { BountyEscrowIDL } from "../idl";
-import { BOUNTY_ESCROW_PROGRAM_ID } from "../constants/programs";
+import { AGENT_REGISTRY_PROGRAM_ID, BOUNTY_ESCROW_PROGRAM_ID } from "../constants/programs";
+import {
+  type AgentAccount,
+  getAgentRegistryProgram,
+  deriveAgentPDA,
+  deriveAttestationPDA,
+  deriveSkillRegistryPDA,
+  deriveValidationPDA,
+  deriveVerifierPDA,
+} from "./agentRegistry";
 
 // Use `any` for the Program generic – the legacy IDL format is supported at
 // runtime by Anchor 0.30 but its TS types expect the new IDL spec.
@@ -16,10 +31,27 @@ export function getBountyEscrowProgram(provider: AnchorProvider): BountyEscrowPr
   );
 }
 
+const ESCROW_PROGRAM = new PublicKey(BOUNTY_ESCROW_PROGRAM_ID);
+const REGISTRY_PROGRAM = new PublicKey(AGENT_REGISTRY_PROGRAM_ID);
+
+/** Bounties listed on one open-bounty index page */
+export const INDEX_PAGE_CAPACITY = 32;
+
+/** Index bucket of bounties that require no skills */
+export const UNSKILLED = 255;
+
+export function deriveEscrowConfigPDA(): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("config")], ESCROW_PROGRAM);
+}
+
+export function deriveEscrowAuthorityPDA(): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("escrow_authority")], ESCROW_PROGRAM);
+}
+
 export function deriveClientStatePDA(client: PublicKey): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
     [Buffer.from("client"), client.toBuffer()],
-    new PublicKey(BOUNTY_ESCROW_PROGRAM_ID)
+    ESCROW_PROGRAM
   );
 }
 
@@ -28,63 +60,189 @@ export function deriveBountyPDA(client: PublicKey, bountyId: number): [PublicKey
   idBuffer.writeBigUInt64LE(BigInt(bountyId));
   return PublicKey.findProgramAddressSync(
     [Buffer.from("bounty"), client.toBuffer(), idBuffer],
-    new PublicKey(BOUNTY_ESCROW_PROGRAM_ID)
+    ESCROW_PROGRAM
   );
 }
 
 export function deriveVaultPDA(bounty: PublicKey): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
     [Buffer.from("vault"), bounty.toBuffer()],
-    new PublicKey(BOUNTY_ESCROW_PROGRAM_ID)
+    ESCROW_PROGRAM
+  );
+}
+
+export function deriveHistoryPDA(bounty: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("history"), bounty.toBuffer()],
+    ESCROW_PROGRAM
+  );
+}
+
+/** Page `page` of the open-bounty index bucket `skill` */
+export function deriveIndexPagePDA(skill: number, page: number): [PublicKey, number] {
+  const pageBuffer = Buffer.alloc(4);
+  pageBuffer.writeUInt32LE(page);
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("index"), Buffer.from([0]), Buffer.from([skill]), pageBuffer],
+    ESCROW_PROGRAM
   );
 }
 
-export async function initClient(program: BountyEscrowProgram): Promise<string> {
+export function deriveReviewPDA(bounty: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("review"), bounty.toBuffer()],
+    ESCROW_PROGRAM
+  );
+}
+
+/** Index buckets of a bounty with `skills`: one per skill bit, or UNSKILLED */
+export function indexBuckets(skills: BN): number[] {
+  if (skills.isZero()) return [UNSKILLED];
+  const buckets: number[] = [];
+  for (let bit = 0; bit < 128; bit++) {
+    if (skills.testn(bit)) buckets.push(bit);
+  }
+  return buckets;
+}
+
+/** Writable index pages a bounty is listed on, as remaining accounts */
+function indexPageAccounts(bounty: BountyAccount): AccountMeta[] {
+  return indexBuckets(bounty.skills)
+    .slice(0, bounty.indexPages.length)
+    .map((skill, i) => ({
+      pubkey: deriveIndexPagePDA(skill, bounty.indexPages[i])[0],
+      isWritable: true,
+      isSigner: false,
+    }));
+}
+
+/**
+ * Associated token accounts of `owners` for `mint`, in order. Owners that are null
+ * or the default key, and token accounts that do not exist, map to null.
+ */
+async function existingTokenAccounts(
+  program: BountyEscrowProgram,
+  mint: PublicKey,
+  owners: (PublicKey | null)[]
+): Promise<(PublicKey | null)[]> {
+  const candidates = await Promise.all(
+    owners.map((owner) =>
+      owner && !owner.equals(PublicKey.default) ? getAssociatedTokenAddress(mint, owner, true) : null
+    )
+  );
+  const addresses = candidates.filter((c): c is PublicKey => c !== null);
+  const infos = await program.provider.connection.getMultipleAccountsInfo(addresses);
+  let next = 0;
+  return candidates.map((candidate) => (candidate && infos[next++] ? candidate : null));
+}
+
+export async function initClient(
+  program: BountyEscrowProgram,
+  referrer: PublicKey | null = null
+): Promise<string> {
+  return initClientMethod(program, referrer).rpc();
+}
+
+function initClientMethod(program: BountyEscrowProgram, referrer: PublicKey | null) {
   const client = program.provider.publicKey!;
   const [clientStatePda] = deriveClientStatePDA(client);
 
-  const tx = await program.methods
-    .initClient()
-    .accounts({
-      clientState: clientStatePda,
-      client,
-      systemProgram: SystemProgram.programId,
-    })
-    .rpc();
+  return program.methods.initClient(referrer).accounts({
+    config: deriveEscrowConfigPDA()[0],
+    clientState: clientStatePda,
+    client,
+    systemProgram: SystemProgram.programId,
+  });
+}
 
-  return tx;
+export type DeclinePolicyName = "refund" | "reopen";
+
+export interface CreateBountyOptions {
+  /** Skill bitmask of the on-chain skill registry, 0 for none */
+  skills?: BN;
+  requireSkillMatch?: boolean;
+  attestationRequirement?: { verifier: PublicKey; skillId: number } | null;
+  validationRequirement?: { validator: PublicKey; minScore: number; autoApprove: boolean } | null;
+  /** Agents the bounty is offered to; empty for an open bounty */
+  allowlist?: PublicKey[];
+  declinePolicy?: DeclinePolicyName;
+  /** Referrer recorded if this is the client's first bounty */
+  referrer?: PublicKey | null;
 }
 
+/**
+ * Creates a bounty funded from the client's USDC token account. `metadataHash`
+ * is the SHA-256 of the metadata content (see `contentHash`). The client state
+ * and any missing index pages are created in the same transaction.
+ */
 export async function createBounty(
   program: BountyEscrowProgram,
   metadataUri: string,
+  metadataHash: number[],
   budget: number,
   deadline: number,
-  usdcMint: PublicKey
+  usdcMint: PublicKey,
+  options: CreateBountyOptions = {}
 ): Promise<string> {
   const client = program.provider.publicKey!;
   const [clientStatePda] = deriveClientStatePDA(client);
+  const skills = options.skills ?? new BN(0);
+  const preInstructions: TransactionInstruction[] = [];
 
-  // Fetch client state to get current bounty count
-  let clientState;
-  try {
-    clientState = await program.account.clientState.fetch(clientStatePda);
-  } catch {
-    // Client state doesn't exist yet, init it first
-    await initClient(program);
-    clientState = await program.account.clientState.fetch(clientStatePda);
+  // A missing client state is created ahead of the bounty
+  const clientState = await program.account.clientState.fetchNullable(clientStatePda);
+  if (!clientState) {
+    preInstructions.push(await initClientMethod(program, options.referrer ?? null).instruction());
+  }
+  const bountyId = clientState ? clientState.bountyCount.toNumber() : 0;
+
+  // List the bounty on the first page with room in each of its index buckets
+  const indexPages: AccountMeta[] = [];
+  for (const skill of indexBuckets(skills)) {
+    for (let page = 0; ; page++) {
+      const [indexPagePda] = deriveIndexPagePDA(skill, page);
+      const listed = await program.account.bountyIndexPage.fetchNullable(indexPagePda);
+      if (listed && listed.bounties.length >= INDEX_PAGE_CAPACITY) continue;
+      if (!listed) {
+        preInstructions.push(
+          await program.methods
+            .initIndexPage({ open: {} }, skill, page)
+            .accounts({
+              indexPage: indexPagePda,
+              payer: client,
+              systemProgram: SystemProgram.programId,
+            })
+            .instruction()
+        );
+      }
+      indexPages.push({ pubkey: indexPagePda, isWritable: true, isSigner: false });
+      break;
+    }
   }
 
-  const bountyCount = clientState.bountyCount.toNumber();
-  const [bountyPda] = deriveBountyPDA(client, bountyCount);
+  const [bountyPda] = deriveBountyPDA(client, bountyId);
   const [vaultPda] = deriveVaultPDA(bountyPda);
   const clientTokenAccount = await getAssociatedTokenAddress(usdcMint, client);
 
   const tx = await program.methods
-    .createBounty(metadataUri, new BN(budget), new BN(deadline))
+    .createBounty(
+      metadataUri,
+      metadataHash,
+      new BN(budget),
+      new BN(deadline),
+      skills,
+      options.requireSkillMatch ?? false,
+      options.attestationRequirement ?? null,
+      options.validationRequirement ?? null,
+      options.allowlist ?? [],
+      { [options.declinePolicy ?? "refund"]: {} }
+    )
     .accounts({
+      config: deriveEscrowConfigPDA()[0],
       clientState: clientStatePda,
       bounty: bountyPda,
+      history: deriveHistoryPDA(bountyPda)[0],
+      skillRegistry: deriveSkillRegistryPDA()[0],
       vault: vaultPda,
       client,
       clientTokenAccount,
@@ -93,66 +251,116 @@ export async function createBounty(
       systemProgram: SystemProgram.programId,
       rent: SYSVAR_RENT_PUBKEY,
     })
+    .remainingAccounts(indexPages)
+    .preInstructions(preInstructions)
     .rpc();
 
   return tx;
 }
 
+/** Claims an open bounty as the connected wallet's agent */
 export async function claimBounty(
   program: BountyEscrowProgram,
   bountyPda: PublicKey
 ): Promise<string> {
   const agent = program.provider.publicKey!;
+  const bounty = (await program.account.bounty.fetch(bountyPda)) as BountyAccount;
+  const [agentProfile] = deriveAgentPDA(agent);
+  const requirement = bounty.attestationRequirement;
 
   const tx = await program.methods
     .claimBounty()
     .accounts({
+      config: deriveEscrowConfigPDA()[0],
       bounty: bountyPda,
+      history: deriveHistoryPDA(bountyPda)[0],
       agent,
+      agentProfile,
+      agentDelegate: null,
+      attestation: requirement
+        ? deriveAttestationPDA(agentProfile, requirement.skillId, requirement.verifier)[0]
+        : null,
+      verifier: requirement ? deriveVerifierPDA(requirement.verifier)[0] : null,
+      escrowAuthority: deriveEscrowAuthorityPDA()[0],
+      agentRegistryProgram: REGISTRY_PROGRAM,
     })
+    .remainingAccounts(indexPageAccounts(bounty))
     .rpc();
 
   return tx;
 }
 
+/** `deliverableHash` is the SHA-256 of the deliverable content (see `contentHash`) */
 export async function submitWork(
   program: BountyEscrowProgram,
   bountyPda: PublicKey,
-  deliverableUri: string
+  deliverableUri: string,
+  deliverableHash: number[]
 ): Promise<string> {
   const agent = program.provider.publicKey!;
 
   const tx = await program.methods
-    .submitWork(deliverableUri)
+    .submitWork(deliverableUri, deliverableHash)
     .accounts({
+      config: deriveEscrowConfigPDA()[0],
       bounty: bountyPda,
+      history: deriveHistoryPDA(bountyPda)[0],
       agent,
+      agentProfile: deriveAgentPDA(agent)[0],
+      agentDelegate: null,
     })
     .rpc();
 
   return tx;
 }
 
+/**
+ * Approves the deliverable and pays the agent, net of the platform fee. The
+ * treasury's and referrers' token accounts are attached when they exist; the
+ * program rejects the payout if one it owes a share to is missing.
+ */
 export async function approveWork(
   program: BountyEscrowProgram,
   bountyPda: PublicKey
 ): Promise<string> {
   const client = program.provider.publicKey!;
-  const bountyAccount = await program.account.bounty.fetch(bountyPda);
-  const [vaultPda] = deriveVaultPDA(bountyPda);
-  const agentTokenAccount = await getAssociatedTokenAddress(
-    bountyAccount.usdcMint,
-    bountyAccount.assignedAgent
+  const bounty = (await program.account.bounty.fetch(bountyPda)) as BountyAccount;
+  const [configPda] = deriveEscrowConfigPDA();
+  const [clientStatePda] = deriveClientStatePDA(bounty.client);
+  const [agentProfile] = deriveAgentPDA(bounty.assignedAgent);
+  const registry = getAgentRegistryProgram(program.provider as AnchorProvider);
+  const [config, clientState, agent] = await Promise.all([
+    program.account.config.fetch(configPda),
+    program.account.clientState.fetch(clientStatePda),
+    registry.account.agent.fetch(agentProfile),
+  ]);
+  const [treasury, agentReferrer, clientReferrer] = await existingTokenAccounts(
+    program,
+    bounty.usdcMint,
+    [config.feeBps > 0 ? config.treasury : null, agent.referrer, clientState.referrer]
   );
+  const requirement = bounty.validationRequirement;
 
   const tx = await program.methods
     .approveWork()
     .accounts({
+      config: configPda,
       bounty: bountyPda,
-      vault: vaultPda,
-      agentTokenAccount,
+      history: deriveHistoryPDA(bountyPda)[0],
+      vault: bounty.vault,
+      agentTokenAccount: await getAssociatedTokenAddress(bounty.usdcMint, bounty.assignedAgent),
+      agentProfile,
+      validation: requirement
+        ? deriveValidationPDA(bountyPda, bounty.deliverableHash, requirement.validator)[0]
+        : null,
+      clientState: clientStatePda,
+      treasuryTokenAccount: treasury,
+      agentReferrerTokenAccount: agentReferrer,
+      clientReferrerTokenAccount: clientReferrer,
+      escrowAuthority: deriveEscrowAuthorityPDA()[0],
       client,
       tokenProgram: TOKEN_PROGRAM_ID,
+      agentRegistryProgram: REGISTRY_PROGRAM,
     })
     .rpc();
 
@@ -168,7 +376,9 @@ export async function disputeBounty(
   const tx = await program.methods
     .disputeBounty()
     .accounts({
+      config: deriveEscrowConfigPDA()[0],
       bounty: bountyPda,
+      history: deriveHistoryPDA(bountyPda)[0],
       authority,
     })
     .rpc();
@@ -182,47 +392,66 @@ export async function cancelBounty(
   usdcMint: PublicKey
 ): Promise<string> {
   const client = program.provider.publicKey!;
-  const [vaultPda] = deriveVaultPDA(bountyPda);
+  const bounty = (await program.account.bounty.fetch(bountyPda)) as BountyAccount;
   const clientTokenAccount = await getAssociatedTokenAddress(usdcMint, client);
 
   const tx = await program.methods
     .cancelBounty()
     .accounts({
       bounty: bountyPda,
-      vault: vaultPda,
+      history: deriveHistoryPDA(bountyPda)[0],
+      vault: bounty.vault,
       clientTokenAccount,
+      parentBounty: bounty.parent.equals(PublicKey.default) ? null : bounty.parent,
       client,
       tokenProgram: TOKEN_PROGRAM_ID,
     })
+    .remainingAccounts(indexPageAccounts(bounty))
     .rpc();
 
   return tx;
 }
 
-export function deriveReviewPDA(bounty: PublicKey): [PublicKey, number] {
-  return PublicKey.findProgramAddressSync(
-    [Buffer.from("review"), bounty.toBuffer()],
-    new PublicKey(BOUNTY_ESCROW_PROGRAM_ID)
-  );
-}
-
+/**
+ * Reviews the agent of a completed bounty. If the agent's profile has since been
+ * transferred, its tombstones are followed to the current profile.
+ * `commentHash` is the SHA-256 of the comment content (see `contentHash`).
+ */
 export async function leaveReview(
   program: BountyEscrowProgram,
   bountyPda: PublicKey,
   rating: number,
-  commentUri: string
+  commentUri: string,
+  commentHash: number[]
 ): Promise<string> {
   const client = program.provider.publicKey!;
   const [reviewPda] = deriveReviewPDA(bountyPda);
+  const bounty = (await program.account.bounty.fetch(bountyPda)) as BountyAccount;
+  const registry = getAgentRegistryProgram(program.provider as AnchorProvider);
+
+  // A transferred profile is kept as a tombstone naming the next one
+  let [agentProfile] = deriveAgentPDA(bounty.assignedAgent);
+  const tombstones: AccountMeta[] = [];
+  let profile = (await registry.account.agent.fetch(agentProfile)) as AgentAccount;
+  while (!profile.successor.equals(PublicKey.default)) {
+    tombstones.push({ pubkey: agentProfile, isWritable: false, isSigner: false });
+    agentProfile = profile.successor;
+    profile = (await registry.account.agent.fetch(agentProfile)) as AgentAccount;
+  }
 
   const tx = await program.methods
-    .leaveReview(new BN(rating), commentUri)
+    .leaveReview(new BN(rating), commentUri, commentHash)
     .accounts({
+      config: deriveEscrowConfigPDA()[0],
       bounty: bountyPda,
       review: reviewPda,
+      agentProfile,
+      escrowAuthority: deriveEscrowAuthorityPDA()[0],
       client,
       systemProgram: SystemProgram.programId,
+      agentRegistryProgram: REGISTRY_PROGRAM,
     })
+    .remainingAccounts(tombstones)
     .rpc();
 
   return tx;
@@ -234,6 +463,7 @@ export interface ReviewAccount {
   agent: PublicKey;
   rating: BN;
   commentUri: string;
+  commentHash: number[];
   bump: number;
   createdAt: BN;
 }
@@ -266,20 +496,61 @@ export async function fetchReviewsForAgent(
   return accounts as { publicKey: PublicKey; account: ReviewAccount }[];
 }
 
+/** Anchor encoding of the on-chain `BountyStatus` enum, e.g. `{ open: {} }` */
+export type BountyStatusAccount =
+  | { open: Record<string, never> }
+  | { claimed: Record<string, never> }
+  | { delivered: Record<string, never> }
+  | { completed: Record<string, never> }
+  | { disputed: Record<string, never> }
+  | { cancelled: Record<string, never> };
+
+export type BountyStatusName =
+  | "open"
+  | "claimed"
+  | "delivered"
+  | "completed"
+  | "disputed"
+  | "cancelled";
+
 export interface BountyAccount {
+  version: number;
   client: PublicKey;
   bountyId: BN;
   metadataUri: string;
+  metadataHash: number[];
   budget: BN;
   deadline: BN;
-  status: number;
+  status: BountyStatusAccount;
   claims: BN;
   assignedAgent: PublicKey;
   deliverableUri: string;
+  deliverableHash: number[];
   vault: PublicKey;
   usdcMint: PublicKey;
   bump: number;
   createdAt: BN;
+  skills: BN;
+  requireSkillMatch: boolean;
+  attestationRequirement: { verifier: PublicKey; skillId: number } | null;
+  validationRequirement: { validator: PublicKey; minScore: number; autoApprove: boolean } | null;
+  allowlist: PublicKey[];
+  declinePolicy: { refund: Record<string, never> } | { reopen: Record<string, never> };
+  recurring: PublicKey;
+  tipsTotal: BN;
+  parent: PublicKey;
+  childCount: BN;
+  reservedForChildren: BN;
+  awaitingParentFunds: boolean;
+  guild: PublicKey;
+  claimedAt: BN;
+  deliveredAt: BN;
+  completedAt: BN;
+  disputedAt: BN;
+  cancelledAt: BN;
+  lastActor: PublicKey;
+  indexPages: number[];
+  disputePayout: BN;
 }
 
 export async function fetchBounty(
@@ -308,7 +579,7 @@ export async function fetchBountiesByClient(
   const accounts = await program.account.bounty.all([
     {
       memcmp: {
-        offset: 8, // discriminator
+        offset: 8 + 1, // discriminator + version => client field
         bytes: client.toBase58(),
       },
     },
@@ -1,19 +1,33 @@
 export {
   getAgentRegistryProgram,
+  deriveRegistryConfigPDA,
+  deriveSkillRegistryPDA,
   deriveAgentPDA,
+  deriveVerifierPDA,
+  deriveAttestationPDA,
+  deriveValidationPDA,
   registerAgent,
   updateAgent,
   fetchAgent,
   fetchAllAgents,
   type AgentAccount,
+  type AgentAvailability,
+  type AgentStatusAccount,
 } from "./agentRegistry";
 
 export {
   getBountyEscrowProgram,
+  deriveEscrowConfigPDA,
+  deriveEscrowAuthorityPDA,
   deriveClientStatePDA,
   deriveBountyPDA,
   deriveVaultPDA,
+  deriveHistoryPDA,
+  deriveIndexPagePDA,
   deriveReviewPDA,
+  indexBuckets,
+  INDEX_PAGE_CAPACITY,
+  UNSKILLED,
   initClient,
   createBounty,
   claimBounty,
@@ -28,5 +42,9 @@ export {
   fetchReview,
   fetchReviewsForAgent,
   type BountyAccount,
+  type BountyStatusAccount,
+  type BountyStatusName,
+  type CreateBountyOptions,
+  type DeclinePolicyName,
   type ReviewAccount,
 } from "./bountyEscrow";
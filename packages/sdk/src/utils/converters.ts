@@ -2,8 +2,13 @@ import type { Agent } from "../types/agent";
 import type { Bounty } from "../types/bounty";
 import type { Review } from "../types/review";
 import type { AgentMetadata, BountyMetadata } from "../types/metadata";
-import type { AgentAccount } from "../programs/agentRegistry";
-import type { BountyAccount, ReviewAccount } from "../programs/bountyEscrow";
+import type { AgentAccount, AgentAvailability, AgentStatusAccount } from "../programs/agentRegistry";
+import type {
+  BountyAccount,
+  BountyStatusAccount,
+  BountyStatusName,
+  ReviewAccount,
+} from "../programs/bountyEscrow";
 
 const USDC_DECIMALS = 6;
 
@@ -17,40 +22,16 @@ export function toUsdcMinorUnits(amount: number): number {
   return Math.round(amount * 10 ** USDC_DECIMALS);
 }
 
-/** Convert on-chain availability u8 to display string */
-export function availabilityToString(availability: number): "available" | "busy" | "offline" {
-  switch (availability) {
-    case 0:
-      return "available";
-    case 1:
-      return "busy";
-    case 2:
-      return "offline";
-    default:
-      return "offline";
-  }
+/** Convert on-chain `AgentStatus` enum to display string */
+export function availabilityToString(availability: AgentStatusAccount): AgentAvailability {
+  return Object.keys(availability)[0] as AgentAvailability;
 }
 
-/** Convert on-chain bounty status u8 to display string */
+/** Convert on-chain `BountyStatus` enum to display string */
 export function statusToString(
-  status: number
+  status: BountyStatusAccount
 ): "open" | "claimed" | "in_progress" | "delivered" | "completed" | "disputed" | "cancelled" {
-  switch (status) {
-    case 0:
-      return "open";
-    case 1:
-      return "claimed";
-    case 2:
-      return "delivered";
-    case 3:
-      return "completed";
-    case 4:
-      return "disputed";
-    case 5:
-      return "cancelled";
-    default:
-      return "open";
-  }
+  return Object.keys(status)[0] as BountyStatusName;
 }
 
 /** Convert on-chain reputation (fixed-point * 100) to human-readable (e.g. 480 -> 4.80) */
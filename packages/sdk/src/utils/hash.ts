@@ -0,0 +1,16 @@
+/**
+ * SHA-256 of off-chain content, as stored next to every metadata, deliverable and
+ * comment URI. Strings are hashed as UTF-8.
+ */
+export async function contentHash(content: string | Uint8Array): Promise<number[]> {
+  const bytes = typeof content === "string" ? new TextEncoder().encode(content) : content;
+  const digest = await crypto.subtle.digest("SHA-256", bytes);
+  return Array.from(new Uint8Array(digest));
+}
+
+/** Fetches `uri` and hashes the content it serves */
+export async function fetchContentHash(uri: string): Promise<number[]> {
+  const res = await fetch(uri);
+  if (!res.ok) throw new Error(`Failed to fetch ${uri}: ${res.status}`);
+  return contentHash(new Uint8Array(await res.arrayBuffer()));
+}
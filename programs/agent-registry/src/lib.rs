@@ -1,62 +1,688 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
 
-declare_id!("AgntReg1stry111111111111111111111111111111");
+declare_id!("DiLuZ4JcnyFcE6FttH5NryQJrM2KKewy2Z8oDk9iJXNF");
 
-/// Maximum length for metadata URI
-const MAX_URI_LEN: usize = 200;
+/// Bounty-escrow program, the only caller allowed to use the CPI-only instructions
+pub const BOUNTY_ESCROW_PROGRAM_ID: Pubkey = pubkey!("2KY4RJwdYKnnDMU4WcuwgU2f8B7JoxjdKaTYL953AKb5");
 
-/// Account space: discriminator(8) + owner(32) + uri_string(4+200) + hourly_rate(8) +
-/// reputation(8) + bounties_completed(8) + total_earned(8) + availability(1) + bump(1) + created_at(8)
-const AGENT_SPACE: usize = 8 + 32 + (4 + MAX_URI_LEN) + 8 + 8 + 8 + 8 + 1 + 1 + 8;
+/// Seed of the bounty-escrow PDA that signs CPIs into this program
+pub const ESCROW_AUTHORITY_SEED: &[u8] = b"escrow_authority";
+
+/// Maximum length for metadata URI (fits IPFS/Arweave gateway URLs)
+const MAX_URI_LEN: usize = 512;
+
+/// Current Agent layout version, stored in the first byte after the discriminator.
+/// Each version appends fields to the previous one: 1 versioned the account (512-byte
+/// URIs, hashes, transfers and delegates), 2 added `skills`, 3 `guild`, and 4
+/// `referrer` and `referral_paid`.
+pub const AGENT_VERSION: u8 = 4;
+
+/// Account space: discriminator(8) + version(1) + owner(32) + uri_string(4+512) + metadata_hash(32) +
+/// hourly_rate(8) + reputation(8) + bounties_completed(8) + total_earned(8) + availability(1) + bump(1) +
+/// created_at(8) + pending_owner(32) + successor(32) + active_claims(8) + delegate_count(8) + skills(16) +
+/// guild(32) + referrer(32) + referral_paid(8)
+const AGENT_SPACE: usize = 8 + 1 + 32 + (4 + MAX_URI_LEN) + 32 + 8 + 8 + 8 + 8 + 1 + 1 + 8 + 32 + 32 + 8 + 8 + 16 + 32 + 32 + 8;
+
+/// Space of the unversioned layout (200-byte URI) that `migrate_agent` upgrades from
+const LEGACY_AGENT_SPACE: usize = 8 + 32 + (4 + 200) + 8 + 8 + 8 + 8 + 1 + 1 + 8;
+
+/// AgentArchive space: discriminator(8) + agent(32) + owner(32) + reputation(8) +
+/// bounties_completed(8) + total_earned(8) + created_at(8) + deregistered_at(8) + bump(1)
+const AGENT_ARCHIVE_SPACE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 1;
+
+/// AgentDelegate space: discriminator(8) + agent(32) + delegate(32) + permissions(1) +
+/// bump(1) + created_at(8)
+const AGENT_DELEGATE_SPACE: usize = 8 + 32 + 32 + 1 + 1 + 8;
+
+/// Config space: discriminator(8) + admin(32) + paused(8) + bump(1)
+const CONFIG_SPACE: usize = 8 + 32 + 8 + 1;
+
+/// Skill ids index bits of a u128 bitmap
+pub const MAX_SKILLS: usize = 128;
+const MAX_SKILL_NAME_LEN: usize = 32;
+
+/// SkillRegistry space: discriminator(8) + names(4 + 128 * (4+32)) + bump(1)
+const SKILL_REGISTRY_SPACE: usize = 8 + (4 + MAX_SKILLS * (4 + MAX_SKILL_NAME_LEN)) + 1;
+
+/// Verifier space: discriminator(8) + authority(32) + active(1) + bump(1) + created_at(8)
+const VERIFIER_SPACE: usize = 8 + 32 + 1 + 1 + 8;
+
+/// Validation space: discriminator(8) + bounty(32) + requester(32) + validator(32) + data_hash(32) +
+/// score(1) + response_hash(32) + responded(1) + requested_at(8) + responded_at(8) + bump(1)
+const VALIDATION_SPACE: usize = 8 + 32 + 32 + 32 + 32 + 1 + 32 + 1 + 8 + 8 + 1;
+
+/// Attestation space: discriminator(8) + agent(32) + skill_id(1) + verifier(32) +
+/// evidence_hash(32) + issued_at(8) + expires_at(8) + bump(1)
+const ATTESTATION_SPACE: usize = 8 + 32 + 1 + 32 + 32 + 8 + 8 + 1;
+
+/// Maximum number of agents in a guild
+pub const MAX_GUILD_MEMBERS: usize = 8;
+
+/// Revenue shares are expressed in basis points and must sum to this
+pub const GUILD_SHARE_TOTAL: u16 = 10_000;
+
+/// Guild space: discriminator(8) + creator(32) + guild_id(8) + metadata_uri(4+512) +
+/// metadata_hash(32) + members(4 + 8 * (32+1+2)) + reputation(8) + bounties_completed(8) +
+/// total_earned(8) + active_claims(8) + bump(1) + created_at(8)
+const GUILD_SPACE: usize = 8 + 32 + 8 + (4 + MAX_URI_LEN) + 32 + (4 + MAX_GUILD_MEMBERS * (32 + 1 + 2)) + 8 + 8 + 8 + 8 + 1 + 8;
+
+/// Pause flags: a set bit in `Config.paused` halts the matching instructions.
+/// Deregistration, delegate removal, migrations and CPIs from bounty-escrow are never paused.
+pub const PAUSE_REGISTER_AGENT: u64 = 1 << 0;
+pub const PAUSE_UPDATE_AGENT: u64 = 1 << 1;
+pub const PAUSE_SET_AVAILABILITY: u64 = 1 << 2;
+/// Covers `add_delegate` and `update_delegate`
+pub const PAUSE_DELEGATES: u64 = 1 << 3;
+/// Covers `propose_owner_transfer`, `cancel_owner_transfer` and `accept_owner_transfer`
+pub const PAUSE_OWNER_TRANSFER: u64 = 1 << 4;
+/// Covers `request_validation` and `respond_validation`
+pub const PAUSE_VALIDATION: u64 = 1 << 5;
+/// Covers `create_guild`, `add_guild_member`, `set_guild_split` and `set_guild_role`
+pub const PAUSE_GUILDS: u64 = 1 << 6;
+
+/// Highest score a validator can post
+pub const MAX_VALIDATION_SCORE: u8 = 100;
+
+/// Delegate may claim bounties on behalf of the agent
+pub const DELEGATE_CLAIM: u8 = 1 << 0;
+/// Delegate may submit work on behalf of the agent
+pub const DELEGATE_SUBMIT: u8 = 1 << 1;
+/// Delegate may change the agent's availability
+pub const DELEGATE_UPDATE_AVAILABILITY: u8 = 1 << 2;
+const DELEGATE_ALL: u8 = DELEGATE_CLAIM | DELEGATE_SUBMIT | DELEGATE_UPDATE_AVAILABILITY;
 
 #[program]
 pub mod agent_registry {
     use super::*;
 
+    /// Creates the config PDA. Only the program's upgrade authority may do this,
+    /// and it becomes the initial admin.
+    pub fn init_config(ctx: Context<InitConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.paused = 0;
+        config.bump = ctx.bumps.config;
+        Ok(())
+    }
+
+    /// Admin replaces the pause bitmask (see PAUSE_* flags).
+    pub fn set_paused(ctx: Context<UpdateConfig>, paused: u64) -> Result<()> {
+        ctx.accounts.config.paused = paused;
+        Ok(())
+    }
+
+    /// Admin hands the config over to a new admin.
+    pub fn set_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.config.admin = new_admin;
+        Ok(())
+    }
+
+    /// Admin creates the skill registry that assigns on-chain skill ids.
+    pub fn init_skill_registry(ctx: Context<InitSkillRegistry>) -> Result<()> {
+        let skill_registry = &mut ctx.accounts.skill_registry;
+        skill_registry.names = Vec::new();
+        skill_registry.bump = ctx.bumps.skill_registry;
+        Ok(())
+    }
+
+    /// Admin defines a new skill. Its id is its index; ids are never reused.
+    pub fn add_skill(ctx: Context<AddSkill>, name: String) -> Result<()> {
+        require!(
+            !name.is_empty() && name.len() <= MAX_SKILL_NAME_LEN,
+            AgentError::InvalidSkillName
+        );
+
+        let skill_registry = &mut ctx.accounts.skill_registry;
+        require!(
+            skill_registry.names.len() < MAX_SKILLS,
+            AgentError::SkillRegistryFull
+        );
+        require!(
+            !skill_registry.names.contains(&name),
+            AgentError::InvalidSkillName
+        );
+
+        skill_registry.names.push(name);
+        Ok(())
+    }
+
+    /// Admin approves an attestation issuer. `authority` may be a wallet or a PDA
+    /// of another program that signs via CPI.
+    pub fn add_verifier(ctx: Context<AddVerifier>) -> Result<()> {
+        let verifier = &mut ctx.accounts.verifier;
+        verifier.authority = ctx.accounts.authority.key();
+        verifier.active = true;
+        verifier.bump = ctx.bumps.verifier;
+        verifier.created_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// Admin enables or disables a verifier. Attestations from a disabled verifier
+    /// stop satisfying bounty requirements.
+    pub fn set_verifier_active(ctx: Context<SetVerifierActive>, active: bool) -> Result<()> {
+        ctx.accounts.verifier.active = active;
+        Ok(())
+    }
+
+    /// Active verifier attests that an agent holds a skill, optionally until
+    /// `expires_at` (0 = no expiry).
+    pub fn issue_attestation(
+        ctx: Context<IssueAttestation>,
+        skill_id: u8,
+        expires_at: i64,
+        evidence_hash: [u8; 32],
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            (skill_id as usize) < ctx.accounts.skill_registry.names.len(),
+            AgentError::UnknownSkill
+        );
+        require!(expires_at == 0 || expires_at > now, AgentError::InvalidExpiry);
+
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.agent = ctx.accounts.agent.key();
+        attestation.skill_id = skill_id;
+        attestation.verifier = ctx.accounts.authority.key();
+        attestation.evidence_hash = evidence_hash;
+        attestation.issued_at = now;
+        attestation.expires_at = expires_at;
+        attestation.bump = ctx.bumps.attestation;
+        Ok(())
+    }
+
+    /// Issuing verifier revokes an attestation, closing it.
+    pub fn revoke_attestation(_ctx: Context<RevokeAttestation>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Client or agent asks `validator` to check the deliverable with `data_hash`
+    /// submitted for `bounty`.
+    pub fn request_validation(
+        ctx: Context<RequestValidation>,
+        bounty: Pubkey,
+        data_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_VALIDATION), AgentError::Paused);
+
+        require!(data_hash != [0u8; 32], AgentError::MissingContentHash);
+
+        let validation = &mut ctx.accounts.validation;
+        validation.bounty = bounty;
+        validation.requester = ctx.accounts.requester.key();
+        validation.validator = ctx.accounts.validator.key();
+        validation.data_hash = data_hash;
+        validation.score = 0;
+        validation.response_hash = [0u8; 32];
+        validation.responded = false;
+        validation.requested_at = Clock::get()?.unix_timestamp;
+        validation.responded_at = 0;
+        validation.bump = ctx.bumps.validation;
+        Ok(())
+    }
+
+    /// Named validator posts (or revises) its score, 0-100, for a requested validation.
+    pub fn respond_validation(
+        ctx: Context<RespondValidation>,
+        score: u8,
+        response_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_VALIDATION), AgentError::Paused);
+
+        require!(score <= MAX_VALIDATION_SCORE, AgentError::InvalidValidationScore);
+
+        let validation = &mut ctx.accounts.validation;
+        validation.score = score;
+        validation.response_hash = response_hash;
+        validation.responded = true;
+        validation.responded_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
     pub fn register_agent(
         ctx: Context<RegisterAgent>,
         metadata_uri: String,
+        metadata_hash: [u8; 32],
         hourly_rate: u64,
+        skills: u128,
+        referrer: Option<Pubkey>,
     ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_REGISTER_AGENT), AgentError::Paused);
+
         require!(metadata_uri.len() <= MAX_URI_LEN, AgentError::UriTooLong);
+        require!(metadata_hash != [0u8; 32], AgentError::MissingContentHash);
         require!(hourly_rate > 0, AgentError::InvalidHourlyRate);
+        require!(
+            ctx.accounts.skill_registry.is_valid(skills),
+            AgentError::UnknownSkill
+        );
+        let referrer = referrer.unwrap_or_default();
+        require!(
+            referrer != ctx.accounts.owner.key(),
+            AgentError::InvalidReferrer
+        );
 
         let agent = &mut ctx.accounts.agent;
+        agent.version = AGENT_VERSION;
         agent.owner = ctx.accounts.owner.key();
         agent.metadata_uri = metadata_uri;
+        agent.metadata_hash = metadata_hash;
         agent.hourly_rate = hourly_rate;
         agent.reputation = 0;
         agent.bounties_completed = 0;
         agent.total_earned = 0;
-        agent.availability = AgentStatus::Available as u8;
+        agent.availability = AgentStatus::Available;
         agent.bump = ctx.bumps.agent;
         agent.created_at = Clock::get()?.unix_timestamp;
+        agent.pending_owner = Pubkey::default();
+        agent.successor = Pubkey::default();
+        agent.active_claims = 0;
+        agent.delegate_count = 0;
+        agent.skills = skills;
+        agent.guild = Pubkey::default();
+        agent.referrer = referrer;
+        agent.referral_paid = 0;
         Ok(())
     }
 
     pub fn update_agent(
         ctx: Context<UpdateAgent>,
         metadata_uri: Option<String>,
+        metadata_hash: Option<[u8; 32]>,
         hourly_rate: Option<u64>,
-        availability: Option<u8>,
+        availability: Option<AgentStatus>,
+        skills: Option<u128>,
     ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_UPDATE_AGENT), AgentError::Paused);
+
         let agent = &mut ctx.accounts.agent;
 
-        if let Some(uri) = metadata_uri {
-            require!(uri.len() <= MAX_URI_LEN, AgentError::UriTooLong);
-            agent.metadata_uri = uri;
+        // A new URI must always come with the hash of the content it points to
+        match (metadata_uri, metadata_hash) {
+            (Some(uri), Some(hash)) => {
+                require!(uri.len() <= MAX_URI_LEN, AgentError::UriTooLong);
+                require!(hash != [0u8; 32], AgentError::MissingContentHash);
+                agent.metadata_uri = uri;
+                agent.metadata_hash = hash;
+            }
+            (None, None) => {}
+            _ => return err!(AgentError::MissingContentHash),
         }
         if let Some(rate) = hourly_rate {
             require!(rate > 0, AgentError::InvalidHourlyRate);
             agent.hourly_rate = rate;
         }
-        if let Some(avail) = availability {
-            require!(avail <= 2, AgentError::InvalidAvailability);
-            agent.availability = avail;
+        if let Some(availability) = availability {
+            agent.availability = availability;
+        }
+        if let Some(skills) = skills {
+            require!(
+                ctx.accounts.skill_registry.is_valid(skills),
+                AgentError::UnknownSkill
+            );
+            agent.skills = skills;
+        }
+        Ok(())
+    }
+
+    /// Owner authorizes a delegate (hot) key with a bitmask of DELEGATE_* permissions.
+    pub fn add_delegate(ctx: Context<AddDelegate>, permissions: u8) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_DELEGATES), AgentError::Paused);
+
+        require!(
+            permissions != 0 && permissions & !DELEGATE_ALL == 0,
+            AgentError::InvalidDelegatePermissions
+        );
+
+        let agent_delegate = &mut ctx.accounts.agent_delegate;
+        agent_delegate.agent = ctx.accounts.agent.key();
+        agent_delegate.delegate = ctx.accounts.delegate.key();
+        agent_delegate.permissions = permissions;
+        agent_delegate.bump = ctx.bumps.agent_delegate;
+        agent_delegate.created_at = Clock::get()?.unix_timestamp;
+
+        let agent = &mut ctx.accounts.agent;
+        agent.delegate_count = agent.delegate_count.checked_add(1).unwrap();
+        Ok(())
+    }
+
+    /// Owner changes the permissions of an existing delegate.
+    pub fn update_delegate(ctx: Context<UpdateDelegate>, permissions: u8) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_DELEGATES), AgentError::Paused);
+
+        require!(
+            permissions != 0 && permissions & !DELEGATE_ALL == 0,
+            AgentError::InvalidDelegatePermissions
+        );
+
+        ctx.accounts.agent_delegate.permissions = permissions;
+        Ok(())
+    }
+
+    /// Owner revokes a delegate, closing its account and reclaiming rent.
+    pub fn remove_delegate(ctx: Context<RemoveDelegate>) -> Result<()> {
+        let agent = &mut ctx.accounts.agent;
+        agent.delegate_count = agent.delegate_count.saturating_sub(1);
+        Ok(())
+    }
+
+    /// Owner or a delegate holding DELEGATE_UPDATE_AVAILABILITY sets availability.
+    pub fn set_availability(ctx: Context<SetAvailability>, availability: AgentStatus) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_SET_AVAILABILITY), AgentError::Paused);
+
+        let agent = &mut ctx.accounts.agent;
+        let authority_key = ctx.accounts.authority.key();
+        if authority_key != agent.owner {
+            let agent_delegate = ctx
+                .accounts
+                .agent_delegate
+                .as_ref()
+                .ok_or(AgentError::DelegateNotAuthorized)?;
+            require!(
+                agent_delegate.agent == agent.key()
+                    && agent_delegate.delegate == authority_key
+                    && agent_delegate.has_permission(DELEGATE_UPDATE_AVAILABILITY),
+                AgentError::DelegateNotAuthorized
+            );
+        }
+
+        agent.availability = availability;
+        Ok(())
+    }
+
+    /// Owner proposes a new wallet to take over the agent. Proposing again replaces
+    /// the pending owner; the transfer only happens once the new owner accepts.
+    pub fn propose_owner_transfer(
+        ctx: Context<ProposeOwnerTransfer>,
+        new_owner: Pubkey,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_OWNER_TRANSFER), AgentError::Paused);
+
+        let agent = &mut ctx.accounts.agent;
+        require!(
+            new_owner != Pubkey::default() && new_owner != agent.owner,
+            AgentError::InvalidNewOwner
+        );
+
+        agent.pending_owner = new_owner;
+        Ok(())
+    }
+
+    /// Owner withdraws a pending ownership transfer.
+    pub fn cancel_owner_transfer(ctx: Context<ProposeOwnerTransfer>) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_OWNER_TRANSFER), AgentError::Paused);
+
+        let agent = &mut ctx.accounts.agent;
+        require!(
+            agent.pending_owner != Pubkey::default(),
+            AgentError::NoPendingTransfer
+        );
+
+        agent.pending_owner = Pubkey::default();
+        Ok(())
+    }
+
+    /// Pending owner accepts the transfer: the profile (reputation, counters, earnings)
+    /// moves to the new owner's agent PDA and the old PDA is kept as a tombstone whose
+    /// `successor` points at it. Reviews and tips of bounties the old owner worked on
+    /// follow it to the new profile, and the old wallet cannot register again.
+    /// Refused while the agent still has claimed bounties in flight, since their
+    /// payouts are bound to the old owner wallet, while delegates remain or while it
+    /// belongs to a guild.
+    pub fn accept_owner_transfer(ctx: Context<AcceptOwnerTransfer>) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_OWNER_TRANSFER), AgentError::Paused);
+
+        let old_agent = &mut ctx.accounts.old_agent;
+        let new_agent = &mut ctx.accounts.new_agent;
+        require!(old_agent.active_claims == 0, AgentError::ActiveClaims);
+        require!(old_agent.delegate_count == 0, AgentError::DelegatesRemain);
+        require!(old_agent.guild == Pubkey::default(), AgentError::InGuild);
+
+        new_agent.version = AGENT_VERSION;
+        new_agent.owner = ctx.accounts.new_owner.key();
+        new_agent.metadata_uri = old_agent.metadata_uri.clone();
+        new_agent.metadata_hash = old_agent.metadata_hash;
+        new_agent.hourly_rate = old_agent.hourly_rate;
+        new_agent.reputation = old_agent.reputation;
+        new_agent.bounties_completed = old_agent.bounties_completed;
+        new_agent.total_earned = old_agent.total_earned;
+        new_agent.availability = old_agent.availability;
+        new_agent.bump = ctx.bumps.new_agent;
+        new_agent.created_at = old_agent.created_at;
+        new_agent.pending_owner = Pubkey::default();
+        new_agent.successor = Pubkey::default();
+        new_agent.active_claims = 0;
+        new_agent.delegate_count = 0;
+        new_agent.skills = old_agent.skills;
+        new_agent.guild = Pubkey::default();
+        new_agent.referrer = old_agent.referrer;
+        new_agent.referral_paid = old_agent.referral_paid;
+
+        old_agent.pending_owner = Pubkey::default();
+        old_agent.successor = new_agent.key();
+        old_agent.availability = AgentStatus::Offline;
+        Ok(())
+    }
+
+    /// Owner closes the agent account and reclaims its rent. Refused while any
+    /// claimed bounty is still in flight, delegates remain or the agent is in a guild. When an `archive`
+    /// account is supplied, a small record is left behind so reviews of the agent
+    /// stay resolvable.
+    pub fn deregister_agent(ctx: Context<DeregisterAgent>) -> Result<()> {
+        let agent = &ctx.accounts.agent;
+        require!(agent.active_claims == 0, AgentError::ActiveClaims);
+        require!(agent.delegate_count == 0, AgentError::DelegatesRemain);
+        require!(agent.guild == Pubkey::default(), AgentError::InGuild);
+
+        if let Some(archive) = ctx.accounts.archive.as_mut() {
+            archive.agent = agent.key();
+            archive.owner = agent.owner;
+            archive.reputation = agent.reputation;
+            archive.bounties_completed = agent.bounties_completed;
+            archive.total_earned = agent.total_earned;
+            archive.created_at = agent.created_at;
+            archive.deregistered_at = Clock::get()?.unix_timestamp;
+            archive.bump = ctx.bumps.archive.unwrap();
+        }
+        Ok(())
+    }
+
+    /// Upgrades an agent account written at an earlier layout, or before layout
+    /// versioning, to the current layout, growing it in place. Fields added since
+    /// its version start at their defaults. Anyone may pay for the migration.
+    pub fn migrate_agent(ctx: Context<MigrateAgent>) -> Result<()> {
+        let agent_info = ctx.accounts.agent.to_account_info();
+        let agent = {
+            let data = agent_info.try_borrow_data()?;
+            require!(
+                data.len() > 8 && data[..8] == Agent::DISCRIMINATOR,
+                AgentError::UnknownAccountVersion
+            );
+            if data.len() == LEGACY_AGENT_SPACE {
+                AgentV1::from(AgentV0::deserialize(&mut &data[8..])?).upgrade()
+            } else {
+                let version = data[8];
+                require!(version != AGENT_VERSION, AgentError::AlreadyMigrated);
+                require!(
+                    (1..AGENT_VERSION).contains(&version),
+                    AgentError::UnknownAccountVersion
+                );
+                read_agent(version, &mut &data[9..])?
+            }
+        };
+
+        grow_account(
+            &agent_info,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            AGENT_SPACE,
+        )?;
+        let mut data = agent_info.try_borrow_mut_data()?;
+        agent.try_serialize(&mut &mut data[..])?;
+        Ok(())
+    }
+
+    /// Agent owner founds a guild and becomes its first admin, holding the whole
+    /// revenue share until `set_guild_split` divides it.
+    pub fn create_guild(
+        ctx: Context<CreateGuild>,
+        guild_id: u64,
+        metadata_uri: String,
+        metadata_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_GUILDS), AgentError::Paused);
+
+        require!(metadata_uri.len() <= MAX_URI_LEN, AgentError::UriTooLong);
+        require!(metadata_hash != [0u8; 32], AgentError::MissingContentHash);
+
+        let creator_agent = &mut ctx.accounts.creator_agent;
+        let guild = &mut ctx.accounts.guild;
+        guild.creator = ctx.accounts.creator.key();
+        guild.guild_id = guild_id;
+        guild.metadata_uri = metadata_uri;
+        guild.metadata_hash = metadata_hash;
+        guild.members = vec![GuildMember {
+            agent: creator_agent.key(),
+            role: GuildRole::Admin,
+            share_bps: GUILD_SHARE_TOTAL,
+        }];
+        guild.reputation = 0;
+        guild.bounties_completed = 0;
+        guild.total_earned = 0;
+        guild.active_claims = 0;
+        guild.bump = ctx.bumps.guild;
+        guild.created_at = Clock::get()?.unix_timestamp;
+
+        creator_agent.guild = guild.key();
+        Ok(())
+    }
+
+    /// Guild admin adds an agent, who must co-sign. New members join with the
+    /// Member role and no revenue share.
+    pub fn add_guild_member(ctx: Context<AddGuildMember>) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_GUILDS), AgentError::Paused);
+
+        let guild = &mut ctx.accounts.guild;
+        require!(
+            guild.is_admin(&ctx.accounts.admin_agent.key()),
+            AgentError::NotGuildAdmin
+        );
+        require!(guild.members.len() < MAX_GUILD_MEMBERS, AgentError::GuildFull);
+
+        let member_agent = &mut ctx.accounts.member_agent;
+        guild.members.push(GuildMember {
+            agent: member_agent.key(),
+            role: GuildRole::Member,
+            share_bps: 0,
+        });
+        member_agent.guild = guild.key();
+        Ok(())
+    }
+
+    /// Guild admin removes a member, or a member leaves. The founding admin cannot
+    /// be removed and receives the departing member's share. Refused while the
+    /// guild has claimed bounties in flight.
+    pub fn remove_guild_member(ctx: Context<RemoveGuildMember>) -> Result<()> {
+        let guild = &mut ctx.accounts.guild;
+        let member_key = ctx.accounts.member_agent.key();
+        let authority_key = ctx.accounts.authority_agent.key();
+        require!(
+            authority_key == member_key || guild.is_admin(&authority_key),
+            AgentError::NotGuildAdmin
+        );
+        require!(guild.active_claims == 0, AgentError::ActiveClaims);
+
+        let index = guild
+            .members
+            .iter()
+            .position(|member| member.agent == member_key)
+            .ok_or(AgentError::NotGuildMember)?;
+        require!(index != 0, AgentError::CannotRemoveFounder);
+        let removed = guild.members.remove(index);
+        guild.members[0].share_bps += removed.share_bps;
+
+        ctx.accounts.member_agent.guild = Pubkey::default();
+        Ok(())
+    }
+
+    /// Founder closes a guild it is the last member of, once no claimed bounties
+    /// are in flight, reclaiming the rent and freeing its agent to transfer or
+    /// deregister.
+    pub fn close_guild(ctx: Context<CloseGuild>) -> Result<()> {
+        let guild = &ctx.accounts.guild;
+        require!(guild.members.len() == 1, AgentError::GuildHasMembers);
+        require!(guild.active_claims == 0, AgentError::ActiveClaims);
+
+        ctx.accounts.founder_agent.guild = Pubkey::default();
+        Ok(())
+    }
+
+    /// Guild admin sets every member's revenue share in basis points, in member
+    /// order. Shares must sum to 10,000 and cannot change while claims are in flight.
+    pub fn set_guild_split(ctx: Context<UpdateGuildSettings>, shares: Vec<u16>) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_GUILDS), AgentError::Paused);
+
+        let guild = &mut ctx.accounts.guild;
+        require!(
+            guild.is_admin(&ctx.accounts.admin_agent.key()),
+            AgentError::NotGuildAdmin
+        );
+        require!(guild.active_claims == 0, AgentError::ActiveClaims);
+        require!(
+            shares.len() == guild.members.len()
+                && shares.iter().map(|share| *share as u32).sum::<u32>()
+                    == GUILD_SHARE_TOTAL as u32,
+            AgentError::InvalidGuildSplit
+        );
+
+        for (member, share) in guild.members.iter_mut().zip(shares) {
+            member.share_bps = share;
         }
         Ok(())
     }
 
+    /// Guild admin changes a member's role. The founding admin always stays Admin.
+    pub fn set_guild_role(
+        ctx: Context<UpdateGuildSettings>,
+        agent: Pubkey,
+        role: GuildRole,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_GUILDS), AgentError::Paused);
+
+        let guild = &mut ctx.accounts.guild;
+        require!(
+            guild.is_admin(&ctx.accounts.admin_agent.key()),
+            AgentError::NotGuildAdmin
+        );
+        let index = guild
+            .members
+            .iter()
+            .position(|member| member.agent == agent)
+            .ok_or(AgentError::NotGuildMember)?;
+        require!(index != 0, AgentError::CannotRemoveFounder);
+
+        guild.members[index].role = role;
+        Ok(())
+    }
+
+    /// Called via CPI from bounty-escrow when the agent claims a bounty.
+    pub fn record_claim(ctx: Context<UpdateReputation>) -> Result<()> {
+        let agent = &mut ctx.accounts.agent;
+        agent.active_claims = agent.active_claims.checked_add(1).unwrap();
+        Ok(())
+    }
+
+    /// Called via CPI from bounty-escrow when a claimed bounty is paid out.
+    pub fn release_claim(ctx: Context<UpdateReputation>) -> Result<()> {
+        let agent = &mut ctx.accounts.agent;
+        agent.active_claims = agent
+            .active_claims
+            .checked_sub(1)
+            .ok_or(AgentError::NoActiveClaims)?;
+        Ok(())
+    }
+
     /// Called via CPI from bounty-escrow to update agent reputation after a review.
     pub fn update_reputation(
         ctx: Context<UpdateReputation>,
@@ -89,9 +715,162 @@ pub mod agent_registry {
         agent.total_earned = agent.total_earned.checked_add(amount).unwrap();
         Ok(())
     }
+
+    /// Called via CPI from bounty-escrow when part of a platform fee goes to the
+    /// agent's referrer, counting toward the referral cap.
+    pub fn record_referral_reward(ctx: Context<UpdateReputation>, amount: u64) -> Result<()> {
+        let agent = &mut ctx.accounts.agent;
+        agent.referral_paid = agent.referral_paid.checked_add(amount).unwrap();
+        Ok(())
+    }
+
+    /// Called via CPI from bounty-escrow when a guild claims a bounty.
+    pub fn record_guild_claim(ctx: Context<UpdateGuild>) -> Result<()> {
+        let guild = &mut ctx.accounts.guild;
+        guild.active_claims = guild.active_claims.checked_add(1).unwrap();
+        Ok(())
+    }
+
+    /// Called via CPI from bounty-escrow when a guild bounty pays out, adding
+    /// the guild-level earnings.
+    pub fn settle_guild_claim(ctx: Context<UpdateGuild>, amount: u64) -> Result<()> {
+        let guild = &mut ctx.accounts.guild;
+        guild.active_claims = guild
+            .active_claims
+            .checked_sub(1)
+            .ok_or(AgentError::NoActiveClaims)?;
+        guild.total_earned = guild.total_earned.checked_add(amount).unwrap();
+        Ok(())
+    }
+
+    /// Called via CPI from bounty-escrow to update guild-level reputation.
+    pub fn update_guild_reputation(ctx: Context<UpdateGuild>, new_rating: u64) -> Result<()> {
+        require!(new_rating > 0 && new_rating <= 500, AgentError::InvalidRating);
+
+        let guild = &mut ctx.accounts.guild;
+        let old_count = guild.bounties_completed;
+        let new_count = old_count + 1;
+        if old_count == 0 {
+            guild.reputation = new_rating;
+        } else {
+            guild.reputation = (guild.reputation * old_count + new_rating) / new_count;
+        }
+
+        guild.bounties_completed = new_count;
+        Ok(())
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+/// Program-wide admin settings, seeded by `["config"]`.
+#[account]
+pub struct Config {
+    /// Authority allowed to change pause flags
+    pub admin: Pubkey,
+    /// Bitmask of PAUSE_* flags
+    pub paused: u64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Config {
+    pub fn is_paused(&self, flag: u64) -> bool {
+        self.paused & flag != 0
+    }
+}
+
+/// Admin-managed list of skills, seeded by `["skills"]`. A skill's id is its index
+/// in `names` and its bit in the `skills` bitmaps on agents and bounties.
+#[account]
+pub struct SkillRegistry {
+    /// Skill names, indexed by skill id
+    pub names: Vec<String>,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl SkillRegistry {
+    /// True if every bit set in `skills` is a defined skill id.
+    pub fn is_valid(&self, skills: u128) -> bool {
+        let count = self.names.len();
+        count >= MAX_SKILLS || skills >> count == 0
+    }
+}
+
+/// An approved attestation issuer, seeded by `["verifier", authority]`.
+#[account]
+pub struct Verifier {
+    /// Key that signs attestations (wallet or program PDA)
+    pub authority: Pubkey,
+    /// Cleared by the admin to withdraw approval
+    pub active: bool,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Unix timestamp of approval
+    pub created_at: i64,
+}
+
+/// A verifier's claim that an agent holds a skill, seeded by
+/// `["attestation", agent, skill_id, verifier]`. Revocation closes the account.
+#[account]
+pub struct Attestation {
+    /// Agent PDA the attestation is about
+    pub agent: Pubkey,
+    /// SkillRegistry id being attested
+    pub skill_id: u8,
+    /// Authority of the issuing Verifier
+    pub verifier: Pubkey,
+    /// Hash of the verifier's off-chain evidence (test results, certificates)
+    pub evidence_hash: [u8; 32],
+    /// Unix timestamp of issuance
+    pub issued_at: i64,
+    /// Unix timestamp after which the attestation lapses (0 = never)
+    pub expires_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Third-party check of a deliverable, seeded by
+/// `["validation", bounty, data_hash, validator]`.
+#[account]
+pub struct Validation {
+    /// Bounty-escrow bounty the deliverable was submitted for
+    pub bounty: Pubkey,
+    /// Key that asked for the validation (client or agent)
+    pub requester: Pubkey,
+    /// Key expected to respond
+    pub validator: Pubkey,
+    /// Hash of the deliverable under validation
+    pub data_hash: [u8; 32],
+    /// Score 0-100 posted by the validator
+    pub score: u8,
+    /// Hash of the validator's off-chain report
+    pub response_hash: [u8; 32],
+    /// Set once the validator has responded
+    pub responded: bool,
+    /// Unix timestamp of the request
+    pub requested_at: i64,
+    /// Unix timestamp of the latest response (0 until responded)
+    pub responded_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Validation {
+    /// True once the validator has responded with at least `min_score`.
+    pub fn passes(&self, min_score: u8) -> bool {
+        self.responded && self.score >= min_score
+    }
+}
+
+impl Attestation {
+    pub fn is_current(&self, now: i64) -> bool {
+        self.expires_at == 0 || now < self.expires_at
+    }
+}
+
+/// Whether an agent is taking work. Stored as its one-byte Borsh variant index,
+/// so the on-chain layout is the same as the `u8` it replaced.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AgentStatus {
     Available = 0,
     Busy = 1,
@@ -100,10 +879,14 @@ pub enum AgentStatus {
 
 #[account]
 pub struct Agent {
+    /// Layout version (AGENT_VERSION)
+    pub version: u8,
     /// Wallet that owns this agent
     pub owner: Pubkey,
     /// Off-chain metadata JSON URI (name, description, skills, endpoint, moltbook)
     pub metadata_uri: String,
+    /// SHA-256 of the metadata JSON the URI pointed to when it was set
+    pub metadata_hash: [u8; 32],
     /// USDC minor units per hour
     pub hourly_rate: u64,
     /// Reputation score (fixed-point * 100, e.g. 480 = 4.80)
@@ -112,57 +895,850 @@ pub struct Agent {
     pub bounties_completed: u64,
     /// Total USDC earned (minor units)
     pub total_earned: u64,
-    /// 0=Available, 1=Busy, 2=Offline
-    pub availability: u8,
+    /// Whether the agent is taking work
+    pub availability: AgentStatus,
     /// PDA bump seed
     pub bump: u8,
     /// Unix timestamp of registration
     pub created_at: i64,
+    /// Wallet proposed to take over this agent (default if none)
+    pub pending_owner: Pubkey,
+    /// Agent PDA this profile was transferred to (default while active). A transferred
+    /// profile stays behind as a tombstone that refuses every instruction, so bounties
+    /// assigned to its owner can follow it to the current profile.
+    pub successor: Pubkey,
+    /// Bounties claimed and not yet paid out
+    pub active_claims: u64,
+    /// Number of AgentDelegate accounts currently authorized
+    pub delegate_count: u64,
+    /// Bitmap of SkillRegistry ids the agent offers
+    pub skills: u128,
+    /// Guild the agent belongs to (default if none)
+    pub guild: Pubkey,
+    /// Wallet that referred the agent (default if none); rewarded from platform fees
+    pub referrer: Pubkey,
+    /// Total referral rewards paid to `referrer` on this agent's bounties
+    pub referral_paid: u64,
 }
 
-#[derive(Accounts)]
-pub struct RegisterAgent<'info> {
-    #[account(
-        init,
-        payer = owner,
-        space = AGENT_SPACE,
-        seeds = [b"agent", owner.key().as_ref()],
-        bump,
-    )]
-    pub agent: Account<'info, Agent>,
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    pub system_program: Program<'info, System>,
+impl Agent {
+    /// False once the profile has been transferred to a new owner.
+    pub fn is_active(&self) -> bool {
+        self.successor == Pubkey::default()
+    }
 }
 
-#[derive(Accounts)]
-pub struct UpdateAgent<'info> {
-    #[account(
-        mut,
-        seeds = [b"agent", owner.key().as_ref()],
-        bump = agent.bump,
-        has_one = owner,
-    )]
-    pub agent: Account<'info, Agent>,
-    pub owner: Signer<'info>,
+/// A member's permissions within a guild. Stored as its one-byte Borsh variant
+/// index, so the on-chain layout is the same as the `u8` it replaced.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GuildRole {
+    /// Manages membership, shares and roles, and claims bounties for the guild
+    Admin = 0,
+    /// Submits work on guild bounties and shares in payouts
+    Member = 1,
 }
 
-/// Used by CPI from bounty-escrow; authority is the bounty-escrow program signer.
-#[derive(Accounts)]
-pub struct UpdateReputation<'info> {
-    #[account(mut)]
-    pub agent: Account<'info, Agent>,
-    pub authority: Signer<'info>,
+/// One agent's seat in a guild.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct GuildMember {
+    /// Agent PDA of the member
+    pub agent: Pubkey,
+    /// What the member may do in the guild
+    pub role: GuildRole,
+    /// Share of guild payouts in basis points
+    pub share_bps: u16,
+}
+
+/// Team of agents that claims bounties as a unit, seeded by
+/// `["guild", creator, guild_id]`. `members[0]` is the founding admin.
+#[account]
+pub struct Guild {
+    /// Wallet that founded the guild
+    pub creator: Pubkey,
+    /// Creator-chosen guild ID
+    pub guild_id: u64,
+    /// Off-chain metadata JSON URI (name, description, portfolio)
+    pub metadata_uri: String,
+    /// SHA-256 of the metadata JSON
+    pub metadata_hash: [u8; 32],
+    /// Members with their roles and revenue shares (shares sum to 10,000 bps)
+    pub members: Vec<GuildMember>,
+    /// Guild-level reputation (fixed-point * 100)
+    pub reputation: u64,
+    /// Number of guild bounties reviewed
+    pub bounties_completed: u64,
+    /// Total USDC paid out to the guild (minor units)
+    pub total_earned: u64,
+    /// Guild bounties claimed and not yet paid out
+    pub active_claims: u64,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Unix timestamp of creation
+    pub created_at: i64,
+}
+
+impl Guild {
+    pub fn member(&self, agent: &Pubkey) -> Option<&GuildMember> {
+        self.members.iter().find(|member| member.agent == *agent)
+    }
+
+    pub fn is_admin(&self, agent: &Pubkey) -> bool {
+        self.member(agent)
+            .is_some_and(|member| member.role == GuildRole::Admin)
+    }
+}
+
+/// Agent layout before versioning; only read by `migrate_agent`.
+#[derive(AnchorDeserialize)]
+struct AgentV0 {
+    owner: Pubkey,
+    metadata_uri: String,
+    hourly_rate: u64,
+    reputation: u64,
+    bounties_completed: u64,
+    total_earned: u64,
+    availability: AgentStatus,
+    bump: u8,
+    created_at: i64,
+}
+
+/// Fields every versioned Agent layout starts with (version 1); only read by
+/// `migrate_agent`.
+#[derive(AnchorDeserialize)]
+struct AgentV1 {
+    owner: Pubkey,
+    metadata_uri: String,
+    metadata_hash: [u8; 32],
+    hourly_rate: u64,
+    reputation: u64,
+    bounties_completed: u64,
+    total_earned: u64,
+    availability: AgentStatus,
+    bump: u8,
+    created_at: i64,
+    pending_owner: Pubkey,
+    successor: Pubkey,
+    active_claims: u64,
+    delegate_count: u64,
+}
+
+impl From<AgentV0> for AgentV1 {
+    fn from(legacy: AgentV0) -> Self {
+        Self {
+            owner: legacy.owner,
+            metadata_uri: legacy.metadata_uri,
+            metadata_hash: [0u8; 32],
+            hourly_rate: legacy.hourly_rate,
+            reputation: legacy.reputation,
+            bounties_completed: legacy.bounties_completed,
+            total_earned: legacy.total_earned,
+            availability: legacy.availability,
+            bump: legacy.bump,
+            created_at: legacy.created_at,
+            pending_owner: Pubkey::default(),
+            successor: Pubkey::default(),
+            active_claims: 0,
+            delegate_count: 0,
+        }
+    }
+}
+
+impl AgentV1 {
+    /// The current layout holding these fields, with every later one at its default.
+    fn upgrade(self) -> Agent {
+        Agent {
+            version: AGENT_VERSION,
+            owner: self.owner,
+            metadata_uri: self.metadata_uri,
+            metadata_hash: self.metadata_hash,
+            hourly_rate: self.hourly_rate,
+            reputation: self.reputation,
+            bounties_completed: self.bounties_completed,
+            total_earned: self.total_earned,
+            availability: self.availability,
+            bump: self.bump,
+            created_at: self.created_at,
+            pending_owner: self.pending_owner,
+            successor: self.successor,
+            active_claims: self.active_claims,
+            delegate_count: self.delegate_count,
+            skills: 0,
+            guild: Pubkey::default(),
+            referrer: Pubkey::default(),
+            referral_paid: 0,
+        }
+    }
+}
+
+/// Reads an agent written at layout `version` (after its version byte), reading the
+/// fields each later version appended in order.
+fn read_agent(version: u8, data: &mut &[u8]) -> Result<Agent> {
+    let mut agent = AgentV1::deserialize(data)?.upgrade();
+    if version >= 2 {
+        agent.skills = u128::deserialize(data)?;
+    }
+    if version >= 3 {
+        agent.guild = Pubkey::deserialize(data)?;
+    }
+    if version >= 4 {
+        agent.referrer = Pubkey::deserialize(data)?;
+        agent.referral_paid = u64::deserialize(data)?;
+    }
+    Ok(agent)
+}
+
+/// Reallocs `account` to `new_len`, topping up rent from `payer` first.
+fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let rent_due = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if rent_due > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    account.realloc(new_len, true)?;
+    Ok(())
+}
+
+/// A hot key the owner has authorized to act for an agent. Earnings still go to the owner.
+#[account]
+pub struct AgentDelegate {
+    /// Agent profile this delegate acts for
+    pub agent: Pubkey,
+    /// Delegate wallet
+    pub delegate: Pubkey,
+    /// Bitmask of DELEGATE_* permissions
+    pub permissions: u8,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Unix timestamp the delegate was added
+    pub created_at: i64,
+}
+
+impl AgentDelegate {
+    pub fn has_permission(&self, permission: u8) -> bool {
+        self.permissions & permission == permission
+    }
+}
+
+/// Left behind by `deregister_agent` so reviews pointing at the agent stay resolvable.
+#[account]
+pub struct AgentArchive {
+    /// Closed agent PDA
+    pub agent: Pubkey,
+    /// Wallet that owned the agent
+    pub owner: Pubkey,
+    /// Final reputation (fixed-point * 100)
+    pub reputation: u64,
+    /// Final number of bounties completed
+    pub bounties_completed: u64,
+    /// Final total USDC earned (minor units)
+    pub total_earned: u64,
+    /// Unix timestamp of original registration
+    pub created_at: i64,
+    /// Unix timestamp of deregistration
+    pub deregistered_at: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct InitConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = CONFIG_SPACE,
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::AgentRegistry>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ AgentError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitSkillRegistry<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = admin,
+        space = SKILL_REGISTRY_SPACE,
+        seeds = [b"skills"],
+        bump,
+    )]
+    pub skill_registry: Account<'info, SkillRegistry>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddSkill<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [b"skills"], bump = skill_registry.bump)]
+    pub skill_registry: Account<'info, SkillRegistry>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddVerifier<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = admin,
+        space = VERIFIER_SPACE,
+        seeds = [b"verifier", authority.key().as_ref()],
+        bump,
+    )]
+    pub verifier: Account<'info, Verifier>,
+    /// CHECK: any key may be approved as a verifier authority
+    pub authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetVerifierActive<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ AgentError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"verifier", verifier.authority.as_ref()],
+        bump = verifier.bump,
+    )]
+    pub verifier: Account<'info, Verifier>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(skill_id: u8)]
+pub struct IssueAttestation<'info> {
+    #[account(
+        seeds = [b"verifier", authority.key().as_ref()],
+        bump = verifier.bump,
+        has_one = authority,
+        constraint = verifier.active @ AgentError::VerifierInactive,
+    )]
+    pub verifier: Account<'info, Verifier>,
+    #[account(seeds = [b"skills"], bump = skill_registry.bump)]
+    pub skill_registry: Account<'info, SkillRegistry>,
+    #[account(constraint = agent.is_active() @ AgentError::AgentTransferred)]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        init,
+        payer = payer,
+        space = ATTESTATION_SPACE,
+        seeds = [b"attestation", agent.key().as_ref(), &[skill_id], authority.key().as_ref()],
+        bump,
+    )]
+    pub attestation: Account<'info, Attestation>,
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAttestation<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"attestation", attestation.agent.as_ref(), &[attestation.skill_id], authority.key().as_ref()],
+        bump = attestation.bump,
+        constraint = attestation.verifier == authority.key() @ AgentError::Unauthorized,
+    )]
+    pub attestation: Account<'info, Attestation>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(bounty: Pubkey, data_hash: [u8; 32])]
+pub struct RequestValidation<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = requester,
+        space = VALIDATION_SPACE,
+        seeds = [b"validation", bounty.as_ref(), data_hash.as_ref(), validator.key().as_ref()],
+        bump,
+    )]
+    pub validation: Account<'info, Validation>,
+    /// CHECK: any key may be named as validator; trust comes from whoever relies on it
+    pub validator: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub requester: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RespondValidation<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            b"validation",
+            validation.bounty.as_ref(),
+            validation.data_hash.as_ref(),
+            validator.key().as_ref(),
+        ],
+        bump = validation.bump,
+        has_one = validator @ AgentError::Unauthorized,
+    )]
+    pub validation: Account<'info, Validation>,
+    pub validator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterAgent<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(seeds = [b"skills"], bump = skill_registry.bump)]
+    pub skill_registry: Account<'info, SkillRegistry>,
+    /// Cannot be initialized while the wallet has a profile, including the tombstone
+    /// left behind when it transferred one away
+    #[account(
+        init,
+        payer = owner,
+        space = AGENT_SPACE,
+        seeds = [b"agent", owner.key().as_ref()],
+        bump,
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAgent<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(seeds = [b"skills"], bump = skill_registry.bump)]
+    pub skill_registry: Account<'info, SkillRegistry>,
+    #[account(
+        mut,
+        seeds = [b"agent", owner.key().as_ref()],
+        bump = agent.bump,
+        has_one = owner,
+        constraint = agent.is_active() @ AgentError::AgentTransferred,
+    )]
+    pub agent: Account<'info, Agent>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddDelegate<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"agent", owner.key().as_ref()],
+        bump = agent.bump,
+        has_one = owner,
+        constraint = agent.is_active() @ AgentError::AgentTransferred,
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        init,
+        payer = owner,
+        space = AGENT_DELEGATE_SPACE,
+        seeds = [b"delegate", agent.key().as_ref(), delegate.key().as_ref()],
+        bump,
+    )]
+    pub agent_delegate: Account<'info, AgentDelegate>,
+    /// CHECK: any wallet may be authorized as a delegate
+    pub delegate: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDelegate<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"agent", owner.key().as_ref()],
+        bump = agent.bump,
+        has_one = owner,
+        constraint = agent.is_active() @ AgentError::AgentTransferred,
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        seeds = [b"delegate", agent.key().as_ref(), agent_delegate.delegate.as_ref()],
+        bump = agent_delegate.bump,
+        has_one = agent,
+    )]
+    pub agent_delegate: Account<'info, AgentDelegate>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveDelegate<'info> {
+    #[account(
+        mut,
+        seeds = [b"agent", owner.key().as_ref()],
+        bump = agent.bump,
+        has_one = owner,
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"delegate", agent.key().as_ref(), agent_delegate.delegate.as_ref()],
+        bump = agent_delegate.bump,
+        has_one = agent,
+    )]
+    pub agent_delegate: Account<'info, AgentDelegate>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetAvailability<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        constraint = agent.is_active() @ AgentError::AgentTransferred,
+    )]
+    pub agent: Account<'info, Agent>,
+    /// Required when `authority` is a delegate rather than the owner
+    pub agent_delegate: Option<Account<'info, AgentDelegate>>,
+    /// Agent owner or an authorized delegate
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeOwnerTransfer<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"agent", owner.key().as_ref()],
+        bump = agent.bump,
+        has_one = owner,
+        constraint = agent.is_active() @ AgentError::AgentTransferred,
+    )]
+    pub agent: Account<'info, Agent>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptOwnerTransfer<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"agent", old_agent.owner.as_ref()],
+        bump = old_agent.bump,
+        constraint = old_agent.is_active() @ AgentError::AgentTransferred,
+        constraint = old_agent.pending_owner == new_owner.key() @ AgentError::NoPendingTransfer,
+    )]
+    pub old_agent: Account<'info, Agent>,
+    #[account(
+        init,
+        payer = new_owner,
+        space = AGENT_SPACE,
+        seeds = [b"agent", new_owner.key().as_ref()],
+        bump,
+    )]
+    pub new_agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeregisterAgent<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"agent", owner.key().as_ref()],
+        bump = agent.bump,
+        has_one = owner,
+        constraint = agent.is_active() @ AgentError::AgentTransferred,
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        init,
+        payer = owner,
+        space = AGENT_ARCHIVE_SPACE,
+        seeds = [b"archive", agent.key().as_ref()],
+        bump,
+    )]
+    pub archive: Option<Account<'info, AgentArchive>>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAgent<'info> {
+    /// CHECK: older layouts cannot be deserialized as `Agent`; ownership is checked
+    /// here and the discriminator and version in the handler
+    #[account(mut, owner = crate::ID)]
+    pub agent: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(guild_id: u64)]
+pub struct CreateGuild<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = creator,
+        space = GUILD_SPACE,
+        seeds = [b"guild", creator.key().as_ref(), &guild_id.to_le_bytes()],
+        bump,
+    )]
+    pub guild: Account<'info, Guild>,
+    #[account(
+        mut,
+        seeds = [b"agent", creator.key().as_ref()],
+        bump = creator_agent.bump,
+        constraint = creator_agent.is_active() @ AgentError::AgentTransferred,
+        constraint = creator_agent.guild == Pubkey::default() @ AgentError::InGuild,
+    )]
+    pub creator_agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddGuildMember<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub guild: Account<'info, Guild>,
+    #[account(
+        seeds = [b"agent", admin.key().as_ref()],
+        bump = admin_agent.bump,
+    )]
+    pub admin_agent: Account<'info, Agent>,
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"agent", member.key().as_ref()],
+        bump = member_agent.bump,
+        constraint = member_agent.is_active() @ AgentError::AgentTransferred,
+        constraint = member_agent.guild == Pubkey::default() @ AgentError::InGuild,
+    )]
+    pub member_agent: Account<'info, Agent>,
+    /// Owner of the joining agent, consenting to membership
+    pub member: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveGuildMember<'info> {
+    #[account(mut)]
+    pub guild: Account<'info, Guild>,
+    /// Agent of the signer: a guild admin, or the leaving member itself
+    #[account(
+        seeds = [b"agent", authority.key().as_ref()],
+        bump = authority_agent.bump,
+    )]
+    pub authority_agent: Account<'info, Agent>,
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = member_agent.guild == guild.key() @ AgentError::NotGuildMember,
+    )]
+    pub member_agent: Account<'info, Agent>,
+}
+
+#[derive(Accounts)]
+pub struct CloseGuild<'info> {
+    #[account(
+        mut,
+        close = founder,
+        constraint = guild.creator == founder.key() @ AgentError::NotGuildAdmin,
+    )]
+    pub guild: Account<'info, Guild>,
+    #[account(
+        mut,
+        seeds = [b"agent", founder.key().as_ref()],
+        bump = founder_agent.bump,
+        constraint = founder_agent.guild == guild.key() @ AgentError::NotGuildMember,
+    )]
+    pub founder_agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub founder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateGuildSettings<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub guild: Account<'info, Guild>,
+    #[account(
+        seeds = [b"agent", admin.key().as_ref()],
+        bump = admin_agent.bump,
+    )]
+    pub admin_agent: Account<'info, Agent>,
+    pub admin: Signer<'info>,
+}
+
+/// Used by CPI from bounty-escrow for guild bounties.
+#[derive(Accounts)]
+pub struct UpdateGuild<'info> {
+    #[account(mut)]
+    pub guild: Account<'info, Guild>,
+    #[account(
+        constraint = authority.key() == escrow_authority() @ AgentError::UnauthorizedCaller,
+    )]
+    pub authority: Signer<'info>,
+}
+
+/// Used by CPI from bounty-escrow; authority is the bounty-escrow program signer.
+#[derive(Accounts)]
+pub struct UpdateReputation<'info> {
+    #[account(
+        mut,
+        constraint = agent.is_active() @ AgentError::AgentTransferred,
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        constraint = authority.key() == escrow_authority() @ AgentError::UnauthorizedCaller,
+    )]
+    pub authority: Signer<'info>,
+}
+
+/// PDA that bounty-escrow signs with when calling the registry via CPI.
+pub fn escrow_authority() -> Pubkey {
+    Pubkey::find_program_address(&[ESCROW_AUTHORITY_SEED], &BOUNTY_ESCROW_PROGRAM_ID).0
 }
 
 #[error_code]
 pub enum AgentError {
-    #[msg("Metadata URI exceeds maximum length of 200 characters")]
+    #[msg("Metadata URI exceeds maximum length of 512 characters")]
     UriTooLong,
     #[msg("Hourly rate must be greater than 0")]
     InvalidHourlyRate,
+    /// No longer raised: availability is typed as `AgentStatus`, so unknown values
+    /// fail to deserialize. Kept so the error codes after it stay stable.
     #[msg("Availability must be 0 (Available), 1 (Busy), or 2 (Offline)")]
     InvalidAvailability,
     #[msg("Rating must be between 1 and 500 (fixed-point * 100)")]
     InvalidRating,
+    #[msg("Delegate permissions must be a non-empty combination of claim, submit and availability")]
+    InvalidDelegatePermissions,
+    #[msg("Signer is not an authorized delegate for this agent")]
+    DelegateNotAuthorized,
+    #[msg("New owner must be a different, non-default wallet")]
+    InvalidNewOwner,
+    #[msg("No ownership transfer is pending for this wallet")]
+    NoPendingTransfer,
+    #[msg("Agent has been transferred to a new owner")]
+    AgentTransferred,
+    #[msg("Agent still has claimed bounties in flight")]
+    ActiveClaims,
+    #[msg("Remove all delegates before deregistering")]
+    DelegatesRemain,
+    #[msg("Caller is not the bounty-escrow program")]
+    UnauthorizedCaller,
+    #[msg("A non-zero content hash must accompany every URI")]
+    MissingContentHash,
+    #[msg("Account is already on the current layout")]
+    AlreadyMigrated,
+    #[msg("Account is not an agent account of a known layout")]
+    UnknownAccountVersion,
+    #[msg("This instruction is paused by the admin")]
+    Paused,
+    #[msg("Unauthorized")]
+    Unauthorized,
+    #[msg("Skill name must be 1-32 characters and not already defined")]
+    InvalidSkillName,
+    #[msg("Skill registry already holds the maximum number of skills")]
+    SkillRegistryFull,
+    #[msg("Skills bitmap references an undefined skill id")]
+    UnknownSkill,
+    #[msg("Expiry must be 0 or in the future")]
+    InvalidExpiry,
+    #[msg("Verifier has been disabled by the admin")]
+    VerifierInactive,
+    #[msg("Validation score must be between 0 and 100")]
+    InvalidValidationScore,
+    #[msg("Agent belongs to a guild; leave it first")]
+    InGuild,
+    #[msg("Signer is not an admin of this guild")]
+    NotGuildAdmin,
+    #[msg("Agent is not a member of this guild")]
+    NotGuildMember,
+    #[msg("Guild already has the maximum number of members")]
+    GuildFull,
+    #[msg("The founding admin cannot be removed or demoted")]
+    CannotRemoveFounder,
+    #[msg("Shares must cover every member and sum to 10,000 bps")]
+    InvalidGuildSplit,
+    /// No longer raised: roles are decoded as `GuildRole`, so unknown ones fail to
+    /// deserialize
+    #[msg("Invalid guild role")]
+    InvalidGuildRole,
+    #[msg("An agent cannot refer itself")]
+    InvalidReferrer,
+    #[msg("No claimed bounty is in flight to release")]
+    NoActiveClaims,
+    #[msg("Remove every other member before closing the guild")]
+    GuildHasMembers,
 }
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::{Bounty, BountyError, BountyStatus};
+
+/// Bounties listed on one BountyIndexPage
+pub const INDEX_PAGE_CAPACITY: usize = 32;
+
+/// Most skills a bounty can require, which bounds the index pages it is listed on
+pub const MAX_BOUNTY_SKILLS: usize = 4;
+
+/// Index bucket of bounties that require no skills
+pub const UNSKILLED: u8 = u8::MAX;
+
+/// One page of the discovery index, seeded by `["index", status, skill, page]`.
+/// Only `Open` bounties are indexed today; the status is part of the seeds so
+/// other statuses can get buckets of their own without moving existing pages.
+#[account]
+pub struct BountyIndexPage {
+    /// Status of every bounty on this page
+    pub status: BountyStatus,
+    /// Skill id of the bucket, or UNSKILLED
+    pub skill: u8,
+    /// Page number within the bucket, starting at 0
+    pub page: u32,
+    /// PDA bump
+    pub bump: u8,
+    /// Listed bounties, in no particular order
+    pub bounties: Vec<Pubkey>,
+}
+
+/// Buckets a bounty with `skills` is listed in: one per skill bit in ascending
+/// order, or just UNSKILLED when it requires none.
+pub fn index_buckets(skills: u128) -> Vec<u8> {
+    if skills == 0 {
+        return vec![UNSKILLED];
+    }
+    (0..128u8).filter(|bit| skills & (1 << bit) != 0).collect()
+}
+
+impl Bounty {
+    /// Lists the bounty on `pages`, one per bucket in `index_buckets` order, and
+    /// remembers the page numbers so it can be removed again.
+    pub(crate) fn add_to_index<'info>(
+        &mut self,
+        key: Pubkey,
+        pages: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let buckets = index_buckets(self.skills);
+        require!(pages.len() == buckets.len(), BountyError::IndexPagesMismatch);
+        self.index_pages = Vec::with_capacity(buckets.len());
+        for (skill, info) in buckets.into_iter().zip(pages) {
+            let mut page = load_page(info, self.status, skill)?;
+            require!(
+                page.bounties.len() < INDEX_PAGE_CAPACITY,
+                BountyError::IndexPageFull
+            );
+            page.bounties.push(key);
+            self.index_pages.push(page.page);
+            page.exit(&crate::ID)?;
+        }
+        Ok(())
+    }
+
+    /// Takes the bounty off the pages it was listed on. Bounties created before
+    /// the index (empty `index_pages`) expect no pages.
+    pub(crate) fn remove_from_index<'info>(
+        &mut self,
+        key: Pubkey,
+        pages: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(
+            pages.len() == self.index_pages.len(),
+            BountyError::IndexPagesMismatch
+        );
+        for ((skill, number), info) in index_buckets(self.skills)
+            .into_iter()
+            .zip(&self.index_pages)
+            .zip(pages)
+        {
+            let mut page = load_page(info, self.status, skill)?;
+            let position = page
+                .bounties
+                .iter()
+                .position(|listed| *listed == key)
+                .filter(|_| page.page == *number)
+                .ok_or(BountyError::IndexPagesMismatch)?;
+            page.bounties.swap_remove(position);
+            page.exit(&crate::ID)?;
+        }
+        self.index_pages.clear();
+        Ok(())
+    }
+}
+
+/// Pages can only be created by `init_index_page` at their seeds, so a page owned
+/// by this program with the expected status and skill is the right bucket.
+/// It must be writable since the listing changes.
+fn load_page<'info>(
+    info: &'info AccountInfo<'info>,
+    status: BountyStatus,
+    skill: u8,
+) -> Result<Account<'info, BountyIndexPage>> {
+    let page = Account::<BountyIndexPage>::try_from(info)?;
+    require!(
+        info.is_writable && page.status == status && page.skill == skill,
+        BountyError::IndexPagesMismatch
+    );
+    Ok(page)
+}
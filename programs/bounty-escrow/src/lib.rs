@@ -1,67 +1,302 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use agent_registry::program::AgentRegistry;
+use agent_registry::{
+    Agent, AgentDelegate, Attestation, Guild, SkillRegistry, Validation, Verifier, DELEGATE_CLAIM,
+    DELEGATE_SUBMIT, ESCROW_AUTHORITY_SEED, GUILD_SHARE_TOTAL, MAX_VALIDATION_SCORE,
+};
 
-declare_id!("BntyEscrw111111111111111111111111111111111");
+mod index;
+mod state_machine;
+pub use index::*;
+pub use state_machine::*;
 
-const MAX_URI_LEN: usize = 200;
+declare_id!("2KY4RJwdYKnnDMU4WcuwgU2f8B7JoxjdKaTYL953AKb5");
 
-/// ClientState space: discriminator(8) + owner(32) + bounty_count(8) + bump(1)
-const CLIENT_STATE_SPACE: usize = 8 + 32 + 8 + 1;
+/// Maximum length for any URI (fits IPFS/Arweave gateway URLs)
+const MAX_URI_LEN: usize = 512;
 
-/// Bounty space: discriminator(8) + client(32) + bounty_id(8) + metadata_uri(4+200) +
-/// budget(8) + deadline(8) + status(1) + claims(8) + assigned_agent(32) +
-/// deliverable_uri(4+200) + vault(32) + usdc_mint(32) + bump(1) + created_at(8)
-const BOUNTY_SPACE: usize = 8 + 32 + 8 + (4 + MAX_URI_LEN) + 8 + 8 + 1 + 8 + 32 + (4 + MAX_URI_LEN) + 32 + 32 + 1 + 8;
+/// Maximum number of agents an invite-only bounty can be offered to
+pub const MAX_ALLOWLIST: usize = 5;
+
+/// Status changes kept in a BountyHistory before the oldest is dropped
+pub const MAX_HISTORY: usize = 8;
+
+/// Current Bounty layout version, stored in the first byte after the discriminator.
+/// Each version appends fields to the previous one: 1 versioned the account (512-byte
+/// URIs and hashes), 2 added skills, 3 the attestation requirement, 4 the validation
+/// requirement, 5 the allowlist, 6 `recurring`, 7 `tips_total`, 8 the subcontract
+/// links, 9 `guild`, 10 the status timestamps and `last_actor` (and the history
+/// account), 11 `index_pages`, and 12 `dispute_payout`.
+pub const BOUNTY_VERSION: u8 = 12;
+
+/// First Bounty layout version created together with its BountyHistory
+const HISTORY_VERSION: u8 = 10;
+
+/// Config space: discriminator(8) + admin(32) + paused(8) + bump(1) + fee_bps(2) + treasury(32) +
+/// referral_share_bps(2) + referral_cap(8) + referral_duration(8)
+const CONFIG_SPACE: usize = 8 + 32 + 8 + 1 + 2 + 32 + 2 + 8 + 8;
+
+/// Basis-point denominator for fees and referral shares
+const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Highest platform fee the admin can set (10%)
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+/// Highest share of the fee each referrer can receive, so both together never exceed it
+pub const MAX_REFERRAL_SHARE_BPS: u16 = 5_000;
+
+/// Pause flags: a set bit in `Config.paused` halts the matching instruction.
+/// Refund paths (`cancel_bounty`) and migrations are never paused.
+pub const PAUSE_INIT_CLIENT: u64 = 1 << 0;
+pub const PAUSE_CREATE_BOUNTY: u64 = 1 << 1;
+pub const PAUSE_CLAIM_BOUNTY: u64 = 1 << 2;
+pub const PAUSE_SUBMIT_WORK: u64 = 1 << 3;
+pub const PAUSE_APPROVE_WORK: u64 = 1 << 4;
+pub const PAUSE_DISPUTE_BOUNTY: u64 = 1 << 5;
+pub const PAUSE_LEAVE_REVIEW: u64 = 1 << 6;
+/// Covers `create_hourly_contract`, `submit_time_log` and `approve_time_log`
+pub const PAUSE_HOURLY: u64 = 1 << 7;
+/// Covers `create_stream` and `withdraw_vested`
+pub const PAUSE_STREAM: u64 = 1 << 8;
+pub const PAUSE_TIP_AGENT: u64 = 1 << 9;
+
+/// ClientState space: discriminator(8) + owner(32) + bounty_count(8) + bump(1) + referrer(32) +
+/// referral_paid(8) + created_at(8)
+const CLIENT_STATE_SPACE: usize = 8 + 32 + 8 + 1 + 32 + 8 + 8;
+
+/// Space of the ClientState layout without referrals that `migrate_client_state` upgrades from
+const LEGACY_CLIENT_STATE_SPACE: usize = 8 + 32 + 8 + 1;
+
+/// Bounty space: discriminator(8) + version(1) + client(32) + bounty_id(8) + metadata_uri(4+512) +
+/// metadata_hash(32) + budget(8) + deadline(8) + status(1) + claims(8) + assigned_agent(32) +
+/// deliverable_uri(4+512) + deliverable_hash(32) + vault(32) + usdc_mint(32) + bump(1) + created_at(8) +
+/// skills(16) + require_skill_match(1) + attestation_requirement(1+32+1) + validation_requirement(1+32+1+1) +
+/// allowlist(4+5*32) + decline_policy(1) + recurring(32) + tips_total(8) + parent(32) +
+/// child_count(8) + reserved_for_children(8) + awaiting_parent_funds(1) + guild(32) +
+/// claimed_at(8) + delivered_at(8) + completed_at(8) + disputed_at(8) + cancelled_at(8) + last_actor(32) +
+/// index_pages(4+4*4) + dispute_payout(8)
+const BOUNTY_SPACE: usize = 8 + 1 + 32 + 8 + (4 + MAX_URI_LEN) + 32 + 8 + 8 + 1 + 8 + 32 + (4 + MAX_URI_LEN) + 32 + 32 + 32 + 1 + 8 + 16 + 1 + (1 + 32 + 1) + (1 + 32 + 1 + 1) + (4 + MAX_ALLOWLIST * 32) + 1 + 32 + 8 + 32 + 8 + 8 + 1 + 32 + 8 + 8 + 8 + 8 + 8 + 32 + (4 + MAX_BOUNTY_SKILLS * 4) + 8;
+
+/// BountyHistory space: discriminator(8) + bounty(32) + bump(1) + total(4) +
+/// entries(4 + MAX_HISTORY * (status(1) + actor(32) + at(8)))
+const BOUNTY_HISTORY_SPACE: usize = 8 + 32 + 1 + 4 + (4 + MAX_HISTORY * (1 + 32 + 8));
+
+/// BountyIndexPage space: discriminator(8) + status(1) + skill(1) + page(4) + bump(1) +
+/// bounties(4 + INDEX_PAGE_CAPACITY * 32)
+const INDEX_PAGE_SPACE: usize = 8 + 1 + 1 + 4 + 1 + (4 + INDEX_PAGE_CAPACITY * 32);
+
+/// Space of the unversioned layout (200-byte URIs) that `migrate_bounty` upgrades from
+const LEGACY_BOUNTY_SPACE: usize = 8 + 32 + 8 + (4 + 200) + 8 + 8 + 1 + 8 + 32 + (4 + 200) + 32 + 32 + 1 + 8;
 
 /// Review space: discriminator(8) + bounty(32) + reviewer(32) + agent(32) +
-/// rating(8) + comment_uri(4+200) + bump(1) + created_at(8)
-const REVIEW_SPACE: usize = 8 + 32 + 32 + 32 + 8 + (4 + MAX_URI_LEN) + 1 + 8;
+/// rating(8) + comment_uri(4+512) + comment_hash(32) + bump(1) + created_at(8)
+const REVIEW_SPACE: usize = 8 + 32 + 32 + 32 + 8 + (4 + MAX_URI_LEN) + 32 + 1 + 8;
+
+/// HourlyContract space: discriminator(8) + client(32) + agent(32) + agent_profile(32) +
+/// contract_id(8) + hourly_rate(8) + cap(8) + released(8) + status(1) + log_count(8) +
+/// pending_logs(8) + vault(32) + usdc_mint(32) + bump(1) + created_at(8)
+const HOURLY_CONTRACT_SPACE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 8 + 8 + 32 + 32 + 1 + 8;
+
+/// TimeLog space: discriminator(8) + contract(32) + index(8) + period_start(8) + period_end(8) +
+/// hours_x100(8) + amount(8) + evidence_uri(4+512) + evidence_hash(32) + status(1) +
+/// submitted_at(8) + bump(1)
+const TIME_LOG_SPACE: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + (4 + MAX_URI_LEN) + 32 + 1 + 8 + 1;
+
+/// Stream space: discriminator(8) + client(32) + agent(32) + agent_profile(32) + stream_id(8) +
+/// total(8) + withdrawn(8) + start_ts(8) + end_ts(8) + status(1) + vault(32) + usdc_mint(32) +
+/// bump(1) + created_at(8)
+const STREAM_SPACE: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + 32 + 32 + 1 + 8;
+
+/// RecurringBounty space: discriminator(8) + client(32) + template_id(8) + metadata_uri(4+512) +
+/// metadata_hash(32) + budget_per_period(8) + period_secs(8) + total_periods(8) + opened_periods(8) +
+/// next_open_at(8) + skills(16) + require_skill_match(1) + allowlist(4+5*32) + decline_policy(1) +
+/// status(1) + last_cycle(32) + vault(32) + usdc_mint(32) + bump(1) + created_at(8)
+const RECURRING_SPACE: usize = 8 + 32 + 8 + (4 + MAX_URI_LEN) + 32 + 8 + 8 + 8 + 8 + 8 + 16 + 1 + (4 + MAX_ALLOWLIST * 32) + 1 + 1 + 32 + 32 + 32 + 1 + 8;
 
 #[program]
 pub mod bounty_escrow {
     use super::*;
 
-    /// Initialize a client state PDA to track bounty count. Called once per client wallet.
-    pub fn init_client(ctx: Context<InitClient>) -> Result<()> {
+    /// Creates the config PDA. Only the program's upgrade authority may do this,
+    /// and it becomes the initial admin.
+    pub fn init_config(ctx: Context<InitConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.paused = 0;
+        config.bump = ctx.bumps.config;
+        config.fee_bps = 0;
+        config.treasury = Pubkey::default();
+        config.referral_share_bps = 0;
+        config.referral_cap = 0;
+        config.referral_duration = 0;
+        Ok(())
+    }
+
+    /// Admin replaces the pause bitmask (see PAUSE_* flags).
+    pub fn set_paused(ctx: Context<UpdateConfig>, paused: u64) -> Result<()> {
+        ctx.accounts.config.paused = paused;
+        Ok(())
+    }
+
+    /// Admin hands the config over to a new admin.
+    pub fn set_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.config.admin = new_admin;
+        Ok(())
+    }
+
+    /// Admin sets the platform fee taken from bounty payouts and the referral
+    /// program: each referrer gets `referral_share_bps` of the fee, up to
+    /// `referral_cap` per referred account (0 = uncapped), for `referral_duration`
+    /// seconds after the referred account was created (0 = no expiry).
+    pub fn set_fees(
+        ctx: Context<UpdateConfig>,
+        fee_bps: u16,
+        treasury: Pubkey,
+        referral_share_bps: u16,
+        referral_cap: u64,
+        referral_duration: i64,
+    ) -> Result<()> {
+        require!(fee_bps <= MAX_FEE_BPS, BountyError::InvalidFeeConfig);
+        require!(
+            referral_share_bps <= MAX_REFERRAL_SHARE_BPS,
+            BountyError::InvalidFeeConfig
+        );
+        require!(referral_duration >= 0, BountyError::InvalidFeeConfig);
+        require!(
+            fee_bps == 0 || treasury != Pubkey::default(),
+            BountyError::InvalidFeeConfig
+        );
+
+        let config = &mut ctx.accounts.config;
+        config.fee_bps = fee_bps;
+        config.treasury = treasury;
+        config.referral_share_bps = referral_share_bps;
+        config.referral_cap = referral_cap;
+        config.referral_duration = referral_duration;
+        Ok(())
+    }
+
+    /// Initialize a client state PDA to track bounty count. Called once per client wallet,
+    /// optionally recording the wallet that referred the client.
+    pub fn init_client(ctx: Context<InitClient>, referrer: Option<Pubkey>) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_INIT_CLIENT), BountyError::Paused);
+
+        let referrer = referrer.unwrap_or_default();
+        require!(
+            referrer != ctx.accounts.client.key(),
+            BountyError::InvalidReferrer
+        );
+
         let client_state = &mut ctx.accounts.client_state;
         client_state.owner = ctx.accounts.client.key();
         client_state.bounty_count = 0;
         client_state.bump = ctx.bumps.client_state;
+        client_state.referrer = referrer;
+        client_state.referral_paid = 0;
+        client_state.created_at = Clock::get()?.unix_timestamp;
         Ok(())
     }
 
     /// Create a bounty: init PDA, init vault token account, transfer USDC from client to vault.
-    pub fn create_bounty(
-        ctx: Context<CreateBounty>,
+    /// Remaining accounts are one index page per bucket of `skills`, in `index_buckets` order.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_bounty<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateBounty<'info>>,
         metadata_uri: String,
+        metadata_hash: [u8; 32],
         budget: u64,
         deadline: i64,
+        skills: u128,
+        require_skill_match: bool,
+        attestation_requirement: Option<AttestationRequirement>,
+        validation_requirement: Option<ValidationRequirement>,
+        allowlist: Vec<Pubkey>,
+        decline_policy: DeclinePolicy,
     ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_CREATE_BOUNTY), BountyError::Paused);
+
         require!(metadata_uri.len() <= MAX_URI_LEN, BountyError::UriTooLong);
+        require!(metadata_hash != [0u8; 32], BountyError::MissingContentHash);
         require!(budget > 0, BountyError::InvalidBudget);
         require!(
             deadline > Clock::get()?.unix_timestamp,
             BountyError::DeadlinePassed
         );
+        require!(
+            skills.count_ones() as usize <= MAX_BOUNTY_SKILLS,
+            BountyError::TooManySkills
+        );
+        require!(
+            ctx.accounts.skill_registry.is_valid(skills),
+            BountyError::UnknownSkill
+        );
+        require!(
+            !require_skill_match || skills != 0,
+            BountyError::NoRequiredSkills
+        );
+        if let Some(requirement) = &attestation_requirement {
+            require!(
+                (requirement.skill_id as usize) < ctx.accounts.skill_registry.names.len(),
+                BountyError::UnknownSkill
+            );
+        }
+        if let Some(requirement) = &validation_requirement {
+            require!(
+                requirement.min_score > 0 && requirement.min_score <= MAX_VALIDATION_SCORE,
+                BountyError::InvalidMinScore
+            );
+        }
+        check_allowlist(&allowlist)?;
 
         let client_state = &mut ctx.accounts.client_state;
         let bounty_id = client_state.bounty_count;
         client_state.bounty_count += 1;
 
         let bounty = &mut ctx.accounts.bounty;
+        bounty.version = BOUNTY_VERSION;
         bounty.client = ctx.accounts.client.key();
         bounty.bounty_id = bounty_id;
         bounty.metadata_uri = metadata_uri;
+        bounty.metadata_hash = metadata_hash;
         bounty.budget = budget;
         bounty.deadline = deadline;
-        bounty.status = BountyStatus::Open as u8;
+        bounty.status = BountyStatus::Open;
         bounty.claims = 0;
         bounty.assigned_agent = Pubkey::default();
         bounty.deliverable_uri = String::new();
+        bounty.deliverable_hash = [0u8; 32];
         bounty.vault = ctx.accounts.vault.key();
         bounty.usdc_mint = ctx.accounts.usdc_mint.key();
         bounty.bump = ctx.bumps.bounty;
         bounty.created_at = Clock::get()?.unix_timestamp;
+        bounty.skills = skills;
+        bounty.require_skill_match = require_skill_match;
+        bounty.attestation_requirement = attestation_requirement;
+        bounty.validation_requirement = validation_requirement;
+        bounty.allowlist = allowlist;
+        bounty.decline_policy = decline_policy;
+        bounty.recurring = Pubkey::default();
+        bounty.tips_total = 0;
+        bounty.parent = Pubkey::default();
+        bounty.child_count = 0;
+        bounty.reserved_for_children = 0;
+        bounty.awaiting_parent_funds = false;
+        bounty.guild = Pubkey::default();
+        bounty.claimed_at = 0;
+        bounty.delivered_at = 0;
+        bounty.completed_at = 0;
+        bounty.disputed_at = 0;
+        bounty.cancelled_at = 0;
+        bounty.index_pages = Vec::new();
+        bounty.last_actor = ctx.accounts.client.key();
+        ctx.accounts
+            .history
+            .open(bounty.key(), ctx.bumps.history, ctx.accounts.client.key(), bounty.created_at);
+        let bounty_key = bounty.key();
+        bounty.add_to_index(bounty_key, ctx.remaining_accounts)?;
 
         // Transfer USDC from client to vault
         let cpi_accounts = Transfer {
@@ -76,78 +311,285 @@ pub mod bounty_escrow {
     }
 
     /// Agent claims an open bounty. Sets status to Claimed and records the agent.
-    pub fn claim_bounty(ctx: Context<ClaimBounty>) -> Result<()> {
+    /// A delegate with DELEGATE_CLAIM may claim; the owner wallet is recorded.
+    /// Invite-only bounties can only be claimed by agents on the allowlist.
+    /// The claim is counted on the agent profile until the bounty is paid out.
+    /// Remaining accounts are the index pages the bounty is listed on.
+    pub fn claim_bounty<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimBounty<'info>>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_CLAIM_BOUNTY), BountyError::Paused);
+
+        let agent_wallet = resolve_agent_wallet(
+            &ctx.accounts.agent,
+            &ctx.accounts.agent_profile,
+            &ctx.accounts.agent_delegate,
+            DELEGATE_CLAIM,
+        )?;
+
         let bounty = &mut ctx.accounts.bounty;
         require!(
-            bounty.status == BountyStatus::Open as u8,
+            bounty.status == BountyStatus::Open,
             BountyError::NotOpen
         );
+        require!(
+            !bounty.require_skill_match || bounty.skills & ctx.accounts.agent_profile.skills != 0,
+            BountyError::SkillMismatch
+        );
+        require!(
+            bounty.allowlist.is_empty() || bounty.allowlist.contains(&agent_wallet),
+            BountyError::NotInvited
+        );
+        if let Some(requirement) = &bounty.attestation_requirement {
+            check_attestation(
+                requirement,
+                &ctx.accounts.agent_profile,
+                &ctx.accounts.attestation,
+                &ctx.accounts.verifier,
+            )?;
+        }
 
-        bounty.assigned_agent = ctx.accounts.agent.key();
-        bounty.status = BountyStatus::Claimed as u8;
+        let bounty_key = bounty.key();
+
+        bounty.remove_from_index(bounty_key, ctx.remaining_accounts)?;
+        bounty.assigned_agent = agent_wallet;
+        bounty.transition(
+            &mut ctx.accounts.history,
+            BountyStatus::Claimed,
+            ctx.accounts.agent.key(),
+        )?;
         bounty.claims += 1;
+
+        let authority_seeds = &[ESCROW_AUTHORITY_SEED, &[ctx.bumps.escrow_authority]];
+        agent_registry::cpi::record_claim(CpiContext::new_with_signer(
+            ctx.accounts.agent_registry_program.to_account_info(),
+            agent_registry::cpi::accounts::UpdateReputation {
+                agent: ctx.accounts.agent_profile.to_account_info(),
+                authority: ctx.accounts.escrow_authority.to_account_info(),
+            },
+            &[&authority_seeds[..]],
+        ))?;
+        Ok(())
+    }
+
+    /// Invited agent accepts a direct-hire offer, claiming the bounty.
+    pub fn accept_offer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimBounty<'info>>,
+    ) -> Result<()> {
+        require!(
+            !ctx.accounts.bounty.allowlist.is_empty(),
+            BountyError::NotInvited
+        );
+        claim_bounty(ctx)
+    }
+
+    /// Invited agent turns down an offer and is removed from the allowlist. When the
+    /// last invitee declines, the client's decline policy either refunds the budget
+    /// and cancels the bounty or reopens it to every agent. Remaining accounts are the
+    /// index pages the bounty is listed on; they are only touched when it is cancelled.
+    pub fn decline_offer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DeclineOffer<'info>>,
+    ) -> Result<()> {
+        let agent_wallet = resolve_agent_wallet(
+            &ctx.accounts.agent,
+            &ctx.accounts.agent_profile,
+            &ctx.accounts.agent_delegate,
+            DELEGATE_CLAIM,
+        )?;
+
+        let bounty = &mut ctx.accounts.bounty;
+        require!(
+            bounty.status == BountyStatus::Open,
+            BountyError::NotOpen
+        );
+        let position = bounty
+            .allowlist
+            .iter()
+            .position(|invited| *invited == agent_wallet)
+            .ok_or(BountyError::NotInvited)?;
+        bounty.allowlist.remove(position);
+
+        if bounty.allowlist.is_empty() && bounty.decline_policy == DeclinePolicy::Refund {
+            let bounty_key = bounty.key();
+            transfer_from_vault(
+                &ctx.accounts.token_program,
+                &ctx.accounts.vault,
+                ctx.accounts.client_token_account.to_account_info(),
+                bounty_key,
+                ctx.bumps.vault,
+                bounty.budget,
+            )?;
+            close_vault(
+                &ctx.accounts.token_program,
+                &ctx.accounts.vault,
+                ctx.accounts.client.to_account_info(),
+                bounty_key,
+                ctx.bumps.vault,
+            )?;
+            bounty.remove_from_index(bounty_key, ctx.remaining_accounts)?;
+            bounty.transition(
+                &mut ctx.accounts.history,
+                BountyStatus::Cancelled,
+                ctx.accounts.agent.key(),
+            )?;
+        }
         Ok(())
     }
 
-    /// Assigned agent submits work with a deliverable URI.
-    pub fn submit_work(ctx: Context<SubmitWork>, deliverable_uri: String) -> Result<()> {
+    /// Assigned agent (or a delegate with DELEGATE_SUBMIT) submits work with a deliverable URI.
+    pub fn submit_work(
+        ctx: Context<SubmitWork>,
+        deliverable_uri: String,
+        deliverable_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_SUBMIT_WORK), BountyError::Paused);
+
         require!(
             deliverable_uri.len() <= MAX_URI_LEN,
             BountyError::UriTooLong
         );
+        require!(deliverable_hash != [0u8; 32], BountyError::MissingContentHash);
+
+        let agent_wallet = resolve_agent_wallet(
+            &ctx.accounts.agent,
+            &ctx.accounts.agent_profile,
+            &ctx.accounts.agent_delegate,
+            DELEGATE_SUBMIT,
+        )?;
 
         let bounty = &mut ctx.accounts.bounty;
         require!(
-            bounty.status == BountyStatus::Claimed as u8,
+            bounty.status == BountyStatus::Claimed,
             BountyError::NotClaimed
         );
         require!(
-            bounty.assigned_agent == ctx.accounts.agent.key(),
+            bounty.assigned_agent == agent_wallet,
             BountyError::NotAssignedAgent
         );
 
         bounty.deliverable_uri = deliverable_uri;
-        bounty.status = BountyStatus::Delivered as u8;
+        bounty.deliverable_hash = deliverable_hash;
+        bounty.transition(
+            &mut ctx.accounts.history,
+            BountyStatus::Delivered,
+            ctx.accounts.agent.key(),
+        )?;
         Ok(())
     }
 
     /// Client approves work: transfers vault funds to agent, sets status to Completed.
+    /// Bounties with a validation requirement also need a passing validation.
+    /// Budget reserved for subcontracts stays in the vault for `fund_subcontract`.
     pub fn approve_work(ctx: Context<ApproveWork>) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_APPROVE_WORK), BountyError::Paused);
+
         let bounty = &ctx.accounts.bounty;
         require!(
-            bounty.status == BountyStatus::Delivered as u8,
+            bounty.status == BountyStatus::Delivered,
             BountyError::NotDelivered
         );
+        require!(!bounty.awaiting_parent_funds, BountyError::SubcontractNotFunded);
+        if let Some(requirement) = &bounty.validation_requirement {
+            let validation = ctx
+                .accounts
+                .validation
+                .as_ref()
+                .ok_or(BountyError::ValidationRequired)?;
+            check_validation(requirement, bounty, validation)?;
+        }
 
-        let budget = bounty.budget;
-        let bounty_key = bounty.key();
+        BountyPayout {
+            config: &ctx.accounts.config,
+            bounty,
+            vault: &ctx.accounts.vault,
+            vault_bump: ctx.bumps.vault,
+            client_state: &mut ctx.accounts.client_state,
+            treasury_token_account: ctx.accounts.treasury_token_account.as_ref(),
+            agent_referrer_token_account: ctx.accounts.agent_referrer_token_account.as_ref(),
+            client_referrer_token_account: ctx.accounts.client_referrer_token_account.as_ref(),
+            token_program: &ctx.accounts.token_program,
+            agent_registry_program: &ctx.accounts.agent_registry_program,
+            escrow_authority: &ctx.accounts.escrow_authority,
+            escrow_authority_bump: ctx.bumps.escrow_authority,
+        }
+        .pay(
+            bounty.budget - bounty.reserved_for_children,
+            &ctx.accounts.agent_token_account,
+            &ctx.accounts.agent_profile,
+        )?;
+        release_agent_claim(
+            &ctx.accounts.agent_registry_program,
+            &ctx.accounts.agent_profile,
+            &ctx.accounts.escrow_authority,
+            ctx.bumps.escrow_authority,
+        )?;
+
+        let bounty = &mut ctx.accounts.bounty;
+        bounty.transition(
+            &mut ctx.accounts.history,
+            BountyStatus::Completed,
+            ctx.accounts.client.key(),
+        )?;
+        Ok(())
+    }
 
-        // PDA-signed transfer from vault to agent token account
-        let seeds = &[
-            b"vault".as_ref(),
-            bounty_key.as_ref(),
-            &[ctx.bumps.vault],
-        ];
-        let signer = &[&seeds[..]];
+    /// Anyone can complete a delivered bounty whose client opted into auto-approval
+    /// once the named validator has posted a passing score for the deliverable.
+    pub fn auto_approve(ctx: Context<AutoApprove>) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_APPROVE_WORK), BountyError::Paused);
 
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.vault.to_account_info(),
-            to: ctx.accounts.agent_token_account.to_account_info(),
-            authority: ctx.accounts.vault.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        token::transfer(
-            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer),
-            budget,
+        let bounty = &ctx.accounts.bounty;
+        require!(
+            bounty.status == BountyStatus::Delivered,
+            BountyError::NotDelivered
+        );
+        let requirement = bounty
+            .validation_requirement
+            .as_ref()
+            .filter(|requirement| requirement.auto_approve)
+            .ok_or(BountyError::AutoApproveDisabled)?;
+        require!(!bounty.awaiting_parent_funds, BountyError::SubcontractNotFunded);
+        check_validation(requirement, bounty, &ctx.accounts.validation)?;
+
+        BountyPayout {
+            config: &ctx.accounts.config,
+            bounty,
+            vault: &ctx.accounts.vault,
+            vault_bump: ctx.bumps.vault,
+            client_state: &mut ctx.accounts.client_state,
+            treasury_token_account: ctx.accounts.treasury_token_account.as_ref(),
+            agent_referrer_token_account: ctx.accounts.agent_referrer_token_account.as_ref(),
+            client_referrer_token_account: ctx.accounts.client_referrer_token_account.as_ref(),
+            token_program: &ctx.accounts.token_program,
+            agent_registry_program: &ctx.accounts.agent_registry_program,
+            escrow_authority: &ctx.accounts.escrow_authority,
+            escrow_authority_bump: ctx.bumps.escrow_authority,
+        }
+        .pay(
+            bounty.budget - bounty.reserved_for_children,
+            &ctx.accounts.agent_token_account,
+            &ctx.accounts.agent_profile,
+        )?;
+        release_agent_claim(
+            &ctx.accounts.agent_registry_program,
+            &ctx.accounts.agent_profile,
+            &ctx.accounts.escrow_authority,
+            ctx.bumps.escrow_authority,
         )?;
 
         let bounty = &mut ctx.accounts.bounty;
-        bounty.status = BountyStatus::Completed as u8;
+        bounty.transition(
+            &mut ctx.accounts.history,
+            BountyStatus::Completed,
+            ctx.accounts.validation.validator,
+        )?;
         Ok(())
     }
 
     /// Client or assigned agent can dispute a bounty.
     pub fn dispute_bounty(ctx: Context<DisputeBounty>) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_DISPUTE_BOUNTY), BountyError::Paused);
+
         let bounty = &mut ctx.accounts.bounty;
         let authority_key = ctx.accounts.authority.key();
 
@@ -159,211 +601,3390 @@ pub mod bounty_escrow {
 
         // Can only dispute claimed or delivered bounties
         require!(
-            bounty.status == BountyStatus::Claimed as u8
-                || bounty.status == BountyStatus::Delivered as u8,
+            bounty.status == BountyStatus::Claimed
+                || bounty.status == BountyStatus::Delivered,
             BountyError::CannotDispute
         );
 
-        bounty.status = BountyStatus::Disputed as u8;
+        bounty.transition(&mut ctx.accounts.history, BountyStatus::Disputed, authority_key)?;
+        Ok(())
+    }
+
+    /// Admin settles a disputed bounty: `agent_amount` of the budget is paid out
+    /// like an approval (net of the platform fee, to the agent or split among the
+    /// guild's members) and the rest is refunded to the client, recording the
+    /// payout in `dispute_payout`. The claim is released either way. The bounty completes if the agent is paid anything and
+    /// is cancelled otherwise. For guild bounties the remaining accounts are the
+    /// members' agent profile and token account pairs, as for `approve_guild_work`.
+    /// For solo bounties they are the subcontracts still awaiting payout funds,
+    /// which are cancelled so their reservations go back to the client (see
+    /// `UnfundedSubcontracts`).
+    pub fn resolve_dispute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveDispute<'info>>,
+        agent_amount: u64,
+    ) -> Result<()> {
+        let bounty = &ctx.accounts.bounty;
+        require!(
+            bounty.status == BountyStatus::Disputed,
+            BountyError::NotDisputed
+        );
+        require!(!bounty.awaiting_parent_funds, BountyError::SubcontractNotFunded);
+        require!(agent_amount <= bounty.budget, BountyError::InvalidResolution);
+
+        if bounty.guild == Pubkey::default() {
+            let freed = UnfundedSubcontracts {
+                parent: bounty,
+                agent: ctx.accounts.agent.as_ref(),
+                token_program: &ctx.accounts.token_program,
+                agent_registry_program: &ctx.accounts.agent_registry_program,
+                escrow_authority: &ctx.accounts.escrow_authority,
+                escrow_authority_bump: ctx.bumps.escrow_authority,
+                actor: ctx.accounts.admin.key(),
+            }
+            .cancel(ctx.remaining_accounts)?;
+            require!(
+                freed == bounty.reserved_for_children,
+                BountyError::UnfundedSubcontractsMismatch
+            );
+            ctx.accounts.bounty.reserved_for_children = 0;
+            let bounty = &ctx.accounts.bounty;
+            let agent_profile = ctx
+                .accounts
+                .agent_profile
+                .as_ref()
+                .filter(|profile| profile.owner == bounty.assigned_agent)
+                .ok_or(BountyError::AgentProfileMismatch)?;
+            if agent_amount > 0 {
+                let agent_token_account = ctx
+                    .accounts
+                    .agent_token_account
+                    .as_ref()
+                    .filter(|account| {
+                        account.owner == bounty.assigned_agent
+                            && account.mint == bounty.usdc_mint
+                    })
+                    .ok_or(BountyError::InvalidPayeeAccount)?;
+                BountyPayout {
+                    config: &ctx.accounts.config,
+                    bounty,
+                    vault: &ctx.accounts.vault,
+                    vault_bump: ctx.bumps.vault,
+                    client_state: &mut ctx.accounts.client_state,
+                    treasury_token_account: ctx.accounts.treasury_token_account.as_ref(),
+                    agent_referrer_token_account: ctx.accounts.agent_referrer_token_account.as_ref(),
+                    client_referrer_token_account: ctx.accounts.client_referrer_token_account.as_ref(),
+                    token_program: &ctx.accounts.token_program,
+                    agent_registry_program: &ctx.accounts.agent_registry_program,
+                    escrow_authority: &ctx.accounts.escrow_authority,
+                    escrow_authority_bump: ctx.bumps.escrow_authority,
+                }
+                .pay(agent_amount, agent_token_account, agent_profile)?;
+            }
+            release_agent_claim(
+                &ctx.accounts.agent_registry_program,
+                agent_profile,
+                &ctx.accounts.escrow_authority,
+                ctx.bumps.escrow_authority,
+            )?;
+        } else {
+            let guild = ctx
+                .accounts
+                .guild
+                .as_ref()
+                .filter(|guild| guild.key() == bounty.guild)
+                .ok_or(BountyError::NotGuildBounty)?;
+            let net = BountyPayout {
+                config: &ctx.accounts.config,
+                bounty,
+                vault: &ctx.accounts.vault,
+                vault_bump: ctx.bumps.vault,
+                client_state: &mut ctx.accounts.client_state,
+                treasury_token_account: ctx.accounts.treasury_token_account.as_ref(),
+                agent_referrer_token_account: None,
+                client_referrer_token_account: ctx.accounts.client_referrer_token_account.as_ref(),
+                token_program: &ctx.accounts.token_program,
+                agent_registry_program: &ctx.accounts.agent_registry_program,
+                escrow_authority: &ctx.accounts.escrow_authority,
+                escrow_authority_bump: ctx.bumps.escrow_authority,
+            }
+            .take_fee(agent_amount, None)?;
+            GuildPayout {
+                bounty,
+                vault: &ctx.accounts.vault,
+                vault_bump: ctx.bumps.vault,
+                guild,
+                token_program: &ctx.accounts.token_program,
+                agent_registry_program: &ctx.accounts.agent_registry_program,
+                escrow_authority: &ctx.accounts.escrow_authority,
+                escrow_authority_bump: ctx.bumps.escrow_authority,
+            }
+            .pay(net, ctx.remaining_accounts)?;
+        }
+
+        let bounty = &ctx.accounts.bounty;
+        let refund = bounty.budget - agent_amount;
+        if refund > 0 {
+            transfer_from_vault(
+                &ctx.accounts.token_program,
+                &ctx.accounts.vault,
+                ctx.accounts.client_token_account.to_account_info(),
+                bounty.key(),
+                ctx.bumps.vault,
+                refund,
+            )?;
+        }
+        let next = if agent_amount > 0 {
+            BountyStatus::Completed
+        } else {
+            close_vault(
+                &ctx.accounts.token_program,
+                &ctx.accounts.vault,
+                ctx.accounts.client.to_account_info(),
+                bounty.key(),
+                ctx.bumps.vault,
+            )?;
+            BountyStatus::Cancelled
+        };
+
+        let bounty = &mut ctx.accounts.bounty;
+        bounty.dispute_payout = agent_amount;
+        bounty.transition(&mut ctx.accounts.history, next, ctx.accounts.admin.key())?;
         Ok(())
     }
 
     /// Client leaves a review after bounty completion. Creates a Review PDA and
-    /// updates the agent's reputation via CPI to agent-registry.
-    pub fn leave_review(
-        ctx: Context<LeaveReview>,
+    /// updates the agent's reputation via CPI to agent-registry. If the agent's
+    /// profile has changed owners since, remaining accounts are the tombstoned
+    /// profiles leading to its current one, oldest first.
+    pub fn leave_review<'info>(
+        ctx: Context<'_, '_, 'info, 'info, LeaveReview<'info>>,
         rating: u64,
         comment_uri: String,
+        comment_hash: [u8; 32],
     ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_LEAVE_REVIEW), BountyError::Paused);
+
         require!(rating > 0 && rating <= 500, BountyError::InvalidRating);
         require!(comment_uri.len() <= MAX_URI_LEN, BountyError::UriTooLong);
+        require!(comment_hash != [0u8; 32], BountyError::MissingContentHash);
 
         let bounty = &ctx.accounts.bounty;
         require!(
-            bounty.status == BountyStatus::Completed as u8,
+            bounty.status == BountyStatus::Completed,
             BountyError::NotCompleted
         );
+        check_agent_profile(
+            &bounty.assigned_agent,
+            &ctx.accounts.agent_profile,
+            ctx.remaining_accounts,
+        )?;
+        update_agent_reputation(
+            &ctx.accounts.agent_registry_program,
+            ctx.accounts.agent_profile.to_account_info(),
+            &ctx.accounts.escrow_authority,
+            ctx.bumps.escrow_authority,
+            rating,
+        )?;
 
-        let review = &mut ctx.accounts.review;
-        review.bounty = bounty.key();
-        review.reviewer = ctx.accounts.client.key();
-        review.agent = bounty.assigned_agent;
-        review.rating = rating;
-        review.comment_uri = comment_uri;
-        review.bump = ctx.bumps.review;
-        review.created_at = Clock::get()?.unix_timestamp;
-
-        Ok(())
+        ctx.accounts.review.record(
+            bounty,
+            ctx.accounts.client.key(),
+            rating,
+            comment_uri,
+            comment_hash,
+            ctx.bumps.review,
+        )
     }
 
     /// Client cancels an open bounty: refund vault to client, close vault.
-    pub fn cancel_bounty(ctx: Context<CancelBounty>) -> Result<()> {
+    /// Remaining accounts are the index pages the bounty is listed on.
+    pub fn cancel_bounty<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelBounty<'info>>,
+    ) -> Result<()> {
         let bounty = &ctx.accounts.bounty;
         require!(
-            bounty.status == BountyStatus::Open as u8,
+            bounty.status == BountyStatus::Open,
             BountyError::NotOpen
         );
 
-        let budget = bounty.budget;
+        if bounty.awaiting_parent_funds {
+            // Nothing was escrowed yet; hand the reservation back to the parent
+            let parent = ctx
+                .accounts
+                .parent_bounty
+                .as_mut()
+                .ok_or(BountyError::ParentMismatch)?;
+            require!(parent.key() == bounty.parent, BountyError::ParentMismatch);
+            require!(
+                parent.status != BountyStatus::Completed,
+                BountyError::FundSubcontractFirst
+            );
+            parent.reserved_for_children -= bounty.budget;
+        } else {
+            // Refund the vault to the client
+            transfer_from_vault(
+                &ctx.accounts.token_program,
+                &ctx.accounts.vault,
+                ctx.accounts.client_token_account.to_account_info(),
+                bounty.key(),
+                ctx.bumps.vault,
+                bounty.budget,
+            )?;
+        }
+        // Close the vault and reclaim rent
+        close_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            ctx.accounts.client.to_account_info(),
+            bounty.key(),
+            ctx.bumps.vault,
+        )?;
+
+        let bounty = &mut ctx.accounts.bounty;
         let bounty_key = bounty.key();
+        bounty.remove_from_index(bounty_key, ctx.remaining_accounts)?;
+        bounty.transition(
+            &mut ctx.accounts.history,
+            BountyStatus::Cancelled,
+            ctx.accounts.client.key(),
+        )?;
+        Ok(())
+    }
 
-        // PDA-signed transfer from vault back to client
-        let seeds = &[
-            b"vault".as_ref(),
-            bounty_key.as_ref(),
-            &[ctx.bumps.vault],
-        ];
-        let signer = &[&seeds[..]];
+    /// Client opens an hourly contract with an agent, funding the vault with `cap`.
+    /// The agent's current `hourly_rate` is locked in for the contract, which
+    /// counts as an active claim on the agent until it is closed.
+    pub fn create_hourly_contract(
+        ctx: Context<CreateHourlyContract>,
+        contract_id: u64,
+        cap: u64,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_HOURLY), BountyError::Paused);
+
+        require!(cap > 0, BountyError::InvalidBudget);
+
+        let agent_profile = &ctx.accounts.agent_profile;
+        let contract = &mut ctx.accounts.contract;
+        contract.client = ctx.accounts.client.key();
+        contract.agent = agent_profile.owner;
+        contract.agent_profile = agent_profile.key();
+        contract.contract_id = contract_id;
+        contract.hourly_rate = agent_profile.hourly_rate;
+        contract.cap = cap;
+        contract.released = 0;
+        contract.status = HourlyContractStatus::Active;
+        contract.log_count = 0;
+        contract.pending_logs = 0;
+        contract.vault = ctx.accounts.vault.key();
+        contract.usdc_mint = ctx.accounts.usdc_mint.key();
+        contract.bump = ctx.bumps.contract;
+        contract.created_at = Clock::get()?.unix_timestamp;
 
         let cpi_accounts = Transfer {
-            from: ctx.accounts.vault.to_account_info(),
-            to: ctx.accounts.client_token_account.to_account_info(),
-            authority: ctx.accounts.vault.to_account_info(),
+            from: ctx.accounts.client_token_account.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.client.to_account_info(),
         };
         let cpi_program = ctx.accounts.token_program.to_account_info();
-        token::transfer(
-            CpiContext::new_with_signer(cpi_program, cpi_accounts, signer),
-            budget,
-        )?;
+        token::transfer(CpiContext::new(cpi_program, cpi_accounts), cap)?;
 
-        // Close the vault token account, reclaim rent to client
-        let close_accounts = anchor_spl::token::CloseAccount {
-            account: ctx.accounts.vault.to_account_info(),
-            destination: ctx.accounts.client.to_account_info(),
-            authority: ctx.accounts.vault.to_account_info(),
-        };
-        anchor_spl::token::close_account(CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            close_accounts,
-            signer,
+        let authority_seeds = &[ESCROW_AUTHORITY_SEED, &[ctx.bumps.escrow_authority]];
+        agent_registry::cpi::record_claim(CpiContext::new_with_signer(
+            ctx.accounts.agent_registry_program.to_account_info(),
+            agent_registry::cpi::accounts::UpdateReputation {
+                agent: ctx.accounts.agent_profile.to_account_info(),
+                authority: ctx.accounts.escrow_authority.to_account_info(),
+            },
+            &[&authority_seeds[..]],
         ))?;
-
-        let bounty = &mut ctx.accounts.bounty;
-        bounty.status = BountyStatus::Cancelled as u8;
         Ok(())
     }
-}
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum BountyStatus {
-    Open = 0,
-    Claimed = 1,
-    Delivered = 2,
-    Completed = 3,
-    Disputed = 4,
-    Cancelled = 5,
-}
+    /// Agent (or a delegate with DELEGATE_SUBMIT) logs hours worked in a period.
+    /// `hours_x100` is fixed-point * 100 (e.g. 150 = 1.5 hours). The period must
+    /// start no earlier than the end of the contract's previous log.
+    pub fn submit_time_log(
+        ctx: Context<SubmitTimeLog>,
+        period_start: i64,
+        period_end: i64,
+        hours_x100: u64,
+        evidence_uri: String,
+        evidence_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_HOURLY), BountyError::Paused);
 
-#[account]
-pub struct ClientState {
-    pub owner: Pubkey,
-    pub bounty_count: u64,
-    pub bump: u8,
-}
+        require!(evidence_uri.len() <= MAX_URI_LEN, BountyError::UriTooLong);
+        require!(evidence_hash != [0u8; 32], BountyError::MissingContentHash);
+        require!(
+            hours_x100 > 0 && period_start < period_end,
+            BountyError::InvalidTimeLog
+        );
+        let agent_wallet = resolve_agent_wallet(
+            &ctx.accounts.agent,
+            &ctx.accounts.agent_profile,
+            &ctx.accounts.agent_delegate,
+            DELEGATE_SUBMIT,
+        )?;
 
-#[account]
-pub struct Bounty {
-    /// Client who posted the bounty
-    pub client: Pubkey,
-    /// Sequential bounty ID for this client
-    pub bounty_id: u64,
-    /// Off-chain metadata URI (title, description, requirements, skills)
-    pub metadata_uri: String,
-    /// Budget in USDC minor units (6 decimals)
-    pub budget: u64,
-    /// Unix timestamp deadline
-    pub deadline: i64,
-    /// Status enum as u8
-    pub status: u8,
-    /// Number of claim attempts
-    pub claims: u64,
-    /// Pubkey of assigned agent (default if unassigned)
-    pub assigned_agent: Pubkey,
-    /// URI of submitted deliverable
-    pub deliverable_uri: String,
-    /// Vault token account address
-    pub vault: Pubkey,
-    /// USDC mint address
-    pub usdc_mint: Pubkey,
-    /// PDA bump
-    pub bump: u8,
-    /// Creation timestamp
-    pub created_at: i64,
-}
+        let contract = &mut ctx.accounts.contract;
+        require!(
+            contract.status == HourlyContractStatus::Active,
+            BountyError::ContractClosed
+        );
+        require!(agent_wallet == contract.agent, BountyError::NotAssignedAgent);
+        if contract.log_count > 0 {
+            let previous_log = ctx
+                .accounts
+                .previous_log
+                .as_ref()
+                .ok_or(BountyError::PreviousTimeLogRequired)?;
+            require!(
+                period_start >= previous_log.period_end,
+                BountyError::TimeLogOverlap
+            );
+        }
 
-#[account]
-pub struct Review {
-    /// The bounty this review is for
-    pub bounty: Pubkey,
-    /// Client who left the review
-    pub reviewer: Pubkey,
-    /// Agent being reviewed
-    pub agent: Pubkey,
-    /// Rating (fixed-point * 100, e.g. 450 = 4.50 stars)
-    pub rating: u64,
-    /// Off-chain comment URI
-    pub comment_uri: String,
-    /// PDA bump
-    pub bump: u8,
-    /// Creation timestamp
-    pub created_at: i64,
-}
+        let amount = contract.amount_for(hours_x100)?;
+        require!(
+            amount <= contract.cap - contract.released,
+            BountyError::ExceedsCap
+        );
 
-// ─── Instruction Contexts ───────────────────────────────────────────
+        let time_log = &mut ctx.accounts.time_log;
+        time_log.contract = contract.key();
+        time_log.index = contract.log_count;
+        time_log.period_start = period_start;
+        time_log.period_end = period_end;
+        time_log.hours_x100 = hours_x100;
+        time_log.amount = amount;
+        time_log.evidence_uri = evidence_uri;
+        time_log.evidence_hash = evidence_hash;
+        time_log.status = TimeLogStatus::Pending;
+        time_log.submitted_at = Clock::get()?.unix_timestamp;
+        time_log.bump = ctx.bumps.time_log;
 
-#[derive(Accounts)]
-pub struct InitClient<'info> {
-    #[account(
-        init,
-        payer = client,
-        space = CLIENT_STATE_SPACE,
-        seeds = [b"client", client.key().as_ref()],
-        bump,
-    )]
-    pub client_state: Account<'info, ClientState>,
-    #[account(mut)]
-    pub client: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
+        contract.log_count += 1;
+        contract.pending_logs += 1;
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct CreateBounty<'info> {
-    #[account(
-        mut,
-        seeds = [b"client", client.key().as_ref()],
-        bump = client_state.bump,
-        constraint = client_state.owner == client.key() @ BountyError::Unauthorized,
-    )]
-    pub client_state: Account<'info, ClientState>,
+    /// Client approves a time log, releasing `hours * hourly_rate` to the agent and
+    /// crediting it to the agent's registry earnings.
+    pub fn approve_time_log(ctx: Context<ApproveTimeLog>) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_HOURLY), BountyError::Paused);
 
-    #[account(
-        init,
-        payer = client,
-        space = BOUNTY_SPACE,
-        seeds = [b"bounty", client.key().as_ref(), &client_state.bounty_count.to_le_bytes()],
-        bump,
-    )]
-    pub bounty: Account<'info, Bounty>,
+        let time_log = &mut ctx.accounts.time_log;
+        require!(
+            time_log.status == TimeLogStatus::Pending,
+            BountyError::TimeLogNotPending
+        );
 
-    #[account(
-        init,
-        payer = client,
-        token::mint = usdc_mint,
-        token::authority = vault,
-        seeds = [b"vault", bounty.key().as_ref()],
-        bump,
-    )]
-    pub vault: Account<'info, TokenAccount>,
+        let contract = &mut ctx.accounts.contract;
+        let amount = time_log.amount;
+        require!(
+            amount <= contract.cap - contract.released,
+            BountyError::ExceedsCap
+        );
 
-    #[account(mut)]
-    pub client: Signer<'info>,
+        transfer_from_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            ctx.accounts.agent_token_account.to_account_info(),
+            contract.key(),
+            ctx.bumps.vault,
+            amount,
+        )?;
+        add_agent_earnings(
+            &ctx.accounts.agent_registry_program,
+            &ctx.accounts.agent_profile,
+            &ctx.accounts.escrow_authority,
+            ctx.bumps.escrow_authority,
+            amount,
+        )?;
 
-    /// Client's USDC token account
-    #[account(
-        mut,
-        constraint = client_token_account.owner == client.key(),
-        constraint = client_token_account.mint == usdc_mint.key(),
-    )]
+        time_log.status = TimeLogStatus::Approved;
+        contract.released += amount;
+        contract.pending_logs -= 1;
+        Ok(())
+    }
+
+    /// Client rejects a time log; nothing is paid for it.
+    pub fn reject_time_log(ctx: Context<RejectTimeLog>) -> Result<()> {
+        let time_log = &mut ctx.accounts.time_log;
+        require!(
+            time_log.status == TimeLogStatus::Pending,
+            BountyError::TimeLogNotPending
+        );
+
+        time_log.status = TimeLogStatus::Rejected;
+        ctx.accounts.contract.pending_logs -= 1;
+        Ok(())
+    }
+
+    /// Client or agent ends the contract once no logs are pending: unused funds
+    /// are refunded to the client, the vault is closed and the agent's claim is
+    /// released.
+    pub fn close_hourly_contract(ctx: Context<CloseHourlyContract>) -> Result<()> {
+        let contract = &mut ctx.accounts.contract;
+        let authority_key = ctx.accounts.authority.key();
+        require!(
+            authority_key == contract.client || authority_key == contract.agent,
+            BountyError::Unauthorized
+        );
+        require!(
+            contract.status == HourlyContractStatus::Active,
+            BountyError::ContractClosed
+        );
+        require!(contract.pending_logs == 0, BountyError::PendingTimeLogs);
+
+        let contract_key = contract.key();
+        transfer_from_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            ctx.accounts.client_token_account.to_account_info(),
+            contract_key,
+            ctx.bumps.vault,
+            contract.cap - contract.released,
+        )?;
+        close_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            ctx.accounts.client.to_account_info(),
+            contract_key,
+            ctx.bumps.vault,
+        )?;
+        release_agent_claim(
+            &ctx.accounts.agent_registry_program,
+            &ctx.accounts.agent_profile,
+            &ctx.accounts.escrow_authority,
+            ctx.bumps.escrow_authority,
+        )?;
+
+        contract.status = HourlyContractStatus::Closed;
+        Ok(())
+    }
+
+    /// Client opens a payment stream to an agent, funding the vault with `total`,
+    /// which vests linearly from `start_ts` to `end_ts`. Counts as an active claim
+    /// on the agent until the stream is finished or cancelled.
+    pub fn create_stream(
+        ctx: Context<CreateStream>,
+        stream_id: u64,
+        total: u64,
+        start_ts: i64,
+        end_ts: i64,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_STREAM), BountyError::Paused);
+
+        require!(total > 0, BountyError::InvalidBudget);
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            start_ts >= now && end_ts > start_ts,
+            BountyError::InvalidStreamSchedule
+        );
+
+        let agent_profile = &ctx.accounts.agent_profile;
+        let stream = &mut ctx.accounts.stream;
+        stream.client = ctx.accounts.client.key();
+        stream.agent = agent_profile.owner;
+        stream.agent_profile = agent_profile.key();
+        stream.stream_id = stream_id;
+        stream.total = total;
+        stream.withdrawn = 0;
+        stream.start_ts = start_ts;
+        stream.end_ts = end_ts;
+        stream.status = StreamStatus::Active;
+        stream.vault = ctx.accounts.vault.key();
+        stream.usdc_mint = ctx.accounts.usdc_mint.key();
+        stream.bump = ctx.bumps.stream;
+        stream.created_at = now;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.client_token_account.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.client.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::transfer(CpiContext::new(cpi_program, cpi_accounts), total)?;
+
+        let authority_seeds = &[ESCROW_AUTHORITY_SEED, &[ctx.bumps.escrow_authority]];
+        agent_registry::cpi::record_claim(CpiContext::new_with_signer(
+            ctx.accounts.agent_registry_program.to_account_info(),
+            agent_registry::cpi::accounts::UpdateReputation {
+                agent: ctx.accounts.agent_profile.to_account_info(),
+                authority: ctx.accounts.escrow_authority.to_account_info(),
+            },
+            &[&authority_seeds[..]],
+        ))?;
+        Ok(())
+    }
+
+    /// Agent withdraws everything vested so far. The final withdrawal completes
+    /// the stream, closes the vault and releases the agent's claim.
+    pub fn withdraw_vested(ctx: Context<WithdrawVested>) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_STREAM), BountyError::Paused);
+
+        let stream = &mut ctx.accounts.stream;
+        require!(
+            stream.status == StreamStatus::Active,
+            BountyError::StreamNotActive
+        );
+
+        let amount = stream.vested_at(Clock::get()?.unix_timestamp) - stream.withdrawn;
+        require!(amount > 0, BountyError::NothingVested);
+
+        let stream_key = stream.key();
+        transfer_from_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            ctx.accounts.agent_token_account.to_account_info(),
+            stream_key,
+            ctx.bumps.vault,
+            amount,
+        )?;
+        add_agent_earnings(
+            &ctx.accounts.agent_registry_program,
+            &ctx.accounts.agent_profile,
+            &ctx.accounts.escrow_authority,
+            ctx.bumps.escrow_authority,
+            amount,
+        )?;
+        stream.withdrawn += amount;
+
+        if stream.withdrawn == stream.total {
+            close_vault(
+                &ctx.accounts.token_program,
+                &ctx.accounts.vault,
+                ctx.accounts.client.to_account_info(),
+                stream_key,
+                ctx.bumps.vault,
+            )?;
+            release_agent_claim(
+                &ctx.accounts.agent_registry_program,
+                &ctx.accounts.agent_profile,
+                &ctx.accounts.escrow_authority,
+                ctx.bumps.escrow_authority,
+            )?;
+            stream.status = StreamStatus::Completed;
+        }
+        Ok(())
+    }
+
+    /// Client stops a stream: whatever has vested but not been withdrawn goes to
+    /// the agent, only the unvested remainder is refunded.
+    pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+        let stream = &mut ctx.accounts.stream;
+        require!(
+            stream.status == StreamStatus::Active,
+            BountyError::StreamNotActive
+        );
+
+        let vested = stream.vested_at(Clock::get()?.unix_timestamp);
+        let owed = vested - stream.withdrawn;
+        let refund = stream.total - vested;
+        let stream_key = stream.key();
+
+        if owed > 0 {
+            transfer_from_vault(
+                &ctx.accounts.token_program,
+                &ctx.accounts.vault,
+                ctx.accounts.agent_token_account.to_account_info(),
+                stream_key,
+                ctx.bumps.vault,
+                owed,
+            )?;
+            add_agent_earnings(
+                &ctx.accounts.agent_registry_program,
+                &ctx.accounts.agent_profile,
+                &ctx.accounts.escrow_authority,
+                ctx.bumps.escrow_authority,
+                owed,
+            )?;
+        }
+        if refund > 0 {
+            transfer_from_vault(
+                &ctx.accounts.token_program,
+                &ctx.accounts.vault,
+                ctx.accounts.client_token_account.to_account_info(),
+                stream_key,
+                ctx.bumps.vault,
+                refund,
+            )?;
+        }
+        close_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            ctx.accounts.client.to_account_info(),
+            stream_key,
+            ctx.bumps.vault,
+        )?;
+        release_agent_claim(
+            &ctx.accounts.agent_registry_program,
+            &ctx.accounts.agent_profile,
+            &ctx.accounts.escrow_authority,
+            ctx.bumps.escrow_authority,
+        )?;
+
+        stream.withdrawn = vested;
+        stream.status = StreamStatus::Cancelled;
+        Ok(())
+    }
+
+    /// Client sets up a bounty that repeats every `period_secs`, funding all
+    /// `total_periods` cycles up front. Cycles are opened by `open_cycle`.
+    /// A single-agent allowlist turns the template into a retainer.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_recurring_bounty(
+        ctx: Context<CreateRecurringBounty>,
+        template_id: u64,
+        metadata_uri: String,
+        metadata_hash: [u8; 32],
+        budget_per_period: u64,
+        period_secs: i64,
+        total_periods: u64,
+        first_open_at: i64,
+        skills: u128,
+        require_skill_match: bool,
+        allowlist: Vec<Pubkey>,
+        decline_policy: DeclinePolicy,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_CREATE_BOUNTY), BountyError::Paused);
+
+        require!(metadata_uri.len() <= MAX_URI_LEN, BountyError::UriTooLong);
+        require!(metadata_hash != [0u8; 32], BountyError::MissingContentHash);
+        require!(budget_per_period > 0, BountyError::InvalidBudget);
+        require!(
+            period_secs > 0 && total_periods > 0,
+            BountyError::InvalidRecurrence
+        );
+        require!(
+            skills.count_ones() as usize <= MAX_BOUNTY_SKILLS,
+            BountyError::TooManySkills
+        );
+        require!(
+            ctx.accounts.skill_registry.is_valid(skills),
+            BountyError::UnknownSkill
+        );
+        require!(
+            !require_skill_match || skills != 0,
+            BountyError::NoRequiredSkills
+        );
+        check_allowlist(&allowlist)?;
+        let funding = budget_per_period
+            .checked_mul(total_periods)
+            .ok_or(BountyError::InvalidBudget)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let recurring = &mut ctx.accounts.recurring;
+        recurring.client = ctx.accounts.client.key();
+        recurring.template_id = template_id;
+        recurring.metadata_uri = metadata_uri;
+        recurring.metadata_hash = metadata_hash;
+        recurring.budget_per_period = budget_per_period;
+        recurring.period_secs = period_secs;
+        recurring.total_periods = total_periods;
+        recurring.opened_periods = 0;
+        recurring.next_open_at = first_open_at.max(now);
+        recurring.skills = skills;
+        recurring.require_skill_match = require_skill_match;
+        recurring.allowlist = allowlist;
+        recurring.decline_policy = decline_policy;
+        recurring.status = RecurringStatus::Active;
+        recurring.last_cycle = Pubkey::default();
+        recurring.vault = ctx.accounts.vault.key();
+        recurring.usdc_mint = ctx.accounts.usdc_mint.key();
+        recurring.bump = ctx.bumps.recurring;
+        recurring.created_at = now;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.client_token_account.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.client.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::transfer(CpiContext::new(cpi_program, cpi_accounts), funding)?;
+
+        Ok(())
+    }
+
+    /// Permissionless crank: once a period is due, opens the next cycle as an
+    /// ordinary Open bounty funded from the template vault. The cycle's deadline
+    /// is one period after it opens. The caller pays the new accounts' rent.
+    /// Remaining accounts are the cycle's index pages, as for `create_bounty`.
+    pub fn open_cycle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, OpenCycle<'info>>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_CREATE_BOUNTY), BountyError::Paused);
+
+        let recurring = &mut ctx.accounts.recurring;
+        require!(
+            recurring.status == RecurringStatus::Active,
+            BountyError::RecurringNotActive
+        );
+        require!(
+            recurring.opened_periods < recurring.total_periods,
+            BountyError::NoPeriodsLeft
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= recurring.next_open_at, BountyError::CycleNotDue);
+
+        let client_state = &mut ctx.accounts.client_state;
+        let bounty_id = client_state.bounty_count;
+        client_state.bounty_count += 1;
+
+        let bounty = &mut ctx.accounts.bounty;
+        bounty.version = BOUNTY_VERSION;
+        bounty.client = recurring.client;
+        bounty.bounty_id = bounty_id;
+        bounty.metadata_uri = recurring.metadata_uri.clone();
+        bounty.metadata_hash = recurring.metadata_hash;
+        bounty.budget = recurring.budget_per_period;
+        bounty.deadline = now + recurring.period_secs;
+        bounty.status = BountyStatus::Open;
+        bounty.claims = 0;
+        bounty.assigned_agent = Pubkey::default();
+        bounty.deliverable_uri = String::new();
+        bounty.deliverable_hash = [0u8; 32];
+        bounty.vault = ctx.accounts.vault.key();
+        bounty.usdc_mint = recurring.usdc_mint;
+        bounty.bump = ctx.bumps.bounty;
+        bounty.created_at = now;
+        bounty.skills = recurring.skills;
+        bounty.require_skill_match = recurring.require_skill_match;
+        bounty.attestation_requirement = None;
+        bounty.validation_requirement = None;
+        bounty.allowlist = recurring.allowlist.clone();
+        bounty.decline_policy = recurring.decline_policy;
+        bounty.recurring = recurring.key();
+        bounty.tips_total = 0;
+        bounty.parent = Pubkey::default();
+        bounty.child_count = 0;
+        bounty.reserved_for_children = 0;
+        bounty.awaiting_parent_funds = false;
+        bounty.guild = Pubkey::default();
+        bounty.claimed_at = 0;
+        bounty.delivered_at = 0;
+        bounty.completed_at = 0;
+        bounty.disputed_at = 0;
+        bounty.cancelled_at = 0;
+        bounty.index_pages = Vec::new();
+        bounty.last_actor = ctx.accounts.payer.key();
+        ctx.accounts
+            .history
+            .open(bounty.key(), ctx.bumps.history, ctx.accounts.payer.key(), now);
+        let bounty_key = bounty.key();
+        bounty.add_to_index(bounty_key, ctx.remaining_accounts)?;
+
+        transfer_from_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.template_vault,
+            ctx.accounts.vault.to_account_info(),
+            recurring.key(),
+            ctx.bumps.template_vault,
+            recurring.budget_per_period,
+        )?;
+
+        recurring.opened_periods += 1;
+        recurring.next_open_at += recurring.period_secs;
+        recurring.last_cycle = bounty.key();
+        Ok(())
+    }
+
+    /// Client stops a recurring bounty, refunding every period not yet opened.
+    /// Cycles already opened are ordinary bounties and settle on their own.
+    pub fn cancel_recurring(ctx: Context<CancelRecurring>) -> Result<()> {
+        let recurring = &mut ctx.accounts.recurring;
+        require!(
+            recurring.status == RecurringStatus::Active,
+            BountyError::RecurringNotActive
+        );
+
+        let recurring_key = recurring.key();
+        transfer_from_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            ctx.accounts.client_token_account.to_account_info(),
+            recurring_key,
+            ctx.bumps.vault,
+            ctx.accounts.vault.amount,
+        )?;
+        close_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.vault,
+            ctx.accounts.client.to_account_info(),
+            recurring_key,
+            ctx.bumps.vault,
+        )?;
+
+        recurring.status = RecurringStatus::Cancelled;
+        Ok(())
+    }
+
+    /// Client sends a bonus on top of the budget for a completed bounty, straight
+    /// from their token account to the agent's. Counts toward the agent's earnings.
+    /// Remaining accounts are the profiles the agent's owner transfers have tombstoned
+    /// since, oldest first.
+    pub fn tip_agent<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TipAgent<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_TIP_AGENT), BountyError::Paused);
+
+        require!(amount > 0, BountyError::InvalidTip);
+        let bounty = &mut ctx.accounts.bounty;
+        require!(
+            bounty.status == BountyStatus::Completed,
+            BountyError::NotCompleted
+        );
+        check_agent_profile(
+            &bounty.assigned_agent,
+            &ctx.accounts.agent_profile,
+            ctx.remaining_accounts,
+        )?;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.client_token_account.to_account_info(),
+            to: ctx.accounts.agent_token_account.to_account_info(),
+            authority: ctx.accounts.client.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        token::transfer(CpiContext::new(cpi_program, cpi_accounts), amount)?;
+
+        add_agent_earnings(
+            &ctx.accounts.agent_registry_program,
+            &ctx.accounts.agent_profile,
+            &ctx.accounts.escrow_authority,
+            ctx.bumps.escrow_authority,
+            amount,
+        )?;
+
+        bounty.tips_total = bounty
+            .tips_total
+            .checked_add(amount)
+            .ok_or(BountyError::InvalidTip)?;
+        Ok(())
+    }
+
+    /// Assigned agent of a claimed bounty posts a child bounty for part of the work,
+    /// acting as its client. The child is funded either from the agent's own tokens
+    /// or, with `fund_from_payout`, from the parent budget: that share is held back
+    /// from the agent's payout and moved to the child by `fund_subcontract`.
+    /// Remaining accounts are the child's index pages, as for `create_bounty`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_subcontract<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateSubcontract<'info>>,
+        metadata_uri: String,
+        metadata_hash: [u8; 32],
+        budget: u64,
+        deadline: i64,
+        skills: u128,
+        require_skill_match: bool,
+        fund_from_payout: bool,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_CREATE_BOUNTY), BountyError::Paused);
+
+        require!(metadata_uri.len() <= MAX_URI_LEN, BountyError::UriTooLong);
+        require!(metadata_hash != [0u8; 32], BountyError::MissingContentHash);
+        require!(budget > 0, BountyError::InvalidBudget);
+        let now = Clock::get()?.unix_timestamp;
+        require!(deadline > now, BountyError::DeadlinePassed);
+        require!(
+            skills.count_ones() as usize <= MAX_BOUNTY_SKILLS,
+            BountyError::TooManySkills
+        );
+        require!(
+            ctx.accounts.skill_registry.is_valid(skills),
+            BountyError::UnknownSkill
+        );
+        require!(
+            !require_skill_match || skills != 0,
+            BountyError::NoRequiredSkills
+        );
+
+        let parent = &mut ctx.accounts.parent;
+        require!(
+            parent.status == BountyStatus::Claimed,
+            BountyError::NotClaimed
+        );
+        if fund_from_payout {
+            let reserved = parent
+                .reserved_for_children
+                .checked_add(budget)
+                .filter(|reserved| *reserved <= parent.budget)
+                .ok_or(BountyError::ExceedsParentBudget)?;
+            parent.reserved_for_children = reserved;
+        } else {
+            let agent_token_account = ctx
+                .accounts
+                .agent_token_account
+                .as_ref()
+                .ok_or(BountyError::MissingTokenAccount)?;
+            let cpi_accounts = Transfer {
+                from: agent_token_account.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.agent.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            token::transfer(CpiContext::new(cpi_program, cpi_accounts), budget)?;
+        }
+        parent.child_count += 1;
+
+        let client_state = &mut ctx.accounts.client_state;
+        let bounty_id = client_state.bounty_count;
+        client_state.bounty_count += 1;
+
+        let bounty = &mut ctx.accounts.bounty;
+        bounty.version = BOUNTY_VERSION;
+        bounty.client = ctx.accounts.agent.key();
+        bounty.bounty_id = bounty_id;
+        bounty.metadata_uri = metadata_uri;
+        bounty.metadata_hash = metadata_hash;
+        bounty.budget = budget;
+        bounty.deadline = deadline;
+        bounty.status = BountyStatus::Open;
+        bounty.claims = 0;
+        bounty.assigned_agent = Pubkey::default();
+        bounty.deliverable_uri = String::new();
+        bounty.deliverable_hash = [0u8; 32];
+        bounty.vault = ctx.accounts.vault.key();
+        bounty.usdc_mint = parent.usdc_mint;
+        bounty.bump = ctx.bumps.bounty;
+        bounty.created_at = now;
+        bounty.skills = skills;
+        bounty.require_skill_match = require_skill_match;
+        bounty.attestation_requirement = None;
+        bounty.validation_requirement = None;
+        bounty.allowlist = Vec::new();
+        bounty.decline_policy = DeclinePolicy::Refund;
+        bounty.recurring = Pubkey::default();
+        bounty.tips_total = 0;
+        bounty.parent = parent.key();
+        bounty.child_count = 0;
+        bounty.reserved_for_children = 0;
+        bounty.awaiting_parent_funds = fund_from_payout;
+        bounty.guild = Pubkey::default();
+        bounty.claimed_at = 0;
+        bounty.delivered_at = 0;
+        bounty.completed_at = 0;
+        bounty.disputed_at = 0;
+        bounty.cancelled_at = 0;
+        bounty.index_pages = Vec::new();
+        bounty.last_actor = ctx.accounts.agent.key();
+        ctx.accounts
+            .history
+            .open(bounty.key(), ctx.bumps.history, ctx.accounts.agent.key(), now);
+        let bounty_key = bounty.key();
+        bounty.add_to_index(bounty_key, ctx.remaining_accounts)?;
+        Ok(())
+    }
+
+    /// Permissionless: once the parent is approved, moves a payout-funded child's
+    /// budget from the parent vault into the child vault.
+    pub fn fund_subcontract(ctx: Context<FundSubcontract>) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_APPROVE_WORK), BountyError::Paused);
+
+        let parent = &mut ctx.accounts.parent;
+        let child = &mut ctx.accounts.child;
+        require!(child.awaiting_parent_funds, BountyError::SubcontractAlreadyFunded);
+        require!(
+            parent.status == BountyStatus::Completed,
+            BountyError::NotCompleted
+        );
+
+        transfer_from_vault(
+            &ctx.accounts.token_program,
+            &ctx.accounts.parent_vault,
+            ctx.accounts.child_vault.to_account_info(),
+            parent.key(),
+            ctx.bumps.parent_vault,
+            child.budget,
+        )?;
+
+        parent.reserved_for_children -= child.budget;
+        child.awaiting_parent_funds = false;
+        Ok(())
+    }
+
+    /// Guild admin claims an open bounty for the whole guild. Skill and attestation
+    /// requirements are checked against the claiming admin's own profile.
+    pub fn claim_guild_bounty<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimGuildBounty<'info>>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_CLAIM_BOUNTY), BountyError::Paused);
+
+        let guild = &ctx.accounts.guild;
+        let member_profile = &ctx.accounts.member_profile;
+        require!(
+            guild.is_admin(&member_profile.key()),
+            BountyError::NotGuildAdmin
+        );
+
+        let bounty = &mut ctx.accounts.bounty;
+        require!(
+            bounty.status == BountyStatus::Open,
+            BountyError::NotOpen
+        );
+        require!(
+            !bounty.require_skill_match || bounty.skills & member_profile.skills != 0,
+            BountyError::SkillMismatch
+        );
+        require!(
+            bounty.allowlist.is_empty() || bounty.allowlist.contains(&guild.key()),
+            BountyError::NotInvited
+        );
+        if let Some(requirement) = &bounty.attestation_requirement {
+            check_attestation(
+                requirement,
+                member_profile,
+                &ctx.accounts.attestation,
+                &ctx.accounts.verifier,
+            )?;
+        }
+
+        let bounty_key = bounty.key();
+
+        bounty.remove_from_index(bounty_key, ctx.remaining_accounts)?;
+        bounty.assigned_agent = guild.key();
+        bounty.guild = guild.key();
+        bounty.transition(
+            &mut ctx.accounts.history,
+            BountyStatus::Claimed,
+            ctx.accounts.member.key(),
+        )?;
+        bounty.claims += 1;
+
+        let authority_seeds = &[ESCROW_AUTHORITY_SEED, &[ctx.bumps.escrow_authority]];
+        agent_registry::cpi::record_guild_claim(CpiContext::new_with_signer(
+            ctx.accounts.agent_registry_program.to_account_info(),
+            agent_registry::cpi::accounts::UpdateGuild {
+                guild: ctx.accounts.guild.to_account_info(),
+                authority: ctx.accounts.escrow_authority.to_account_info(),
+            },
+            &[&authority_seeds[..]],
+        ))?;
+        Ok(())
+    }
+
+    /// Any guild member submits work on a bounty the guild claimed.
+    pub fn submit_guild_work(
+        ctx: Context<SubmitGuildWork>,
+        deliverable_uri: String,
+        deliverable_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_SUBMIT_WORK), BountyError::Paused);
+
+        require!(
+            deliverable_uri.len() <= MAX_URI_LEN,
+            BountyError::UriTooLong
+        );
+        require!(deliverable_hash != [0u8; 32], BountyError::MissingContentHash);
+
+        let bounty = &mut ctx.accounts.bounty;
+        require!(
+            bounty.status == BountyStatus::Claimed,
+            BountyError::NotClaimed
+        );
+
+        bounty.deliverable_uri = deliverable_uri;
+        bounty.deliverable_hash = deliverable_hash;
+        bounty.transition(
+            &mut ctx.accounts.history,
+            BountyStatus::Delivered,
+            ctx.accounts.member.key(),
+        )?;
+        Ok(())
+    }
+
+    /// Client approves a guild's work. The payout, net of the platform fee, is split
+    /// by the guild's revenue shares; rounding dust goes to the founding admin. Remaining accounts are
+    /// `[agent_profile, token_account]` pairs for every member, in guild order.
+    pub fn approve_guild_work<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ApproveGuildWork<'info>>,
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_APPROVE_WORK), BountyError::Paused);
+
+        let bounty = &ctx.accounts.bounty;
+        require!(
+            bounty.status == BountyStatus::Delivered,
+            BountyError::NotDelivered
+        );
+        require!(!bounty.awaiting_parent_funds, BountyError::SubcontractNotFunded);
+        if let Some(requirement) = &bounty.validation_requirement {
+            let validation = ctx
+                .accounts
+                .validation
+                .as_ref()
+                .ok_or(BountyError::ValidationRequired)?;
+            check_validation(requirement, bounty, validation)?;
+        }
+
+        let net = BountyPayout {
+            config: &ctx.accounts.config,
+            bounty,
+            vault: &ctx.accounts.vault,
+            vault_bump: ctx.bumps.vault,
+            client_state: &mut ctx.accounts.client_state,
+            treasury_token_account: ctx.accounts.treasury_token_account.as_ref(),
+            agent_referrer_token_account: None,
+            client_referrer_token_account: ctx.accounts.client_referrer_token_account.as_ref(),
+            token_program: &ctx.accounts.token_program,
+            agent_registry_program: &ctx.accounts.agent_registry_program,
+            escrow_authority: &ctx.accounts.escrow_authority,
+            escrow_authority_bump: ctx.bumps.escrow_authority,
+        }
+        .take_fee(bounty.budget - bounty.reserved_for_children, None)?;
+        GuildPayout {
+            bounty,
+            vault: &ctx.accounts.vault,
+            vault_bump: ctx.bumps.vault,
+            guild: &ctx.accounts.guild,
+            token_program: &ctx.accounts.token_program,
+            agent_registry_program: &ctx.accounts.agent_registry_program,
+            escrow_authority: &ctx.accounts.escrow_authority,
+            escrow_authority_bump: ctx.bumps.escrow_authority,
+        }
+        .pay(net, ctx.remaining_accounts)?;
+
+        let bounty = &mut ctx.accounts.bounty;
+        bounty.transition(
+            &mut ctx.accounts.history,
+            BountyStatus::Completed,
+            ctx.accounts.client.key(),
+        )?;
+        Ok(())
+    }
+
+    /// Client reviews a completed guild bounty. The rating is applied to the guild
+    /// and to every member's reputation; remaining accounts are the members'
+    /// agent profiles, in guild order.
+    pub fn leave_guild_review<'info>(
+        ctx: Context<'_, '_, 'info, 'info, LeaveGuildReview<'info>>,
+        rating: u64,
+        comment_uri: String,
+        comment_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(!ctx.accounts.config.is_paused(PAUSE_LEAVE_REVIEW), BountyError::Paused);
+
+        require!(rating > 0 && rating <= 500, BountyError::InvalidRating);
+        require!(comment_uri.len() <= MAX_URI_LEN, BountyError::UriTooLong);
+        require!(comment_hash != [0u8; 32], BountyError::MissingContentHash);
+
+        let bounty = &ctx.accounts.bounty;
+        require!(
+            bounty.status == BountyStatus::Completed,
+            BountyError::NotCompleted
+        );
+        let members = &ctx.accounts.guild.members;
+        require!(
+            ctx.remaining_accounts.len() == members.len(),
+            BountyError::GuildMembersMismatch
+        );
+
+        for (member, info) in members.iter().zip(ctx.remaining_accounts) {
+            require!(info.key() == member.agent, BountyError::GuildMembersMismatch);
+            update_agent_reputation(
+                &ctx.accounts.agent_registry_program,
+                info.clone(),
+                &ctx.accounts.escrow_authority,
+                ctx.bumps.escrow_authority,
+                rating,
+            )?;
+        }
+        let authority_seeds = &[ESCROW_AUTHORITY_SEED, &[ctx.bumps.escrow_authority]];
+        agent_registry::cpi::update_guild_reputation(
+            CpiContext::new_with_signer(
+                ctx.accounts.agent_registry_program.to_account_info(),
+                agent_registry::cpi::accounts::UpdateGuild {
+                    guild: ctx.accounts.guild.to_account_info(),
+                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                },
+                &[&authority_seeds[..]],
+            ),
+            rating,
+        )?;
+
+        ctx.accounts.review.record(
+            bounty,
+            ctx.accounts.client.key(),
+            rating,
+            comment_uri,
+            comment_hash,
+            ctx.bumps.review,
+        )
+    }
+
+    /// Permissionless: creates an empty page of the discovery index. Creators list
+    /// new bounties on the first page of each bucket that has room, so a new page
+    /// is only needed once the previous one is full.
+    pub fn init_index_page(
+        ctx: Context<InitIndexPage>,
+        status: BountyStatus,
+        skill: u8,
+        page: u32,
+    ) -> Result<()> {
+        require!(status == BountyStatus::Open, BountyError::StatusNotIndexed);
+        require!(
+            (skill as usize) < agent_registry::MAX_SKILLS || skill == UNSKILLED,
+            BountyError::UnknownSkill
+        );
+
+        let index_page = &mut ctx.accounts.index_page;
+        index_page.status = status;
+        index_page.skill = skill;
+        index_page.page = page;
+        index_page.bump = ctx.bumps.index_page;
+        index_page.bounties = Vec::new();
+        Ok(())
+    }
+
+    /// Grows a client state created before referrals to the current layout, with
+    /// no referrer. Anyone may pay for the migration.
+    pub fn migrate_client_state(ctx: Context<MigrateClientState>) -> Result<()> {
+        let client_state_info = ctx.accounts.client_state.to_account_info();
+        let legacy = {
+            let data = client_state_info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == ClientState::DISCRIMINATOR,
+                BountyError::UnknownAccountVersion
+            );
+            require!(
+                data.len() == LEGACY_CLIENT_STATE_SPACE,
+                BountyError::AlreadyMigrated
+            );
+            ClientStateV0::deserialize(&mut &data[8..])?
+        };
+
+        let client_state = ClientState {
+            owner: legacy.owner,
+            bounty_count: legacy.bounty_count,
+            bump: legacy.bump,
+            referrer: Pubkey::default(),
+            referral_paid: 0,
+            created_at: Clock::get()?.unix_timestamp,
+        };
+
+        grow_account(
+            &client_state_info,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            CLIENT_STATE_SPACE,
+        )?;
+        let mut data = client_state_info.try_borrow_mut_data()?;
+        client_state.try_serialize(&mut &mut data[..])?;
+        Ok(())
+    }
+
+    /// Upgrades a bounty account written at an earlier layout, or before layout
+    /// versioning, to the current layout, growing it in place. Fields added since
+    /// its version start at their defaults. Anyone may pay for the migration.
+    /// A legacy bounty still held by its agent was claimed before the registry
+    /// counted claims, so the claim is recorded on the agent's (already migrated)
+    /// profile now, letting the payout release it.
+    pub fn migrate_bounty(ctx: Context<MigrateBounty>) -> Result<()> {
+        let bounty_info = ctx.accounts.bounty.to_account_info();
+        let (version, bounty) = {
+            let data = bounty_info.try_borrow_data()?;
+            require!(
+                data.len() > 8 && data[..8] == Bounty::DISCRIMINATOR,
+                BountyError::UnknownAccountVersion
+            );
+            if data.len() == LEGACY_BOUNTY_SPACE {
+                let legacy = BountyV0::deserialize(&mut &data[8..])?;
+                (0, BountyV1::from(legacy).upgrade())
+            } else {
+                let version = data[8];
+                require!(version != BOUNTY_VERSION, BountyError::AlreadyMigrated);
+                require!(
+                    (1..BOUNTY_VERSION).contains(&version),
+                    BountyError::UnknownAccountVersion
+                );
+                (version, read_bounty(version, &mut &data[9..])?)
+            }
+        };
+
+        grow_account(
+            &bounty_info,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            BOUNTY_SPACE,
+        )?;
+        bounty.try_serialize(&mut &mut bounty_info.try_borrow_mut_data()?[..])?;
+
+        if version == 0
+            && matches!(
+                bounty.status,
+                BountyStatus::Claimed | BountyStatus::Delivered | BountyStatus::Disputed
+            )
+        {
+            let agent_profile = ctx
+                .accounts
+                .agent_profile
+                .as_ref()
+                .filter(|profile| profile.owner == bounty.assigned_agent)
+                .ok_or(BountyError::AgentProfileMismatch)?;
+            let authority_seeds = &[ESCROW_AUTHORITY_SEED, &[ctx.bumps.escrow_authority]];
+            agent_registry::cpi::record_claim(CpiContext::new_with_signer(
+                ctx.accounts.agent_registry_program.to_account_info(),
+                agent_registry::cpi::accounts::UpdateReputation {
+                    agent: agent_profile.to_account_info(),
+                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                },
+                &[&authority_seeds[..]],
+            ))?;
+        }
+
+        if version >= HISTORY_VERSION {
+            return Ok(());
+        }
+
+        // Status changes made before the migration were never recorded
+        let history_info = ctx.accounts.history.to_account_info();
+        create_pda(
+            &history_info,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            BOUNTY_HISTORY_SPACE,
+            &[b"history", bounty_info.key.as_ref(), &[ctx.bumps.history]],
+        )?;
+        let history = BountyHistory {
+            bounty: bounty_info.key(),
+            bump: ctx.bumps.history,
+            total: 0,
+            entries: Vec::new(),
+        };
+        history.try_serialize(&mut &mut history_info.try_borrow_mut_data()?[..])?;
+        Ok(())
+    }
+}
+
+/// Returns the agent owner wallet an agent-side action is attributed to. Without a
+/// delegate account the signer must own `agent_profile`; with one, the signer must be
+/// a delegate of `agent_profile` holding `permission`.
+fn resolve_agent_wallet<'info>(
+    signer: &Signer<'info>,
+    agent_profile: &Account<'info, Agent>,
+    agent_delegate: &Option<Account<'info, AgentDelegate>>,
+    permission: u8,
+) -> Result<Pubkey> {
+    require!(agent_profile.is_active(), BountyError::AgentTransferred);
+    match agent_delegate {
+        None => require!(
+            agent_profile.owner == signer.key(),
+            BountyError::AgentProfileMismatch
+        ),
+        Some(delegate) => require!(
+            delegate.agent == agent_profile.key()
+                && delegate.delegate == signer.key()
+                && delegate.has_permission(permission),
+            BountyError::DelegateNotAuthorized
+        ),
+    }
+    Ok(agent_profile.owner)
+}
+
+/// Fails unless `agent_profile` is the current registry profile of `agent`, the wallet
+/// a bounty was assigned to: either its own profile or, after owner transfers, the one
+/// reached by following the `successor` of each tombstoned profile in `tombstones`,
+/// oldest first.
+fn check_agent_profile<'info>(
+    agent: &Pubkey,
+    agent_profile: &Account<'info, Agent>,
+    tombstones: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    let mut successor = None;
+    for info in tombstones {
+        let tombstone = Account::<Agent>::try_from(info)?;
+        require!(
+            !tombstone.is_active()
+                && successor.map_or(tombstone.owner == *agent, |key| key == tombstone.key()),
+            BountyError::AgentProfileMismatch
+        );
+        successor = Some(tombstone.successor);
+    }
+    require!(agent_profile.is_active(), BountyError::AgentTransferred);
+    require!(
+        successor.map_or(agent_profile.owner == *agent, |key| key == agent_profile.key()),
+        BountyError::AgentProfileMismatch
+    );
+    Ok(())
+}
+
+/// Fails unless `attestation` is a current attestation of `requirement.skill_id` for
+/// `agent_profile`, issued by the still-active verifier named in `requirement`.
+fn check_attestation<'info>(
+    requirement: &AttestationRequirement,
+    agent_profile: &Account<'info, Agent>,
+    attestation: &Option<Account<'info, Attestation>>,
+    verifier: &Option<Account<'info, Verifier>>,
+) -> Result<()> {
+    let (Some(attestation), Some(verifier)) = (attestation, verifier) else {
+        return err!(BountyError::AttestationRequired);
+    };
+    require!(
+        attestation.agent == agent_profile.key()
+            && attestation.skill_id == requirement.skill_id
+            && attestation.verifier == requirement.verifier
+            && attestation.is_current(Clock::get()?.unix_timestamp),
+        BountyError::AttestationRequired
+    );
+    require!(
+        verifier.authority == requirement.verifier && verifier.active,
+        BountyError::AttestationRequired
+    );
+    Ok(())
+}
+
+/// Fails unless `validation` is the named validator's passing response for this
+/// bounty's submitted deliverable.
+fn check_validation(
+    requirement: &ValidationRequirement,
+    bounty: &Account<Bounty>,
+    validation: &Validation,
+) -> Result<()> {
+    require!(
+        validation.bounty == bounty.key()
+            && validation.validator == requirement.validator
+            && validation.data_hash == bounty.deliverable_hash
+            && validation.passes(requirement.min_score),
+        BountyError::ValidationRequired
+    );
+    Ok(())
+}
+
+/// PDA-signed transfer of `amount` out of the vault of a bounty or contract.
+fn transfer_from_vault<'info>(
+    token_program: &Program<'info, Token>,
+    vault: &Account<'info, TokenAccount>,
+    to: AccountInfo<'info>,
+    escrow_key: Pubkey,
+    vault_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[b"vault".as_ref(), escrow_key.as_ref(), &[vault_bump]];
+    let cpi_accounts = Transfer {
+        from: vault.to_account_info(),
+        to,
+        authority: vault.to_account_info(),
+    };
+    token::transfer(
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, &[&seeds[..]]),
+        amount,
+    )
+}
+
+/// Closes an emptied vault of a bounty or contract, sending its rent to `destination`.
+fn close_vault<'info>(
+    token_program: &Program<'info, Token>,
+    vault: &Account<'info, TokenAccount>,
+    destination: AccountInfo<'info>,
+    escrow_key: Pubkey,
+    vault_bump: u8,
+) -> Result<()> {
+    let seeds = &[b"vault".as_ref(), escrow_key.as_ref(), &[vault_bump]];
+    let close_accounts = anchor_spl::token::CloseAccount {
+        account: vault.to_account_info(),
+        destination,
+        authority: vault.to_account_info(),
+    };
+    anchor_spl::token::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        close_accounts,
+        &[&seeds[..]],
+    ))
+}
+
+/// Tells agent-registry a claimed bounty has been paid out.
+fn release_agent_claim<'info>(
+    agent_registry_program: &Program<'info, AgentRegistry>,
+    agent_profile: &Account<'info, Agent>,
+    escrow_authority: &UncheckedAccount<'info>,
+    escrow_authority_bump: u8,
+) -> Result<()> {
+    let authority_seeds = &[ESCROW_AUTHORITY_SEED, &[escrow_authority_bump]];
+    agent_registry::cpi::release_claim(CpiContext::new_with_signer(
+        agent_registry_program.to_account_info(),
+        agent_registry::cpi::accounts::UpdateReputation {
+            agent: agent_profile.to_account_info(),
+            authority: escrow_authority.to_account_info(),
+        },
+        &[&authority_seeds[..]],
+    ))
+}
+
+/// Checks a direct-hire allowlist (no duplicates, no default key).
+fn check_allowlist(allowlist: &[Pubkey]) -> Result<()> {
+    require!(allowlist.len() <= MAX_ALLOWLIST, BountyError::InvalidAllowlist);
+    require!(
+        !allowlist.contains(&Pubkey::default())
+            && allowlist
+                .iter()
+                .enumerate()
+                .all(|(i, agent)| !allowlist[..i].contains(agent)),
+        BountyError::InvalidAllowlist
+    );
+    Ok(())
+}
+
+/// Accounts for paying out a bounty net of the platform fee.
+struct BountyPayout<'a, 'info> {
+    config: &'a Config,
+    bounty: &'a Account<'info, Bounty>,
+    vault: &'a Account<'info, TokenAccount>,
+    vault_bump: u8,
+    client_state: &'a mut Account<'info, ClientState>,
+    treasury_token_account: Option<&'a Account<'info, TokenAccount>>,
+    agent_referrer_token_account: Option<&'a Account<'info, TokenAccount>>,
+    client_referrer_token_account: Option<&'a Account<'info, TokenAccount>>,
+    token_program: &'a Program<'info, Token>,
+    agent_registry_program: &'a Program<'info, AgentRegistry>,
+    escrow_authority: &'a UncheckedAccount<'info>,
+    escrow_authority_bump: u8,
+}
+
+impl<'info> BountyPayout<'_, 'info> {
+    /// Pays `amount` out of the vault to the agent minus the platform fee,
+    /// credited to its registry earnings.
+    fn pay(
+        mut self,
+        amount: u64,
+        agent_token_account: &Account<'info, TokenAccount>,
+        agent_profile: &Account<'info, Agent>,
+    ) -> Result<()> {
+        let net = self.take_fee(amount, Some(agent_profile))?;
+        self.transfer(agent_token_account.to_account_info(), net)?;
+        add_agent_earnings(
+            self.agent_registry_program,
+            agent_profile,
+            self.escrow_authority,
+            self.escrow_authority_bump,
+            net,
+        )
+    }
+
+    /// Takes the platform fee on a payout of `amount` out of the vault and returns
+    /// what is left for the payee. Referrers of the agent and client get their
+    /// share of the fee while eligible and the treasury keeps the rest; guild
+    /// payouts pass no `agent_profile`, so only the client's referrer is rewarded.
+    fn take_fee(&mut self, amount: u64, agent_profile: Option<&Account<'info, Agent>>) -> Result<u64> {
+        let now = Clock::get()?.unix_timestamp;
+        let fee = self.config.platform_fee(amount)?;
+        let agent_reward = match agent_profile {
+            Some(profile) => self.config.referral_reward(
+                fee,
+                profile.referrer,
+                profile.created_at,
+                profile.referral_paid,
+                now,
+            )?,
+            None => 0,
+        };
+        let client_reward = self.config.referral_reward(
+            fee,
+            self.client_state.referrer,
+            self.client_state.created_at,
+            self.client_state.referral_paid,
+            now,
+        )?;
+
+        if let Some(profile) = agent_profile.filter(|_| agent_reward > 0) {
+            let to = self.token_account_of(
+                self.agent_referrer_token_account,
+                profile.referrer,
+                BountyError::ReferrerAccountRequired,
+            )?;
+            self.transfer(to, agent_reward)?;
+            let authority_seeds = &[ESCROW_AUTHORITY_SEED, &[self.escrow_authority_bump]];
+            agent_registry::cpi::record_referral_reward(
+                CpiContext::new_with_signer(
+                    self.agent_registry_program.to_account_info(),
+                    agent_registry::cpi::accounts::UpdateReputation {
+                        agent: profile.to_account_info(),
+                        authority: self.escrow_authority.to_account_info(),
+                    },
+                    &[&authority_seeds[..]],
+                ),
+                agent_reward,
+            )?;
+        }
+        if client_reward > 0 {
+            let to = self.token_account_of(
+                self.client_referrer_token_account,
+                self.client_state.referrer,
+                BountyError::ReferrerAccountRequired,
+            )?;
+            self.transfer(to, client_reward)?;
+            self.client_state.referral_paid += client_reward;
+        }
+        let treasury_cut = fee - agent_reward - client_reward;
+        if treasury_cut > 0 {
+            let to = self.token_account_of(
+                self.treasury_token_account,
+                self.config.treasury,
+                BountyError::TreasuryAccountRequired,
+            )?;
+            self.transfer(to, treasury_cut)?;
+        }
+        Ok(amount - fee)
+    }
+
+    fn transfer(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        transfer_from_vault(
+            self.token_program,
+            self.vault,
+            to,
+            self.bounty.key(),
+            self.vault_bump,
+            amount,
+        )
+    }
+
+    /// Checks an optional payee token account belongs to `owner` and holds the bounty mint.
+    fn token_account_of(
+        &self,
+        token_account: Option<&Account<'info, TokenAccount>>,
+        owner: Pubkey,
+        missing: BountyError,
+    ) -> Result<AccountInfo<'info>> {
+        let token_account = token_account.ok_or(missing)?;
+        require!(
+            token_account.owner == owner && token_account.mint == self.bounty.usdc_mint,
+            BountyError::InvalidPayeeAccount
+        );
+        Ok(token_account.to_account_info())
+    }
+}
+
+/// Credits `amount` paid out by the escrow to the agent's registry earnings.
+fn add_agent_earnings<'info>(
+    agent_registry_program: &Program<'info, AgentRegistry>,
+    agent_profile: &Account<'info, Agent>,
+    escrow_authority: &UncheckedAccount<'info>,
+    escrow_authority_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let authority_seeds = &[ESCROW_AUTHORITY_SEED, &[escrow_authority_bump]];
+    agent_registry::cpi::add_earnings(
+        CpiContext::new_with_signer(
+            agent_registry_program.to_account_info(),
+            agent_registry::cpi::accounts::UpdateReputation {
+                agent: agent_profile.to_account_info(),
+                authority: escrow_authority.to_account_info(),
+            },
+            &[&authority_seeds[..]],
+        ),
+        amount,
+    )
+}
+
+/// Applies a client's `rating` to the reputation of the agent profile `agent`.
+fn update_agent_reputation<'info>(
+    agent_registry_program: &Program<'info, AgentRegistry>,
+    agent: AccountInfo<'info>,
+    escrow_authority: &UncheckedAccount<'info>,
+    escrow_authority_bump: u8,
+    rating: u64,
+) -> Result<()> {
+    let authority_seeds = &[ESCROW_AUTHORITY_SEED, &[escrow_authority_bump]];
+    agent_registry::cpi::update_reputation(
+        CpiContext::new_with_signer(
+            agent_registry_program.to_account_info(),
+            agent_registry::cpi::accounts::UpdateReputation {
+                agent,
+                authority: escrow_authority.to_account_info(),
+            },
+            &[&authority_seeds[..]],
+        ),
+        rating,
+    )
+}
+
+/// Accounts for splitting a guild bounty payout among the guild's members.
+struct GuildPayout<'a, 'info> {
+    bounty: &'a Account<'info, Bounty>,
+    vault: &'a Account<'info, TokenAccount>,
+    vault_bump: u8,
+    guild: &'a Account<'info, Guild>,
+    token_program: &'a Program<'info, Token>,
+    agent_registry_program: &'a Program<'info, AgentRegistry>,
+    escrow_authority: &'a UncheckedAccount<'info>,
+    escrow_authority_bump: u8,
+}
+
+impl<'info> GuildPayout<'_, 'info> {
+    /// Pays `amount` out of the vault split by the members' revenue shares, the
+    /// founder taking the rounding remainder, credits each member's earnings and
+    /// settles the guild's claim. `members` are `[agent_profile, token_account]`
+    /// pairs in guild order.
+    fn pay(self, amount: u64, members: &'info [AccountInfo<'info>]) -> Result<()> {
+        let guild_members = &self.guild.members;
+        require!(
+            members.len() == guild_members.len() * 2,
+            BountyError::GuildMembersMismatch
+        );
+        let mut shares: Vec<u64> = guild_members
+            .iter()
+            .map(|member| {
+                ((amount as u128) * (member.share_bps as u128) / (GUILD_SHARE_TOTAL as u128)) as u64
+            })
+            .collect();
+        shares[0] += amount - shares.iter().sum::<u64>();
+
+        for ((member, pair), share) in guild_members
+            .iter()
+            .zip(members.chunks(2))
+            .zip(shares)
+        {
+            let agent_profile = Account::<Agent>::try_from(&pair[0])?;
+            let token_account = Account::<TokenAccount>::try_from(&pair[1])?;
+            require!(
+                agent_profile.key() == member.agent
+                    && token_account.owner == agent_profile.owner
+                    && token_account.mint == self.bounty.usdc_mint,
+                BountyError::GuildMembersMismatch
+            );
+            if share == 0 {
+                continue;
+            }
+            transfer_from_vault(
+                self.token_program,
+                self.vault,
+                pair[1].clone(),
+                self.bounty.key(),
+                self.vault_bump,
+                share,
+            )?;
+            add_agent_earnings(
+                self.agent_registry_program,
+                &agent_profile,
+                self.escrow_authority,
+                self.escrow_authority_bump,
+                share,
+            )?;
+        }
+
+        let authority_seeds = &[ESCROW_AUTHORITY_SEED, &[self.escrow_authority_bump]];
+        agent_registry::cpi::settle_guild_claim(
+            CpiContext::new_with_signer(
+                self.agent_registry_program.to_account_info(),
+                agent_registry::cpi::accounts::UpdateGuild {
+                    guild: self.guild.to_account_info(),
+                    authority: self.escrow_authority.to_account_info(),
+                },
+                &[&authority_seeds[..]],
+            ),
+            amount,
+        )
+    }
+}
+
+/// Accounts for cancelling the payout-funded subcontracts of a bounty that were
+/// never funded, once its dispute is resolved and the reserved budget goes back
+/// to the client.
+struct UnfundedSubcontracts<'a, 'info> {
+    parent: &'a Account<'info, Bounty>,
+    agent: Option<&'a UncheckedAccount<'info>>,
+    token_program: &'a Program<'info, Token>,
+    agent_registry_program: &'a Program<'info, AgentRegistry>,
+    escrow_authority: &'a UncheckedAccount<'info>,
+    escrow_authority_bump: u8,
+    actor: Pubkey,
+}
+
+impl<'info> UnfundedSubcontracts<'_, 'info> {
+    /// Cancels each listed child, closing its empty vault to the agent that created
+    /// it, and returns their total budget. `children` hold one group per child:
+    /// `[child, child_history, child_vault]`, followed by its index pages while it
+    /// is open, or by the agent profile (or guild) it is assigned to, whose claim is
+    /// released.
+    fn cancel(self, mut children: &'info [AccountInfo<'info>]) -> Result<u64> {
+        let mut freed = 0u64;
+        while !children.is_empty() {
+            require!(children.len() >= 3, BountyError::UnfundedSubcontractsMismatch);
+            let mut child = Account::<Bounty>::try_from(&children[0])?;
+            let mut history = Account::<BountyHistory>::try_from(&children[1])?;
+            let vault = Account::<TokenAccount>::try_from(&children[2])?;
+            let child_key = child.key();
+            require!(
+                child.parent == self.parent.key()
+                    && child.awaiting_parent_funds
+                    && history.bounty == child_key
+                    && vault.key() == child.vault,
+                BountyError::UnfundedSubcontractsMismatch
+            );
+            let extra = if child.status == BountyStatus::Open {
+                child.index_pages.len()
+            } else {
+                1
+            };
+            require!(
+                children.len() >= 3 + extra,
+                BountyError::UnfundedSubcontractsMismatch
+            );
+            let (extra, rest) = children[3..].split_at(extra);
+
+            if child.status == BountyStatus::Open {
+                child.remove_from_index(child_key, extra)?;
+            } else if child.guild == Pubkey::default() {
+                let agent_profile = Account::<Agent>::try_from(&extra[0])?;
+                require!(
+                    agent_profile.owner == child.assigned_agent,
+                    BountyError::AgentProfileMismatch
+                );
+                release_agent_claim(
+                    self.agent_registry_program,
+                    &agent_profile,
+                    self.escrow_authority,
+                    self.escrow_authority_bump,
+                )?;
+            } else {
+                let guild = Account::<Guild>::try_from(&extra[0])?;
+                require!(guild.key() == child.guild, BountyError::NotGuildBounty);
+                let authority_seeds = &[ESCROW_AUTHORITY_SEED, &[self.escrow_authority_bump]];
+                agent_registry::cpi::settle_guild_claim(
+                    CpiContext::new_with_signer(
+                        self.agent_registry_program.to_account_info(),
+                        agent_registry::cpi::accounts::UpdateGuild {
+                            guild: guild.to_account_info(),
+                            authority: self.escrow_authority.to_account_info(),
+                        },
+                        &[&authority_seeds[..]],
+                    ),
+                    0,
+                )?;
+            }
+
+            let agent = self.agent.ok_or(BountyError::UnfundedSubcontractsMismatch)?;
+            let (_, vault_bump) =
+                Pubkey::find_program_address(&[b"vault", child_key.as_ref()], &crate::ID);
+            close_vault(
+                self.token_program,
+                &vault,
+                agent.to_account_info(),
+                child_key,
+                vault_bump,
+            )?;
+
+            child.awaiting_parent_funds = false;
+            child.transition(&mut history, BountyStatus::Cancelled, self.actor)?;
+            child.exit(&crate::ID)?;
+            history.exit(&crate::ID)?;
+            freed += child.budget;
+            children = rest;
+        }
+        Ok(freed)
+    }
+}
+
+/// Validation a deliverable must pass before the bounty pays out.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct ValidationRequirement {
+    /// Key that must respond to the agent-registry validation request
+    pub validator: Pubkey,
+    /// Minimum passing score (1-100)
+    pub min_score: u8,
+    /// If set, anyone may trigger payout once validation passes, without the client
+    pub auto_approve: bool,
+}
+
+/// Verifier attestation an agent must hold to claim a bounty.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct AttestationRequirement {
+    /// Authority of the agent-registry Verifier that must have issued it
+    pub verifier: Pubkey,
+    /// SkillRegistry id that must be attested
+    pub skill_id: u8,
+}
+
+/// What happens when every invited agent has declined a direct-hire offer. Stored
+/// as its one-byte Borsh variant index, like the `u8` it replaced.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeclinePolicy {
+    /// Refund the budget to the client and cancel the bounty
+    Refund = 0,
+    /// Open the bounty to every agent
+    Reopen = 1,
+}
+
+/// Program-wide admin settings, seeded by `["config"]`.
+#[account]
+pub struct Config {
+    /// Authority allowed to change pause flags
+    pub admin: Pubkey,
+    /// Bitmask of PAUSE_* flags
+    pub paused: u64,
+    /// PDA bump
+    pub bump: u8,
+    /// Platform fee on bounty payouts, in basis points
+    pub fee_bps: u16,
+    /// Wallet whose token account receives platform fees
+    pub treasury: Pubkey,
+    /// Share of the fee paid to each eligible referrer, in basis points
+    pub referral_share_bps: u16,
+    /// Lifetime referral rewards per referred account (0 = uncapped)
+    pub referral_cap: u64,
+    /// Seconds after the referred account's creation that rewards are paid (0 = forever)
+    pub referral_duration: i64,
+}
+
+impl Config {
+    pub fn is_paused(&self, flag: u64) -> bool {
+        self.paused & flag != 0
+    }
+
+    /// Platform fee owed on a payout of `amount`.
+    pub fn platform_fee(&self, amount: u64) -> Result<u64> {
+        bps_of(amount, self.fee_bps)
+    }
+
+    /// Referral reward owed out of `fee` to `referrer`, or 0 once the referral
+    /// has expired or reached its cap.
+    pub fn referral_reward(
+        &self,
+        fee: u64,
+        referrer: Pubkey,
+        referred_at: i64,
+        paid: u64,
+        now: i64,
+    ) -> Result<u64> {
+        if referrer == Pubkey::default()
+            || (self.referral_duration > 0
+                && now >= referred_at.saturating_add(self.referral_duration))
+        {
+            return Ok(0);
+        }
+        let reward = bps_of(fee, self.referral_share_bps)?;
+        if self.referral_cap == 0 {
+            Ok(reward)
+        } else {
+            Ok(reward.min(self.referral_cap.saturating_sub(paid)))
+        }
+    }
+}
+
+/// `bps` basis points of `amount`, computed in u128 so the product cannot overflow.
+fn bps_of(amount: u64, bps: u16) -> Result<u64> {
+    let share = (amount as u128) * (bps as u128) / (BPS_DENOMINATOR as u128);
+    u64::try_from(share).map_err(|_| error!(BountyError::InvalidFeeConfig))
+}
+
+#[account]
+pub struct ClientState {
+    pub owner: Pubkey,
+    pub bounty_count: u64,
+    pub bump: u8,
+    /// Wallet that referred the client (default if none); rewarded from platform fees
+    pub referrer: Pubkey,
+    /// Total referral rewards paid to `referrer` on this client's bounties
+    pub referral_paid: u64,
+    /// Creation timestamp (start of the referral window)
+    pub created_at: i64,
+}
+
+/// ClientState layout before referrals; only read by `migrate_client_state`.
+#[derive(AnchorDeserialize)]
+struct ClientStateV0 {
+    owner: Pubkey,
+    bounty_count: u64,
+    bump: u8,
+}
+
+#[account]
+pub struct Bounty {
+    /// Layout version (BOUNTY_VERSION)
+    pub version: u8,
+    /// Client who posted the bounty
+    pub client: Pubkey,
+    /// Sequential bounty ID for this client
+    pub bounty_id: u64,
+    /// Off-chain metadata URI (title, description, requirements, skills)
+    pub metadata_uri: String,
+    /// SHA-256 of the bounty metadata JSON
+    pub metadata_hash: [u8; 32],
+    /// Budget in USDC minor units (6 decimals)
+    pub budget: u64,
+    /// Unix timestamp deadline
+    pub deadline: i64,
+    /// Lifecycle stage; only changed through `Bounty::transition`
+    pub status: BountyStatus,
+    /// Number of claim attempts
+    pub claims: u64,
+    /// Pubkey of assigned agent (default if unassigned)
+    pub assigned_agent: Pubkey,
+    /// URI of submitted deliverable
+    pub deliverable_uri: String,
+    /// SHA-256 of the deliverable content (zero until submitted)
+    pub deliverable_hash: [u8; 32],
+    /// Vault token account address
+    pub vault: Pubkey,
+    /// USDC mint address
+    pub usdc_mint: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+    /// Creation timestamp
+    pub created_at: i64,
+    /// Bitmap of agent-registry SkillRegistry ids the work needs
+    pub skills: u128,
+    /// If set, only agents sharing at least one skill with the bounty can claim it
+    pub require_skill_match: bool,
+    /// If set, claiming agents must hold this verifier attestation
+    pub attestation_requirement: Option<AttestationRequirement>,
+    /// If set, payout requires a passing validation of the deliverable
+    pub validation_requirement: Option<ValidationRequirement>,
+    /// Agent wallets invited to a direct-hire bounty (empty = public)
+    pub allowlist: Vec<Pubkey>,
+    /// Applied when the last invitee declines
+    pub decline_policy: DeclinePolicy,
+    /// Recurring template this bounty is a cycle of (default for one-off bounties)
+    pub recurring: Pubkey,
+    /// Total tipped to the agent on top of the budget
+    pub tips_total: u64,
+    /// Bounty this one subcontracts part of (default for top-level bounties)
+    pub parent: Pubkey,
+    /// Number of subcontracts created from this bounty
+    pub child_count: u64,
+    /// Part of the budget held back from the agent's payout to fund subcontracts
+    pub reserved_for_children: u64,
+    /// Subcontract whose budget is still waiting on the parent's payout
+    pub awaiting_parent_funds: bool,
+    /// Guild that claimed the bounty as a unit (default for solo claims)
+    pub guild: Pubkey,
+    /// When the bounty was claimed (0 if never)
+    pub claimed_at: i64,
+    /// When work was last submitted (0 if never)
+    pub delivered_at: i64,
+    /// When the payout was approved (0 if never)
+    pub completed_at: i64,
+    /// When the bounty was disputed (0 if never)
+    pub disputed_at: i64,
+    /// When the bounty was cancelled (0 if never)
+    pub cancelled_at: i64,
+    /// Signer of the latest status change (the creator until the first one)
+    pub last_actor: Pubkey,
+    /// Page of each `index_buckets(skills)` bucket the bounty is listed on while
+    /// open; empty once it leaves Open or if it predates the index
+    pub index_pages: Vec<u32>,
+    /// Part of the budget `resolve_dispute` awarded the agent (0 unless a dispute
+    /// was resolved in the agent's favour)
+    pub dispute_payout: u64,
+}
+
+/// Bounty layout before versioning; only read by `migrate_bounty`.
+#[derive(AnchorDeserialize)]
+struct BountyV0 {
+    client: Pubkey,
+    bounty_id: u64,
+    metadata_uri: String,
+    budget: u64,
+    deadline: i64,
+    status: BountyStatus,
+    claims: u64,
+    assigned_agent: Pubkey,
+    deliverable_uri: String,
+    vault: Pubkey,
+    usdc_mint: Pubkey,
+    bump: u8,
+    created_at: i64,
+}
+
+/// Fields every versioned Bounty layout starts with (version 1); only read by
+/// `migrate_bounty`.
+#[derive(AnchorDeserialize)]
+struct BountyV1 {
+    client: Pubkey,
+    bounty_id: u64,
+    metadata_uri: String,
+    metadata_hash: [u8; 32],
+    budget: u64,
+    deadline: i64,
+    status: BountyStatus,
+    claims: u64,
+    assigned_agent: Pubkey,
+    deliverable_uri: String,
+    deliverable_hash: [u8; 32],
+    vault: Pubkey,
+    usdc_mint: Pubkey,
+    bump: u8,
+    created_at: i64,
+}
+
+impl From<BountyV0> for BountyV1 {
+    fn from(legacy: BountyV0) -> Self {
+        Self {
+            client: legacy.client,
+            bounty_id: legacy.bounty_id,
+            metadata_uri: legacy.metadata_uri,
+            metadata_hash: [0u8; 32],
+            budget: legacy.budget,
+            deadline: legacy.deadline,
+            status: legacy.status,
+            claims: legacy.claims,
+            assigned_agent: legacy.assigned_agent,
+            deliverable_uri: legacy.deliverable_uri,
+            deliverable_hash: [0u8; 32],
+            vault: legacy.vault,
+            usdc_mint: legacy.usdc_mint,
+            bump: legacy.bump,
+            created_at: legacy.created_at,
+        }
+    }
+}
+
+impl BountyV1 {
+    /// The current layout holding these fields, with every later one at its default.
+    fn upgrade(self) -> Bounty {
+        Bounty {
+            version: BOUNTY_VERSION,
+            client: self.client,
+            bounty_id: self.bounty_id,
+            metadata_uri: self.metadata_uri,
+            metadata_hash: self.metadata_hash,
+            budget: self.budget,
+            deadline: self.deadline,
+            status: self.status,
+            claims: self.claims,
+            assigned_agent: self.assigned_agent,
+            deliverable_uri: self.deliverable_uri,
+            deliverable_hash: self.deliverable_hash,
+            vault: self.vault,
+            usdc_mint: self.usdc_mint,
+            bump: self.bump,
+            created_at: self.created_at,
+            skills: 0,
+            require_skill_match: false,
+            attestation_requirement: None,
+            validation_requirement: None,
+            allowlist: Vec::new(),
+            decline_policy: DeclinePolicy::Refund,
+            recurring: Pubkey::default(),
+            tips_total: 0,
+            parent: Pubkey::default(),
+            child_count: 0,
+            reserved_for_children: 0,
+            awaiting_parent_funds: false,
+            guild: Pubkey::default(),
+            claimed_at: 0,
+            delivered_at: 0,
+            completed_at: 0,
+            disputed_at: 0,
+            cancelled_at: 0,
+            last_actor: Pubkey::default(),
+            index_pages: Vec::new(),
+            dispute_payout: 0,
+        }
+    }
+}
+
+/// Reads a bounty written at layout `version` (after its version byte), reading the
+/// fields each later version appended in order.
+fn read_bounty(version: u8, data: &mut &[u8]) -> Result<Bounty> {
+    let mut bounty = BountyV1::deserialize(data)?.upgrade();
+    if version >= 2 {
+        bounty.skills = u128::deserialize(data)?;
+        bounty.require_skill_match = bool::deserialize(data)?;
+    }
+    if version >= 3 {
+        bounty.attestation_requirement = Option::deserialize(data)?;
+    }
+    if version >= 4 {
+        bounty.validation_requirement = Option::deserialize(data)?;
+    }
+    if version >= 5 {
+        bounty.allowlist = Vec::deserialize(data)?;
+        bounty.decline_policy = DeclinePolicy::deserialize(data)?;
+    }
+    if version >= 6 {
+        bounty.recurring = Pubkey::deserialize(data)?;
+    }
+    if version >= 7 {
+        bounty.tips_total = u64::deserialize(data)?;
+    }
+    if version >= 8 {
+        bounty.parent = Pubkey::deserialize(data)?;
+        bounty.child_count = u64::deserialize(data)?;
+        bounty.reserved_for_children = u64::deserialize(data)?;
+        bounty.awaiting_parent_funds = bool::deserialize(data)?;
+    }
+    if version >= 9 {
+        bounty.guild = Pubkey::deserialize(data)?;
+    }
+    if version >= HISTORY_VERSION {
+        bounty.claimed_at = i64::deserialize(data)?;
+        bounty.delivered_at = i64::deserialize(data)?;
+        bounty.completed_at = i64::deserialize(data)?;
+        bounty.disputed_at = i64::deserialize(data)?;
+        bounty.cancelled_at = i64::deserialize(data)?;
+        bounty.last_actor = Pubkey::deserialize(data)?;
+    }
+    if version >= 11 {
+        bounty.index_pages = Vec::deserialize(data)?;
+    }
+    Ok(bounty)
+}
+
+/// Reallocs `account` to `new_len`, topping up rent from `payer` first.
+fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let rent_due = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if rent_due > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    account.realloc(new_len, true)?;
+    Ok(())
+}
+
+/// Creates a program-owned PDA of `space` bytes, as `init` would, for handlers that
+/// must run their own checks first. Lamports already sent to the address are kept.
+fn create_pda<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    space: usize,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let signer_seeds = &[seeds];
+    if account.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+                signer_seeds,
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+    let rent_due = rent.saturating_sub(account.lamports());
+    if rent_due > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Allocate {
+                account_to_allocate: account.clone(),
+            },
+            signer_seeds,
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Assign {
+                account_to_assign: account.clone(),
+            },
+            signer_seeds,
+        ),
+        &crate::ID,
+    )
+}
+
+#[account]
+pub struct Review {
+    /// The bounty this review is for
+    pub bounty: Pubkey,
+    /// Client who left the review
+    pub reviewer: Pubkey,
+    /// Agent being reviewed
+    pub agent: Pubkey,
+    /// Rating (fixed-point * 100, e.g. 450 = 4.50 stars)
+    pub rating: u64,
+    /// Off-chain comment URI
+    pub comment_uri: String,
+    /// SHA-256 of the comment content
+    pub comment_hash: [u8; 32],
+    /// PDA bump
+    pub bump: u8,
+    /// Creation timestamp
+    pub created_at: i64,
+}
+
+impl Review {
+    fn record(
+        &mut self,
+        bounty: &Account<Bounty>,
+        reviewer: Pubkey,
+        rating: u64,
+        comment_uri: String,
+        comment_hash: [u8; 32],
+        bump: u8,
+    ) -> Result<()> {
+        self.bounty = bounty.key();
+        self.reviewer = reviewer;
+        self.agent = bounty.assigned_agent;
+        self.rating = rating;
+        self.comment_uri = comment_uri;
+        self.comment_hash = comment_hash;
+        self.bump = bump;
+        self.created_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+}
+
+/// Lifecycle of an `HourlyContract`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HourlyContractStatus {
+    Active = 0,
+    Closed = 1,
+}
+
+/// Review state of a `TimeLog`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeLogStatus {
+    Pending = 0,
+    Approved = 1,
+    Rejected = 2,
+}
+
+/// Hourly engagement between a client and an agent, seeded by
+/// `["hourly", client, contract_id]`. Funds sit in a `["vault", contract]` token account.
+#[account]
+pub struct HourlyContract {
+    /// Client funding the contract
+    pub client: Pubkey,
+    /// Agent owner wallet receiving payment
+    pub agent: Pubkey,
+    /// Agent-registry profile of the agent
+    pub agent_profile: Pubkey,
+    /// Client-chosen contract ID
+    pub contract_id: u64,
+    /// USDC minor units per hour, copied from the agent profile at creation
+    pub hourly_rate: u64,
+    /// Total funded into the vault
+    pub cap: u64,
+    /// Total paid out for approved logs
+    pub released: u64,
+    /// Whether logs can still be submitted and paid
+    pub status: HourlyContractStatus,
+    /// Number of time logs submitted (next log index)
+    pub log_count: u64,
+    /// Time logs awaiting approval or rejection
+    pub pending_logs: u64,
+    /// Vault token account address
+    pub vault: Pubkey,
+    /// USDC mint address
+    pub usdc_mint: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+    /// Creation timestamp
+    pub created_at: i64,
+}
+
+impl HourlyContract {
+    /// Payment owed for `hours_x100` (fixed-point * 100) at the contract rate.
+    pub fn amount_for(&self, hours_x100: u64) -> Result<u64> {
+        let amount = (hours_x100 as u128) * (self.hourly_rate as u128) / 100;
+        u64::try_from(amount).map_err(|_| error!(BountyError::ExceedsCap))
+    }
+}
+
+/// Hours an agent logged for one period, seeded by `["timelog", contract, index]`.
+#[account]
+pub struct TimeLog {
+    /// Contract the log belongs to
+    pub contract: Pubkey,
+    /// Sequential log index within the contract
+    pub index: u64,
+    /// Start of the logged period (unix timestamp)
+    pub period_start: i64,
+    /// End of the logged period (unix timestamp)
+    pub period_end: i64,
+    /// Hours worked (fixed-point * 100)
+    pub hours_x100: u64,
+    /// Payment for the log at the contract rate
+    pub amount: u64,
+    /// Off-chain evidence URI (activity logs, commits, reports)
+    pub evidence_uri: String,
+    /// SHA-256 of the evidence content
+    pub evidence_hash: [u8; 32],
+    /// Review outcome of the log
+    pub status: TimeLogStatus,
+    /// Submission timestamp
+    pub submitted_at: i64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Lifecycle of a `Stream`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StreamStatus {
+    Active = 0,
+    Completed = 1,
+    Cancelled = 2,
+}
+
+/// Linear-vesting payment stream from a client to an agent, seeded by
+/// `["stream", client, stream_id]`. Funds sit in a `["vault", stream]` token account.
+#[account]
+pub struct Stream {
+    /// Client funding the stream
+    pub client: Pubkey,
+    /// Agent owner wallet receiving payment
+    pub agent: Pubkey,
+    /// Agent-registry profile of the agent
+    pub agent_profile: Pubkey,
+    /// Client-chosen stream ID
+    pub stream_id: u64,
+    /// Total amount streamed over the schedule
+    pub total: u64,
+    /// Amount paid to the agent so far
+    pub withdrawn: u64,
+    /// Vesting start (unix timestamp)
+    pub start_ts: i64,
+    /// Vesting end (unix timestamp); everything is vested from here on
+    pub end_ts: i64,
+    /// Whether the stream is still vesting
+    pub status: StreamStatus,
+    /// Vault token account address
+    pub vault: Pubkey,
+    /// USDC mint address
+    pub usdc_mint: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+    /// Creation timestamp
+    pub created_at: i64,
+}
+
+impl Stream {
+    /// Amount vested at `now`, rounding down.
+    pub fn vested_at(&self, now: i64) -> u64 {
+        if now <= self.start_ts {
+            return 0;
+        }
+        if now >= self.end_ts {
+            return self.total;
+        }
+        let elapsed = (now - self.start_ts) as u128;
+        let duration = (self.end_ts - self.start_ts) as u128;
+        ((self.total as u128) * elapsed / duration) as u64
+    }
+}
+
+/// Lifecycle of a `RecurringBounty`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecurringStatus {
+    Active = 0,
+    Cancelled = 1,
+}
+
+/// Template for a bounty that reopens every period, seeded by
+/// `["recurring", client, template_id]`. Unopened periods are held in a
+/// `["vault", recurring]` token account.
+#[account]
+pub struct RecurringBounty {
+    /// Client funding the cycles
+    pub client: Pubkey,
+    /// Client-chosen template ID
+    pub template_id: u64,
+    /// Off-chain metadata URI copied into every cycle
+    pub metadata_uri: String,
+    /// SHA-256 of the metadata JSON
+    pub metadata_hash: [u8; 32],
+    /// Budget of each cycle in USDC minor units
+    pub budget_per_period: u64,
+    /// Seconds between cycles; also each cycle's time to deadline
+    pub period_secs: i64,
+    /// Number of cycles funded
+    pub total_periods: u64,
+    /// Number of cycles opened so far
+    pub opened_periods: u64,
+    /// Earliest time the next cycle can be opened
+    pub next_open_at: i64,
+    /// Skill bitmap copied into every cycle
+    pub skills: u128,
+    /// Skill-match flag copied into every cycle
+    pub require_skill_match: bool,
+    /// Invited agents copied into every cycle (one agent = retainer)
+    pub allowlist: Vec<Pubkey>,
+    /// Decline policy copied into every cycle
+    pub decline_policy: DeclinePolicy,
+    /// Whether cycles can still be opened
+    pub status: RecurringStatus,
+    /// Bounty opened for the most recent cycle (default before the first)
+    pub last_cycle: Pubkey,
+    /// Vault token account address
+    pub vault: Pubkey,
+    /// USDC mint address
+    pub usdc_mint: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+    /// Creation timestamp
+    pub created_at: i64,
+}
+
+// ─── Instruction Contexts ───────────────────────────────────────────
+
+#[derive(Accounts)]
+pub struct InitConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = CONFIG_SPACE,
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::BountyEscrow>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ BountyError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ BountyError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitClient<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = client,
+        space = CLIENT_STATE_SPACE,
+        seeds = [b"client", client.key().as_ref()],
+        bump,
+    )]
+    pub client_state: Account<'info, ClientState>,
+    #[account(mut)]
+    pub client: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateBounty<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"client", client.key().as_ref()],
+        bump = client_state.bump,
+        constraint = client_state.owner == client.key() @ BountyError::Unauthorized,
+    )]
+    pub client_state: Account<'info, ClientState>,
+
+    #[account(
+        init,
+        payer = client,
+        space = BOUNTY_SPACE,
+        seeds = [b"bounty", client.key().as_ref(), &client_state.bounty_count.to_le_bytes()],
+        bump,
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(
+        init,
+        payer = client,
+        space = BOUNTY_HISTORY_SPACE,
+        seeds = [b"history", bounty.key().as_ref()],
+        bump,
+    )]
+    pub history: Account<'info, BountyHistory>,
+
+    #[account(
+        seeds = [b"skills"],
+        bump = skill_registry.bump,
+        seeds::program = agent_registry::ID,
+    )]
+    pub skill_registry: Account<'info, SkillRegistry>,
+
+    #[account(
+        init,
+        payer = client,
+        token::mint = usdc_mint,
+        token::authority = vault,
+        seeds = [b"vault", bounty.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    /// Client's USDC token account
+    #[account(
+        mut,
+        constraint = client_token_account.owner == client.key(),
+        constraint = client_token_account.mint == usdc_mint.key(),
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+
+    pub usdc_mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimBounty<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(
+        mut,
+        seeds = [b"history", bounty.key().as_ref()],
+        bump = history.bump,
+    )]
+    pub history: Account<'info, BountyHistory>,
+    /// Agent owner wallet, or a delegate when `agent_delegate` is supplied
+    pub agent: Signer<'info>,
+    /// Registry profile of the claiming agent
+    #[account(mut)]
+    pub agent_profile: Account<'info, Agent>,
+    /// Delegate record authorizing `agent` (delegate path only)
+    pub agent_delegate: Option<Account<'info, AgentDelegate>>,
+    /// Agent's attestation (bounties with an attestation requirement only)
+    pub attestation: Option<Account<'info, Attestation>>,
+    /// Issuer of `attestation`
+    pub verifier: Option<Account<'info, Verifier>>,
+    /// CHECK: PDA signer for registry CPIs
+    #[account(seeds = [ESCROW_AUTHORITY_SEED], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+    pub agent_registry_program: Program<'info, AgentRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitWork<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(
+        mut,
+        seeds = [b"history", bounty.key().as_ref()],
+        bump = history.bump,
+    )]
+    pub history: Account<'info, BountyHistory>,
+    /// Agent owner wallet, or a delegate when `agent_delegate` is supplied
+    pub agent: Signer<'info>,
+    /// Registry profile of the assigned agent
+    pub agent_profile: Account<'info, Agent>,
+    /// Delegate record authorizing `agent` (delegate path only)
+    pub agent_delegate: Option<Account<'info, AgentDelegate>>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveWork<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        has_one = client,
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(
+        mut,
+        seeds = [b"history", bounty.key().as_ref()],
+        bump = history.bump,
+    )]
+    pub history: Account<'info, BountyHistory>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", bounty.key().as_ref()],
+        bump,
+        constraint = vault.key() == bounty.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Agent's USDC token account to receive payment
+    #[account(
+        mut,
+        constraint = agent_token_account.owner == bounty.assigned_agent,
+        constraint = agent_token_account.mint == bounty.usdc_mint,
+    )]
+    pub agent_token_account: Account<'info, TokenAccount>,
+
+    /// Registry profile of the assigned agent
+    #[account(
+        mut,
+        constraint = agent_profile.owner == bounty.assigned_agent @ BountyError::AgentProfileMismatch,
+    )]
+    pub agent_profile: Account<'info, Agent>,
+
+    /// Validation of the deliverable (bounties with a validation requirement only)
+    pub validation: Option<Account<'info, Validation>>,
+
+    /// Client state of the bounty's client, for its referrer
+    #[account(
+        mut,
+        seeds = [b"client", bounty.client.as_ref()],
+        bump = client_state.bump,
+    )]
+    pub client_state: Account<'info, ClientState>,
+
+    /// Treasury token account (required when a platform fee is due)
+    #[account(mut)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Token account of the agent's referrer (required while a reward is due)
+    #[account(mut)]
+    pub agent_referrer_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Token account of the client's referrer (required while a reward is due)
+    #[account(mut)]
+    pub client_referrer_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: PDA signer for registry CPIs
+    #[account(seeds = [ESCROW_AUTHORITY_SEED], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    pub client: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub agent_registry_program: Program<'info, AgentRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct AutoApprove<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(
+        mut,
+        seeds = [b"history", bounty.key().as_ref()],
+        bump = history.bump,
+    )]
+    pub history: Account<'info, BountyHistory>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", bounty.key().as_ref()],
+        bump,
+        constraint = vault.key() == bounty.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Agent's USDC token account to receive payment
+    #[account(
+        mut,
+        constraint = agent_token_account.owner == bounty.assigned_agent,
+        constraint = agent_token_account.mint == bounty.usdc_mint,
+    )]
+    pub agent_token_account: Account<'info, TokenAccount>,
+
+    /// Registry profile of the assigned agent
+    #[account(
+        mut,
+        constraint = agent_profile.owner == bounty.assigned_agent @ BountyError::AgentProfileMismatch,
+    )]
+    pub agent_profile: Account<'info, Agent>,
+
+    /// Passing validation of the deliverable
+    pub validation: Account<'info, Validation>,
+
+    /// Client state of the bounty's client, for its referrer
+    #[account(
+        mut,
+        seeds = [b"client", bounty.client.as_ref()],
+        bump = client_state.bump,
+    )]
+    pub client_state: Account<'info, ClientState>,
+
+    /// Treasury token account (required when a platform fee is due)
+    #[account(mut)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Token account of the agent's referrer (required while a reward is due)
+    #[account(mut)]
+    pub agent_referrer_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Token account of the client's referrer (required while a reward is due)
+    #[account(mut)]
+    pub client_referrer_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: PDA signer for registry CPIs
+    #[account(seeds = [ESCROW_AUTHORITY_SEED], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub agent_registry_program: Program<'info, AgentRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeBounty<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(
+        mut,
+        seeds = [b"history", bounty.key().as_ref()],
+        bump = history.bump,
+    )]
+    pub history: Account<'info, BountyHistory>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ BountyError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(
+        mut,
+        seeds = [b"history", bounty.key().as_ref()],
+        bump = history.bump,
+    )]
+    pub history: Account<'info, BountyHistory>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", bounty.key().as_ref()],
+        bump,
+        constraint = vault.key() == bounty.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Client's USDC token account for the refunded part
+    #[account(
+        mut,
+        constraint = client_token_account.owner == bounty.client,
+        constraint = client_token_account.mint == bounty.usdc_mint,
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: receives the vault's rent when the bounty is cancelled
+    #[account(mut, address = bounty.client)]
+    pub client: UncheckedAccount<'info>,
+
+    /// Agent's USDC token account (solo bounties paying the agent)
+    #[account(mut)]
+    pub agent_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Registry profile of the assigned agent (solo bounties)
+    #[account(mut)]
+    pub agent_profile: Option<Account<'info, Agent>>,
+
+    /// CHECK: the assigned agent's wallet, which receives the vault rent of the
+    /// unfunded subcontracts it created (required when there are any)
+    #[account(mut, address = bounty.assigned_agent)]
+    pub agent: Option<UncheckedAccount<'info>>,
+
+    /// Guild the bounty is assigned to (guild bounties)
+    #[account(mut)]
+    pub guild: Option<Account<'info, Guild>>,
+
+    /// Client state of the bounty's client, for its referrer
+    #[account(
+        mut,
+        seeds = [b"client", bounty.client.as_ref()],
+        bump = client_state.bump,
+    )]
+    pub client_state: Account<'info, ClientState>,
+
+    /// Treasury token account (required when a platform fee is due)
+    #[account(mut)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Token account of the agent's referrer (required while a reward is due)
+    #[account(mut)]
+    pub agent_referrer_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Token account of the client's referrer (required while a reward is due)
+    #[account(mut)]
+    pub client_referrer_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: PDA signer for registry CPIs
+    #[account(seeds = [ESCROW_AUTHORITY_SEED], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    pub admin: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub agent_registry_program: Program<'info, AgentRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveReview<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        has_one = client,
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(
+        init,
+        payer = client,
+        space = REVIEW_SPACE,
+        seeds = [b"review", bounty.key().as_ref()],
+        bump,
+    )]
+    pub review: Account<'info, Review>,
+
+    /// Current profile of the assigned agent; checked in the handler
+    #[account(mut)]
+    pub agent_profile: Account<'info, Agent>,
+
+    /// CHECK: PDA signer for registry CPIs
+    #[account(seeds = [ESCROW_AUTHORITY_SEED], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub client: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub agent_registry_program: Program<'info, AgentRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct CancelBounty<'info> {
+    #[account(
+        mut,
+        has_one = client,
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(
+        mut,
+        seeds = [b"history", bounty.key().as_ref()],
+        bump = history.bump,
+    )]
+    pub history: Account<'info, BountyHistory>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", bounty.key().as_ref()],
+        bump,
+        constraint = vault.key() == bounty.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Client's USDC token account for refund
+    #[account(
+        mut,
+        constraint = client_token_account.owner == client.key(),
+        constraint = client_token_account.mint == bounty.usdc_mint,
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+
+    /// Parent of a subcontract still awaiting its payout-funded budget
+    #[account(mut)]
+    pub parent_bounty: Option<Account<'info, Bounty>>,
+
+    #[account(mut)]
+    pub client: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct DeclineOffer<'info> {
+    #[account(mut)]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(
+        mut,
+        seeds = [b"history", bounty.key().as_ref()],
+        bump = history.bump,
+    )]
+    pub history: Account<'info, BountyHistory>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", bounty.key().as_ref()],
+        bump,
+        constraint = vault.key() == bounty.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Client's USDC token account, refunded under the Refund policy
+    #[account(
+        mut,
+        constraint = client_token_account.owner == bounty.client,
+        constraint = client_token_account.mint == bounty.usdc_mint,
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: receives the vault rent under the Refund policy; must be the bounty client
+    #[account(mut, address = bounty.client)]
+    pub client: UncheckedAccount<'info>,
+
+    /// Invited agent wallet, or a delegate when `agent_delegate` is supplied
+    pub agent: Signer<'info>,
+    /// Registry profile of the invited agent
+    pub agent_profile: Account<'info, Agent>,
+    /// Delegate record authorizing `agent` (delegate path only)
+    pub agent_delegate: Option<Account<'info, AgentDelegate>>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(contract_id: u64)]
+pub struct CreateHourlyContract<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = client,
+        space = HOURLY_CONTRACT_SPACE,
+        seeds = [b"hourly", client.key().as_ref(), &contract_id.to_le_bytes()],
+        bump,
+    )]
+    pub contract: Account<'info, HourlyContract>,
+
+    #[account(
+        init,
+        payer = client,
+        token::mint = usdc_mint,
+        token::authority = vault,
+        seeds = [b"vault", contract.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Registry profile of the agent being hired
+    #[account(mut, constraint = agent_profile.is_active() @ BountyError::AgentTransferred)]
+    pub agent_profile: Account<'info, Agent>,
+
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    /// Client's USDC token account
+    #[account(
+        mut,
+        constraint = client_token_account.owner == client.key(),
+        constraint = client_token_account.mint == usdc_mint.key(),
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA signer for registry CPIs
+    #[account(seeds = [ESCROW_AUTHORITY_SEED], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+    pub agent_registry_program: Program<'info, AgentRegistry>,
+    pub usdc_mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitTimeLog<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub contract: Account<'info, HourlyContract>,
+    #[account(
+        init,
+        payer = agent,
+        space = TIME_LOG_SPACE,
+        seeds = [b"timelog", contract.key().as_ref(), &contract.log_count.to_le_bytes()],
+        bump,
+    )]
+    pub time_log: Account<'info, TimeLog>,
+    /// Latest log on the contract (required once it has one)
+    #[account(
+        seeds = [
+            b"timelog",
+            contract.key().as_ref(),
+            &contract.log_count.saturating_sub(1).to_le_bytes(),
+        ],
+        bump = previous_log.bump,
+    )]
+    pub previous_log: Option<Account<'info, TimeLog>>,
+    /// Agent owner wallet, or a delegate when `agent_delegate` is supplied
+    #[account(mut)]
+    pub agent: Signer<'info>,
+    /// Registry profile of the contracted agent
+    #[account(address = contract.agent_profile @ BountyError::AgentProfileMismatch)]
+    pub agent_profile: Account<'info, Agent>,
+    /// Delegate record authorizing `agent` (delegate path only)
+    pub agent_delegate: Option<Account<'info, AgentDelegate>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveTimeLog<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut, has_one = client, has_one = agent_profile)]
+    pub contract: Account<'info, HourlyContract>,
+    #[account(
+        mut,
+        seeds = [b"timelog", contract.key().as_ref(), &time_log.index.to_le_bytes()],
+        bump = time_log.bump,
+    )]
+    pub time_log: Account<'info, TimeLog>,
+    #[account(
+        mut,
+        seeds = [b"vault", contract.key().as_ref()],
+        bump,
+        constraint = vault.key() == contract.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    /// Agent's USDC token account to receive payment
+    #[account(
+        mut,
+        constraint = agent_token_account.owner == contract.agent,
+        constraint = agent_token_account.mint == contract.usdc_mint,
+    )]
+    pub agent_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub agent_profile: Account<'info, Agent>,
+    pub client: Signer<'info>,
+    /// CHECK: PDA signer for registry CPIs
+    #[account(seeds = [ESCROW_AUTHORITY_SEED], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+    pub agent_registry_program: Program<'info, AgentRegistry>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RejectTimeLog<'info> {
+    #[account(mut, has_one = client)]
+    pub contract: Account<'info, HourlyContract>,
+    #[account(
+        mut,
+        seeds = [b"timelog", contract.key().as_ref(), &time_log.index.to_le_bytes()],
+        bump = time_log.bump,
+    )]
+    pub time_log: Account<'info, TimeLog>,
+    pub client: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseHourlyContract<'info> {
+    #[account(mut, has_one = agent_profile)]
+    pub contract: Account<'info, HourlyContract>,
+    #[account(
+        mut,
+        seeds = [b"vault", contract.key().as_ref()],
+        bump,
+        constraint = vault.key() == contract.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    /// Client's USDC token account for the refund
+    #[account(
+        mut,
+        constraint = client_token_account.owner == contract.client,
+        constraint = client_token_account.mint == contract.usdc_mint,
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+    /// CHECK: receives the vault rent; must be the contract client
+    #[account(mut, address = contract.client)]
+    pub client: UncheckedAccount<'info>,
+    /// Client or contracted agent
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub agent_profile: Account<'info, Agent>,
+    /// CHECK: PDA signer for registry CPIs
+    #[account(seeds = [ESCROW_AUTHORITY_SEED], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+    pub agent_registry_program: Program<'info, AgentRegistry>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(stream_id: u64)]
+pub struct CreateStream<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = client,
+        space = STREAM_SPACE,
+        seeds = [b"stream", client.key().as_ref(), &stream_id.to_le_bytes()],
+        bump,
+    )]
+    pub stream: Account<'info, Stream>,
+
+    #[account(
+        init,
+        payer = client,
+        token::mint = usdc_mint,
+        token::authority = vault,
+        seeds = [b"vault", stream.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Registry profile of the agent being paid
+    #[account(mut)]
+    pub agent_profile: Account<'info, Agent>,
+
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    /// Client's USDC token account
+    #[account(
+        mut,
+        constraint = client_token_account.owner == client.key(),
+        constraint = client_token_account.mint == usdc_mint.key(),
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA signer for registry CPIs
+    #[account(seeds = [ESCROW_AUTHORITY_SEED], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+    pub agent_registry_program: Program<'info, AgentRegistry>,
+    pub usdc_mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawVested<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut, has_one = agent, has_one = agent_profile)]
+    pub stream: Account<'info, Stream>,
+    #[account(
+        mut,
+        seeds = [b"vault", stream.key().as_ref()],
+        bump,
+        constraint = vault.key() == stream.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    /// Agent's USDC token account to receive payment
+    #[account(
+        mut,
+        constraint = agent_token_account.owner == stream.agent,
+        constraint = agent_token_account.mint == stream.usdc_mint,
+    )]
+    pub agent_token_account: Account<'info, TokenAccount>,
+    pub agent: Signer<'info>,
+    #[account(mut)]
+    pub agent_profile: Account<'info, Agent>,
+    /// CHECK: receives the vault rent once the stream is finished; must be the stream client
+    #[account(mut, address = stream.client)]
+    pub client: UncheckedAccount<'info>,
+    /// CHECK: PDA signer for registry CPIs
+    #[account(seeds = [ESCROW_AUTHORITY_SEED], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+    pub agent_registry_program: Program<'info, AgentRegistry>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    #[account(mut, has_one = client, has_one = agent_profile)]
+    pub stream: Account<'info, Stream>,
+    #[account(
+        mut,
+        seeds = [b"vault", stream.key().as_ref()],
+        bump,
+        constraint = vault.key() == stream.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    /// Agent's USDC token account for the vested remainder
+    #[account(
+        mut,
+        constraint = agent_token_account.owner == stream.agent,
+        constraint = agent_token_account.mint == stream.usdc_mint,
+    )]
+    pub agent_token_account: Account<'info, TokenAccount>,
+    /// Client's USDC token account for the refund
+    #[account(
+        mut,
+        constraint = client_token_account.owner == client.key(),
+        constraint = client_token_account.mint == stream.usdc_mint,
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub client: Signer<'info>,
+    #[account(mut)]
+    pub agent_profile: Account<'info, Agent>,
+    /// CHECK: PDA signer for registry CPIs
+    #[account(seeds = [ESCROW_AUTHORITY_SEED], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+    pub agent_registry_program: Program<'info, AgentRegistry>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(template_id: u64)]
+pub struct CreateRecurringBounty<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = client,
+        space = RECURRING_SPACE,
+        seeds = [b"recurring", client.key().as_ref(), &template_id.to_le_bytes()],
+        bump,
+    )]
+    pub recurring: Account<'info, RecurringBounty>,
+
+    #[account(
+        seeds = [b"skills"],
+        bump = skill_registry.bump,
+        seeds::program = agent_registry::ID,
+    )]
+    pub skill_registry: Account<'info, SkillRegistry>,
+
+    #[account(
+        init,
+        payer = client,
+        token::mint = usdc_mint,
+        token::authority = vault,
+        seeds = [b"vault", recurring.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    /// Client's USDC token account
+    #[account(
+        mut,
+        constraint = client_token_account.owner == client.key(),
+        constraint = client_token_account.mint == usdc_mint.key(),
+    )]
     pub client_token_account: Account<'info, TokenAccount>,
 
     pub usdc_mint: Account<'info, Mint>,
@@ -373,36 +3994,107 @@ pub struct CreateBounty<'info> {
 }
 
 #[derive(Accounts)]
-pub struct ClaimBounty<'info> {
+pub struct OpenCycle<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
     #[account(mut)]
+    pub recurring: Account<'info, RecurringBounty>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", recurring.key().as_ref()],
+        bump,
+        constraint = template_vault.key() == recurring.vault,
+    )]
+    pub template_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"client", recurring.client.as_ref()],
+        bump = client_state.bump,
+    )]
+    pub client_state: Account<'info, ClientState>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = BOUNTY_SPACE,
+        seeds = [b"bounty", recurring.client.as_ref(), &client_state.bounty_count.to_le_bytes()],
+        bump,
+    )]
     pub bounty: Account<'info, Bounty>,
-    pub agent: Signer<'info>,
-}
 
-#[derive(Accounts)]
-pub struct SubmitWork<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = BOUNTY_HISTORY_SPACE,
+        seeds = [b"history", bounty.key().as_ref()],
+        bump,
+    )]
+    pub history: Account<'info, BountyHistory>,
+
+    #[account(
+        init,
+        payer = payer,
+        token::mint = usdc_mint,
+        token::authority = vault,
+        seeds = [b"vault", bounty.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(address = recurring.usdc_mint)]
+    pub usdc_mint: Account<'info, Mint>,
+
+    /// Anyone cranking the schedule; pays rent for the new cycle
     #[account(mut)]
-    pub bounty: Account<'info, Bounty>,
-    pub agent: Signer<'info>,
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct ApproveWork<'info> {
+pub struct CancelRecurring<'info> {
+    #[account(mut, has_one = client)]
+    pub recurring: Account<'info, RecurringBounty>,
     #[account(
         mut,
-        has_one = client,
+        seeds = [b"vault", recurring.key().as_ref()],
+        bump,
+        constraint = vault.key() == recurring.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    /// Client's USDC token account for the refund
+    #[account(
+        mut,
+        constraint = client_token_account.owner == client.key(),
+        constraint = client_token_account.mint == recurring.usdc_mint,
     )]
+    pub client_token_account: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub client: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct TipAgent<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut, has_one = client)]
     pub bounty: Account<'info, Bounty>,
 
+    /// Client's USDC token account the tip is paid from
     #[account(
         mut,
-        seeds = [b"vault", bounty.key().as_ref()],
-        bump,
-        constraint = vault.key() == bounty.vault,
+        constraint = client_token_account.owner == client.key(),
+        constraint = client_token_account.mint == bounty.usdc_mint,
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub client_token_account: Account<'info, TokenAccount>,
 
-    /// Agent's USDC token account to receive payment
+    /// Agent's USDC token account to receive the tip
     #[account(
         mut,
         constraint = agent_token_account.owner == bounty.assigned_agent,
@@ -410,46 +4102,199 @@ pub struct ApproveWork<'info> {
     )]
     pub agent_token_account: Account<'info, TokenAccount>,
 
+    /// Current registry profile of the assigned agent; checked in the handler, which
+    /// follows owner transfers
+    #[account(mut)]
+    pub agent_profile: Account<'info, Agent>,
+
+    /// CHECK: PDA signer for registry CPIs
+    #[account(seeds = [ESCROW_AUTHORITY_SEED], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+
     pub client: Signer<'info>,
     pub token_program: Program<'info, Token>,
+    pub agent_registry_program: Program<'info, AgentRegistry>,
 }
 
 #[derive(Accounts)]
-pub struct DisputeBounty<'info> {
-    #[account(mut)]
-    pub bounty: Account<'info, Bounty>,
-    pub authority: Signer<'info>,
-}
+pub struct CreateSubcontract<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
 
-#[derive(Accounts)]
-pub struct LeaveReview<'info> {
+    /// Bounty being subcontracted; the signer must be its assigned agent
     #[account(
-        has_one = client,
+        mut,
+        constraint = parent.assigned_agent == agent.key() @ BountyError::NotAssignedAgent,
+    )]
+    pub parent: Account<'info, Bounty>,
+
+    /// The agent's own client state, which numbers the child bounty
+    #[account(
+        mut,
+        seeds = [b"client", agent.key().as_ref()],
+        bump = client_state.bump,
+        constraint = client_state.owner == agent.key() @ BountyError::Unauthorized,
+    )]
+    pub client_state: Account<'info, ClientState>,
+
+    #[account(
+        init,
+        payer = agent,
+        space = BOUNTY_SPACE,
+        seeds = [b"bounty", agent.key().as_ref(), &client_state.bounty_count.to_le_bytes()],
+        bump,
     )]
     pub bounty: Account<'info, Bounty>,
 
     #[account(
         init,
-        payer = client,
-        space = REVIEW_SPACE,
-        seeds = [b"review", bounty.key().as_ref()],
+        payer = agent,
+        space = BOUNTY_HISTORY_SPACE,
+        seeds = [b"history", bounty.key().as_ref()],
         bump,
     )]
-    pub review: Account<'info, Review>,
+    pub history: Account<'info, BountyHistory>,
+
+    #[account(
+        seeds = [b"skills"],
+        bump = skill_registry.bump,
+        seeds::program = agent_registry::ID,
+    )]
+    pub skill_registry: Account<'info, SkillRegistry>,
+
+    #[account(
+        init,
+        payer = agent,
+        token::mint = usdc_mint,
+        token::authority = vault,
+        seeds = [b"vault", bounty.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
 
+    /// Assigned agent of the parent, acting as the child's client
     #[account(mut)]
-    pub client: Signer<'info>,
+    pub agent: Signer<'info>,
+
+    /// Agent's USDC token account (self-funded subcontracts only)
+    #[account(
+        mut,
+        constraint = agent_token_account.owner == agent.key(),
+        constraint = agent_token_account.mint == usdc_mint.key(),
+    )]
+    pub agent_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(address = parent.usdc_mint)]
+    pub usdc_mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct CancelBounty<'info> {
+pub struct FundSubcontract<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub parent: Account<'info, Bounty>,
+    #[account(
+        mut,
+        seeds = [b"vault", parent.key().as_ref()],
+        bump,
+        constraint = parent_vault.key() == parent.vault,
+    )]
+    pub parent_vault: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = child.parent == parent.key() @ BountyError::ParentMismatch,
+    )]
+    pub child: Account<'info, Bounty>,
+    #[account(
+        mut,
+        constraint = child_vault.key() == child.vault,
+    )]
+    pub child_vault: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimGuildBounty<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(
+        mut,
+        seeds = [b"history", bounty.key().as_ref()],
+        bump = history.bump,
+    )]
+    pub history: Account<'info, BountyHistory>,
+    #[account(mut)]
+    pub guild: Account<'info, Guild>,
+    /// Guild admin claiming on the guild's behalf
+    pub member: Signer<'info>,
+    /// Registry profile of `member`
+    #[account(
+        constraint = member_profile.owner == member.key() @ BountyError::AgentProfileMismatch,
+        constraint = member_profile.guild == guild.key() @ BountyError::NotGuildMember,
+    )]
+    pub member_profile: Account<'info, Agent>,
+    /// Member's attestation (bounties with an attestation requirement only)
+    pub attestation: Option<Account<'info, Attestation>>,
+    /// Issuer of `attestation`
+    pub verifier: Option<Account<'info, Verifier>>,
+    /// CHECK: PDA signer for registry CPIs
+    #[account(seeds = [ESCROW_AUTHORITY_SEED], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+    pub agent_registry_program: Program<'info, AgentRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitGuildWork<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        constraint = bounty.guild == guild.key() @ BountyError::NotGuildBounty,
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(
+        mut,
+        seeds = [b"history", bounty.key().as_ref()],
+        bump = history.bump,
+    )]
+    pub history: Account<'info, BountyHistory>,
+    pub guild: Account<'info, Guild>,
+    /// Any member of the guild
+    pub member: Signer<'info>,
+    /// Registry profile of `member`
+    #[account(
+        constraint = member_profile.owner == member.key() @ BountyError::AgentProfileMismatch,
+        constraint = member_profile.guild == guild.key() @ BountyError::NotGuildMember,
+    )]
+    pub member_profile: Account<'info, Agent>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveGuildWork<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
     #[account(
         mut,
         has_one = client,
+        constraint = bounty.guild == guild.key() @ BountyError::NotGuildBounty,
     )]
     pub bounty: Account<'info, Bounty>,
 
+    #[account(
+        mut,
+        seeds = [b"history", bounty.key().as_ref()],
+        bump = history.bump,
+    )]
+    pub history: Account<'info, BountyHistory>,
+
     #[account(
         mut,
         seeds = [b"vault", bounty.key().as_ref()],
@@ -458,17 +4303,116 @@ pub struct CancelBounty<'info> {
     )]
     pub vault: Account<'info, TokenAccount>,
 
-    /// Client's USDC token account for refund
+    #[account(mut)]
+    pub guild: Account<'info, Guild>,
+
+    /// Validation of the deliverable (bounties with a validation requirement only)
+    pub validation: Option<Account<'info, Validation>>,
+
+    /// Client state of the bounty's client, for its referrer
     #[account(
         mut,
-        constraint = client_token_account.owner == client.key(),
-        constraint = client_token_account.mint == bounty.usdc_mint,
+        seeds = [b"client", bounty.client.as_ref()],
+        bump = client_state.bump,
     )]
-    pub client_token_account: Account<'info, TokenAccount>,
+    pub client_state: Account<'info, ClientState>,
+
+    /// Treasury token account (required when a platform fee is due)
+    #[account(mut)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
 
+    /// Token account of the client's referrer (required while a reward is due)
     #[account(mut)]
+    pub client_referrer_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: PDA signer for registry CPIs
+    #[account(seeds = [ESCROW_AUTHORITY_SEED], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+
     pub client: Signer<'info>,
     pub token_program: Program<'info, Token>,
+    pub agent_registry_program: Program<'info, AgentRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveGuildReview<'info> {
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        has_one = client,
+        constraint = bounty.guild == guild.key() @ BountyError::NotGuildBounty,
+    )]
+    pub bounty: Account<'info, Bounty>,
+
+    #[account(
+        init,
+        payer = client,
+        space = REVIEW_SPACE,
+        seeds = [b"review", bounty.key().as_ref()],
+        bump,
+    )]
+    pub review: Account<'info, Review>,
+
+    #[account(mut)]
+    pub guild: Account<'info, Guild>,
+
+    /// CHECK: PDA signer for registry CPIs
+    #[account(seeds = [ESCROW_AUTHORITY_SEED], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub client: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub agent_registry_program: Program<'info, AgentRegistry>,
+}
+
+#[derive(Accounts)]
+#[instruction(status: BountyStatus, skill: u8, page: u32)]
+pub struct InitIndexPage<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = INDEX_PAGE_SPACE,
+        seeds = [b"index".as_ref(), &[status as u8], &[skill], &page.to_le_bytes()],
+        bump,
+    )]
+    pub index_page: Account<'info, BountyIndexPage>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateClientState<'info> {
+    /// CHECK: legacy layouts cannot be deserialized as `ClientState`; ownership is
+    /// checked here and the discriminator and length in the handler
+    #[account(mut, owner = crate::ID)]
+    pub client_state: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateBounty<'info> {
+    /// CHECK: older layouts cannot be deserialized as `Bounty`; ownership is checked
+    /// here and the discriminator and version in the handler
+    #[account(mut, owner = crate::ID)]
+    pub bounty: UncheckedAccount<'info>,
+    /// CHECK: created by the handler for bounties written before the history existed
+    #[account(mut, seeds = [b"history", bounty.key().as_ref()], bump)]
+    pub history: UncheckedAccount<'info>,
+    /// Migrated profile of the agent holding a claimed, delivered or disputed
+    /// legacy bounty, whose claim is recorded (required for those)
+    #[account(mut)]
+    pub agent_profile: Option<Account<'info, Agent>>,
+    /// CHECK: PDA signer for registry CPIs
+    #[account(seeds = [ESCROW_AUTHORITY_SEED], bump)]
+    pub escrow_authority: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub agent_registry_program: Program<'info, AgentRegistry>,
 }
 
 #[error_code]
@@ -495,4 +4439,116 @@ pub enum BountyError {
     InvalidRating,
     #[msg("Bounty is not in completed state")]
     NotCompleted,
+    #[msg("Signer is not an authorized delegate for this agent")]
+    DelegateNotAuthorized,
+    #[msg("Agent profile has been transferred to a new owner")]
+    AgentTransferred,
+    #[msg("Agent profile does not belong to this agent")]
+    AgentProfileMismatch,
+    #[msg("A non-zero content hash must accompany every URI")]
+    MissingContentHash,
+    #[msg("Account is already on the current layout")]
+    AlreadyMigrated,
+    #[msg("Account is not a bounty account of a known layout")]
+    UnknownAccountVersion,
+    #[msg("This instruction is paused by the admin")]
+    Paused,
+    #[msg("Skills bitmap references an undefined skill id")]
+    UnknownSkill,
+    #[msg("Agent has none of the skills this bounty requires")]
+    SkillMismatch,
+    #[msg("Skill matching requires at least one skill on the bounty")]
+    NoRequiredSkills,
+    #[msg("A current attestation from the required verifier is needed to claim")]
+    AttestationRequired,
+    #[msg("Minimum validation score must be between 1 and 100")]
+    InvalidMinScore,
+    #[msg("A passing validation of the deliverable from the required validator is needed")]
+    ValidationRequired,
+    #[msg("Bounty does not allow validation-based auto-approval")]
+    AutoApproveDisabled,
+    #[msg("Allowlist must hold at most 5 distinct, non-default agents")]
+    InvalidAllowlist,
+    /// No longer raised: decline policies are decoded as `DeclinePolicy`, so unknown
+    /// ones fail to deserialize
+    #[msg("Decline policy must be 0 (Refund) or 1 (Reopen)")]
+    InvalidDeclinePolicy,
+    #[msg("Agent is not invited to this bounty")]
+    NotInvited,
+    #[msg("Time log needs positive hours and a period that ends after it starts")]
+    InvalidTimeLog,
+    #[msg("Hourly contract is closed")]
+    ContractClosed,
+    #[msg("Payment would exceed the contract's remaining cap")]
+    ExceedsCap,
+    #[msg("Time log is not pending")]
+    TimeLogNotPending,
+    #[msg("Approve or reject pending time logs before closing the contract")]
+    PendingTimeLogs,
+    #[msg("Stream must start now or later and end after it starts")]
+    InvalidStreamSchedule,
+    #[msg("Stream is not active")]
+    StreamNotActive,
+    #[msg("Nothing has vested since the last withdrawal")]
+    NothingVested,
+    #[msg("Recurring bounty needs a positive period and at least one period")]
+    InvalidRecurrence,
+    #[msg("Recurring bounty is cancelled")]
+    RecurringNotActive,
+    #[msg("All funded periods have been opened")]
+    NoPeriodsLeft,
+    #[msg("Next cycle is not due yet")]
+    CycleNotDue,
+    #[msg("Tip amount must be greater than zero")]
+    InvalidTip,
+    #[msg("Subcontracts funded from the payout would exceed the parent budget")]
+    ExceedsParentBudget,
+    #[msg("A token account is required to fund the subcontract")]
+    MissingTokenAccount,
+    #[msg("Bounty is not the parent of this subcontract")]
+    ParentMismatch,
+    #[msg("Subcontract budget has not been funded from the parent yet")]
+    SubcontractNotFunded,
+    #[msg("Subcontract is already funded")]
+    SubcontractAlreadyFunded,
+    #[msg("Parent is completed; fund the subcontract before cancelling it")]
+    FundSubcontractFirst,
+    #[msg("Bounty was not claimed by this guild")]
+    NotGuildBounty,
+    #[msg("Agent is not a member of this guild")]
+    NotGuildMember,
+    #[msg("Signer is not an admin of this guild")]
+    NotGuildAdmin,
+    #[msg("Remaining accounts must match the guild's members in order")]
+    GuildMembersMismatch,
+    #[msg("Fee above maximum, referral share above maximum, negative duration or missing treasury")]
+    InvalidFeeConfig,
+    #[msg("A client cannot refer itself")]
+    InvalidReferrer,
+    #[msg("Treasury token account is required while a platform fee is set")]
+    TreasuryAccountRequired,
+    #[msg("Referrer token account is required while a referral reward is due")]
+    ReferrerAccountRequired,
+    #[msg("Payee token account has the wrong owner or mint")]
+    InvalidPayeeAccount,
+    #[msg("Bounty cannot move to that status from its current one")]
+    InvalidStatusTransition,
+    #[msg("Bounty requires more skills than it can be indexed under")]
+    TooManySkills,
+    #[msg("Index page is full; list the bounty on the next page")]
+    IndexPageFull,
+    #[msg("Remaining accounts must be the bounty's index pages in bucket order")]
+    IndexPagesMismatch,
+    #[msg("Only open bounties are indexed")]
+    StatusNotIndexed,
+    #[msg("Bounty is not disputed")]
+    NotDisputed,
+    #[msg("Dispute payout exceeds the bounty budget")]
+    InvalidResolution,
+    #[msg("The contract's previous time log must be supplied")]
+    PreviousTimeLogRequired,
+    #[msg("Time log period overlaps the previous log")]
+    TimeLogOverlap,
+    #[msg("Remaining accounts must list every subcontract still awaiting the bounty's funds")]
+    UnfundedSubcontractsMismatch,
 }
@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+
+use crate::{Bounty, BountyError, MAX_HISTORY};
+
+/// Lifecycle of a bounty. Stored as its one-byte Borsh variant index, so the
+/// on-chain layout is the same as the `u8` it replaced.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BountyStatus {
+    /// Funded and waiting for an agent
+    Open = 0,
+    /// Assigned to an agent (or guild) who is doing the work
+    Claimed = 1,
+    /// Deliverable submitted, waiting for the client
+    Delivered = 2,
+    /// Approved and paid out
+    Completed = 3,
+    /// Frozen by the client or agent until the admin resolves it
+    Disputed = 4,
+    /// Refunded to the client, before anyone was assigned or by a dispute resolution.
+    /// An unfunded subcontract is also cancelled when its parent's dispute is resolved.
+    Cancelled = 5,
+}
+
+impl BountyStatus {
+    /// Whether an instruction may move a bounty from `self` to `next`:
+    ///
+    /// ```text
+    /// Open ──claim──▶ Claimed ──submit──▶ Delivered ──approve──▶ Completed
+    ///  │                 │                    │                      ▲
+    ///  └─cancel/decline  └──────dispute───────┴──▶ Disputed ─resolve─┤
+    ///       ▼                                                        ▼
+    ///   Cancelled ◀──────────────────────────────────────────────────┘
+    /// ```
+    ///
+    /// A payout-funded subcontract still waiting for its budget may also go from
+    /// `Claimed` or `Delivered` to `Cancelled`, when its parent's dispute is resolved.
+    pub fn can_transition_to(self, next: BountyStatus) -> bool {
+        use BountyStatus::*;
+        matches!(
+            (self, next),
+            (Open, Claimed)
+                | (Open, Cancelled)
+                | (Claimed, Delivered)
+                | (Claimed, Disputed)
+                | (Claimed, Cancelled)
+                | (Delivered, Completed)
+                | (Delivered, Disputed)
+                | (Delivered, Cancelled)
+                | (Disputed, Completed)
+                | (Disputed, Cancelled)
+        )
+    }
+
+    /// Completed and cancelled bounties never change again.
+    pub fn is_terminal(self) -> bool {
+        matches!(self, BountyStatus::Completed | BountyStatus::Cancelled)
+    }
+}
+
+impl Bounty {
+    /// Moves the bounty to `next`, rejecting any step outside the lifecycle, and
+    /// records who made the move and when on the bounty and in its `history`.
+    /// Handlers still check the status they expect first so callers get the
+    /// specific error (`NotOpen`, `NotClaimed`, ...).
+    pub fn transition(
+        &mut self,
+        history: &mut BountyHistory,
+        next: BountyStatus,
+        actor: Pubkey,
+    ) -> Result<()> {
+        require!(
+            self.status.can_transition_to(next),
+            BountyError::InvalidStatusTransition
+        );
+        let now = Clock::get()?.unix_timestamp;
+        match next {
+            BountyStatus::Open => {}
+            BountyStatus::Claimed => self.claimed_at = now,
+            BountyStatus::Delivered => self.delivered_at = now,
+            BountyStatus::Completed => self.completed_at = now,
+            BountyStatus::Disputed => self.disputed_at = now,
+            BountyStatus::Cancelled => self.cancelled_at = now,
+        }
+        self.status = next;
+        self.last_actor = actor;
+        history.push(next, actor, now);
+        Ok(())
+    }
+}
+
+/// One status change in a `BountyHistory`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StatusChange {
+    /// Status the bounty moved to
+    pub status: BountyStatus,
+    /// Signer that made the change (the validator for `auto_approve`)
+    pub actor: Pubkey,
+    /// Unix timestamp of the change
+    pub at: i64,
+}
+
+/// Audit trail of a bounty's status changes, seeded by `["history", bounty]`.
+/// Keeps the latest MAX_HISTORY entries; `total` counts every change ever made,
+/// so readers can tell when older ones were dropped.
+#[account]
+pub struct BountyHistory {
+    /// The bounty this history belongs to
+    pub bounty: Pubkey,
+    /// PDA bump
+    pub bump: u8,
+    /// Number of changes recorded, including dropped ones
+    pub total: u32,
+    /// Most recent changes, oldest first
+    pub entries: Vec<StatusChange>,
+}
+
+impl BountyHistory {
+    /// Starts the history of a bounty created by `actor` at `created_at`.
+    pub fn open(&mut self, bounty: Pubkey, bump: u8, actor: Pubkey, created_at: i64) {
+        self.bounty = bounty;
+        self.bump = bump;
+        self.total = 0;
+        self.entries = Vec::with_capacity(MAX_HISTORY);
+        self.push(BountyStatus::Open, actor, created_at);
+    }
+
+    fn push(&mut self, status: BountyStatus, actor: Pubkey, at: i64) {
+        if self.entries.len() == MAX_HISTORY {
+            self.entries.remove(0);
+        }
+        self.entries.push(StatusChange { status, actor, at });
+        self.total += 1;
+    }
+}
@@ -0,0 +1,621 @@
+//! Integration test harness for agent-registry and bounty-escrow.
+//!
+//! Both programs run natively inside `solana-program-test`, next to the SPL token
+//! programs it bundles, so the suite needs no validator or network. [`Env::new`]
+//! starts a bank with both configs, a skill registry and a USDC mint in place;
+//! the test files under `tests/` drive it through the `clawedwork-client` builders.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::account_info::AccountInfo;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction, InstructionError};
+use anchor_lang::solana_program::{program_pack::Pack, system_instruction};
+use anchor_lang::{AccountDeserialize, AccountSerialize};
+use anchor_spl::associated_token::{
+    get_associated_token_address, spl_associated_token_account::instruction as ata_instruction,
+};
+use anchor_spl::token::{spl_token, TokenAccount};
+use clawedwork_client::{escrow, pda, registry};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+use agent_registry::instruction as registry_ix;
+use bounty_escrow::instruction as escrow_ix;
+use bounty_escrow::{index_buckets, BountyStatus, DeclinePolicy, INDEX_PAGE_CAPACITY};
+use clawedwork_client::accounts;
+
+/// Skills defined in every test bank, by id.
+pub const SKILLS: [&str; 3] = ["rust", "solana", "design"];
+pub const SKILL_RUST: u128 = 1 << 0;
+pub const SKILL_SOLANA: u128 = 1 << 1;
+pub const SKILL_DESIGN: u128 = 1 << 2;
+
+/// USDC balance minted to every funded wallet.
+pub const STARTING_USDC: u64 = 1_000_000_000;
+/// SOL balance of every funded wallet.
+const STARTING_LAMPORTS: u64 = 10_000_000_000;
+const USDC_DECIMALS: u8 = 6;
+
+pub const HASH: [u8; 32] = [7u8; 32];
+pub const URI: &str = "ipfs://metadata";
+
+pub type TxResult = Result<(), BanksClientError>;
+
+// ─── Program entrypoints ────────────────────────────────────────────
+
+// `processor!` hands out accounts with an anonymous lifetime while Anchor's entry
+// wants them to outlive the call; leaking the slice is fine for a test process.
+fn agent_registry_entry(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    agent_registry::entry(program_id, accounts, data)
+}
+
+fn bounty_escrow_entry(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    bounty_escrow::entry(program_id, accounts, data)
+}
+
+// ─── Environment ────────────────────────────────────────────────────
+
+/// A wallet with SOL and a USDC token account.
+pub struct User {
+    pub keypair: Keypair,
+    /// Associated USDC token account
+    pub token: Pubkey,
+}
+
+impl User {
+    pub fn pubkey(&self) -> Pubkey {
+        self.keypair.pubkey()
+    }
+
+    /// Agent-registry profile PDA owned by this wallet.
+    pub fn agent(&self) -> Pubkey {
+        pda::agent(&self.pubkey())
+    }
+}
+
+pub struct Env {
+    pub ctx: ProgramTestContext,
+    /// Admin of both configs and mint authority of the USDC mint
+    pub admin: Keypair,
+    pub mint: Pubkey,
+    /// Bumped per transaction so repeated identical transactions stay distinct
+    nonce: u64,
+}
+
+impl Env {
+    pub async fn new() -> Self {
+        Self::with_accounts(Vec::new()).await
+    }
+
+    /// Starts a bank with `accounts` preloaded, for state the programs can no
+    /// longer create themselves (legacy layouts).
+    pub async fn with_accounts(accounts: Vec<(Pubkey, Account)>) -> Self {
+        let admin = Keypair::new();
+        let mut program_test = ProgramTest::default();
+        program_test.prefer_bpf(false);
+        program_test.add_program(
+            "agent_registry",
+            agent_registry::ID,
+            processor!(agent_registry_entry),
+        );
+        program_test.add_program(
+            "bounty_escrow",
+            bounty_escrow::ID,
+            processor!(bounty_escrow_entry),
+        );
+
+        // `init_config` requires the upgradeable loader's ProgramData, which native
+        // test programs don't have, so the configs are written directly.
+        let (registry_config, bump) =
+            Pubkey::find_program_address(&[b"config"], &agent_registry::ID);
+        program_test.add_account(
+            registry_config,
+            program_account(
+                &agent_registry::ID,
+                8 + 32 + 8 + 1,
+                &agent_registry::Config {
+                    admin: admin.pubkey(),
+                    paused: 0,
+                    bump,
+                },
+            ),
+        );
+        let (escrow_config, bump) = Pubkey::find_program_address(&[b"config"], &bounty_escrow::ID);
+        program_test.add_account(
+            escrow_config,
+            program_account(
+                &bounty_escrow::ID,
+                8 + 32 + 8 + 1 + 2 + 32 + 2 + 8 + 8,
+                &bounty_escrow::Config {
+                    admin: admin.pubkey(),
+                    paused: 0,
+                    bump,
+                    fee_bps: 0,
+                    treasury: Pubkey::default(),
+                    referral_share_bps: 0,
+                    referral_cap: 0,
+                    referral_duration: 0,
+                },
+            ),
+        );
+        program_test.add_account(
+            admin.pubkey(),
+            Account::new(STARTING_LAMPORTS, 0, &solana_sdk::system_program::ID),
+        );
+        for (address, account) in accounts {
+            program_test.add_account(address, account);
+        }
+
+        let ctx = program_test.start_with_context().await;
+        let mut env = Env {
+            ctx,
+            admin,
+            mint: Pubkey::default(),
+            nonce: 0,
+        };
+
+        let admin_key = env.admin.pubkey();
+        let mut ixs = vec![registry::init_skill_registry(&admin_key)];
+        ixs.extend(
+            SKILLS
+                .iter()
+                .map(|name| registry::add_skill(&admin_key, name.to_string())),
+        );
+        env.send_as_admin(&ixs).await.unwrap();
+
+        let mint = Keypair::new();
+        let rent = env.ctx.banks_client.get_rent().await.unwrap();
+        let ixs = [
+            system_instruction::create_account(
+                &env.ctx.payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint2(
+                &spl_token::ID,
+                &mint.pubkey(),
+                &admin_key,
+                None,
+                USDC_DECIMALS,
+            )
+            .unwrap(),
+        ];
+        env.send(&ixs, &[&mint]).await.unwrap();
+        env.mint = mint.pubkey();
+        env
+    }
+
+    /// Starts a bank holding an agent profile tombstoned by an owner transfer (successor
+    /// set, account kept), and returns its funded owner.
+    pub async fn with_tombstone() -> (Self, User) {
+        let owner = Keypair::new();
+        let (agent, bump) =
+            Pubkey::find_program_address(&[b"agent", owner.pubkey().as_ref()], &agent_registry::ID);
+        let tombstone = accounts::Agent {
+            version: agent_registry::AGENT_VERSION,
+            owner: owner.pubkey(),
+            metadata_uri: URI.to_string(),
+            metadata_hash: HASH,
+            hourly_rate: 50_000_000,
+            reputation: 0,
+            bounties_completed: 0,
+            total_earned: 0,
+            availability: agent_registry::AgentStatus::Offline,
+            bump,
+            created_at: 0,
+            pending_owner: Pubkey::default(),
+            successor: Pubkey::new_unique(),
+            active_claims: 0,
+            delegate_count: 0,
+            skills: 0,
+            guild: Pubkey::default(),
+            referrer: Pubkey::default(),
+            referral_paid: 0,
+        };
+        let mut env = Self::with_accounts(vec![(
+            agent,
+            program_account(&agent_registry::ID, 1024, &tombstone),
+        )])
+        .await;
+        let owner = env.wallet(owner, 0).await;
+        (env, owner)
+    }
+
+    // ─── Transactions ───────────────────────────────────────────────
+
+    /// Sends `ixs` paid for by the bank's payer and signed by `signers`.
+    pub async fn send(&mut self, ixs: &[Instruction], signers: &[&Keypair]) -> TxResult {
+        self.nonce += 1;
+        let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_price(self.nonce)];
+        instructions.extend_from_slice(ixs);
+
+        let payer = self.ctx.payer.insecure_clone();
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            self.ctx.last_blockhash,
+        );
+        self.ctx.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn send_as_admin(&mut self, ixs: &[Instruction]) -> TxResult {
+        let admin = self.admin.insecure_clone();
+        self.send(ixs, &[&admin]).await
+    }
+
+    // ─── Accounts ───────────────────────────────────────────────────
+
+    /// New wallet with SOL, an associated USDC account and `usdc` minted to it.
+    pub async fn user(&mut self, usdc: u64) -> User {
+        self.wallet(Keypair::new(), usdc).await
+    }
+
+    /// Funds `keypair` like [`Env::user`].
+    pub async fn wallet(&mut self, keypair: Keypair, usdc: u64) -> User {
+        let token = self.token_account(&keypair.pubkey()).await;
+        let payer = self.ctx.payer.pubkey();
+        let ix = system_instruction::transfer(&payer, &keypair.pubkey(), STARTING_LAMPORTS);
+        self.send(&[ix], &[]).await.unwrap();
+        if usdc > 0 {
+            self.mint_to(&token, usdc).await;
+        }
+        User { keypair, token }
+    }
+
+    /// Funded wallet registered as an agent with `skills`.
+    pub async fn agent(&mut self, skills: u128) -> User {
+        let user = self.user(0).await;
+        self.register(&user, skills, None).await.unwrap();
+        user
+    }
+
+    /// Funded wallet with an initialized client state.
+    pub async fn client(&mut self) -> User {
+        let user = self.user(STARTING_USDC).await;
+        self.send(
+            &[escrow::init_client(&user.pubkey(), None)],
+            &[&user.keypair],
+        )
+        .await
+        .unwrap();
+        user
+    }
+
+    pub async fn register(
+        &mut self,
+        user: &User,
+        skills: u128,
+        referrer: Option<Pubkey>,
+    ) -> TxResult {
+        let ix = registry::register_agent(
+            &user.pubkey(),
+            registry_ix::RegisterAgent {
+                metadata_uri: URI.to_string(),
+                metadata_hash: HASH,
+                hourly_rate: 50_000_000,
+                skills,
+                referrer,
+            },
+        );
+        self.send(&[ix], &[&user.keypair]).await
+    }
+
+    /// Associated USDC account of `owner`, created if missing.
+    pub async fn token_account(&mut self, owner: &Pubkey) -> Pubkey {
+        let address = get_associated_token_address(owner, &self.mint);
+        if !self.exists(&address).await {
+            let ix = ata_instruction::create_associated_token_account(
+                &self.ctx.payer.pubkey(),
+                owner,
+                &self.mint,
+                &spl_token::ID,
+            );
+            self.send(&[ix], &[]).await.unwrap();
+        }
+        address
+    }
+
+    pub async fn mint_to(&mut self, token: &Pubkey, amount: u64) {
+        let ix = spl_token::instruction::mint_to(
+            &spl_token::ID,
+            &self.mint,
+            token,
+            &self.admin.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap();
+        self.send_as_admin(&[ix]).await.unwrap();
+    }
+
+    pub async fn exists(&mut self, address: &Pubkey) -> bool {
+        self.ctx
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .is_some()
+    }
+
+    /// Lamports held by `address`, 0 once the account is closed.
+    pub async fn lamports(&mut self, address: &Pubkey) -> u64 {
+        self.ctx.banks_client.get_balance(*address).await.unwrap()
+    }
+
+    /// Decodes the Anchor account at `address`.
+    pub async fn fetch<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        let account = self
+            .ctx
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("account {address} does not exist"));
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    /// Rewrites the Anchor account at `address` with `value`, keeping its size, to
+    /// reach states no instruction leaves an account in.
+    pub async fn overwrite<T: AccountSerialize>(&mut self, address: &Pubkey, value: &T) {
+        let mut account = self
+            .ctx
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("account {address} does not exist"));
+        let space = account.data.len();
+        account.data.clear();
+        value.try_serialize(&mut account.data).unwrap();
+        account.data.resize(space, 0);
+        self.ctx.set_account(address, &account.into());
+    }
+
+    /// Token balance of `address`, 0 once the account is closed.
+    pub async fn balance(&mut self, address: &Pubkey) -> u64 {
+        if !self.exists(address).await {
+            return 0;
+        }
+        self.fetch::<TokenAccount>(address).await.amount
+    }
+
+    // ─── Clock ──────────────────────────────────────────────────────
+
+    pub async fn now(&mut self) -> i64 {
+        self.clock().await.unix_timestamp
+    }
+
+    async fn clock(&mut self) -> Clock {
+        self.ctx.banks_client.get_sysvar::<Clock>().await.unwrap()
+    }
+
+    /// Moves the bank's clock forward by `seconds`.
+    pub async fn warp(&mut self, seconds: i64) {
+        let mut clock = self.clock().await;
+        clock.unix_timestamp += seconds;
+        self.ctx.set_sysvar(&clock);
+    }
+
+    // ─── Admin ──────────────────────────────────────────────────────
+
+    pub async fn pause_registry(&mut self, paused: u64) {
+        let ix = registry::set_paused(&self.admin.pubkey(), paused);
+        self.send_as_admin(&[ix]).await.unwrap();
+    }
+
+    pub async fn pause_escrow(&mut self, paused: u64) {
+        let ix = escrow::set_paused(&self.admin.pubkey(), paused);
+        self.send_as_admin(&[ix]).await.unwrap();
+    }
+
+    pub async fn set_fees(&mut self, args: escrow_ix::SetFees) {
+        let ix = escrow::set_fees(&self.admin.pubkey(), args);
+        self.send_as_admin(&[ix]).await.unwrap();
+    }
+
+    // ─── Bounty flow ────────────────────────────────────────────────
+
+    /// Page of each index bucket of `skills` with room for another open bounty,
+    /// creating the page when it does not exist yet.
+    pub async fn index_pages(&mut self, skills: u128) -> Vec<u32> {
+        let mut pages = Vec::new();
+        for skill in index_buckets(skills) {
+            let mut page = 0;
+            loop {
+                let address = pda::index_page(BountyStatus::Open, skill, page);
+                if !self.exists(&address).await {
+                    let payer = self.ctx.payer.pubkey();
+                    let ix = escrow::init_index_page(&payer, BountyStatus::Open, skill, page);
+                    self.send(&[ix], &[]).await.unwrap();
+                    break;
+                }
+                let listed: accounts::BountyIndexPage = self.fetch(&address).await;
+                if listed.bounties.len() < INDEX_PAGE_CAPACITY {
+                    break;
+                }
+                page += 1;
+            }
+            pages.push(page);
+        }
+        pages
+    }
+
+    /// Open-to-all bounty arguments with a one-week deadline.
+    pub async fn bounty_args(&mut self, budget: u64) -> escrow_ix::CreateBounty {
+        escrow_ix::CreateBounty {
+            metadata_uri: URI.to_string(),
+            metadata_hash: HASH,
+            budget,
+            deadline: self.now().await + 7 * 24 * 3600,
+            skills: 0,
+            require_skill_match: false,
+            attestation_requirement: None,
+            validation_requirement: None,
+            allowlist: Vec::new(),
+            decline_policy: DeclinePolicy::Refund,
+        }
+    }
+
+    /// Posts the client's next bounty and returns its address.
+    pub async fn try_create_bounty(
+        &mut self,
+        client: &User,
+        args: escrow_ix::CreateBounty,
+    ) -> Result<Pubkey, BanksClientError> {
+        let state: accounts::ClientState = self.fetch(&pda::client(&client.pubkey())).await;
+        let index_pages = self.index_pages(args.skills).await;
+        let ix = escrow::create_bounty(
+            &client.pubkey(),
+            state.bounty_count,
+            &client.token,
+            &self.mint,
+            &index_pages,
+            args,
+        );
+        self.send(&[ix], &[&client.keypair]).await?;
+        Ok(pda::bounty(&client.pubkey(), state.bounty_count))
+    }
+
+    pub async fn create_bounty(&mut self, client: &User, args: escrow_ix::CreateBounty) -> Pubkey {
+        self.try_create_bounty(client, args).await.unwrap()
+    }
+
+    /// Posts a plain open bounty of `budget`.
+    pub async fn open_bounty(&mut self, client: &User, budget: u64) -> Pubkey {
+        let args = self.bounty_args(budget).await;
+        self.create_bounty(client, args).await
+    }
+
+    pub async fn claim(&mut self, bounty: &Pubkey, agent: &User) -> TxResult {
+        let state: accounts::Bounty = self.fetch(bounty).await;
+        let ix = escrow::claim_bounty(bounty, &state, &agent.pubkey(), &agent.pubkey());
+        self.send(&[ix], &[&agent.keypair]).await
+    }
+
+    pub async fn submit(&mut self, bounty: &Pubkey, agent: &User) -> TxResult {
+        let ix = escrow::submit_work(
+            bounty,
+            &agent.pubkey(),
+            &agent.pubkey(),
+            URI.to_string(),
+            HASH,
+        );
+        self.send(&[ix], &[&agent.keypair]).await
+    }
+
+    /// Approves with payouts to the assigned agent's associated account and, when
+    /// they exist, the treasury's and referrers' associated accounts.
+    pub async fn approve(&mut self, bounty: &Pubkey, client: &User) -> TxResult {
+        let payout = self.payout_accounts(bounty).await;
+        let state: accounts::Bounty = self.fetch(bounty).await;
+        let ix = escrow::approve_work(bounty, &state, &payout);
+        self.send(&[ix], &[&client.keypair]).await
+    }
+
+    pub async fn payout_accounts(&mut self, bounty: &Pubkey) -> escrow::PayoutAccounts {
+        let state: accounts::Bounty = self.fetch(bounty).await;
+        let agent: accounts::Agent = self.fetch(&pda::agent(&state.assigned_agent)).await;
+        let client_state: accounts::ClientState = self.fetch(&pda::client(&state.client)).await;
+        let config: accounts::EscrowConfig = self.fetch(&pda::escrow_config()).await;
+        escrow::PayoutAccounts {
+            agent_token_account: get_associated_token_address(&state.assigned_agent, &self.mint),
+            treasury: self.existing_token_account(&config.treasury).await,
+            agent_referrer: self.existing_token_account(&agent.referrer).await,
+            client_referrer: self.existing_token_account(&client_state.referrer).await,
+        }
+    }
+
+    async fn existing_token_account(&mut self, owner: &Pubkey) -> Option<Pubkey> {
+        if *owner == Pubkey::default() {
+            return None;
+        }
+        let address = get_associated_token_address(owner, &self.mint);
+        self.exists(&address).await.then_some(address)
+    }
+
+    /// Posts, claims and submits a bounty of `budget`, leaving it Delivered.
+    pub async fn delivered_bounty(&mut self, client: &User, agent: &User, budget: u64) -> Pubkey {
+        let bounty = self.open_bounty(client, budget).await;
+        self.claim(&bounty, agent).await.unwrap();
+        self.submit(&bounty, agent).await.unwrap();
+        bounty
+    }
+
+    /// Runs a bounty of `budget` all the way to Completed.
+    pub async fn completed_bounty(&mut self, client: &User, agent: &User, budget: u64) -> Pubkey {
+        let bounty = self.delivered_bounty(client, agent, budget).await;
+        self.approve(&bounty, client).await.unwrap();
+        bounty
+    }
+}
+
+/// Anchor account with `value` serialized at the front of `space` bytes.
+pub fn program_account<T: AccountSerialize>(owner: &Pubkey, space: usize, value: &T) -> Account {
+    let mut data = Vec::with_capacity(space);
+    value.try_serialize(&mut data).unwrap();
+    data.resize(space, 0);
+    raw_account(owner, data)
+}
+
+/// Rent-exempt account owned by `owner` holding `data` verbatim.
+pub fn raw_account(owner: &Pubkey, data: Vec<u8>) -> Account {
+    Account {
+        lamports: solana_sdk::rent::Rent::default().minimum_balance(data.len()),
+        data,
+        owner: *owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Passes `address` to `ix` as an absent optional account (the program id, as
+/// Anchor encodes `None`), to exercise the handler's missing-account path.
+pub fn omit_account(ix: &mut Instruction, address: &Pubkey) {
+    let meta = ix
+        .accounts
+        .iter_mut()
+        .find(|meta| meta.pubkey == *address)
+        .unwrap_or_else(|| panic!("{address} is not an account of the instruction"));
+    *meta = AccountMeta::new_readonly(ix.program_id, false);
+}
+
+/// Custom program error code a failed transaction ended with, if any.
+pub fn error_code(error: &BanksClientError) -> Option<u32> {
+    match error.unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => Some(code),
+        _ => None,
+    }
+}
+
+/// Asserts that `result` failed with the program error `expected`.
+#[track_caller]
+pub fn assert_error<E: Into<u32> + std::fmt::Debug + Clone, T: std::fmt::Debug>(
+    result: Result<T, BanksClientError>,
+    expected: E,
+) {
+    let error = result.expect_err(&format!("expected {expected:?}, transaction succeeded"));
+    assert_eq!(
+        error_code(&error),
+        Some(expected.clone().into()),
+        "expected {expected:?}, got {error:?}"
+    );
+}
@@ -0,0 +1,648 @@
+//! BountyError paths of the core bounty instructions.
+
+use agent_registry::DELEGATE_UPDATE_AVAILABILITY;
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::Pubkey;
+use bounty_escrow::instruction as escrow_ix;
+use bounty_escrow::{
+    AttestationRequirement, BountyError, BountyStatus, ValidationRequirement,
+    INDEX_PAGE_CAPACITY, PAUSE_APPROVE_WORK, PAUSE_CLAIM_BOUNTY, PAUSE_CREATE_BOUNTY,
+    PAUSE_DISPUTE_BOUNTY, PAUSE_INIT_CLIENT, PAUSE_LEAVE_REVIEW, PAUSE_SUBMIT_WORK,
+    PAUSE_TIP_AGENT, UNSKILLED,
+};
+use clawedwork_client::accounts::{Bounty, BountyIndexPage, ClientState};
+use clawedwork_client::{escrow, pda, registry};
+use clawedwork_tests::*;
+use solana_sdk::signature::Signer;
+
+const BUDGET: u64 = 10_000_000;
+
+fn review(rating: u64) -> escrow_ix::LeaveReview {
+    escrow_ix::LeaveReview {
+        rating,
+        comment_uri: URI.to_string(),
+        comment_hash: HASH,
+    }
+}
+
+#[tokio::test]
+async fn create_bounty_validates_arguments() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let base = env.bounty_args(BUDGET).await;
+    let args = |change: &dyn Fn(&mut escrow_ix::CreateBounty)| {
+        let mut args = escrow_ix::CreateBounty {
+            metadata_uri: base.metadata_uri.clone(),
+            allowlist: Vec::new(),
+            attestation_requirement: None,
+            validation_requirement: None,
+            ..base
+        };
+        change(&mut args);
+        args
+    };
+    let invalid_score = |min_score| {
+        Some(ValidationRequirement {
+            validator: Pubkey::new_unique(),
+            min_score,
+            auto_approve: false,
+        })
+    };
+
+    let cases = [
+        (
+            args(&|a| a.metadata_uri = "x".repeat(513)),
+            BountyError::UriTooLong,
+        ),
+        (
+            args(&|a| a.metadata_hash = [0u8; 32]),
+            BountyError::MissingContentHash,
+        ),
+        (args(&|a| a.budget = 0), BountyError::InvalidBudget),
+        (args(&|a| a.deadline = 0), BountyError::DeadlinePassed),
+        (
+            args(&|a| a.skills = 1 << SKILLS.len()),
+            BountyError::UnknownSkill,
+        ),
+        (args(&|a| a.skills = 0b11111), BountyError::TooManySkills),
+        (
+            args(&|a| {
+                a.attestation_requirement = Some(AttestationRequirement {
+                    verifier: Pubkey::new_unique(),
+                    skill_id: SKILLS.len() as u8,
+                })
+            }),
+            BountyError::UnknownSkill,
+        ),
+        (
+            args(&|a| a.require_skill_match = true),
+            BountyError::NoRequiredSkills,
+        ),
+        (
+            args(&|a| a.validation_requirement = invalid_score(0)),
+            BountyError::InvalidMinScore,
+        ),
+        (
+            args(&|a| a.validation_requirement = invalid_score(101)),
+            BountyError::InvalidMinScore,
+        ),
+        (
+            args(&|a| a.allowlist = (0..6).map(|_| Pubkey::new_unique()).collect()),
+            BountyError::InvalidAllowlist,
+        ),
+        (
+            args(&|a| a.allowlist = vec![Pubkey::default()]),
+            BountyError::InvalidAllowlist,
+        ),
+        (
+            args(&|a| {
+                let agent = Pubkey::new_unique();
+                a.allowlist = vec![agent, agent];
+            }),
+            BountyError::InvalidAllowlist,
+        ),
+    ];
+    for (args, error) in cases {
+        assert_error(env.try_create_bounty(&client, args).await, error);
+    }
+
+    // Decline policies outside DeclinePolicy do not decode
+    let state: ClientState = env.fetch(&pda::client(&client.pubkey())).await;
+    let index_pages = env.index_pages(0).await;
+    let args = env.bounty_args(BUDGET).await;
+    let mut ix = escrow::create_bounty(
+        &client.pubkey(),
+        state.bounty_count,
+        &client.token,
+        &env.mint,
+        &index_pages,
+        args,
+    );
+    *ix.data.last_mut().unwrap() = 2;
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        ErrorCode::InstructionDidNotDeserialize,
+    );
+}
+
+#[tokio::test]
+async fn init_client_rejects_self_referral() {
+    let mut env = Env::new().await;
+    let client = env.user(0).await;
+    let ix = escrow::init_client(&client.pubkey(), Some(client.pubkey()));
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::InvalidReferrer,
+    );
+}
+
+#[tokio::test]
+async fn claims_must_match_bounty_requirements() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let rust_agent = env.agent(SKILL_RUST).await;
+    let design_agent = env.agent(SKILL_DESIGN).await;
+
+    let mut args = env.bounty_args(BUDGET).await;
+    args.skills = SKILL_DESIGN;
+    args.require_skill_match = true;
+    let bounty = env.create_bounty(&client, args).await;
+    assert_error(
+        env.claim(&bounty, &rust_agent).await,
+        BountyError::SkillMismatch,
+    );
+    env.claim(&bounty, &design_agent).await.unwrap();
+    assert_error(
+        env.claim(&bounty, &design_agent).await,
+        BountyError::NotOpen,
+    );
+
+    // An attestation bounty without the attestation account attached
+    let verifier = env.user(0).await;
+    let mut args = env.bounty_args(BUDGET).await;
+    args.attestation_requirement = Some(AttestationRequirement {
+        verifier: verifier.pubkey(),
+        skill_id: 0,
+    });
+    let bounty = env.create_bounty(&client, args).await;
+    let state: Bounty = env.fetch(&bounty).await;
+    let mut ix = escrow::claim_bounty(&bounty, &state, &rust_agent.pubkey(), &rust_agent.pubkey());
+    omit_account(
+        &mut ix,
+        &pda::attestation(&rust_agent.agent(), 0, &verifier.pubkey()),
+    );
+    omit_account(&mut ix, &pda::verifier(&verifier.pubkey()));
+    assert_error(
+        env.send(&[ix], &[&rust_agent.keypair]).await,
+        BountyError::AttestationRequired,
+    );
+
+    // The attestation stops counting once its verifier is disabled
+    let admin = env.admin.pubkey();
+    let ix = registry::add_verifier(&admin, &verifier.pubkey());
+    env.send_as_admin(&[ix]).await.unwrap();
+    let ix = registry::issue_attestation(
+        &verifier.pubkey(),
+        &verifier.pubkey(),
+        &rust_agent.agent(),
+        agent_registry::instruction::IssueAttestation {
+            skill_id: 0,
+            expires_at: 0,
+            evidence_hash: HASH,
+        },
+    );
+    env.send(&[ix], &[&verifier.keypair]).await.unwrap();
+    let ix = registry::set_verifier_active(&admin, &verifier.pubkey(), false);
+    env.send_as_admin(&[ix]).await.unwrap();
+    assert_error(
+        env.claim(&bounty, &rust_agent).await,
+        BountyError::AttestationRequired,
+    );
+    let ix = registry::set_verifier_active(&admin, &verifier.pubkey(), true);
+    env.send_as_admin(&[ix]).await.unwrap();
+    env.claim(&bounty, &rust_agent).await.unwrap();
+}
+
+#[tokio::test]
+async fn claim_resolves_agent_wallet() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+    let stranger = env.user(0).await;
+    let bounty = env.open_bounty(&client, BUDGET).await;
+    let state: Bounty = env.fetch(&bounty).await;
+
+    // Someone else's profile without a delegate record
+    let mut ix = escrow::claim_bounty(&bounty, &state, &stranger.pubkey(), &agent.pubkey());
+    omit_account(
+        &mut ix,
+        &pda::agent_delegate(&agent.agent(), &stranger.pubkey()),
+    );
+    assert_error(
+        env.send(&[ix], &[&stranger.keypair]).await,
+        BountyError::AgentProfileMismatch,
+    );
+
+    // A delegate lacking DELEGATE_CLAIM
+    let ix = registry::add_delegate(
+        &agent.pubkey(),
+        &stranger.pubkey(),
+        DELEGATE_UPDATE_AVAILABILITY,
+    );
+    env.send(&[ix], &[&agent.keypair]).await.unwrap();
+    let ix = escrow::claim_bounty(&bounty, &state, &stranger.pubkey(), &agent.pubkey());
+    assert_error(
+        env.send(&[ix], &[&stranger.keypair]).await,
+        BountyError::DelegateNotAuthorized,
+    );
+
+    // A profile whose ownership moved on
+    let successor = env.user(0).await;
+    let ix = registry::remove_delegate(&agent.pubkey(), &stranger.pubkey());
+    let ix_propose = registry::propose_owner_transfer(&agent.pubkey(), &successor.pubkey());
+    env.send(&[ix, ix_propose], &[&agent.keypair])
+        .await
+        .unwrap();
+    let ix = registry::accept_owner_transfer(&successor.pubkey(), &agent.pubkey());
+    env.send(&[ix], &[&successor.keypair]).await.unwrap();
+    assert_error(
+        env.claim(&bounty, &agent).await,
+        BountyError::AgentTransferred,
+    );
+    env.claim(&bounty, &successor).await.unwrap();
+}
+
+#[tokio::test]
+async fn tombstoned_profile_cannot_claim() {
+    let (mut env, tombstoned) = Env::with_tombstone().await;
+    let client = env.client().await;
+    let bounty = env.open_bounty(&client, BUDGET).await;
+    assert_error(
+        env.claim(&bounty, &tombstoned).await,
+        BountyError::AgentTransferred,
+    );
+}
+
+#[tokio::test]
+async fn work_follows_the_status_order() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+    let other = env.agent(0).await;
+    let bounty = env.open_bounty(&client, BUDGET).await;
+
+    assert_error(env.submit(&bounty, &agent).await, BountyError::NotClaimed);
+    let ix = escrow::dispute_bounty(&bounty, &client.pubkey());
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::CannotDispute,
+    );
+
+    env.claim(&bounty, &agent).await.unwrap();
+    assert_error(
+        env.submit(&bounty, &other).await,
+        BountyError::NotAssignedAgent,
+    );
+    assert_error(
+        env.approve(&bounty, &client).await,
+        BountyError::NotDelivered,
+    );
+    let state: Bounty = env.fetch(&bounty).await;
+    let ix = escrow::cancel_bounty(&bounty, &state, &client.token);
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::NotOpen,
+    );
+    let ix = escrow::dispute_bounty(&bounty, &other.pubkey());
+    assert_error(
+        env.send(&[ix], &[&other.keypair]).await,
+        BountyError::Unauthorized,
+    );
+
+    for (uri, hash, error) in [
+        ("x".repeat(513), HASH, BountyError::UriTooLong),
+        (URI.to_string(), [0u8; 32], BountyError::MissingContentHash),
+    ] {
+        let ix = escrow::submit_work(&bounty, &agent.pubkey(), &agent.pubkey(), uri, hash);
+        assert_error(env.send(&[ix], &[&agent.keypair]).await, error);
+    }
+    env.submit(&bounty, &agent).await.unwrap();
+
+    // Reviews and tips only once the bounty is completed
+    let state: Bounty = env.fetch(&bounty).await;
+    let ix = escrow::leave_review(&bounty, &state, review(500), &[]);
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::NotCompleted,
+    );
+    let ix = escrow::tip_agent(&bounty, &state, &client.token, &agent.token, 1, &[]);
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::NotCompleted,
+    );
+
+    env.approve(&bounty, &client).await.unwrap();
+    let ix = escrow::tip_agent(&bounty, &state, &client.token, &agent.token, 0, &[]);
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::InvalidTip,
+    );
+    let ix = escrow::dispute_bounty(&bounty, &agent.pubkey());
+    assert_error(
+        env.send(&[ix], &[&agent.keypair]).await,
+        BountyError::CannotDispute,
+    );
+}
+
+#[tokio::test]
+async fn resolve_dispute_validates_arguments() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+    let admin = env.admin.pubkey();
+    let bounty = env.delivered_bounty(&client, &agent, BUDGET).await;
+    let payout = env.payout_accounts(&bounty).await;
+
+    let state: Bounty = env.fetch(&bounty).await;
+    let ix = escrow::resolve_dispute(&bounty, &state, &admin, &client.token, &payout, &[], 0);
+    assert_error(env.send_as_admin(&[ix]).await, BountyError::NotDisputed);
+
+    let ix = escrow::dispute_bounty(&bounty, &client.pubkey());
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let ix = escrow::resolve_dispute(
+        &bounty,
+        &state,
+        &client.pubkey(),
+        &client.token,
+        &payout,
+        &[],
+        0,
+    );
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::Unauthorized,
+    );
+    let ix = escrow::resolve_dispute(
+        &bounty,
+        &state,
+        &admin,
+        &client.token,
+        &payout,
+        &[],
+        BUDGET + 1,
+    );
+    assert_error(
+        env.send_as_admin(&[ix]).await,
+        BountyError::InvalidResolution,
+    );
+}
+
+#[tokio::test]
+async fn review_validates_arguments() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+    let bounty = env.completed_bounty(&client, &agent, BUDGET).await;
+    let state: Bounty = env.fetch(&bounty).await;
+
+    let mut long_uri = review(100);
+    long_uri.comment_uri = "x".repeat(513);
+    let mut no_hash = review(100);
+    no_hash.comment_hash = [0u8; 32];
+    for (args, error) in [
+        (review(0), BountyError::InvalidRating),
+        (review(501), BountyError::InvalidRating),
+        (long_uri, BountyError::UriTooLong),
+        (no_hash, BountyError::MissingContentHash),
+    ] {
+        let ix = escrow::leave_review(&bounty, &state, args, &[]);
+        assert_error(env.send(&[ix], &[&client.keypair]).await, error);
+    }
+}
+
+#[tokio::test]
+async fn validation_gates_payout() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+    let validator = env.user(0).await;
+
+    let requirement = ValidationRequirement {
+        validator: validator.pubkey(),
+        min_score: 50,
+        auto_approve: false,
+    };
+    let mut args = env.bounty_args(BUDGET).await;
+    args.validation_requirement = Some(requirement.clone());
+    let bounty = env.create_bounty(&client, args).await;
+    env.claim(&bounty, &agent).await.unwrap();
+    env.submit(&bounty, &agent).await.unwrap();
+    let mut args = env.bounty_args(BUDGET).await;
+    args.validation_requirement = Some(requirement);
+    let other = env.create_bounty(&client, args).await;
+    env.claim(&other, &agent).await.unwrap();
+    env.submit(&other, &agent).await.unwrap();
+    let ix = registry::request_validation(&agent.pubkey(), &validator.pubkey(), &bounty, HASH);
+    env.send(&[ix], &[&agent.keypair]).await.unwrap();
+
+    let payout = env.payout_accounts(&bounty).await;
+    let state: Bounty = env.fetch(&bounty).await;
+    let validation = pda::validation(&bounty, &HASH, &validator.pubkey());
+    let mut ix = escrow::approve_work(&bounty, &state, &payout);
+    omit_account(&mut ix, &validation);
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::ValidationRequired,
+    );
+    // Requested but not yet answered
+    assert_error(
+        env.approve(&bounty, &client).await,
+        BountyError::ValidationRequired,
+    );
+
+    let ix = registry::respond_validation(&validator.pubkey(), &bounty, &HASH, 50, HASH);
+    env.send(&[ix], &[&validator.keypair]).await.unwrap();
+    let ix = escrow::auto_approve(&bounty, &state, &payout).unwrap();
+    assert_error(env.send(&[ix], &[]).await, BountyError::AutoApproveDisabled);
+
+    // A pass for the same deliverable on another bounty does not count
+    let other_payout = env.payout_accounts(&other).await;
+    let other_state: Bounty = env.fetch(&other).await;
+    let mut ix = escrow::approve_work(&other, &other_state, &other_payout);
+    let other_validation = pda::validation(&other, &HASH, &validator.pubkey());
+    for meta in &mut ix.accounts {
+        if meta.pubkey == other_validation {
+            meta.pubkey = validation;
+        }
+    }
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::ValidationRequired,
+    );
+    env.approve(&bounty, &client).await.unwrap();
+}
+
+#[tokio::test]
+async fn pause_flags_block_their_instructions() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+    let newcomer = env.user(0).await;
+    let open = env.open_bounty(&client, BUDGET).await;
+    let claimed = env.open_bounty(&client, BUDGET).await;
+    env.claim(&claimed, &agent).await.unwrap();
+    let delivered = env.delivered_bounty(&client, &agent, BUDGET).await;
+    let completed = env.completed_bounty(&client, &agent, BUDGET).await;
+    let args = env.bounty_args(BUDGET).await;
+    let completed_state: Bounty = env.fetch(&completed).await;
+
+    env.pause_escrow(u64::MAX).await;
+    let ix = escrow::init_client(&newcomer.pubkey(), None);
+    assert_error(
+        env.send(&[ix], &[&newcomer.keypair]).await,
+        BountyError::Paused,
+    );
+    assert_error(
+        env.try_create_bounty(&client, args).await,
+        BountyError::Paused,
+    );
+    assert_error(env.claim(&open, &agent).await, BountyError::Paused);
+    assert_error(env.submit(&claimed, &agent).await, BountyError::Paused);
+    assert_error(env.approve(&delivered, &client).await, BountyError::Paused);
+    let ix = escrow::dispute_bounty(&delivered, &client.pubkey());
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::Paused,
+    );
+    let ix = escrow::leave_review(&completed, &completed_state, review(500), &[]);
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::Paused,
+    );
+    let ix = escrow::tip_agent(&completed, &completed_state, &client.token, &agent.token, 1, &[]);
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::Paused,
+    );
+
+    // Refunds stay available while paused
+    let state: Bounty = env.fetch(&open).await;
+    let ix = escrow::cancel_bounty(&open, &state, &client.token);
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+
+    // Each flag only blocks its own instruction
+    env.pause_escrow(
+        PAUSE_INIT_CLIENT
+            | PAUSE_CREATE_BOUNTY
+            | PAUSE_CLAIM_BOUNTY
+            | PAUSE_SUBMIT_WORK
+            | PAUSE_DISPUTE_BOUNTY
+            | PAUSE_LEAVE_REVIEW
+            | PAUSE_TIP_AGENT,
+    )
+    .await;
+    env.approve(&delivered, &client).await.unwrap();
+    env.pause_escrow(PAUSE_APPROVE_WORK).await;
+    env.submit(&claimed, &agent).await.unwrap();
+}
+
+#[tokio::test]
+async fn admin_instructions_require_admin() {
+    let mut env = Env::new().await;
+    let outsider = env.user(0).await;
+    let ix = escrow::set_paused(&outsider.pubkey(), 1);
+    assert_error(
+        env.send(&[ix], &[&outsider.keypair]).await,
+        BountyError::Unauthorized,
+    );
+}
+
+#[tokio::test]
+async fn migrations_reject_current_and_foreign_accounts() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let bounty = env.open_bounty(&client, BUDGET).await;
+    let payer = env.ctx.payer.pubkey();
+
+    let ix = escrow::migrate_bounty(&bounty, None, &payer);
+    assert_error(env.send(&[ix], &[]).await, BountyError::AlreadyMigrated);
+    let ix = escrow::migrate_client_state(&pda::client(&client.pubkey()), &payer);
+    assert_error(env.send(&[ix], &[]).await, BountyError::AlreadyMigrated);
+    let ix = escrow::migrate_bounty(&pda::escrow_config(), None, &payer);
+    assert_error(
+        env.send(&[ix], &[]).await,
+        BountyError::UnknownAccountVersion,
+    );
+    let ix = escrow::migrate_client_state(&bounty, &payer);
+    assert_error(
+        env.send(&[ix], &[]).await,
+        BountyError::UnknownAccountVersion,
+    );
+}
+
+async fn listed(env: &mut Env, skill: u8, page: u32) -> Vec<Pubkey> {
+    let page: BountyIndexPage = env
+        .fetch(&pda::index_page(BountyStatus::Open, skill, page))
+        .await;
+    page.bounties
+}
+
+#[tokio::test]
+async fn index_lists_open_bounties_by_skill() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(SKILL_RUST).await;
+    let payer = env.ctx.payer.pubkey();
+
+    let ix = escrow::init_index_page(&payer, BountyStatus::Claimed, 0, 0);
+    assert_error(env.send(&[ix], &[]).await, BountyError::StatusNotIndexed);
+    let ix = escrow::init_index_page(&payer, BountyStatus::Open, 200, 0);
+    assert_error(env.send(&[ix], &[]).await, BountyError::UnknownSkill);
+
+    // One listing per required skill, or the skill-less bucket
+    let mut args = env.bounty_args(BUDGET).await;
+    args.skills = SKILL_RUST | SKILL_DESIGN;
+    let both = env.create_bounty(&client, args).await;
+    let mut args = env.bounty_args(BUDGET).await;
+    args.skills = SKILL_RUST;
+    let rust = env.create_bounty(&client, args).await;
+    let plain = env.open_bounty(&client, BUDGET).await;
+    assert_eq!(listed(&mut env, 0, 0).await, [both, rust]);
+    assert_eq!(listed(&mut env, 2, 0).await, [both]);
+    assert_eq!(listed(&mut env, UNSKILLED, 0).await, [plain]);
+    let state: Bounty = env.fetch(&both).await;
+    assert_eq!(state.index_pages, [0, 0]);
+
+    // Leaving Open delists the bounty from every bucket
+    env.claim(&both, &agent).await.unwrap();
+    assert_eq!(listed(&mut env, 0, 0).await, [rust]);
+    assert!(listed(&mut env, 2, 0).await.is_empty());
+    let state: Bounty = env.fetch(&both).await;
+    assert!(state.index_pages.is_empty());
+
+    let state: Bounty = env.fetch(&rust).await;
+    let mut ix = escrow::cancel_bounty(&rust, &state, &client.token);
+    ix.accounts.pop();
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::IndexPagesMismatch,
+    );
+    let ix = escrow::cancel_bounty(&rust, &state, &client.token);
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    assert!(listed(&mut env, 0, 0).await.is_empty());
+
+    // A full page sends new bounties to the next one
+    for _ in 1..INDEX_PAGE_CAPACITY {
+        env.open_bounty(&client, 1).await;
+    }
+    let client_state: ClientState = env.fetch(&pda::client(&client.pubkey())).await;
+    for (pages, error) in [
+        (&[0][..], BountyError::IndexPageFull),
+        (&[], BountyError::IndexPagesMismatch),
+    ] {
+        let args = env.bounty_args(1).await;
+        let ix = escrow::create_bounty(
+            &client.pubkey(),
+            client_state.bounty_count,
+            &client.token,
+            &env.mint,
+            pages,
+            args,
+        );
+        assert_error(env.send(&[ix], &[&client.keypair]).await, error);
+    }
+    let overflow = env.open_bounty(&client, 1).await;
+    assert_eq!(listed(&mut env, UNSKILLED, 1).await, [overflow]);
+
+    // Delisting checks the page the bounty was recorded on
+    let mut state: Bounty = env.fetch(&overflow).await;
+    assert_eq!(state.index_pages, [1]);
+    state.index_pages = vec![0];
+    let ix = escrow::cancel_bounty(&overflow, &state, &client.token);
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::IndexPagesMismatch,
+    );
+    env.claim(&overflow, &agent).await.unwrap();
+    assert!(listed(&mut env, UNSKILLED, 1).await.is_empty());
+}
@@ -0,0 +1,619 @@
+//! Hourly contracts, payment streams and recurring bounties.
+
+use agent_registry::AgentError;
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::Pubkey;
+use bounty_escrow::instruction as escrow_ix;
+use bounty_escrow::{BountyError, PAUSE_CREATE_BOUNTY, PAUSE_HOURLY, PAUSE_STREAM};
+use clawedwork_client::accounts::{
+    Agent, Bounty, HourlyContract, RecurringBounty, Stream, TimeLog,
+};
+use clawedwork_client::status::{
+    BountyStatus, DeclinePolicy, HourlyContractStatus, RecurringStatus, StreamStatus,
+    TimeLogStatus,
+};
+use clawedwork_client::{escrow, pda, registry};
+use clawedwork_tests::*;
+use solana_sdk::signature::Signer;
+
+/// Registered agents bill 50 USDC an hour.
+const HOURLY_RATE: u64 = 50_000_000;
+
+/// Log for the `period`-th hour of the contract.
+fn time_log(period: i64, hours_x100: u64) -> escrow_ix::SubmitTimeLog {
+    escrow_ix::SubmitTimeLog {
+        period_start: period * 3600,
+        period_end: (period + 1) * 3600,
+        hours_x100,
+        evidence_uri: URI.to_string(),
+        evidence_hash: HASH,
+    }
+}
+
+#[tokio::test]
+async fn hourly_contract_pays_approved_logs() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+    let cap = 2 * HOURLY_RATE;
+
+    let ix = escrow::create_hourly_contract(
+        &client.pubkey(),
+        &agent.pubkey(),
+        &client.token,
+        &env.mint,
+        escrow_ix::CreateHourlyContract {
+            contract_id: 0,
+            cap: 0,
+        },
+    );
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::InvalidBudget,
+    );
+    let ix = escrow::create_hourly_contract(
+        &client.pubkey(),
+        &agent.pubkey(),
+        &client.token,
+        &env.mint,
+        escrow_ix::CreateHourlyContract {
+            contract_id: 0,
+            cap,
+        },
+    );
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let address = pda::hourly_contract(&client.pubkey(), 0);
+    let contract: HourlyContract = env.fetch(&address).await;
+    assert_eq!(contract.hourly_rate, HOURLY_RATE);
+    assert_eq!(env.balance(&contract.vault).await, cap);
+
+    // 1.5 hours, then 0.5 hours
+    for (period, hours_x100) in [(0, 150), (1, 50)] {
+        let contract: HourlyContract = env.fetch(&address).await;
+        let args = time_log(period, hours_x100);
+        let ix = escrow::submit_time_log(&address, &contract, &agent.pubkey(), args);
+        env.send(&[ix], &[&agent.keypair]).await.unwrap();
+    }
+    let log: TimeLog = env.fetch(&pda::time_log(&address, 0)).await;
+    assert_eq!(log.amount, HOURLY_RATE * 3 / 2);
+    assert_eq!(log.status, TimeLogStatus::Pending);
+
+    // Periods follow each other without overlapping, checked against the latest log
+    let contract: HourlyContract = env.fetch(&address).await;
+    let mut overlapping = time_log(1, 50);
+    overlapping.period_start += 1800;
+    let ix = escrow::submit_time_log(&address, &contract, &agent.pubkey(), overlapping);
+    assert_error(
+        env.send(&[ix], &[&agent.keypair]).await,
+        BountyError::TimeLogOverlap,
+    );
+    let mut ix = escrow::submit_time_log(&address, &contract, &agent.pubkey(), time_log(2, 50));
+    omit_account(&mut ix, &pda::time_log(&address, 1));
+    assert_error(
+        env.send(&[ix], &[&agent.keypair]).await,
+        BountyError::PreviousTimeLogRequired,
+    );
+
+    let ix = escrow::close_hourly_contract(&address, &contract, &agent.pubkey(), &client.token);
+    assert_error(
+        env.send(&[ix], &[&agent.keypair]).await,
+        BountyError::PendingTimeLogs,
+    );
+
+    let ix = escrow::approve_time_log(&address, &contract, 0, &agent.token);
+    env.send(std::slice::from_ref(&ix), &[&client.keypair])
+        .await
+        .unwrap();
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::TimeLogNotPending,
+    );
+    let ix = escrow::reject_time_log(&address, &contract, 1);
+    env.send(std::slice::from_ref(&ix), &[&client.keypair])
+        .await
+        .unwrap();
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::TimeLogNotPending,
+    );
+    let log: TimeLog = env.fetch(&pda::time_log(&address, 1)).await;
+    assert_eq!(log.status, TimeLogStatus::Rejected);
+
+    let ix = escrow::close_hourly_contract(&address, &contract, &agent.pubkey(), &client.token);
+    env.send(std::slice::from_ref(&ix), &[&agent.keypair])
+        .await
+        .unwrap();
+    // The vault is closed along with the contract
+    assert_error(
+        env.send(&[ix], &[&agent.keypair]).await,
+        ErrorCode::AccountNotInitialized,
+    );
+
+    let contract: HourlyContract = env.fetch(&address).await;
+    assert_eq!(contract.status, HourlyContractStatus::Closed);
+    assert_eq!(contract.released, HOURLY_RATE * 3 / 2);
+    assert_eq!(env.balance(&agent.token).await, HOURLY_RATE * 3 / 2);
+    assert_eq!(
+        env.balance(&client.token).await,
+        STARTING_USDC - HOURLY_RATE * 3 / 2
+    );
+    assert!(!env.exists(&contract.vault).await);
+    let profile: Agent = env.fetch(&agent.agent()).await;
+    assert_eq!(profile.total_earned, HOURLY_RATE * 3 / 2);
+
+    let ix = escrow::submit_time_log(&address, &contract, &agent.pubkey(), time_log(2, 100));
+    assert_error(
+        env.send(&[ix], &[&agent.keypair]).await,
+        BountyError::ContractClosed,
+    );
+}
+
+#[tokio::test]
+async fn open_hourly_contract_blocks_deregistration() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+
+    let ix = escrow::create_hourly_contract(
+        &client.pubkey(),
+        &agent.pubkey(),
+        &client.token,
+        &env.mint,
+        escrow_ix::CreateHourlyContract {
+            contract_id: 0,
+            cap: HOURLY_RATE,
+        },
+    );
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let profile: Agent = env.fetch(&agent.agent()).await;
+    assert_eq!(profile.active_claims, 1);
+
+    let ix = registry::deregister_agent(&agent.pubkey(), false);
+    assert_error(
+        env.send(&[ix], &[&agent.keypair]).await,
+        AgentError::ActiveClaims,
+    );
+
+    let address = pda::hourly_contract(&client.pubkey(), 0);
+    let contract: HourlyContract = env.fetch(&address).await;
+    let ix = escrow::close_hourly_contract(&address, &contract, &client.pubkey(), &client.token);
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let profile: Agent = env.fetch(&agent.agent()).await;
+    assert_eq!(profile.active_claims, 0);
+
+    let ix = registry::deregister_agent(&agent.pubkey(), false);
+    env.send(&[ix], &[&agent.keypair]).await.unwrap();
+    assert!(!env.exists(&agent.agent()).await);
+}
+
+#[tokio::test]
+async fn time_logs_stay_within_cap() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+    let other = env.agent(0).await;
+    let stranger = env.user(0).await;
+    let ix = escrow::create_hourly_contract(
+        &client.pubkey(),
+        &agent.pubkey(),
+        &client.token,
+        &env.mint,
+        escrow_ix::CreateHourlyContract {
+            contract_id: 7,
+            cap: HOURLY_RATE,
+        },
+    );
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let address = pda::hourly_contract(&client.pubkey(), 7);
+    let contract: HourlyContract = env.fetch(&address).await;
+
+    let mut long_uri = time_log(0, 100);
+    long_uri.evidence_uri = "x".repeat(513);
+    let mut no_hash = time_log(0, 100);
+    no_hash.evidence_hash = [0u8; 32];
+    let mut backwards = time_log(0, 100);
+    backwards.period_end = backwards.period_start;
+    for (args, error) in [
+        (long_uri, BountyError::UriTooLong),
+        (no_hash, BountyError::MissingContentHash),
+        (time_log(0, 0), BountyError::InvalidTimeLog),
+        (backwards, BountyError::InvalidTimeLog),
+        (time_log(0, 101), BountyError::ExceedsCap),
+    ] {
+        let ix = escrow::submit_time_log(&address, &contract, &agent.pubkey(), args);
+        assert_error(env.send(&[ix], &[&agent.keypair]).await, error);
+    }
+
+    // Another agent, with their own profile and with the contract's
+    let mut ix = escrow::submit_time_log(&address, &contract, &other.pubkey(), time_log(0, 100));
+    omit_account(
+        &mut ix,
+        &pda::agent_delegate(&agent.agent(), &other.pubkey()),
+    );
+    assert_error(
+        env.send(std::slice::from_ref(&ix), &[&other.keypair]).await,
+        BountyError::AgentProfileMismatch,
+    );
+    let agent_profile = agent.agent();
+    let other_profile = other.agent();
+    ix.accounts
+        .iter_mut()
+        .filter(|meta| meta.pubkey == agent_profile)
+        .for_each(|meta| meta.pubkey = other_profile);
+    assert_error(
+        env.send(&[ix], &[&other.keypair]).await,
+        BountyError::AgentProfileMismatch,
+    );
+
+    // Two logs that each fit but not together
+    for period in 0..2 {
+        let contract: HourlyContract = env.fetch(&address).await;
+        let args = time_log(period, 60);
+        let ix = escrow::submit_time_log(&address, &contract, &agent.pubkey(), args);
+        env.send(&[ix], &[&agent.keypair]).await.unwrap();
+    }
+    let ix = escrow::approve_time_log(&address, &contract, 0, &agent.token);
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let ix = escrow::approve_time_log(&address, &contract, 1, &agent.token);
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::ExceedsCap,
+    );
+
+    let ix = escrow::close_hourly_contract(&address, &contract, &stranger.pubkey(), &client.token);
+    assert_error(
+        env.send(&[ix], &[&stranger.keypair]).await,
+        BountyError::Unauthorized,
+    );
+
+    env.pause_escrow(PAUSE_HOURLY).await;
+    let ix = escrow::approve_time_log(&address, &contract, 1, &agent.token);
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::Paused,
+    );
+    let contract: HourlyContract = env.fetch(&address).await;
+    let ix = escrow::submit_time_log(&address, &contract, &agent.pubkey(), time_log(2, 1));
+    assert_error(
+        env.send(&[ix], &[&agent.keypair]).await,
+        BountyError::Paused,
+    );
+    // Rejecting and closing stay open so funds can always come back
+    let ix = escrow::reject_time_log(&address, &contract, 1);
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let ix = escrow::close_hourly_contract(&address, &contract, &client.pubkey(), &client.token);
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+}
+
+async fn create_stream(
+    env: &mut Env,
+    client: &User,
+    agent: &User,
+    id: u64,
+    total: u64,
+    duration: i64,
+) -> Pubkey {
+    let start_ts = env.now().await;
+    let ix = escrow::create_stream(
+        &client.pubkey(),
+        &agent.pubkey(),
+        &client.token,
+        &env.mint,
+        escrow_ix::CreateStream {
+            stream_id: id,
+            total,
+            start_ts,
+            end_ts: start_ts + duration,
+        },
+    );
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    pda::stream(&client.pubkey(), id)
+}
+
+#[tokio::test]
+async fn stream_vests_linearly() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+    let address = create_stream(&mut env, &client, &agent, 0, 1_000, 100).await;
+    let stream: Stream = env.fetch(&address).await;
+    let profile: Agent = env.fetch(&agent.agent()).await;
+    assert_eq!(profile.active_claims, 1);
+
+    let ix = escrow::withdraw_vested(&address, &stream, &agent.token);
+    assert_error(
+        env.send(std::slice::from_ref(&ix), &[&agent.keypair]).await,
+        BountyError::NothingVested,
+    );
+
+    env.warp(25).await;
+    env.send(std::slice::from_ref(&ix), &[&agent.keypair])
+        .await
+        .unwrap();
+    assert_eq!(env.balance(&agent.token).await, 250);
+
+    env.warp(100).await;
+    env.send(std::slice::from_ref(&ix), &[&agent.keypair])
+        .await
+        .unwrap();
+    let stream: Stream = env.fetch(&address).await;
+    assert_eq!(stream.status, StreamStatus::Completed);
+    assert_eq!(stream.withdrawn, 1_000);
+    assert_eq!(env.balance(&agent.token).await, 1_000);
+    assert!(!env.exists(&stream.vault).await);
+    let profile: Agent = env.fetch(&agent.agent()).await;
+    assert_eq!(profile.active_claims, 0);
+    assert_eq!(profile.total_earned, 1_000);
+
+    // The vault is closed once the stream is fully withdrawn
+    assert_error(
+        env.send(&[ix], &[&agent.keypair]).await,
+        ErrorCode::AccountNotInitialized,
+    );
+    let ix = escrow::cancel_stream(&address, &stream, &agent.token, &client.token);
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        ErrorCode::AccountNotInitialized,
+    );
+}
+
+#[tokio::test]
+async fn cancelled_stream_splits_at_vested_amount() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+    let address = create_stream(&mut env, &client, &agent, 3, 1_000, 100).await;
+    let stream: Stream = env.fetch(&address).await;
+
+    env.warp(40).await;
+    let ix = escrow::cancel_stream(&address, &stream, &agent.token, &client.token);
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+
+    let stream: Stream = env.fetch(&address).await;
+    assert_eq!(stream.status, StreamStatus::Cancelled);
+    assert_eq!(stream.withdrawn, 400);
+    assert_eq!(env.balance(&agent.token).await, 400);
+    assert_eq!(env.balance(&client.token).await, STARTING_USDC - 400);
+    let profile: Agent = env.fetch(&agent.agent()).await;
+    assert_eq!(profile.active_claims, 0);
+    assert_eq!(profile.total_earned, 400);
+}
+
+#[tokio::test]
+async fn finished_stream_rejects_withdrawals_and_cancels() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+    let address = create_stream(&mut env, &client, &agent, 0, 1_000, 100).await;
+    env.warp(50).await;
+
+    // Finishing a stream closes its vault, so only a stale status reaches the check
+    for status in [StreamStatus::Completed, StreamStatus::Cancelled] {
+        let mut stream: Stream = env.fetch(&address).await;
+        stream.status = status;
+        env.overwrite(&address, &stream).await;
+        let ix = escrow::withdraw_vested(&address, &stream, &agent.token);
+        assert_error(
+            env.send(&[ix], &[&agent.keypair]).await,
+            BountyError::StreamNotActive,
+        );
+        let ix = escrow::cancel_stream(&address, &stream, &agent.token, &client.token);
+        assert_error(
+            env.send(&[ix], &[&client.keypair]).await,
+            BountyError::StreamNotActive,
+        );
+    }
+    assert_eq!(env.balance(&agent.token).await, 0);
+}
+
+#[tokio::test]
+async fn stream_schedule_is_validated() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+    let now = env.now().await;
+
+    for (total, start_ts, end_ts, error) in [
+        (0, now, now + 10, BountyError::InvalidBudget),
+        (10, now - 1, now + 10, BountyError::InvalidStreamSchedule),
+        (10, now + 10, now + 10, BountyError::InvalidStreamSchedule),
+    ] {
+        let ix = escrow::create_stream(
+            &client.pubkey(),
+            &agent.pubkey(),
+            &client.token,
+            &env.mint,
+            escrow_ix::CreateStream {
+                stream_id: 0,
+                total,
+                start_ts,
+                end_ts,
+            },
+        );
+        assert_error(env.send(&[ix], &[&client.keypair]).await, error);
+    }
+
+    let address = create_stream(&mut env, &client, &agent, 0, 10, 10).await;
+    let stream: Stream = env.fetch(&address).await;
+    env.warp(5).await;
+    env.pause_escrow(PAUSE_STREAM).await;
+    let ix = escrow::withdraw_vested(&address, &stream, &agent.token);
+    assert_error(
+        env.send(&[ix], &[&agent.keypair]).await,
+        BountyError::Paused,
+    );
+    let ix = escrow::cancel_stream(&address, &stream, &agent.token, &client.token);
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+}
+
+fn recurring_args(
+    template_id: u64,
+    period_secs: i64,
+    total_periods: u64,
+) -> escrow_ix::CreateRecurringBounty {
+    escrow_ix::CreateRecurringBounty {
+        template_id,
+        metadata_uri: URI.to_string(),
+        metadata_hash: HASH,
+        budget_per_period: 1_000,
+        period_secs,
+        total_periods,
+        first_open_at: 0,
+        skills: 0,
+        require_skill_match: false,
+        allowlist: Vec::new(),
+        decline_policy: DeclinePolicy::Refund,
+    }
+}
+
+async fn open_cycle(
+    env: &mut Env,
+    address: &Pubkey,
+) -> Result<Pubkey, solana_program_test::BanksClientError> {
+    let recurring: RecurringBounty = env.fetch(address).await;
+    let client_state: clawedwork_client::accounts::ClientState =
+        env.fetch(&pda::client(&recurring.client)).await;
+    let payer = env.ctx.payer.pubkey();
+    let index_pages = env.index_pages(recurring.skills).await;
+    let ix = escrow::open_cycle(
+        address,
+        &recurring,
+        client_state.bounty_count,
+        &payer,
+        &index_pages,
+    );
+    env.send(&[ix], &[]).await?;
+    Ok(pda::bounty(&recurring.client, client_state.bounty_count))
+}
+
+#[tokio::test]
+async fn recurring_bounty_opens_a_cycle_per_period() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+
+    let ix = escrow::create_recurring_bounty(
+        &client.pubkey(),
+        &client.token,
+        &env.mint,
+        recurring_args(0, 100, 3),
+    );
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let address = pda::recurring(&client.pubkey(), 0);
+    let recurring: RecurringBounty = env.fetch(&address).await;
+    assert_eq!(env.balance(&recurring.vault).await, 3_000);
+
+    let first = open_cycle(&mut env, &address).await.unwrap();
+    let cycle: Bounty = env.fetch(&first).await;
+    assert_eq!(cycle.status, BountyStatus::Open);
+    assert_eq!(cycle.recurring, address);
+    assert_eq!(cycle.budget, 1_000);
+    assert_eq!(env.balance(&cycle.vault).await, 1_000);
+    assert_eq!(env.balance(&recurring.vault).await, 2_000);
+    assert_error(
+        open_cycle(&mut env, &address).await,
+        BountyError::CycleNotDue,
+    );
+
+    // Cycles are ordinary bounties
+    env.claim(&first, &agent).await.unwrap();
+    env.submit(&first, &agent).await.unwrap();
+    env.approve(&first, &client).await.unwrap();
+    assert_eq!(env.balance(&agent.token).await, 1_000);
+
+    for _ in 0..2 {
+        env.warp(100).await;
+        open_cycle(&mut env, &address).await.unwrap();
+    }
+    env.warp(100).await;
+    assert_error(
+        open_cycle(&mut env, &address).await,
+        BountyError::NoPeriodsLeft,
+    );
+    let recurring: RecurringBounty = env.fetch(&address).await;
+    assert_eq!(recurring.opened_periods, 3);
+    assert_eq!(env.balance(&recurring.vault).await, 0);
+}
+
+#[tokio::test]
+async fn cancelling_recurring_bounty_refunds_unopened_periods() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+
+    for (args, error) in [
+        (recurring_args(0, 0, 1), BountyError::InvalidRecurrence),
+        (recurring_args(0, 10, 0), BountyError::InvalidRecurrence),
+        (
+            escrow_ix::CreateRecurringBounty {
+                budget_per_period: u64::MAX,
+                ..recurring_args(0, 10, 2)
+            },
+            BountyError::InvalidBudget,
+        ),
+    ] {
+        let ix = escrow::create_recurring_bounty(&client.pubkey(), &client.token, &env.mint, args);
+        assert_error(env.send(&[ix], &[&client.keypair]).await, error);
+    }
+
+    let ix = escrow::create_recurring_bounty(
+        &client.pubkey(),
+        &client.token,
+        &env.mint,
+        recurring_args(1, 10, 4),
+    );
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let address = pda::recurring(&client.pubkey(), 1);
+    open_cycle(&mut env, &address).await.unwrap();
+
+    env.pause_escrow(PAUSE_CREATE_BOUNTY).await;
+    env.warp(10).await;
+    assert_error(open_cycle(&mut env, &address).await, BountyError::Paused);
+    let recurring: RecurringBounty = env.fetch(&address).await;
+    let ix = escrow::cancel_recurring(&address, &recurring, &client.token);
+    env.send(std::slice::from_ref(&ix), &[&client.keypair])
+        .await
+        .unwrap();
+    env.pause_escrow(0).await;
+
+    let recurring: RecurringBounty = env.fetch(&address).await;
+    assert_eq!(recurring.status, RecurringStatus::Cancelled);
+    assert!(!env.exists(&recurring.vault).await);
+    assert_eq!(env.balance(&client.token).await, STARTING_USDC - 1_000);
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        ErrorCode::AccountNotInitialized,
+    );
+    assert_error(
+        open_cycle(&mut env, &address).await,
+        ErrorCode::AccountNotInitialized,
+    );
+}
+
+#[tokio::test]
+async fn cancelled_recurring_bounty_opens_no_cycles() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let ix = escrow::create_recurring_bounty(
+        &client.pubkey(),
+        &client.token,
+        &env.mint,
+        recurring_args(0, 10, 2),
+    );
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let address = pda::recurring(&client.pubkey(), 0);
+
+    // Cancelling closes the vault, so only a stale status reaches the check
+    let mut recurring: RecurringBounty = env.fetch(&address).await;
+    recurring.status = RecurringStatus::Cancelled;
+    env.overwrite(&address, &recurring).await;
+    assert_error(
+        open_cycle(&mut env, &address).await,
+        BountyError::RecurringNotActive,
+    );
+    let ix = escrow::cancel_recurring(&address, &recurring, &client.token);
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::RecurringNotActive,
+    );
+    assert_eq!(env.balance(&recurring.vault).await, 2_000);
+}
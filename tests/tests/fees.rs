@@ -0,0 +1,272 @@
+//! Platform fees and referral rewards on bounty payouts.
+
+use agent_registry::instruction as registry_ix;
+use anchor_lang::prelude::Pubkey;
+use bounty_escrow::instruction as escrow_ix;
+use bounty_escrow::{BountyError, MAX_FEE_BPS, MAX_REFERRAL_SHARE_BPS};
+use clawedwork_client::accounts::{Agent, Bounty, ClientState, Guild};
+use clawedwork_client::{escrow, pda, registry};
+use clawedwork_tests::*;
+use solana_sdk::signature::Signer;
+
+fn fees(treasury: Pubkey, referral_cap: u64, referral_duration: i64) -> escrow_ix::SetFees {
+    escrow_ix::SetFees {
+        fee_bps: MAX_FEE_BPS,
+        treasury,
+        referral_share_bps: 2_500,
+        referral_cap,
+        referral_duration,
+    }
+}
+
+/// Treasury, an agent and a client, each referred by their own wallet.
+struct Parties {
+    treasury: User,
+    agent: User,
+    agent_referrer: User,
+    client: User,
+    client_referrer: User,
+}
+
+async fn parties(env: &mut Env) -> Parties {
+    let treasury = env.user(0).await;
+    let agent_referrer = env.user(0).await;
+    let client_referrer = env.user(0).await;
+    let agent = env.user(0).await;
+    env.register(&agent, 0, Some(agent_referrer.pubkey()))
+        .await
+        .unwrap();
+    let client = env.user(STARTING_USDC).await;
+    let ix = escrow::init_client(&client.pubkey(), Some(client_referrer.pubkey()));
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    Parties {
+        treasury,
+        agent,
+        agent_referrer,
+        client,
+        client_referrer,
+    }
+}
+
+#[tokio::test]
+async fn fee_config_is_bounded() {
+    let mut env = Env::new().await;
+    let treasury = Pubkey::new_unique();
+    for args in [
+        escrow_ix::SetFees {
+            fee_bps: MAX_FEE_BPS + 1,
+            ..fees(treasury, 0, 0)
+        },
+        escrow_ix::SetFees {
+            referral_share_bps: MAX_REFERRAL_SHARE_BPS + 1,
+            ..fees(treasury, 0, 0)
+        },
+        fees(treasury, 0, -1),
+        fees(Pubkey::default(), 0, 0),
+    ] {
+        let ix = escrow::set_fees(&env.admin.pubkey(), args);
+        assert_error(
+            env.send_as_admin(&[ix]).await,
+            BountyError::InvalidFeeConfig,
+        );
+    }
+
+    // No fee needs no treasury
+    env.set_fees(escrow_ix::SetFees {
+        fee_bps: 0,
+        ..fees(Pubkey::default(), 0, 0)
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn fee_is_split_with_referrers() {
+    let mut env = Env::new().await;
+    let p = parties(&mut env).await;
+    env.set_fees(fees(p.treasury.pubkey(), 0, 0)).await;
+    let bounty = env.delivered_bounty(&p.client, &p.agent, 10_000).await;
+
+    let state: Bounty = env.fetch(&bounty).await;
+    let payout = env.payout_accounts(&bounty).await;
+    for (accounts, error) in [
+        (
+            escrow::PayoutAccounts {
+                treasury: None,
+                ..payout.clone()
+            },
+            BountyError::TreasuryAccountRequired,
+        ),
+        (
+            escrow::PayoutAccounts {
+                agent_referrer: None,
+                ..payout.clone()
+            },
+            BountyError::ReferrerAccountRequired,
+        ),
+        (
+            escrow::PayoutAccounts {
+                client_referrer: None,
+                ..payout.clone()
+            },
+            BountyError::ReferrerAccountRequired,
+        ),
+        (
+            escrow::PayoutAccounts {
+                agent_referrer: payout.client_referrer,
+                ..payout.clone()
+            },
+            BountyError::InvalidPayeeAccount,
+        ),
+        (
+            escrow::PayoutAccounts {
+                treasury: Some(p.client.token),
+                ..payout.clone()
+            },
+            BountyError::InvalidPayeeAccount,
+        ),
+    ] {
+        let ix = escrow::approve_work(&bounty, &state, &accounts);
+        assert_error(env.send(&[ix], &[&p.client.keypair]).await, error);
+    }
+    env.approve(&bounty, &p.client).await.unwrap();
+
+    // 10% fee: a quarter of it to each referrer, the rest to the treasury
+    assert_eq!(env.balance(&p.agent.token).await, 9_000);
+    assert_eq!(env.balance(&p.agent_referrer.token).await, 250);
+    assert_eq!(env.balance(&p.client_referrer.token).await, 250);
+    assert_eq!(env.balance(&p.treasury.token).await, 500);
+    let agent: Agent = env.fetch(&p.agent.agent()).await;
+    assert_eq!(agent.referral_paid, 250);
+    let client_state: ClientState = env.fetch(&pda::client(&p.client.pubkey())).await;
+    assert_eq!(client_state.referral_paid, 250);
+}
+
+#[tokio::test]
+async fn large_payouts_split_without_overflow() {
+    let mut env = Env::new().await;
+    let p = parties(&mut env).await;
+    env.set_fees(escrow_ix::SetFees {
+        referral_share_bps: MAX_REFERRAL_SHARE_BPS,
+        ..fees(p.treasury.pubkey(), 0, 0)
+    })
+    .await;
+    // The fee times the referral share no longer fits in a u64
+    let budget = 100_000_000_000_000_000;
+    env.mint_to(&p.client.token, budget).await;
+    env.completed_bounty(&p.client, &p.agent, budget).await;
+
+    assert_eq!(env.balance(&p.agent.token).await, budget / 10 * 9);
+    assert_eq!(env.balance(&p.agent_referrer.token).await, budget / 20);
+    assert_eq!(env.balance(&p.client_referrer.token).await, budget / 20);
+    assert_eq!(env.balance(&p.treasury.token).await, 0);
+}
+
+#[tokio::test]
+async fn guild_payouts_are_split_net_of_the_fee() {
+    let mut env = Env::new().await;
+    let p = parties(&mut env).await;
+    env.set_fees(fees(p.treasury.pubkey(), 0, 0)).await;
+    let member = env.agent(0).await;
+    let ix = registry::create_guild(
+        &p.agent.pubkey(),
+        registry_ix::CreateGuild {
+            guild_id: 0,
+            metadata_uri: URI.to_string(),
+            metadata_hash: HASH,
+        },
+    );
+    env.send(&[ix], &[&p.agent.keypair]).await.unwrap();
+    let guild = pda::guild(&p.agent.pubkey(), 0);
+    let ix = registry::add_guild_member(&guild, &p.agent.pubkey(), &member.pubkey());
+    env.send(&[ix], &[&p.agent.keypair, &member.keypair]).await.unwrap();
+    let ix = registry::set_guild_split(&guild, &p.agent.pubkey(), vec![5_000, 5_000]);
+    env.send(&[ix], &[&p.agent.keypair]).await.unwrap();
+
+    let bounty = env.open_bounty(&p.client, 10_000).await;
+    let state: Bounty = env.fetch(&bounty).await;
+    let ix = escrow::claim_guild_bounty(&bounty, &state, &guild, &p.agent.pubkey());
+    env.send(&[ix], &[&p.agent.keypair]).await.unwrap();
+    let state: Bounty = env.fetch(&bounty).await;
+    let ix = escrow::submit_guild_work(&bounty, &state, &member.pubkey(), URI.to_string(), HASH);
+    env.send(&[ix], &[&member.keypair]).await.unwrap();
+
+    let state: Bounty = env.fetch(&bounty).await;
+    let guild_state: Guild = env.fetch(&guild).await;
+    let tokens = [p.agent.token, member.token];
+    let payout = escrow::PayoutAccounts {
+        treasury: Some(p.treasury.token),
+        client_referrer: Some(p.client_referrer.token),
+        ..Default::default()
+    };
+    let ix = escrow::approve_guild_work(
+        &bounty,
+        &state,
+        &guild_state,
+        &tokens,
+        &escrow::PayoutAccounts {
+            treasury: None,
+            ..payout.clone()
+        },
+    );
+    assert_error(
+        env.send(&[ix], &[&p.client.keypair]).await,
+        BountyError::TreasuryAccountRequired,
+    );
+    let ix = escrow::approve_guild_work(&bounty, &state, &guild_state, &tokens, &payout);
+    env.send(&[ix], &[&p.client.keypair]).await.unwrap();
+
+    // The guild has no referrer, so the agent referrer's quarter stays with the treasury
+    assert_eq!(env.balance(&p.agent.token).await, 4_500);
+    assert_eq!(env.balance(&member.token).await, 4_500);
+    assert_eq!(env.balance(&p.agent_referrer.token).await, 0);
+    assert_eq!(env.balance(&p.client_referrer.token).await, 250);
+    assert_eq!(env.balance(&p.treasury.token).await, 750);
+    let guild_state: Guild = env.fetch(&guild).await;
+    assert_eq!(guild_state.total_earned, 9_000);
+    let client_state: ClientState = env.fetch(&pda::client(&p.client.pubkey())).await;
+    assert_eq!(client_state.referral_paid, 250);
+}
+
+#[tokio::test]
+async fn referral_rewards_stop_at_cap() {
+    let mut env = Env::new().await;
+    let p = parties(&mut env).await;
+    env.set_fees(fees(p.treasury.pubkey(), 300, 0)).await;
+
+    env.completed_bounty(&p.client, &p.agent, 10_000).await;
+    env.completed_bounty(&p.client, &p.agent, 10_000).await;
+    assert_eq!(env.balance(&p.agent_referrer.token).await, 300);
+    assert_eq!(env.balance(&p.client_referrer.token).await, 300);
+    assert_eq!(env.balance(&p.treasury.token).await, 2_000 - 600);
+
+    // Capped referrers need no token account
+    let bounty = env.delivered_bounty(&p.client, &p.agent, 10_000).await;
+    let state: Bounty = env.fetch(&bounty).await;
+    let payout = escrow::PayoutAccounts {
+        agent_referrer: None,
+        client_referrer: None,
+        ..env.payout_accounts(&bounty).await
+    };
+    let ix = escrow::approve_work(&bounty, &state, &payout);
+    env.send(&[ix], &[&p.client.keypair]).await.unwrap();
+    assert_eq!(env.balance(&p.treasury.token).await, 3_000 - 600);
+    let agent: Agent = env.fetch(&p.agent.agent()).await;
+    assert_eq!(agent.referral_paid, 300);
+}
+
+#[tokio::test]
+async fn referral_rewards_expire() {
+    let mut env = Env::new().await;
+    let p = parties(&mut env).await;
+    env.set_fees(fees(p.treasury.pubkey(), 0, 3600)).await;
+
+    env.completed_bounty(&p.client, &p.agent, 10_000).await;
+    assert_eq!(env.balance(&p.agent_referrer.token).await, 250);
+
+    env.warp(3600).await;
+    env.completed_bounty(&p.client, &p.agent, 10_000).await;
+    assert_eq!(env.balance(&p.agent_referrer.token).await, 250);
+    assert_eq!(env.balance(&p.client_referrer.token).await, 250);
+    assert_eq!(env.balance(&p.treasury.token).await, 500 + 1_000);
+    assert_eq!(env.balance(&p.agent.token).await, 18_000);
+}
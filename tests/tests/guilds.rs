@@ -0,0 +1,404 @@
+//! Guild membership, guild bounty claims and split payouts.
+
+use agent_registry::instruction as registry_ix;
+use agent_registry::{AgentError, GuildRole, MAX_GUILD_MEMBERS};
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::Pubkey;
+use bounty_escrow::instruction as escrow_ix;
+use bounty_escrow::BountyError;
+use clawedwork_client::accounts::{Agent, Bounty, Guild};
+use clawedwork_client::status::BountyStatus;
+use clawedwork_client::{escrow, pda, registry};
+use clawedwork_tests::*;
+use solana_sdk::signature::Signer;
+
+/// Registers the founder's guild 0.
+async fn create_guild(env: &mut Env, founder: &User) -> TxResult {
+    let ix = registry::create_guild(
+        &founder.pubkey(),
+        registry_ix::CreateGuild {
+            guild_id: 0,
+            metadata_uri: URI.to_string(),
+            metadata_hash: HASH,
+        },
+    );
+    env.send(&[ix], &[&founder.keypair]).await
+}
+
+async fn add_member(env: &mut Env, guild: &Pubkey, admin: &User, member: &User) -> TxResult {
+    let ix = registry::add_guild_member(guild, &admin.pubkey(), &member.pubkey());
+    env.send(&[ix], &[&admin.keypair, &member.keypair]).await
+}
+
+/// `size` registered agents in one guild, the first of them its founder.
+async fn guild_of(env: &mut Env, size: usize) -> (Pubkey, Vec<User>) {
+    let mut members = Vec::new();
+    for _ in 0..size {
+        members.push(env.agent(SKILL_RUST).await);
+    }
+    create_guild(env, &members[0]).await.unwrap();
+    let guild = pda::guild(&members[0].pubkey(), 0);
+    for member in &members[1..] {
+        add_member(env, &guild, &members[0], member).await.unwrap();
+    }
+    (guild, members)
+}
+
+#[tokio::test]
+async fn membership_is_managed_by_admins() {
+    let mut env = Env::new().await;
+    let (guild, members) = guild_of(&mut env, MAX_GUILD_MEMBERS).await;
+    let founder = &members[0];
+    let state: Guild = env.fetch(&guild).await;
+    assert_eq!(state.members.len(), MAX_GUILD_MEMBERS);
+    assert_eq!(state.members[0].share_bps, 10_000);
+    assert_eq!(state.members[0].role, GuildRole::Admin);
+    let profile: Agent = env.fetch(&members[1].agent()).await;
+    assert_eq!(profile.guild, guild);
+
+    let extra = env.agent(0).await;
+    assert_error(
+        add_member(&mut env, &guild, founder, &extra).await,
+        AgentError::GuildFull,
+    );
+    assert_error(
+        create_guild(&mut env, &members[1]).await,
+        AgentError::InGuild,
+    );
+    let ix = registry::deregister_agent(&members[1].pubkey(), false);
+    assert_error(
+        env.send(&[ix], &[&members[1].keypair]).await,
+        AgentError::InGuild,
+    );
+
+    // Members cannot manage the guild
+    let ix = registry::remove_guild_member(&guild, &members[1].pubkey(), &members[2].agent());
+    assert_error(
+        env.send(&[ix], &[&members[1].keypair]).await,
+        AgentError::NotGuildAdmin,
+    );
+    let ix = registry::set_guild_split(&guild, &members[1].pubkey(), vec![1_250; 8]);
+    assert_error(
+        env.send(&[ix], &[&members[1].keypair]).await,
+        AgentError::NotGuildAdmin,
+    );
+
+    let ix = registry::remove_guild_member(&guild, &founder.pubkey(), &founder.agent());
+    assert_error(
+        env.send(&[ix], &[&founder.keypair]).await,
+        AgentError::CannotRemoveFounder,
+    );
+    let ix = registry::remove_guild_member(&guild, &founder.pubkey(), &extra.agent());
+    assert_error(
+        env.send(&[ix], &[&founder.keypair]).await,
+        AgentError::NotGuildMember,
+    );
+
+    // Roles outside GuildRole do not decode
+    let mut ix = registry::set_guild_role(
+        &guild,
+        &founder.pubkey(),
+        &members[1].agent(),
+        GuildRole::Member,
+    );
+    *ix.data.last_mut().unwrap() = 2;
+    assert_error(
+        env.send(&[ix], &[&founder.keypair]).await,
+        ErrorCode::InstructionDidNotDeserialize,
+    );
+    for (role, agent, error) in [
+        (
+            GuildRole::Member,
+            founder.agent(),
+            AgentError::CannotRemoveFounder,
+        ),
+        (GuildRole::Admin, extra.agent(), AgentError::NotGuildMember),
+    ] {
+        let ix = registry::set_guild_role(&guild, &founder.pubkey(), &agent, role);
+        assert_error(env.send(&[ix], &[&founder.keypair]).await, error);
+    }
+    for shares in [
+        vec![1_250; 7],
+        vec![1_000; 8],
+        vec![5_000, 5_001, 0, 0, 0, 0, 0, 0],
+    ] {
+        let ix = registry::set_guild_split(&guild, &founder.pubkey(), shares);
+        assert_error(
+            env.send(&[ix], &[&founder.keypair]).await,
+            AgentError::InvalidGuildSplit,
+        );
+    }
+
+    // A promoted admin can remove members; a member can leave on their own
+    let ix = registry::set_guild_role(
+        &guild,
+        &founder.pubkey(),
+        &members[1].agent(),
+        GuildRole::Admin,
+    );
+    env.send(&[ix], &[&founder.keypair]).await.unwrap();
+    let ix = registry::set_guild_split(&guild, &founder.pubkey(), vec![1_250; 8]);
+    env.send(&[ix], &[&founder.keypair]).await.unwrap();
+    let ix = registry::remove_guild_member(&guild, &members[1].pubkey(), &members[2].agent());
+    env.send(&[ix], &[&members[1].keypair]).await.unwrap();
+    let ix = registry::remove_guild_member(&guild, &members[3].pubkey(), &members[3].agent());
+    env.send(&[ix], &[&members[3].keypair]).await.unwrap();
+
+    let state: Guild = env.fetch(&guild).await;
+    assert_eq!(state.members.len(), MAX_GUILD_MEMBERS - 2);
+    assert_eq!(state.members[0].share_bps, 3 * 1_250);
+    let profile: Agent = env.fetch(&members[3].agent()).await;
+    assert_eq!(profile.guild, Pubkey::default());
+    add_member(&mut env, &guild, &members[1], &extra)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn founder_closes_guild_to_leave() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let (guild, members) = guild_of(&mut env, 2).await;
+    let [founder, member] = &members[..] else {
+        unreachable!()
+    };
+
+    let ix = registry::close_guild(&guild, &founder.pubkey());
+    assert_error(
+        env.send(&[ix], &[&founder.keypair]).await,
+        AgentError::GuildHasMembers,
+    );
+    let ix = registry::remove_guild_member(&guild, &member.pubkey(), &member.agent());
+    env.send(&[ix], &[&member.keypair]).await.unwrap();
+    let mut ix = registry::close_guild(&guild, &member.pubkey());
+    ix.accounts[0].pubkey = guild;
+    assert_error(
+        env.send(&[ix], &[&member.keypair]).await,
+        AgentError::NotGuildAdmin,
+    );
+
+    // Not while the guild has a claim in flight
+    let bounty = env.open_bounty(&client, 1_000_000).await;
+    let state: Bounty = env.fetch(&bounty).await;
+    let ix = escrow::claim_guild_bounty(&bounty, &state, &guild, &founder.pubkey());
+    env.send(&[ix], &[&founder.keypair]).await.unwrap();
+    let ix = registry::close_guild(&guild, &founder.pubkey());
+    assert_error(
+        env.send(&[ix], &[&founder.keypair]).await,
+        AgentError::ActiveClaims,
+    );
+
+    // A sole founder closes its guild, gets the rent back and can deregister
+    create_guild(&mut env, member).await.unwrap();
+    let own_guild = pda::guild(&member.pubkey(), 0);
+    let rent = env.lamports(&own_guild).await;
+    let before = env.lamports(&member.pubkey()).await;
+    let ix = registry::close_guild(&own_guild, &member.pubkey());
+    env.send(&[ix], &[&member.keypair]).await.unwrap();
+    assert!(!env.exists(&own_guild).await);
+    assert_eq!(env.lamports(&member.pubkey()).await, before + rent);
+    let profile: Agent = env.fetch(&member.agent()).await;
+    assert_eq!(profile.guild, Pubkey::default());
+    let ix = registry::deregister_agent(&member.pubkey(), false);
+    env.send(&[ix], &[&member.keypair]).await.unwrap();
+}
+
+#[tokio::test]
+async fn guild_bounty_pays_by_split() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let (guild, members) = guild_of(&mut env, 3).await;
+    let [founder, member, junior] = &members[..] else {
+        unreachable!()
+    };
+    let outsider = env.agent(SKILL_RUST).await;
+    let ix = registry::set_guild_split(&guild, &founder.pubkey(), vec![5_000, 3_000, 2_000]);
+    env.send(&[ix], &[&founder.keypair]).await.unwrap();
+
+    let bounty = env.open_bounty(&client, 1_001).await;
+    let state: Bounty = env.fetch(&bounty).await;
+    let ix = escrow::claim_guild_bounty(&bounty, &state, &guild, &member.pubkey());
+    assert_error(
+        env.send(&[ix], &[&member.keypair]).await,
+        BountyError::NotGuildAdmin,
+    );
+    let ix = escrow::claim_guild_bounty(&bounty, &state, &guild, &outsider.pubkey());
+    assert_error(
+        env.send(&[ix], &[&outsider.keypair]).await,
+        BountyError::NotGuildMember,
+    );
+    let ix = escrow::claim_guild_bounty(&bounty, &state, &guild, &founder.pubkey());
+    env.send(&[ix], &[&founder.keypair]).await.unwrap();
+
+    let state: Bounty = env.fetch(&bounty).await;
+    assert_eq!(state.assigned_agent, guild);
+    assert_eq!(state.guild, guild);
+    let guild_state: Guild = env.fetch(&guild).await;
+    assert_eq!(guild_state.active_claims, 1);
+
+    // Membership and shares are frozen while the guild has work in flight
+    let ix = registry::set_guild_split(&guild, &founder.pubkey(), vec![10_000, 0, 0]);
+    assert_error(
+        env.send(&[ix], &[&founder.keypair]).await,
+        AgentError::ActiveClaims,
+    );
+    let ix = registry::remove_guild_member(&guild, &junior.pubkey(), &junior.agent());
+    assert_error(
+        env.send(&[ix], &[&junior.keypair]).await,
+        AgentError::ActiveClaims,
+    );
+
+    let ix = escrow::submit_guild_work(&bounty, &state, &outsider.pubkey(), URI.to_string(), HASH);
+    assert_error(
+        env.send(&[ix], &[&outsider.keypair]).await,
+        BountyError::NotGuildMember,
+    );
+    let ix = escrow::submit_guild_work(&bounty, &state, &junior.pubkey(), URI.to_string(), HASH);
+    env.send(&[ix], &[&junior.keypair]).await.unwrap();
+
+    let state: Bounty = env.fetch(&bounty).await;
+    let tokens: Vec<Pubkey> = members.iter().map(|member| member.token).collect();
+    let no_fee = escrow::PayoutAccounts::default();
+    let ix = escrow::approve_guild_work(&bounty, &state, &guild_state, &tokens[..2], &no_fee);
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::GuildMembersMismatch,
+    );
+    let swapped = [tokens[1], tokens[0], tokens[2]];
+    let ix = escrow::approve_guild_work(&bounty, &state, &guild_state, &swapped, &no_fee);
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::GuildMembersMismatch,
+    );
+    let ix = escrow::approve_guild_work(&bounty, &state, &guild_state, &tokens, &no_fee);
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+
+    // Rounding dust goes to the founder
+    assert_eq!(env.balance(&founder.token).await, 501);
+    assert_eq!(env.balance(&member.token).await, 300);
+    assert_eq!(env.balance(&junior.token).await, 200);
+    let state: Bounty = env.fetch(&bounty).await;
+    assert_eq!(state.status, BountyStatus::Completed);
+    assert_eq!(env.balance(&state.vault).await, 0);
+    let guild_state: Guild = env.fetch(&guild).await;
+    assert_eq!(guild_state.active_claims, 0);
+    assert_eq!(guild_state.total_earned, 1_001);
+    let profile: Agent = env.fetch(&member.agent()).await;
+    assert_eq!(profile.total_earned, 300);
+
+    let review = |rating| escrow_ix::LeaveGuildReview {
+        rating,
+        comment_uri: URI.to_string(),
+        comment_hash: HASH,
+    };
+    let mut ix = escrow::leave_guild_review(&bounty, &state, &guild_state, review(400));
+    ix.accounts.pop();
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::GuildMembersMismatch,
+    );
+    let ix = escrow::leave_guild_review(&bounty, &state, &guild_state, review(0));
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::InvalidRating,
+    );
+    let ix = escrow::leave_guild_review(&bounty, &state, &guild_state, review(400));
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+
+    let guild_state: Guild = env.fetch(&guild).await;
+    assert_eq!(guild_state.reputation, 400);
+    assert_eq!(guild_state.bounties_completed, 1);
+    for member in &members {
+        let profile: Agent = env.fetch(&member.agent()).await;
+        assert_eq!(profile.reputation, 400);
+        assert_eq!(profile.bounties_completed, 1);
+    }
+}
+
+#[tokio::test]
+async fn disputed_guild_bounty_is_split_by_resolution() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let (guild, members) = guild_of(&mut env, 2).await;
+    let founder = &members[0];
+    let ix = registry::set_guild_split(&guild, &founder.pubkey(), vec![5_000, 5_000]);
+    env.send(&[ix], &[&founder.keypair]).await.unwrap();
+
+    let bounty = env.open_bounty(&client, 1_000).await;
+    let state: Bounty = env.fetch(&bounty).await;
+    let ix = escrow::claim_guild_bounty(&bounty, &state, &guild, &founder.pubkey());
+    env.send(&[ix], &[&founder.keypair]).await.unwrap();
+    let ix = escrow::dispute_bounty(&bounty, &client.pubkey());
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+
+    let state: Bounty = env.fetch(&bounty).await;
+    let guild_state: Guild = env.fetch(&guild).await;
+    let tokens: Vec<Pubkey> = members.iter().map(|member| member.token).collect();
+    let client_before = env.balance(&client.token).await;
+    let ix = escrow::resolve_guild_dispute(
+        &bounty,
+        &state,
+        &guild_state,
+        &env.admin.pubkey(),
+        &client.token,
+        &tokens,
+        &escrow::PayoutAccounts::default(),
+        600,
+    );
+    env.send_as_admin(&[ix]).await.unwrap();
+
+    for member in &members {
+        assert_eq!(env.balance(&member.token).await, 300);
+    }
+    assert_eq!(env.balance(&client.token).await, client_before + 400);
+    let state: Bounty = env.fetch(&bounty).await;
+    assert_eq!(state.status, BountyStatus::Completed);
+    let guild_state: Guild = env.fetch(&guild).await;
+    assert_eq!(guild_state.active_claims, 0);
+    assert_eq!(guild_state.total_earned, 600);
+}
+
+#[tokio::test]
+async fn guild_instructions_need_a_guild_bounty() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let (guild, members) = guild_of(&mut env, 2).await;
+    let guild_state: Guild = env.fetch(&guild).await;
+    let solo = env.agent(0).await;
+
+    // A bounty claimed by a single agent is not the guild's to submit or approve
+    let bounty = env.open_bounty(&client, 1_000).await;
+    env.claim(&bounty, &solo).await.unwrap();
+    let mut state: Bounty = env.fetch(&bounty).await;
+    state.assigned_agent = guild;
+    let ix =
+        escrow::submit_guild_work(&bounty, &state, &members[1].pubkey(), URI.to_string(), HASH);
+    assert_error(
+        env.send(&[ix], &[&members[1].keypair]).await,
+        BountyError::NotGuildBounty,
+    );
+    env.submit(&bounty, &solo).await.unwrap();
+    let tokens: Vec<Pubkey> = members.iter().map(|member| member.token).collect();
+    let no_fee = escrow::PayoutAccounts::default();
+    let ix = escrow::approve_guild_work(&bounty, &state, &guild_state, &tokens, &no_fee);
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::NotGuildBounty,
+    );
+    env.approve(&bounty, &client).await.unwrap();
+    let ix = escrow::leave_guild_review(
+        &bounty,
+        &state,
+        &guild_state,
+        escrow_ix::LeaveGuildReview {
+            rating: 500,
+            comment_uri: URI.to_string(),
+            comment_hash: HASH,
+        },
+    );
+    assert_error(
+        env.send(&[ix], &[&client.keypair]).await,
+        BountyError::NotGuildBounty,
+    );
+    assert_eq!(env.balance(&solo.token).await, 1_000);
+}
@@ -0,0 +1,264 @@
+//! The indexer's SQLite store and query API fed with accounts from a local validator.
+
+use agent_registry::instruction as registry_ix;
+use anchor_lang::prelude::Pubkey;
+use bounty_escrow::instruction as escrow_ix;
+use clawedwork_client::accounts::Bounty;
+use clawedwork_client::status::DeclinePolicy;
+use clawedwork_client::{escrow, pda, registry};
+use clawedwork_indexer::api::{self, ApiError};
+use clawedwork_indexer::{RawAccount, Store};
+use clawedwork_tests::*;
+use serde_json::Value;
+use solana_sdk::signature::Signer;
+
+/// Raw data of the existing `addresses`, as a program-account snapshot returns it.
+async fn snapshot(env: &mut Env, addresses: &[Pubkey]) -> Vec<RawAccount> {
+    let mut accounts = Vec::new();
+    for address in addresses {
+        if let Some(account) = env.ctx.banks_client.get_account(*address).await.unwrap() {
+            accounts.push((*address, account.data));
+        }
+    }
+    accounts
+}
+
+fn get(store: &Store, url: &str) -> Value {
+    api::handle(store, url).unwrap()
+}
+
+/// Addresses of the bounties listed at `url`, in response order.
+fn listed(store: &Store, url: &str) -> Vec<String> {
+    get(store, url)
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|bounty| bounty["address"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn indexer_answers_queries_from_snapshots() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(SKILL_RUST).await;
+    let treasury = env.user(0).await;
+    env.set_fees(escrow_ix::SetFees {
+        fee_bps: 1_000,
+        treasury: treasury.pubkey(),
+        referral_share_bps: 0,
+        referral_cap: 0,
+        referral_duration: 0,
+    })
+    .await;
+
+    let mut rust_args = env.bounty_args(5_000_000).await;
+    rust_args.skills = SKILL_RUST;
+    let small = env.create_bounty(&client, rust_args).await;
+    let mut rust_args = env.bounty_args(50_000_000).await;
+    rust_args.skills = SKILL_RUST;
+    let large = env.create_bounty(&client, rust_args).await;
+    let mut design_args = env.bounty_args(20_000_000).await;
+    design_args.skills = SKILL_DESIGN;
+    let design = env.create_bounty(&client, design_args).await;
+
+    let done = env.completed_bounty(&client, &agent, 10_000_000).await;
+    let state: Bounty = env.fetch(&done).await;
+    let ix = escrow::leave_review(
+        &done,
+        &state,
+        escrow_ix::LeaveReview {
+            rating: 450,
+            comment_uri: URI.to_string(),
+            comment_hash: HASH,
+        },
+        &[],
+    );
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let ix = escrow::tip_agent(&done, &state, &client.token, &agent.token, 1_000_000, &[]);
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+
+    let cancelled = env.open_bounty(&client, 7_000_000).await;
+    let state: Bounty = env.fetch(&cancelled).await;
+    let ix = escrow::cancel_bounty(&cancelled, &state, &client.token);
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+
+    // A dispute resolved with a quarter of the budget to another agent
+    let other = env.agent(0).await;
+    let resolved = env.delivered_bounty(&client, &other, 4_000_000).await;
+    let ix = escrow::dispute_bounty(&resolved, &client.pubkey());
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let state: Bounty = env.fetch(&resolved).await;
+    let payout = env.payout_accounts(&resolved).await;
+    let admin = env.admin.pubkey();
+    let ix = escrow::resolve_dispute(
+        &resolved,
+        &state,
+        &admin,
+        &client.token,
+        &payout,
+        &[],
+        1_000_000,
+    );
+    env.send_as_admin(&[ix]).await.unwrap();
+
+    // Three unopened periods of a recurring bounty stay in escrow
+    let ix = escrow::create_recurring_bounty(
+        &client.pubkey(),
+        &client.token,
+        &env.mint,
+        escrow_ix::CreateRecurringBounty {
+            template_id: 0,
+            metadata_uri: URI.to_string(),
+            metadata_hash: HASH,
+            budget_per_period: 1_000_000,
+            period_secs: 3600,
+            total_periods: 3,
+            first_open_at: 0,
+            skills: 0,
+            require_skill_match: false,
+            allowlist: Vec::new(),
+            decline_policy: DeclinePolicy::Refund,
+        },
+    );
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let ix = registry::create_guild(
+        &agent.pubkey(),
+        registry_ix::CreateGuild {
+            guild_id: 0,
+            metadata_uri: URI.to_string(),
+            metadata_hash: HASH,
+        },
+    );
+    env.send(&[ix], &[&agent.keypair]).await.unwrap();
+    let guild = pda::guild(&agent.pubkey(), 0);
+
+    let bounties = [small, large, design, done, cancelled, resolved];
+    let mut addresses: Vec<Pubkey> = bounties.to_vec();
+    addresses.extend(bounties.iter().map(pda::history));
+    addresses.extend([
+        agent.agent(),
+        pda::client(&client.pubkey()),
+        pda::review(&done),
+        pda::escrow_config(),
+        pda::recurring(&client.pubkey(), 0),
+        guild,
+        // Accounts the indexer does not track are skipped
+        pda::index_page(bounty_escrow::BountyStatus::Open, 0, 0),
+    ]);
+    let accounts = snapshot(&mut env, &addresses).await;
+    assert_eq!(accounts.len(), addresses.len());
+
+    let mut store = Store::open_in_memory().unwrap();
+    assert_eq!(get(&store, "/status"), Value::Null);
+    assert_eq!(store.apply(1, &accounts).unwrap(), addresses.len() - 1);
+    assert_eq!(get(&store, "/status")["slot"], 1);
+    assert_eq!(get(&store, "/status")["changed"], addresses.len() - 1);
+
+    // Re-applying the same data rewrites nothing
+    assert_eq!(store.apply(2, &accounts).unwrap(), addresses.len() - 1);
+    assert_eq!(get(&store, "/status")["slot"], 2);
+    assert_eq!(get(&store, "/status")["changed"], 0);
+
+    // Open bounties, largest budget first
+    let key = |bounty: &Pubkey| bounty.to_string();
+    assert_eq!(
+        listed(&store, "/bounties/open"),
+        [key(&large), key(&design), key(&small)]
+    );
+    assert_eq!(
+        listed(&store, "/bounties/open?skill=0"),
+        [key(&large), key(&small)]
+    );
+    assert_eq!(
+        listed(&store, "/bounties/open?skill=0&max_budget=10000000"),
+        [key(&small)]
+    );
+    assert_eq!(
+        listed(&store, "/bounties/open?min_budget=10000000&max_budget=20000000"),
+        [key(&design)]
+    );
+    assert_eq!(listed(&store, "/bounties/open?limit=1"), [key(&large)]);
+    assert!(listed(&store, "/bounties/open?skill=1").is_empty());
+
+    // Agent history
+    let history = get(&store, &format!("/agents/{}/history", agent.pubkey()));
+    assert_eq!(history["agent"]["address"], agent.agent().to_string());
+    assert_eq!(history["agent"]["owner"], agent.pubkey().to_string());
+    assert_eq!(history["agent"]["guild"], guild.to_string());
+    assert_eq!(history["bounties"][0]["address"], key(&done));
+    assert_eq!(history["bounties"][0]["status"], "completed");
+    assert_eq!(history["bounties"][0]["tips_total"], 1_000_000);
+    assert_eq!(history["reviews"][0]["rating"], 450);
+    assert_eq!(history["reviews"][0]["reviewer"], client.pubkey().to_string());
+    let activity: Vec<&str> = history["activity"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|change| change["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(activity, ["claimed", "delivered"]);
+
+    // Client spend: payouts net of the 10% fee, the resolved dispute counting
+    // only what it awarded, and recurring funds in escrow
+    let spend = get(&store, &format!("/clients/{}/spend", client.pubkey()));
+    assert_eq!(spend["bounties_posted"], 6);
+    assert_eq!(spend["bounties_completed"], 2);
+    assert_eq!(spend["bounty_payouts"], 9_900_000);
+    assert_eq!(spend["platform_fees"], 1_100_000);
+    assert_eq!(spend["tips"], 1_000_000);
+    assert_eq!(spend["total_spent"], 12_000_000);
+    assert_eq!(spend["in_escrow"], 78_000_000);
+    assert_eq!(spend["refunded"], 10_000_000);
+
+    // Guilds with their members
+    let state = get(&store, &format!("/guilds/{guild}"));
+    assert_eq!(state["guild"]["creator"], agent.pubkey().to_string());
+    assert_eq!(state["members"][0]["agent"], agent.agent().to_string());
+    assert_eq!(state["members"][0]["role"], "admin");
+    assert_eq!(state["members"][0]["share_bps"], 10_000);
+    assert!(state["bounties"].as_array().unwrap().is_empty());
+
+    // A later snapshot replaces account state but keeps status changes whose
+    // history account is gone, and payouts at the fee they were first seen with
+    env.claim(&large, &agent).await.unwrap();
+    env.set_fees(escrow_ix::SetFees {
+        fee_bps: 0,
+        treasury: Pubkey::default(),
+        referral_share_bps: 0,
+        referral_cap: 0,
+        referral_duration: 0,
+    })
+    .await;
+    let gone = [agent.agent(), pda::history(&done)];
+    addresses.retain(|address| !gone.contains(address));
+    let accounts = snapshot(&mut env, &addresses).await;
+    store.apply(3, &accounts).unwrap();
+    // The claimed bounty, its history, the fee config and the two closed accounts
+    assert_eq!(get(&store, "/status")["slot"], 3);
+    assert_eq!(get(&store, "/status")["changed"], 5);
+    assert_eq!(listed(&store, "/bounties/open?skill=0"), [key(&small)]);
+    let history = get(&store, &format!("/agents/{}/history", agent.pubkey()));
+    assert_eq!(history["agent"], Value::Null);
+    assert_eq!(history["activity"].as_array().unwrap().len(), 3);
+    let spend = get(&store, &format!("/clients/{}/spend", client.pubkey()));
+    assert_eq!(spend["platform_fees"], 1_100_000);
+
+    // Errors
+    assert!(matches!(
+        api::handle(&store, "/bounties"),
+        Err(ApiError::NotFound)
+    ));
+    assert!(matches!(
+        api::handle(&store, &format!("/guilds/{}", Pubkey::new_unique())),
+        Err(ApiError::NotFound)
+    ));
+    assert!(matches!(
+        api::handle(&store, "/agents/not-a-key/history"),
+        Err(ApiError::BadRequest(_))
+    ));
+    assert!(matches!(
+        api::handle(&store, "/bounties/open?limit=many"),
+        Err(ApiError::BadRequest(_))
+    ));
+}
@@ -0,0 +1,423 @@
+//! Bounty lifecycle from posting to review, including the agent-registry side effects.
+
+use agent_registry::{DELEGATE_CLAIM, DELEGATE_SUBMIT};
+use bounty_escrow::instruction as escrow_ix;
+use bounty_escrow::{AttestationRequirement, BountyError, ValidationRequirement};
+use clawedwork_client::accounts::{Agent, Bounty, BountyHistory, ClientState, Review};
+use clawedwork_client::status::{BountyStatus, DeclinePolicy};
+use clawedwork_client::{escrow, pda, registry};
+use clawedwork_tests::*;
+use solana_sdk::signature::Signer;
+
+const BUDGET: u64 = 100_000_000;
+
+#[tokio::test]
+async fn bounty_runs_from_open_to_completed() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(SKILL_RUST).await;
+
+    let bounty = env.open_bounty(&client, BUDGET).await;
+    let state: Bounty = env.fetch(&bounty).await;
+    assert_eq!(state.status, BountyStatus::Open);
+    assert_eq!(state.budget, BUDGET);
+    assert_eq!(env.balance(&state.vault).await, BUDGET);
+    assert_eq!(env.balance(&client.token).await, STARTING_USDC - BUDGET);
+    let client_state: ClientState = env.fetch(&pda::client(&client.pubkey())).await;
+    assert_eq!(client_state.bounty_count, 1);
+
+    env.claim(&bounty, &agent).await.unwrap();
+    let state: Bounty = env.fetch(&bounty).await;
+    assert_eq!(state.status, BountyStatus::Claimed);
+    assert_eq!(state.assigned_agent, agent.pubkey());
+    assert_eq!(state.claims, 1);
+    let profile: Agent = env.fetch(&agent.agent()).await;
+    assert_eq!(profile.active_claims, 1);
+
+    env.submit(&bounty, &agent).await.unwrap();
+    let state: Bounty = env.fetch(&bounty).await;
+    assert_eq!(state.status, BountyStatus::Delivered);
+    assert_eq!(state.deliverable_uri, URI);
+    assert_eq!(state.deliverable_hash, HASH);
+
+    env.approve(&bounty, &client).await.unwrap();
+    let state: Bounty = env.fetch(&bounty).await;
+    assert_eq!(state.status, BountyStatus::Completed);
+    assert_eq!(env.balance(&state.vault).await, 0);
+    assert_eq!(env.balance(&agent.token).await, BUDGET);
+    let profile: Agent = env.fetch(&agent.agent()).await;
+    assert_eq!(profile.active_claims, 0);
+}
+
+#[tokio::test]
+async fn status_changes_are_audited() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+    let bounty = env.open_bounty(&client, BUDGET).await;
+    let created_at = env.now().await;
+
+    env.warp(60).await;
+    env.claim(&bounty, &agent).await.unwrap();
+    let claimed_at = env.now().await;
+    env.warp(60).await;
+    env.submit(&bounty, &agent).await.unwrap();
+    let delivered_at = env.now().await;
+    env.warp(60).await;
+    let ix = escrow::dispute_bounty(&bounty, &client.pubkey());
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let disputed_at = env.now().await;
+
+    let state: Bounty = env.fetch(&bounty).await;
+    assert_eq!(state.claimed_at, claimed_at);
+    assert_eq!(state.delivered_at, delivered_at);
+    assert_eq!(state.disputed_at, disputed_at);
+    assert_eq!(state.completed_at, 0);
+    assert_eq!(state.cancelled_at, 0);
+    assert_eq!(state.last_actor, client.pubkey());
+
+    let history: BountyHistory = env.fetch(&pda::history(&bounty)).await;
+    assert_eq!(history.bounty, bounty);
+    assert_eq!(history.total, 4);
+    let entries: Vec<_> = history
+        .entries
+        .iter()
+        .map(|change| (change.status, change.actor, change.at))
+        .collect();
+    assert_eq!(
+        entries,
+        [
+            (BountyStatus::Open, client.pubkey(), created_at),
+            (BountyStatus::Claimed, agent.pubkey(), claimed_at),
+            (BountyStatus::Delivered, agent.pubkey(), delivered_at),
+            (BountyStatus::Disputed, client.pubkey(), disputed_at),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn client_reviews_and_tips_completed_bounty() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+    let bounty = env.completed_bounty(&client, &agent, BUDGET).await;
+    let state: Bounty = env.fetch(&bounty).await;
+
+    let ix = escrow::leave_review(
+        &bounty,
+        &state,
+        escrow_ix::LeaveReview {
+            rating: 450,
+            comment_uri: URI.to_string(),
+            comment_hash: HASH,
+        },
+        &[],
+    );
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let review: Review = env.fetch(&pda::review(&bounty)).await;
+    assert_eq!(review.bounty, bounty);
+    assert_eq!(review.reviewer, client.pubkey());
+    assert_eq!(review.agent, agent.pubkey());
+    assert_eq!(review.rating, 450);
+    let profile: Agent = env.fetch(&agent.agent()).await;
+    assert_eq!(profile.reputation, 450);
+    assert_eq!(profile.bounties_completed, 1);
+
+    let ix = escrow::tip_agent(&bounty, &state, &client.token, &agent.token, 5_000_000, &[]);
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let state: Bounty = env.fetch(&bounty).await;
+    assert_eq!(state.tips_total, 5_000_000);
+    assert_eq!(env.balance(&agent.token).await, BUDGET + 5_000_000);
+    let profile: Agent = env.fetch(&agent.agent()).await;
+    assert_eq!(profile.total_earned, BUDGET + 5_000_000);
+}
+
+#[tokio::test]
+async fn cancelling_open_bounty_refunds_client() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let bounty = env.open_bounty(&client, BUDGET).await;
+    let state: Bounty = env.fetch(&bounty).await;
+
+    let ix = escrow::cancel_bounty(&bounty, &state, &client.token);
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+
+    let cancelled: Bounty = env.fetch(&bounty).await;
+    assert_eq!(cancelled.status, BountyStatus::Cancelled);
+    assert!(!env.exists(&state.vault).await);
+    assert_eq!(env.balance(&client.token).await, STARTING_USDC);
+}
+
+#[tokio::test]
+async fn delegate_claims_and_submits_for_owner() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+    let hot_key = env.user(0).await;
+    let ix = registry::add_delegate(
+        &agent.pubkey(),
+        &hot_key.pubkey(),
+        DELEGATE_CLAIM | DELEGATE_SUBMIT,
+    );
+    env.send(&[ix], &[&agent.keypair]).await.unwrap();
+
+    let bounty = env.open_bounty(&client, BUDGET).await;
+    let state: Bounty = env.fetch(&bounty).await;
+    let ix = escrow::claim_bounty(&bounty, &state, &hot_key.pubkey(), &agent.pubkey());
+    env.send(&[ix], &[&hot_key.keypair]).await.unwrap();
+    let ix = escrow::submit_work(
+        &bounty,
+        &hot_key.pubkey(),
+        &agent.pubkey(),
+        URI.to_string(),
+        HASH,
+    );
+    env.send(&[ix], &[&hot_key.keypair]).await.unwrap();
+
+    let state: Bounty = env.fetch(&bounty).await;
+    assert_eq!(state.assigned_agent, agent.pubkey());
+    assert_eq!(state.status, BountyStatus::Delivered);
+
+    // The payout goes to the owner, not the delegate
+    env.approve(&bounty, &client).await.unwrap();
+    assert_eq!(env.balance(&agent.token).await, BUDGET);
+    assert_eq!(env.balance(&hot_key.token).await, 0);
+}
+
+#[tokio::test]
+async fn invited_agent_accepts_offer() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let invited = env.agent(0).await;
+    let outsider = env.agent(0).await;
+
+    let mut args = env.bounty_args(BUDGET).await;
+    args.allowlist = vec![invited.pubkey()];
+    let bounty = env.create_bounty(&client, args).await;
+
+    assert_error(env.claim(&bounty, &outsider).await, BountyError::NotInvited);
+    let state: Bounty = env.fetch(&bounty).await;
+    let ix = escrow::accept_offer(&bounty, &state, &invited.pubkey(), &invited.pubkey());
+    env.send(&[ix], &[&invited.keypair]).await.unwrap();
+
+    let state: Bounty = env.fetch(&bounty).await;
+    assert_eq!(state.status, BountyStatus::Claimed);
+    assert_eq!(state.assigned_agent, invited.pubkey());
+}
+
+#[tokio::test]
+async fn last_decline_applies_decline_policy() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let invited = env.agent(0).await;
+    let other = env.agent(0).await;
+
+    // Refund: the bounty is cancelled and the budget returned
+    let mut args = env.bounty_args(BUDGET).await;
+    args.allowlist = vec![invited.pubkey()];
+    let refunded = env.create_bounty(&client, args).await;
+    let state: Bounty = env.fetch(&refunded).await;
+    let ix = escrow::decline_offer(
+        &refunded,
+        &state,
+        &invited.pubkey(),
+        &invited.pubkey(),
+        &client.token,
+    );
+    env.send(&[ix], &[&invited.keypair]).await.unwrap();
+    let state: Bounty = env.fetch(&refunded).await;
+    assert_eq!(state.status, BountyStatus::Cancelled);
+    assert!(!env.exists(&state.vault).await);
+    assert_eq!(env.balance(&client.token).await, STARTING_USDC);
+
+    // Reopen: anyone may claim once every invitee declined
+    let mut args = env.bounty_args(BUDGET).await;
+    args.allowlist = vec![invited.pubkey()];
+    args.decline_policy = DeclinePolicy::Reopen;
+    let reopened = env.create_bounty(&client, args).await;
+    let state: Bounty = env.fetch(&reopened).await;
+    let ix = escrow::decline_offer(
+        &reopened,
+        &state,
+        &invited.pubkey(),
+        &invited.pubkey(),
+        &client.token,
+    );
+    env.send(&[ix], &[&invited.keypair]).await.unwrap();
+    let state: Bounty = env.fetch(&reopened).await;
+    assert_eq!(state.status, BountyStatus::Open);
+    assert!(state.allowlist.is_empty());
+    env.claim(&reopened, &other).await.unwrap();
+}
+
+#[tokio::test]
+async fn disputed_bounty_is_frozen() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+    let bounty = env.delivered_bounty(&client, &agent, BUDGET).await;
+
+    let ix = escrow::dispute_bounty(&bounty, &agent.pubkey());
+    env.send(&[ix], &[&agent.keypair]).await.unwrap();
+    let state: Bounty = env.fetch(&bounty).await;
+    assert_eq!(state.status, BountyStatus::Disputed);
+
+    assert_error(
+        env.approve(&bounty, &client).await,
+        BountyError::NotDelivered,
+    );
+    assert_eq!(env.balance(&state.vault).await, BUDGET);
+}
+
+#[tokio::test]
+async fn admin_resolves_disputes() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+    let admin = env.admin.pubkey();
+
+    // Part of the budget to the agent, the rest back to the client
+    let bounty = env.delivered_bounty(&client, &agent, BUDGET).await;
+    let ix = escrow::dispute_bounty(&bounty, &client.pubkey());
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let state: Bounty = env.fetch(&bounty).await;
+    let payout = env.payout_accounts(&bounty).await;
+    let client_before = env.balance(&client.token).await;
+    let ix = escrow::resolve_dispute(
+        &bounty,
+        &state,
+        &admin,
+        &client.token,
+        &payout,
+        &[],
+        BUDGET / 4,
+    );
+    env.send_as_admin(&[ix]).await.unwrap();
+
+    assert_eq!(env.balance(&agent.token).await, BUDGET / 4);
+    assert_eq!(
+        env.balance(&client.token).await,
+        client_before + BUDGET - BUDGET / 4
+    );
+    let state: Bounty = env.fetch(&bounty).await;
+    assert_eq!(state.status, BountyStatus::Completed);
+    assert_eq!(state.dispute_payout, BUDGET / 4);
+    assert_eq!(state.last_actor, admin);
+    let profile: Agent = env.fetch(&agent.agent()).await;
+    assert_eq!(profile.active_claims, 0);
+
+    // Nothing to the agent refunds everything and cancels the bounty
+    let bounty = env.open_bounty(&client, BUDGET).await;
+    env.claim(&bounty, &agent).await.unwrap();
+    let ix = escrow::dispute_bounty(&bounty, &agent.pubkey());
+    env.send(&[ix], &[&agent.keypair]).await.unwrap();
+    let state: Bounty = env.fetch(&bounty).await;
+    let client_before = env.balance(&client.token).await;
+    let ix = escrow::resolve_dispute(&bounty, &state, &admin, &client.token, &payout, &[], 0);
+    env.send_as_admin(&[ix]).await.unwrap();
+
+    assert_eq!(env.balance(&client.token).await, client_before + BUDGET);
+    assert!(!env.exists(&state.vault).await);
+    let state: Bounty = env.fetch(&bounty).await;
+    assert_eq!(state.status, BountyStatus::Cancelled);
+
+    // With its claims released the agent is free to leave
+    let ix = registry::deregister_agent(&agent.pubkey(), false);
+    env.send(&[ix], &[&agent.keypair]).await.unwrap();
+}
+
+#[tokio::test]
+async fn passing_validation_auto_approves() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+    let validator = env.user(0).await;
+
+    let mut args = env.bounty_args(BUDGET).await;
+    args.validation_requirement = Some(ValidationRequirement {
+        validator: validator.pubkey(),
+        min_score: 80,
+        auto_approve: true,
+    });
+    let bounty = env.create_bounty(&client, args).await;
+    env.claim(&bounty, &agent).await.unwrap();
+    env.submit(&bounty, &agent).await.unwrap();
+
+    let ix = registry::request_validation(&agent.pubkey(), &validator.pubkey(), &bounty, HASH);
+    env.send(&[ix], &[&agent.keypair]).await.unwrap();
+    let ix = registry::respond_validation(&validator.pubkey(), &bounty, &HASH, 79, [1u8; 32]);
+    env.send(&[ix], &[&validator.keypair]).await.unwrap();
+
+    let payout = env.payout_accounts(&bounty).await;
+    let state: Bounty = env.fetch(&bounty).await;
+    let ix = escrow::auto_approve(&bounty, &state, &payout).unwrap();
+    assert_error(
+        env.send(std::slice::from_ref(&ix), &[]).await,
+        BountyError::ValidationRequired,
+    );
+
+    // The validator revises its score to a pass; anyone may now trigger payout
+    let ix_respond =
+        registry::respond_validation(&validator.pubkey(), &bounty, &HASH, 90, [1u8; 32]);
+    env.send(&[ix_respond], &[&validator.keypair])
+        .await
+        .unwrap();
+    env.send(&[ix], &[]).await.unwrap();
+
+    let state: Bounty = env.fetch(&bounty).await;
+    assert_eq!(state.status, BountyStatus::Completed);
+    assert_eq!(env.balance(&agent.token).await, BUDGET);
+}
+
+#[tokio::test]
+async fn deadlines_and_expiries_follow_the_clock() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(SKILL_SOLANA).await;
+    let verifier = env.user(0).await;
+
+    let ix = registry::add_verifier(&env.admin.pubkey(), &verifier.pubkey());
+    env.send_as_admin(&[ix]).await.unwrap();
+    let expires_at = env.now().await + 3600;
+    let ix = registry::issue_attestation(
+        &verifier.pubkey(),
+        &verifier.pubkey(),
+        &agent.agent(),
+        agent_registry::instruction::IssueAttestation {
+            skill_id: 1,
+            expires_at,
+            evidence_hash: HASH,
+        },
+    );
+    env.send(&[ix], &[&verifier.keypair]).await.unwrap();
+
+    let requirement = AttestationRequirement {
+        verifier: verifier.pubkey(),
+        skill_id: 1,
+    };
+    let mut args = env.bounty_args(BUDGET).await;
+    args.deadline = env.now().await + 60;
+    args.attestation_requirement = Some(requirement.clone());
+    let deadline = args.deadline;
+    let before_expiry = env.create_bounty(&client, args).await;
+    env.claim(&before_expiry, &agent).await.unwrap();
+
+    // A minute later the same deadline can no longer be used
+    env.warp(61).await;
+    let mut args = env.bounty_args(BUDGET).await;
+    args.deadline = deadline;
+    assert_error(
+        env.try_create_bounty(&client, args).await,
+        BountyError::DeadlinePassed,
+    );
+
+    // Past the attestation's expiry it no longer satisfies the requirement
+    let mut args = env.bounty_args(BUDGET).await;
+    args.attestation_requirement = Some(requirement);
+    let after_expiry = env.create_bounty(&client, args).await;
+    env.warp(3600).await;
+    assert_error(
+        env.claim(&after_expiry, &agent).await,
+        BountyError::AttestationRequired,
+    );
+}
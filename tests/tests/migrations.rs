@@ -0,0 +1,350 @@
+//! Accounts written before layout versioning, or at an earlier layout version, are
+//! upgraded in place.
+
+use agent_registry::instruction as registry_ix;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorSerialize, Discriminator};
+use anchor_spl::token::spl_token;
+use anchor_spl::token::spl_token::state::AccountState;
+use clawedwork_client::accounts::{Agent, Bounty, BountyHistory, ClientState};
+use clawedwork_client::status::{AgentStatus, BountyStatus, DeclinePolicy};
+use clawedwork_client::{escrow, pda, registry};
+use clawedwork_tests::*;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+
+const LEGACY_AGENT_SPACE: usize = 8 + 32 + (4 + 200) + 8 + 8 + 8 + 8 + 1 + 1 + 8;
+const LEGACY_CLIENT_STATE_SPACE: usize = 8 + 32 + 8 + 1;
+const LEGACY_BOUNTY_SPACE: usize =
+    8 + 32 + 8 + (4 + 200) + 8 + 8 + 1 + 8 + 32 + (4 + 200) + 32 + 32 + 1 + 8;
+
+/// Borsh-encodes `fields` after `discriminator`, padded to `space` bytes.
+fn legacy_data<T: AnchorSerialize>(discriminator: [u8; 8], space: usize, fields: T) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    fields.serialize(&mut data).unwrap();
+    data.resize(space, 0);
+    data
+}
+
+#[tokio::test]
+async fn legacy_accounts_migrate_in_place() {
+    let owner = Keypair::new();
+    let client = Keypair::new();
+    let (agent, agent_bump) =
+        Pubkey::find_program_address(&[b"agent", owner.pubkey().as_ref()], &agent_registry::ID);
+    let (client_state, client_bump) =
+        Pubkey::find_program_address(&[b"client", client.pubkey().as_ref()], &bounty_escrow::ID);
+    let bounty_id = 0u64;
+    let (bounty, bounty_bump) = Pubkey::find_program_address(
+        &[
+            b"bounty",
+            client.pubkey().as_ref(),
+            &bounty_id.to_le_bytes(),
+        ],
+        &bounty_escrow::ID,
+    );
+    let vault = pda::vault(&bounty);
+    let mint = Pubkey::new_unique();
+
+    let agent_data = legacy_data(
+        Agent::DISCRIMINATOR,
+        LEGACY_AGENT_SPACE,
+        (
+            owner.pubkey(),
+            URI.to_string(),
+            40_000_000u64,
+            450u64,
+            3u64,
+            120_000_000u64,
+            1u8,
+            agent_bump,
+            1_600_000_000i64,
+        ),
+    );
+    let client_data = legacy_data(
+        ClientState::DISCRIMINATOR,
+        LEGACY_CLIENT_STATE_SPACE,
+        (client.pubkey(), 5u64, client_bump),
+    );
+    let bounty_data = legacy_data(
+        Bounty::DISCRIMINATOR,
+        LEGACY_BOUNTY_SPACE,
+        (
+            (
+                client.pubkey(),
+                bounty_id,
+                URI.to_string(),
+                2_000u64,
+                1_700_000_000i64,
+                BountyStatus::Delivered as u8,
+                1u64,
+            ),
+            (
+                owner.pubkey(),
+                "ipfs://deliverable".to_string(),
+                vault,
+                mint,
+                bounty_bump,
+                1_600_000_000i64,
+            ),
+        ),
+    );
+    let mut env = Env::with_accounts(vec![
+        (agent, raw_account(&agent_registry::ID, agent_data)),
+        (client_state, raw_account(&bounty_escrow::ID, client_data)),
+        (bounty, raw_account(&bounty_escrow::ID, bounty_data)),
+    ])
+    .await;
+    let payer = env.ctx.payer.pubkey();
+
+    let ix = registry::migrate_agent(&agent, &payer);
+    env.send(std::slice::from_ref(&ix), &[]).await.unwrap();
+    assert_error(
+        env.send(&[ix], &[]).await,
+        agent_registry::AgentError::AlreadyMigrated,
+    );
+    let migrated: Agent = env.fetch(&agent).await;
+    assert_eq!(migrated.version, agent_registry::AGENT_VERSION);
+    assert_eq!(migrated.owner, owner.pubkey());
+    assert_eq!(migrated.metadata_uri, URI);
+    assert_eq!(migrated.metadata_hash, [0u8; 32]);
+    assert_eq!(migrated.hourly_rate, 40_000_000);
+    assert_eq!(migrated.reputation, 450);
+    assert_eq!(migrated.bounties_completed, 3);
+    assert_eq!(migrated.total_earned, 120_000_000);
+    assert_eq!(migrated.availability, AgentStatus::Busy);
+    assert_eq!(migrated.created_at, 1_600_000_000);
+    assert_eq!(migrated.guild, Pubkey::default());
+
+    // The delivered bounty was claimed before claims were counted
+    let ix = escrow::migrate_bounty(&bounty, None, &payer);
+    assert_error(
+        env.send(&[ix], &[]).await,
+        bounty_escrow::BountyError::AgentProfileMismatch,
+    );
+    for ix in [
+        escrow::migrate_client_state(&client_state, &payer),
+        escrow::migrate_bounty(&bounty, Some(&owner.pubkey()), &payer),
+    ] {
+        env.send(std::slice::from_ref(&ix), &[]).await.unwrap();
+        assert_error(
+            env.send(&[ix], &[]).await,
+            bounty_escrow::BountyError::AlreadyMigrated,
+        );
+    }
+    let migrated: ClientState = env.fetch(&client_state).await;
+    assert_eq!(migrated.owner, client.pubkey());
+    assert_eq!(migrated.bounty_count, 5);
+    assert_eq!(migrated.referrer, Pubkey::default());
+    assert_eq!(migrated.created_at, env.now().await);
+    let migrated: Bounty = env.fetch(&bounty).await;
+    assert_eq!(migrated.version, bounty_escrow::BOUNTY_VERSION);
+    assert_eq!(migrated.client, client.pubkey());
+    assert_eq!(migrated.budget, 2_000);
+    assert_eq!(migrated.status, BountyStatus::Delivered);
+    assert_eq!(migrated.assigned_agent, owner.pubkey());
+    assert_eq!(migrated.deliverable_uri, "ipfs://deliverable");
+    assert_eq!(migrated.vault, vault);
+    assert_eq!(migrated.usdc_mint, mint);
+    assert_eq!(migrated.created_at, 1_600_000_000);
+    assert_eq!(migrated.parent, Pubkey::default());
+    assert!(migrated.allowlist.is_empty());
+    assert_eq!(migrated.claimed_at, 0);
+    assert_eq!(migrated.last_actor, Pubkey::default());
+    let history: BountyHistory = env.fetch(&pda::history(&bounty)).await;
+    assert_eq!(history.bounty, bounty);
+    assert_eq!(history.total, 0);
+    assert!(history.entries.is_empty());
+    let migrated: Agent = env.fetch(&agent).await;
+    assert_eq!(migrated.active_claims, 1);
+
+    // The migrated profile is a regular, writable account again
+    let ix = system_instruction::transfer(&payer, &owner.pubkey(), LAMPORTS_PER_SOL);
+    env.send(&[ix], &[]).await.unwrap();
+    let ix = registry::update_agent(
+        &owner.pubkey(),
+        registry_ix::UpdateAgent {
+            metadata_uri: Some(URI.to_string()),
+            metadata_hash: Some(HASH),
+            hourly_rate: Some(60_000_000),
+            availability: None,
+            skills: None,
+        },
+    );
+    env.send(&[ix], &[&owner]).await.unwrap();
+    let updated: Agent = env.fetch(&agent).await;
+    assert_eq!(updated.metadata_hash, HASH);
+    assert_eq!(updated.hourly_rate, 60_000_000);
+    assert_eq!(updated.reputation, 450);
+
+    // With its vault funded in the test mint, the migrated bounty can be approved,
+    // releasing the claim its migration recorded
+    let mut state: Bounty = env.fetch(&bounty).await;
+    state.usdc_mint = env.mint;
+    env.overwrite(&bounty, &state).await;
+    let mut vault_data = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint: env.mint,
+        owner: vault,
+        amount: 2_000,
+        state: AccountState::Initialized,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut vault_data);
+    env.ctx
+        .set_account(&vault, &raw_account(&spl_token::ID, vault_data).into());
+    let agent_wallet = env.wallet(owner, 0).await;
+    let client_wallet = env.wallet(client, 0).await;
+    env.approve(&bounty, &client_wallet).await.unwrap();
+    assert_eq!(env.balance(&agent_wallet.token).await, 2_000);
+    let state: Bounty = env.fetch(&bounty).await;
+    assert_eq!(state.status, BountyStatus::Completed);
+    let updated: Agent = env.fetch(&agent).await;
+    assert_eq!(updated.active_claims, 0);
+    assert_eq!(updated.total_earned, 120_002_000);
+}
+
+#[tokio::test]
+async fn versioned_accounts_migrate_from_their_version() {
+    let owner = Keypair::new();
+    let client = Keypair::new();
+    let guild = Pubkey::new_unique();
+    let (agent, agent_bump) =
+        Pubkey::find_program_address(&[b"agent", owner.pubkey().as_ref()], &agent_registry::ID);
+    let bounty_id = 3u64;
+    let (bounty, bounty_bump) = Pubkey::find_program_address(
+        &[
+            b"bounty",
+            client.pubkey().as_ref(),
+            &bounty_id.to_le_bytes(),
+        ],
+        &bounty_escrow::ID,
+    );
+    let vault = pda::vault(&bounty);
+    let mint = Pubkey::new_unique();
+    let unknown = Pubkey::new_unique();
+
+    // Version 2: the versioned agent plus its skills
+    let agent_data = legacy_data(
+        Agent::DISCRIMINATOR,
+        700,
+        (
+            (
+                2u8,
+                owner.pubkey(),
+                URI.to_string(),
+                HASH,
+                40_000_000u64,
+                450u64,
+                3u64,
+                120_000_000u64,
+            ),
+            (
+                0u8,
+                agent_bump,
+                1_600_000_000i64,
+                Pubkey::default(),
+                Pubkey::default(),
+                1u64,
+                2u64,
+                SKILL_RUST,
+            ),
+        ),
+    );
+    // Version 9: everything up to the guild link, before status timestamps
+    let bounty_data = legacy_data(
+        Bounty::DISCRIMINATOR,
+        1_700,
+        (
+            (
+                9u8,
+                client.pubkey(),
+                bounty_id,
+                URI.to_string(),
+                HASH,
+                5_000_000u64,
+                1_700_000_000i64,
+                BountyStatus::Claimed as u8,
+            ),
+            (
+                1u64,
+                owner.pubkey(),
+                String::new(),
+                [0u8; 32],
+                vault,
+                mint,
+                bounty_bump,
+                1_600_000_000i64,
+            ),
+            (SKILL_RUST, true, 0u8, 0u8, vec![owner.pubkey()], 1u8),
+            (
+                Pubkey::default(),
+                250_000u64,
+                Pubkey::default(),
+                2u64,
+                1_000_000u64,
+                false,
+                guild,
+            ),
+        ),
+    );
+    let unknown_data = legacy_data(Agent::DISCRIMINATOR, 700, (200u8, owner.pubkey()));
+    let mut env = Env::with_accounts(vec![
+        (agent, raw_account(&agent_registry::ID, agent_data)),
+        (bounty, raw_account(&bounty_escrow::ID, bounty_data)),
+        (unknown, raw_account(&agent_registry::ID, unknown_data)),
+    ])
+    .await;
+    let payer = env.ctx.payer.pubkey();
+
+    assert_error(
+        env.send(&[registry::migrate_agent(&unknown, &payer)], &[]).await,
+        agent_registry::AgentError::UnknownAccountVersion,
+    );
+
+    let ix = registry::migrate_agent(&agent, &payer);
+    env.send(std::slice::from_ref(&ix), &[]).await.unwrap();
+    assert_error(
+        env.send(&[ix], &[]).await,
+        agent_registry::AgentError::AlreadyMigrated,
+    );
+    let migrated: Agent = env.fetch(&agent).await;
+    assert_eq!(migrated.version, agent_registry::AGENT_VERSION);
+    assert_eq!(migrated.metadata_hash, HASH);
+    assert_eq!(migrated.total_earned, 120_000_000);
+    assert_eq!(migrated.availability, AgentStatus::Available);
+    assert_eq!(migrated.active_claims, 1);
+    assert_eq!(migrated.delegate_count, 2);
+    assert_eq!(migrated.skills, SKILL_RUST);
+    assert_eq!(migrated.guild, Pubkey::default());
+    assert_eq!(migrated.referrer, Pubkey::default());
+
+    let ix = escrow::migrate_bounty(&bounty, None, &payer);
+    env.send(std::slice::from_ref(&ix), &[]).await.unwrap();
+    assert_error(
+        env.send(&[ix], &[]).await,
+        bounty_escrow::BountyError::AlreadyMigrated,
+    );
+    let migrated: Bounty = env.fetch(&bounty).await;
+    assert_eq!(migrated.version, bounty_escrow::BOUNTY_VERSION);
+    assert_eq!(migrated.metadata_hash, HASH);
+    assert_eq!(migrated.status, BountyStatus::Claimed);
+    assert_eq!(migrated.assigned_agent, owner.pubkey());
+    assert_eq!(migrated.skills, SKILL_RUST);
+    assert!(migrated.require_skill_match);
+    assert!(migrated.attestation_requirement.is_none());
+    assert_eq!(migrated.allowlist, [owner.pubkey()]);
+    assert_eq!(migrated.decline_policy, DeclinePolicy::Reopen);
+    assert_eq!(migrated.tips_total, 250_000);
+    assert_eq!(migrated.child_count, 2);
+    assert_eq!(migrated.reserved_for_children, 1_000_000);
+    assert_eq!(migrated.guild, guild);
+    assert_eq!(migrated.claimed_at, 0);
+    assert!(migrated.index_pages.is_empty());
+    assert_eq!(migrated.dispute_payout, 0);
+    let history: BountyHistory = env.fetch(&pda::history(&bounty)).await;
+    assert_eq!(history.bounty, bounty);
+    assert!(history.entries.is_empty());
+}
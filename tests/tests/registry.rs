@@ -0,0 +1,578 @@
+//! agent-registry instructions and every AgentError outside guilds (see guilds.rs).
+//!
+//! `InvalidRating` is not covered: only bounty-escrow may call `update_reputation`,
+//! and it rejects out-of-range ratings before making the call. Nor is
+//! `NoActiveClaims`: bounty-escrow only releases claims it recorded. `InvalidAvailability`
+//! is no longer raised: unknown availabilities fail to deserialize instead.
+
+use agent_registry::instruction as registry_ix;
+use agent_registry::{
+    AgentError, DELEGATE_CLAIM, DELEGATE_UPDATE_AVAILABILITY, PAUSE_REGISTER_AGENT,
+    PAUSE_UPDATE_AGENT,
+};
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use bounty_escrow::instruction as escrow_ix;
+use bounty_escrow::BountyError;
+use clawedwork_client::accounts::{Agent, AgentArchive, Attestation, Bounty, SkillRegistry};
+use clawedwork_client::status::AgentStatus;
+use clawedwork_client::{escrow, pda, registry};
+use clawedwork_tests::*;
+use solana_sdk::signature::Signer;
+use solana_sdk::system_instruction::SystemError;
+
+fn update(args: impl FnOnce(&mut registry_ix::UpdateAgent)) -> registry_ix::UpdateAgent {
+    let mut update = registry_ix::UpdateAgent {
+        metadata_uri: None,
+        metadata_hash: None,
+        hourly_rate: None,
+        availability: None,
+        skills: None,
+    };
+    args(&mut update);
+    update
+}
+
+#[tokio::test]
+async fn registers_and_updates_agent() {
+    let mut env = Env::new().await;
+    let referrer = env.user(0).await;
+    let agent = env.user(0).await;
+    env.register(&agent, SKILL_RUST | SKILL_DESIGN, Some(referrer.pubkey()))
+        .await
+        .unwrap();
+
+    let profile: Agent = env.fetch(&agent.agent()).await;
+    assert_eq!(profile.owner, agent.pubkey());
+    assert_eq!(profile.skills, SKILL_RUST | SKILL_DESIGN);
+    assert_eq!(profile.referrer, referrer.pubkey());
+    assert_eq!(profile.availability, AgentStatus::Available);
+
+    let args = update(|args| {
+        args.metadata_uri = Some("ipfs://v2".to_string());
+        args.metadata_hash = Some([9u8; 32]);
+        args.hourly_rate = Some(75_000_000);
+        args.skills = Some(SKILL_SOLANA);
+    });
+    let ix = registry::update_agent(&agent.pubkey(), args);
+    env.send(&[ix], &[&agent.keypair]).await.unwrap();
+
+    let profile: Agent = env.fetch(&agent.agent()).await;
+    assert_eq!(profile.metadata_uri, "ipfs://v2");
+    assert_eq!(profile.metadata_hash, [9u8; 32]);
+    assert_eq!(profile.hourly_rate, 75_000_000);
+    assert_eq!(profile.skills, SKILL_SOLANA);
+}
+
+#[tokio::test]
+async fn register_validates_arguments() {
+    let mut env = Env::new().await;
+    let agent = env.user(0).await;
+    let args = |change: fn(&mut registry_ix::RegisterAgent)| {
+        let mut args = registry_ix::RegisterAgent {
+            metadata_uri: URI.to_string(),
+            metadata_hash: HASH,
+            hourly_rate: 1,
+            skills: 0,
+            referrer: None,
+        };
+        change(&mut args);
+        args
+    };
+
+    let cases: [(registry_ix::RegisterAgent, AgentError); 4] = [
+        (
+            args(|a| a.metadata_uri = "x".repeat(513)),
+            AgentError::UriTooLong,
+        ),
+        (
+            args(|a| a.metadata_hash = [0u8; 32]),
+            AgentError::MissingContentHash,
+        ),
+        (args(|a| a.hourly_rate = 0), AgentError::InvalidHourlyRate),
+        (
+            args(|a| a.skills = 1 << SKILLS.len()),
+            AgentError::UnknownSkill,
+        ),
+    ];
+    for (args, error) in cases {
+        let ix = registry::register_agent(&agent.pubkey(), args);
+        assert_error(env.send(&[ix], &[&agent.keypair]).await, error);
+    }
+    assert_error(
+        env.register(&agent, 0, Some(agent.pubkey())).await,
+        AgentError::InvalidReferrer,
+    );
+}
+
+#[tokio::test]
+async fn update_validates_arguments() {
+    let mut env = Env::new().await;
+    let agent = env.agent(0).await;
+
+    let cases = [
+        (
+            update(|args| {
+                args.metadata_uri = Some("x".repeat(513));
+                args.metadata_hash = Some(HASH);
+            }),
+            AgentError::UriTooLong,
+        ),
+        (
+            update(|args| args.metadata_uri = Some(URI.to_string())),
+            AgentError::MissingContentHash,
+        ),
+        (
+            update(|args| {
+                args.metadata_uri = Some(URI.to_string());
+                args.metadata_hash = Some([0u8; 32]);
+            }),
+            AgentError::MissingContentHash,
+        ),
+        (
+            update(|args| args.hourly_rate = Some(0)),
+            AgentError::InvalidHourlyRate,
+        ),
+        (
+            update(|args| args.skills = Some(1 << 100)),
+            AgentError::UnknownSkill,
+        ),
+    ];
+    for (args, error) in cases {
+        let ix = registry::update_agent(&agent.pubkey(), args);
+        assert_error(env.send(&[ix], &[&agent.keypair]).await, error);
+    }
+
+    // Availability outside the enum no longer reaches the handler
+    let mut ix = registry::update_agent(
+        &agent.pubkey(),
+        update(|args| args.availability = Some(AgentStatus::Offline)),
+    );
+    // ... Some(availability), None(skills)
+    let availability = ix.data.len() - 2;
+    ix.data[availability] = 3;
+    assert_error(
+        env.send(&[ix], &[&agent.keypair]).await,
+        ErrorCode::InstructionDidNotDeserialize,
+    );
+}
+
+#[tokio::test]
+async fn pause_flags_block_their_instructions() {
+    let mut env = Env::new().await;
+    let agent = env.agent(0).await;
+    let newcomer = env.user(0).await;
+
+    env.pause_registry(PAUSE_REGISTER_AGENT | PAUSE_UPDATE_AGENT)
+        .await;
+    assert_error(env.register(&newcomer, 0, None).await, AgentError::Paused);
+    let ix = registry::update_agent(&agent.pubkey(), update(|args| args.hourly_rate = Some(2)));
+    assert_error(env.send(&[ix], &[&agent.keypair]).await, AgentError::Paused);
+
+    env.pause_registry(0).await;
+    env.register(&newcomer, 0, None).await.unwrap();
+}
+
+#[tokio::test]
+async fn admin_instructions_require_admin() {
+    let mut env = Env::new().await;
+    let outsider = env.user(0).await;
+
+    let ix = registry::set_paused(&outsider.pubkey(), 1);
+    assert_error(
+        env.send(&[ix], &[&outsider.keypair]).await,
+        AgentError::Unauthorized,
+    );
+    let ix = registry::add_skill(&outsider.pubkey(), "go".to_string());
+    assert_error(
+        env.send(&[ix], &[&outsider.keypair]).await,
+        AgentError::Unauthorized,
+    );
+    let ix = registry::add_verifier(&outsider.pubkey(), &outsider.pubkey());
+    assert_error(
+        env.send(&[ix], &[&outsider.keypair]).await,
+        AgentError::Unauthorized,
+    );
+
+    // Handing over the config moves the admin rights
+    let ix = registry::set_admin(&env.admin.pubkey(), &outsider.pubkey());
+    env.send_as_admin(&[ix]).await.unwrap();
+    let ix = registry::add_skill(&outsider.pubkey(), "go".to_string());
+    env.send(&[ix], &[&outsider.keypair]).await.unwrap();
+}
+
+#[tokio::test]
+async fn skill_registry_fills_up() {
+    let mut env = Env::new().await;
+    let admin = env.admin.pubkey();
+
+    let ix = registry::add_skill(&admin, String::new());
+    assert_error(env.send_as_admin(&[ix]).await, AgentError::InvalidSkillName);
+    let ix = registry::add_skill(&admin, "x".repeat(33));
+    assert_error(env.send_as_admin(&[ix]).await, AgentError::InvalidSkillName);
+    let ix = registry::add_skill(&admin, SKILLS[0].to_string());
+    assert_error(env.send_as_admin(&[ix]).await, AgentError::InvalidSkillName);
+
+    let remaining: Vec<_> = (SKILLS.len()..agent_registry::MAX_SKILLS)
+        .map(|id| registry::add_skill(&admin, format!("skill-{id}")))
+        .collect();
+    for batch in remaining.chunks(10) {
+        env.send_as_admin(batch).await.unwrap();
+    }
+    let skills: SkillRegistry = env.fetch(&pda::skill_registry()).await;
+    assert_eq!(skills.names.len(), agent_registry::MAX_SKILLS);
+
+    let ix = registry::add_skill(&admin, "one-too-many".to_string());
+    assert_error(
+        env.send_as_admin(&[ix]).await,
+        AgentError::SkillRegistryFull,
+    );
+}
+
+#[tokio::test]
+async fn delegates_need_matching_permissions() {
+    let mut env = Env::new().await;
+    let agent = env.agent(0).await;
+    let hot_key = env.user(0).await;
+
+    for permissions in [0, 1 << 7] {
+        let ix = registry::add_delegate(&agent.pubkey(), &hot_key.pubkey(), permissions);
+        assert_error(
+            env.send(&[ix], &[&agent.keypair]).await,
+            AgentError::InvalidDelegatePermissions,
+        );
+    }
+
+    let ix = registry::add_delegate(&agent.pubkey(), &hot_key.pubkey(), DELEGATE_CLAIM);
+    env.send(&[ix], &[&agent.keypair]).await.unwrap();
+    let offline = AgentStatus::Offline;
+    let ix = registry::set_availability(&hot_key.pubkey(), &agent.pubkey(), offline);
+    assert_error(
+        env.send(&[ix], &[&hot_key.keypair]).await,
+        AgentError::DelegateNotAuthorized,
+    );
+
+    let ix = registry::update_delegate(&agent.pubkey(), &hot_key.pubkey(), 0);
+    assert_error(
+        env.send(&[ix], &[&agent.keypair]).await,
+        AgentError::InvalidDelegatePermissions,
+    );
+    let ix = registry::update_delegate(
+        &agent.pubkey(),
+        &hot_key.pubkey(),
+        DELEGATE_UPDATE_AVAILABILITY,
+    );
+    env.send(&[ix], &[&agent.keypair]).await.unwrap();
+    let mut ix = registry::set_availability(&hot_key.pubkey(), &agent.pubkey(), offline);
+    *ix.data.last_mut().unwrap() = 3;
+    assert_error(
+        env.send(&[ix], &[&hot_key.keypair]).await,
+        ErrorCode::InstructionDidNotDeserialize,
+    );
+    let ix = registry::set_availability(&hot_key.pubkey(), &agent.pubkey(), offline);
+    env.send(&[ix], &[&hot_key.keypair]).await.unwrap();
+    let profile: Agent = env.fetch(&agent.agent()).await;
+    assert_eq!(profile.availability, offline);
+    assert_eq!(profile.delegate_count, 1);
+
+    // Delegates block deregistration until removed
+    let ix = registry::deregister_agent(&agent.pubkey(), false);
+    assert_error(
+        env.send(&[ix], &[&agent.keypair]).await,
+        AgentError::DelegatesRemain,
+    );
+    let ix = registry::remove_delegate(&agent.pubkey(), &hot_key.pubkey());
+    env.send(&[ix], &[&agent.keypair]).await.unwrap();
+    let ix = registry::deregister_agent(&agent.pubkey(), false);
+    env.send(&[ix], &[&agent.keypair]).await.unwrap();
+    assert!(!env.exists(&agent.agent()).await);
+}
+
+#[tokio::test]
+async fn owner_transfer_moves_profile() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let old_owner = env.agent(SKILL_RUST).await;
+    let new_owner = env.user(0).await;
+
+    let ix = registry::cancel_owner_transfer(&old_owner.pubkey());
+    assert_error(
+        env.send(&[ix], &[&old_owner.keypair]).await,
+        AgentError::NoPendingTransfer,
+    );
+    for invalid in [Default::default(), old_owner.pubkey()] {
+        let ix = registry::propose_owner_transfer(&old_owner.pubkey(), &invalid);
+        assert_error(
+            env.send(&[ix], &[&old_owner.keypair]).await,
+            AgentError::InvalidNewOwner,
+        );
+    }
+
+    let ix = registry::propose_owner_transfer(&old_owner.pubkey(), &new_owner.pubkey());
+    env.send(&[ix], &[&old_owner.keypair]).await.unwrap();
+    let stranger = env.user(0).await;
+    let ix = registry::accept_owner_transfer(&stranger.pubkey(), &old_owner.pubkey());
+    assert_error(
+        env.send(&[ix], &[&stranger.keypair]).await,
+        AgentError::NoPendingTransfer,
+    );
+
+    // An open claim pins the profile to the old wallet
+    let bounty = env.open_bounty(&client, 1_000_000).await;
+    env.claim(&bounty, &old_owner).await.unwrap();
+    let ix = registry::accept_owner_transfer(&new_owner.pubkey(), &old_owner.pubkey());
+    assert_error(
+        env.send(std::slice::from_ref(&ix), &[&new_owner.keypair])
+            .await,
+        AgentError::ActiveClaims,
+    );
+    env.submit(&bounty, &old_owner).await.unwrap();
+    env.approve(&bounty, &client).await.unwrap();
+
+    // So do delegates, whose records would be stranded
+    let ix_delegate =
+        registry::add_delegate(&old_owner.pubkey(), &stranger.pubkey(), DELEGATE_CLAIM);
+    env.send(&[ix_delegate], &[&old_owner.keypair]).await.unwrap();
+    assert_error(
+        env.send(std::slice::from_ref(&ix), &[&new_owner.keypair])
+            .await,
+        AgentError::DelegatesRemain,
+    );
+    let ix_remove = registry::remove_delegate(&old_owner.pubkey(), &stranger.pubkey());
+    env.send(&[ix_remove], &[&old_owner.keypair]).await.unwrap();
+
+    let old_profile: Agent = env.fetch(&old_owner.agent()).await;
+    env.send(&[ix], &[&new_owner.keypair]).await.unwrap();
+
+    // The old account stays behind as a tombstone pointing at the new profile
+    let tombstone: Agent = env.fetch(&old_owner.agent()).await;
+    assert!(!tombstone.is_active());
+    assert_eq!(tombstone.successor, new_owner.agent());
+    assert_eq!(tombstone.pending_owner, Pubkey::default());
+    assert_eq!(tombstone.availability, AgentStatus::Offline);
+    let new_profile: Agent = env.fetch(&new_owner.agent()).await;
+    assert_eq!(new_profile.owner, new_owner.pubkey());
+    assert_eq!(new_profile.skills, SKILL_RUST);
+    assert_eq!(new_profile.created_at, old_profile.created_at);
+    assert_eq!(new_profile.total_earned, old_profile.total_earned);
+
+    // Tips on the old owner's bounties follow the tombstone to the new profile
+    let state: Bounty = env.fetch(&bounty).await;
+    let tip = |transfers: &[_]| {
+        escrow::tip_agent(&bounty, &state, &client.token, &old_owner.token, 1_000, transfers)
+    };
+    assert_error(
+        env.send(&[tip(&[])], &[&client.keypair]).await,
+        BountyError::AgentTransferred,
+    );
+    assert_error(
+        env.send(&[tip(&[stranger.pubkey()])], &[&client.keypair])
+            .await,
+        ErrorCode::AccountNotInitialized,
+    );
+    env.send(&[tip(&[new_owner.pubkey()])], &[&client.keypair])
+        .await
+        .unwrap();
+    let new_profile_after: Agent = env.fetch(&new_owner.agent()).await;
+    assert_eq!(new_profile_after.total_earned, new_profile.total_earned + 1_000);
+
+    // So do reviews
+    let ix = escrow::leave_review(
+        &bounty,
+        &state,
+        escrow_ix::LeaveReview {
+            rating: 300,
+            comment_uri: URI.to_string(),
+            comment_hash: HASH,
+        },
+        &[new_owner.pubkey()],
+    );
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let new_profile_after: Agent = env.fetch(&new_owner.agent()).await;
+    assert_eq!(new_profile_after.reputation, 300);
+    assert_eq!(new_profile_after.bounties_completed, 1);
+
+    // The old wallet can neither act with the tombstone nor register a fresh profile
+    // to collect the old bounties' tips and reviews
+    let ix = registry::update_agent(
+        &old_owner.pubkey(),
+        update(|args| args.hourly_rate = Some(2)),
+    );
+    assert_error(
+        env.send(&[ix], &[&old_owner.keypair]).await,
+        AgentError::AgentTransferred,
+    );
+    assert_error(
+        env.register(&old_owner, 0, None).await,
+        SystemError::AccountAlreadyInUse as u32,
+    );
+}
+
+#[tokio::test]
+async fn tombstoned_profile_cannot_act() {
+    let (mut env, tombstoned) = Env::with_tombstone().await;
+    let ix = registry::update_agent(
+        &tombstoned.pubkey(),
+        update(|args| args.hourly_rate = Some(2)),
+    );
+    assert_error(
+        env.send(&[ix], &[&tombstoned.keypair]).await,
+        AgentError::AgentTransferred,
+    );
+}
+
+#[tokio::test]
+async fn deregistering_leaves_archive() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = env.agent(0).await;
+
+    let bounty = env.open_bounty(&client, 1_000_000).await;
+    env.claim(&bounty, &agent).await.unwrap();
+    let ix = registry::deregister_agent(&agent.pubkey(), true);
+    assert_error(
+        env.send(std::slice::from_ref(&ix), &[&agent.keypair]).await,
+        AgentError::ActiveClaims,
+    );
+
+    env.submit(&bounty, &agent).await.unwrap();
+    env.approve(&bounty, &client).await.unwrap();
+    let profile: Agent = env.fetch(&agent.agent()).await;
+    env.send(&[ix], &[&agent.keypair]).await.unwrap();
+
+    assert!(!env.exists(&agent.agent()).await);
+    let archive: AgentArchive = env.fetch(&pda::agent_archive(&agent.agent())).await;
+    assert_eq!(archive.agent, agent.agent());
+    assert_eq!(archive.owner, agent.pubkey());
+    assert_eq!(archive.created_at, profile.created_at);
+}
+
+#[tokio::test]
+async fn attestations_follow_verifier_rules() {
+    let mut env = Env::new().await;
+    let agent = env.agent(0).await;
+    let verifier = env.user(0).await;
+    let other_verifier = env.user(0).await;
+    let admin = env.admin.pubkey();
+    let ix = registry::add_verifier(&admin, &verifier.pubkey());
+    let ix_other = registry::add_verifier(&admin, &other_verifier.pubkey());
+    env.send_as_admin(&[ix, ix_other]).await.unwrap();
+
+    let attest = |skill_id: u8, expires_at: i64| {
+        registry::issue_attestation(
+            &verifier.pubkey(),
+            &verifier.pubkey(),
+            &agent.agent(),
+            registry_ix::IssueAttestation {
+                skill_id,
+                expires_at,
+                evidence_hash: HASH,
+            },
+        )
+    };
+    assert_error(
+        env.send(&[attest(SKILLS.len() as u8, 0)], &[&verifier.keypair])
+            .await,
+        AgentError::UnknownSkill,
+    );
+    assert_error(
+        env.send(&[attest(0, 1)], &[&verifier.keypair]).await,
+        AgentError::InvalidExpiry,
+    );
+    env.send(&[attest(0, 0)], &[&verifier.keypair])
+        .await
+        .unwrap();
+    let address = pda::attestation(&agent.agent(), 0, &verifier.pubkey());
+    let attestation: Attestation = env.fetch(&address).await;
+    assert_eq!(attestation.agent, agent.agent());
+    assert_eq!(attestation.verifier, verifier.pubkey());
+
+    // Only the issuing verifier may revoke
+    let ix = registry::revoke_attestation(&other_verifier.pubkey(), &agent.agent(), 0);
+    assert!(env.send(&[ix], &[&other_verifier.keypair]).await.is_err());
+    let ix = registry::revoke_attestation(&verifier.pubkey(), &agent.agent(), 0);
+    env.send(&[ix], &[&verifier.keypair]).await.unwrap();
+    assert!(!env.exists(&address).await);
+
+    let ix = registry::set_verifier_active(&admin, &verifier.pubkey(), false);
+    env.send_as_admin(&[ix]).await.unwrap();
+    assert_error(
+        env.send(&[attest(1, 0)], &[&verifier.keypair]).await,
+        AgentError::VerifierInactive,
+    );
+}
+
+#[tokio::test]
+async fn validations_are_answered_by_named_validator() {
+    let mut env = Env::new().await;
+    let requester = env.user(0).await;
+    let validator = env.user(0).await;
+    let impostor = env.user(0).await;
+    let bounty = Pubkey::new_unique();
+
+    let ix = registry::request_validation(
+        &requester.pubkey(),
+        &validator.pubkey(),
+        &bounty,
+        [0u8; 32],
+    );
+    assert_error(
+        env.send(&[ix], &[&requester.keypair]).await,
+        AgentError::MissingContentHash,
+    );
+    let ix = registry::request_validation(&requester.pubkey(), &validator.pubkey(), &bounty, HASH);
+    env.send(&[ix], &[&requester.keypair]).await.unwrap();
+
+    let ix = registry::respond_validation(&validator.pubkey(), &bounty, &HASH, 101, HASH);
+    assert_error(
+        env.send(&[ix], &[&validator.keypair]).await,
+        AgentError::InvalidValidationScore,
+    );
+    let mut ix = registry::respond_validation(&validator.pubkey(), &bounty, &HASH, 90, HASH);
+    ix.accounts[2].pubkey = impostor.pubkey();
+    assert!(env.send(&[ix], &[&impostor.keypair]).await.is_err());
+
+    env.pause_registry(agent_registry::PAUSE_VALIDATION).await;
+    let ix = registry::respond_validation(&validator.pubkey(), &bounty, &HASH, 90, HASH);
+    assert_error(
+        env.send(&[ix], &[&validator.keypair]).await,
+        AgentError::Paused,
+    );
+}
+
+#[tokio::test]
+async fn escrow_only_instructions_reject_other_callers() {
+    let mut env = Env::new().await;
+    let agent = env.agent(0).await;
+
+    let ix = Instruction {
+        program_id: agent_registry::ID,
+        accounts: agent_registry::accounts::UpdateReputation {
+            agent: agent.agent(),
+            authority: agent.pubkey(),
+        }
+        .to_account_metas(None),
+        data: registry_ix::UpdateReputation { new_rating: 500 }.data(),
+    };
+    assert_error(
+        env.send(&[ix], &[&agent.keypair]).await,
+        AgentError::UnauthorizedCaller,
+    );
+}
+
+#[tokio::test]
+async fn migrations_reject_current_and_foreign_accounts() {
+    let mut env = Env::new().await;
+    let agent = env.agent(0).await;
+    let payer = env.ctx.payer.pubkey();
+
+    let ix = registry::migrate_agent(&agent.agent(), &payer);
+    assert_error(env.send(&[ix], &[]).await, AgentError::AlreadyMigrated);
+    let ix = registry::migrate_agent(&pda::skill_registry(), &payer);
+    assert_error(
+        env.send(&[ix], &[]).await,
+        AgentError::UnknownAccountVersion,
+    );
+}
@@ -0,0 +1,475 @@
+//! Property-based fuzzing of the bounty state machine.
+//!
+//! Each case posts two bounties — one open to all, one inviting two agents — and
+//! replays a random sequence of escrow instructions from four wallets that are
+//! all both clients and agents. Instructions are signed by the acting wallet (the
+//! admin for dispute resolutions) and pay out to a randomly chosen wallet's token
+//! account, so most of them fail; the invariants below must hold after every step,
+//! whether it landed or not.
+//!
+//! Raise `PROPTEST_CASES` for longer runs. Failing sequences are shrunk and saved
+//! under `proptest-regressions/`.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use bounty_escrow::{BountyError, UNSKILLED};
+use clawedwork_client::accounts::{Bounty, BountyHistory, BountyIndexPage};
+use clawedwork_client::{escrow, pda};
+use clawedwork_client::status::{BountyStatus, DeclinePolicy};
+use clawedwork_tests::*;
+use proptest::prelude::*;
+use solana_sdk::signature::Signer;
+
+const ACTORS: usize = 4;
+const BOUNTIES: usize = 2;
+
+#[derive(Clone, Copy, Debug)]
+enum Action {
+    Claim,
+    AcceptOffer,
+    DeclineOffer,
+    Submit,
+    Approve,
+    Dispute,
+    /// Admin pays the agent this share of the budget, in basis points (above
+    /// 10,000 is rejected), and refunds the rest
+    Resolve(u64),
+    Cancel,
+    Tip(u64),
+}
+
+/// `actor` signs; `payee` owns the token account the instruction pays into, or
+/// is `None` for the account the program expects (agent for payouts and tips,
+/// client for refunds).
+#[derive(Clone, Copy, Debug)]
+struct Step {
+    action: Action,
+    bounty: usize,
+    actor: usize,
+    payee: Option<usize>,
+}
+
+#[derive(Clone, Debug)]
+struct Scenario {
+    budgets: [u64; BOUNTIES],
+    decline_policy: DeclinePolicy,
+    steps: Vec<Step>,
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    prop_oneof![
+        3 => Just(Action::Claim),
+        1 => Just(Action::AcceptOffer),
+        1 => Just(Action::DeclineOffer),
+        3 => Just(Action::Submit),
+        3 => Just(Action::Approve),
+        1 => Just(Action::Dispute),
+        1 => (0..=11_000u64).prop_map(Action::Resolve),
+        1 => Just(Action::Cancel),
+        1 => (1..1_000u64).prop_map(Action::Tip),
+    ]
+}
+
+fn step() -> impl Strategy<Value = Step> {
+    let payee = prop_oneof![2 => Just(None), 1 => (0..ACTORS).prop_map(Some)];
+    (action(), 0..BOUNTIES, 0..ACTORS, payee).prop_map(|(action, bounty, actor, payee)| Step {
+        action,
+        bounty,
+        actor,
+        payee,
+    })
+}
+
+fn scenario() -> impl Strategy<Value = Scenario> {
+    (
+        [1..=1_000_000u64, 1..=1_000_000u64],
+        prop_oneof![Just(DeclinePolicy::Refund), Just(DeclinePolicy::Reopen)],
+        prop::collection::vec(step(), 1..48),
+    )
+        .prop_map(|(budgets, decline_policy, steps)| Scenario {
+            budgets,
+            decline_policy,
+            steps,
+        })
+}
+
+fn is_live(status: BountyStatus) -> bool {
+    [
+        BountyStatus::Open,
+        BountyStatus::Claimed,
+        BountyStatus::Delivered,
+        BountyStatus::Disputed,
+    ]
+    .contains(&status)
+}
+
+/// Status changes a single instruction may make, spelled out independently of
+/// `BountyStatus::can_transition_to`.
+fn is_transition(from: BountyStatus, to: BountyStatus) -> bool {
+    use BountyStatus::*;
+    from == to
+        || [
+            (Open, Claimed),
+            (Open, Cancelled),
+            (Claimed, Delivered),
+            (Claimed, Disputed),
+            (Claimed, Cancelled),
+            (Delivered, Completed),
+            (Delivered, Disputed),
+            (Delivered, Cancelled),
+            (Disputed, Completed),
+            (Disputed, Cancelled),
+        ]
+        .contains(&(from, to))
+}
+
+/// Re-signs every signer slot held by `role` with `actor` instead, so the
+/// program's own authorization checks decide.
+fn sign_as(mut ix: Instruction, role: &Pubkey, actor: &Pubkey) -> Instruction {
+    ix.accounts
+        .iter_mut()
+        .filter(|meta| meta.is_signer && meta.pubkey == *role)
+        .for_each(|meta| meta.pubkey = *actor);
+    ix
+}
+
+/// Token balances and bounty states at one point of the run.
+struct Snapshot {
+    wallets: [u64; ACTORS],
+    vaults: [u64; BOUNTIES],
+    bounties: Vec<Bounty>,
+    /// Bounties listed on the first page of the skill-less index bucket
+    listed: Vec<Pubkey>,
+}
+
+async fn snapshot(env: &mut Env, actors: &[User], bounties: &[Pubkey]) -> Snapshot {
+    let mut wallets = [0; ACTORS];
+    for (balance, actor) in wallets.iter_mut().zip(actors) {
+        *balance = env.balance(&actor.token).await;
+    }
+    let mut states = Vec::new();
+    let mut vaults = [0; BOUNTIES];
+    for (balance, bounty) in vaults.iter_mut().zip(bounties) {
+        let state: Bounty = env.fetch(bounty).await;
+        *balance = env.balance(&state.vault).await;
+        states.push(state);
+    }
+    let page: BountyIndexPage = env
+        .fetch(&pda::index_page(BountyStatus::Open, UNSKILLED, 0))
+        .await;
+    Snapshot {
+        wallets,
+        vaults,
+        bounties: states,
+        listed: page.bounties,
+    }
+}
+
+/// Replays `scenario`, returning the final bounty statuses.
+async fn run(scenario: Scenario) -> Result<Vec<BountyStatus>, TestCaseError> {
+    let mut env = Env::new().await;
+    let mut actors = Vec::new();
+    for _ in 0..ACTORS {
+        let user = env.user(STARTING_USDC).await;
+        env.register(&user, 0, None).await.unwrap();
+        let ix = escrow::init_client(&user.pubkey(), None);
+        env.send(&[ix], &[&user.keypair]).await.unwrap();
+        actors.push(user);
+    }
+    let open = env.open_bounty(&actors[0], scenario.budgets[0]).await;
+    let mut args = env.bounty_args(scenario.budgets[1]).await;
+    args.allowlist = vec![actors[2].pubkey(), actors[3].pubkey()];
+    args.decline_policy = scenario.decline_policy;
+    let invited = env.create_bounty(&actors[1], args).await;
+    let bounties = [open, invited];
+    let supply = ACTORS as u64 * STARTING_USDC;
+
+    let mut before = snapshot(&mut env, &actors, &bounties).await;
+    for step in &scenario.steps {
+        let key = bounties[step.bounty];
+        let state = &before.bounties[step.bounty];
+        let actor = &actors[step.actor];
+        let payee = match step.payee {
+            Some(payee) => actors[payee].token,
+            None => {
+                let owner = match step.action {
+                    Action::Approve | Action::Resolve(_) | Action::Tip(_) => {
+                        state.assigned_agent
+                    }
+                    _ => state.client,
+                };
+                actors
+                    .iter()
+                    .find(|actor| actor.pubkey() == owner)
+                    .map_or(actors[0].token, |actor| actor.token)
+            }
+        };
+        let ix = match step.action {
+            Action::Claim => escrow::claim_bounty(&key, state, &actor.pubkey(), &actor.pubkey()),
+            Action::AcceptOffer => {
+                escrow::accept_offer(&key, state, &actor.pubkey(), &actor.pubkey())
+            }
+            Action::DeclineOffer => {
+                escrow::decline_offer(&key, state, &actor.pubkey(), &actor.pubkey(), &payee)
+            }
+            Action::Submit => escrow::submit_work(
+                &key,
+                &actor.pubkey(),
+                &actor.pubkey(),
+                URI.to_string(),
+                HASH,
+            ),
+            Action::Approve => {
+                let payout = escrow::PayoutAccounts {
+                    agent_token_account: payee,
+                    ..Default::default()
+                };
+                escrow::approve_work(&key, state, &payout)
+            }
+            Action::Dispute => escrow::dispute_bounty(&key, &actor.pubkey()),
+            Action::Resolve(share_bps) => {
+                let payout = escrow::PayoutAccounts {
+                    agent_token_account: payee,
+                    ..Default::default()
+                };
+                let client_token = actors
+                    .iter()
+                    .find(|actor| actor.pubkey() == state.client)
+                    .map_or(actors[0].token, |actor| actor.token);
+                let admin = env.admin.pubkey();
+                let amount = state.budget * share_bps / 10_000;
+                escrow::resolve_dispute(&key, state, &admin, &client_token, &payout, &[], amount)
+            }
+            Action::Cancel => escrow::cancel_bounty(&key, state, &payee),
+            Action::Tip(amount) => escrow::tip_agent(&key, state, &actor.token, &payee, amount, &[]),
+        };
+        let landed = match step.action {
+            Action::Resolve(_) => env.send_as_admin(&[ix]).await.is_ok(),
+            _ => {
+                let ix = sign_as(ix, &state.client, &actor.pubkey());
+                env.send(&[ix], &[&actor.keypair]).await.is_ok()
+            }
+        };
+        let after = snapshot(&mut env, &actors, &bounties).await;
+
+        // Tokens are neither minted nor burned
+        let total = after.wallets.iter().sum::<u64>() + after.vaults.iter().sum::<u64>();
+        prop_assert_eq!(total, supply, "supply changed after {:?}", step);
+
+        for (index, (old, new)) in before.bounties.iter().zip(&after.bounties).enumerate() {
+            prop_assert!(
+                is_transition(old.status, new.status),
+                "bounty {} went from {:?} to {:?} after {:?}",
+                index,
+                old.status,
+                new.status,
+                step
+            );
+            // A live bounty holds exactly its budget; a settled one holds nothing
+            let expected = if is_live(new.status) { new.budget } else { 0 };
+            prop_assert_eq!(
+                after.vaults[index],
+                expected,
+                "bounty {} vault after {:?}",
+                index,
+                step
+            );
+            if index != step.bounty {
+                prop_assert_eq!(old.status, new.status, "untouched bounty {} changed", index);
+            }
+            // Exactly the open bounties are indexed
+            prop_assert_eq!(
+                after.listed.contains(&bounties[index]),
+                new.status == BountyStatus::Open,
+                "bounty {} index listing after {:?}",
+                index,
+                step
+            );
+        }
+
+        // Only the target bounty's client and assigned agent ever gain tokens,
+        // and only its client ever pays any
+        let target = &before.bounties[step.bounty];
+        for (index, actor) in actors.iter().enumerate() {
+            let (old, new) = (before.wallets[index], after.wallets[index]);
+            let wallet = actor.pubkey();
+            if new > old {
+                prop_assert!(
+                    wallet == target.client || wallet == target.assigned_agent,
+                    "wallet {} received {} after {:?}",
+                    index,
+                    new - old,
+                    step
+                );
+            }
+            if new < old {
+                prop_assert!(
+                    wallet == target.client,
+                    "wallet {} paid {} after {:?}",
+                    index,
+                    old - new,
+                    step
+                );
+            }
+        }
+        if !landed {
+            prop_assert_eq!(
+                &before.wallets,
+                &after.wallets,
+                "failed {:?} moved tokens",
+                step
+            );
+        }
+        before = after;
+    }
+    Ok(before.bounties.iter().map(|bounty| bounty.status).collect())
+}
+
+fn config() -> ProptestConfig {
+    // Every case boots a fresh bank, so keep the default run short
+    let cases = std::env::var("PROPTEST_CASES")
+        .ok()
+        .and_then(|cases| cases.parse().ok())
+        .unwrap_or(32);
+    ProptestConfig {
+        cases,
+        ..ProptestConfig::default()
+    }
+}
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn escrow_invariants_hold(scenario in scenario()) {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(run(scenario))?;
+    }
+}
+
+/// Fixed sequence through both payout paths, mixing in rejected attempts, so the
+/// invariant checks are known to see tokens leave a vault.
+#[test]
+fn happy_path_sequence_holds() {
+    let step = |action, bounty, actor, payee: usize| Step {
+        action,
+        bounty,
+        actor,
+        payee: Some(payee),
+    };
+    let scenario = Scenario {
+        budgets: [5_000, 7_000],
+        decline_policy: DeclinePolicy::Refund,
+        steps: vec![
+            step(Action::Claim, 0, 2, 2),
+            step(Action::Submit, 0, 2, 2),
+            step(Action::Approve, 0, 1, 2),
+            step(Action::Approve, 0, 0, 3),
+            step(Action::Approve, 0, 0, 2),
+            step(Action::Tip(10), 0, 0, 2),
+            step(Action::DeclineOffer, 1, 3, 1),
+            step(Action::Cancel, 1, 0, 0),
+            step(Action::DeclineOffer, 1, 2, 0),
+            step(Action::DeclineOffer, 1, 2, 1),
+        ],
+    };
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let statuses = runtime.block_on(run(scenario)).unwrap();
+    assert_eq!(statuses, [BountyStatus::Completed, BountyStatus::Cancelled]);
+}
+
+/// Fixed sequence through dispute resolutions, one split and one full refund.
+#[test]
+fn dispute_sequence_holds() {
+    let step = |action, bounty, actor, payee: usize| Step {
+        action,
+        bounty,
+        actor,
+        payee: Some(payee),
+    };
+    let scenario = Scenario {
+        budgets: [5_000, 7_000],
+        decline_policy: DeclinePolicy::Refund,
+        steps: vec![
+            step(Action::Claim, 0, 2, 2),
+            step(Action::Resolve(5_000), 0, 0, 2),
+            step(Action::Dispute, 0, 0, 0),
+            step(Action::Resolve(12_000), 0, 0, 2),
+            step(Action::Resolve(4_000), 0, 0, 3),
+            step(Action::Resolve(4_000), 0, 0, 2),
+            step(Action::AcceptOffer, 1, 3, 3),
+            step(Action::Dispute, 1, 3, 3),
+            step(Action::Resolve(0), 1, 0, 3),
+        ],
+    };
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let statuses = runtime.block_on(run(scenario)).unwrap();
+    assert_eq!(statuses, [BountyStatus::Completed, BountyStatus::Cancelled]);
+}
+
+/// The program's own transition table agrees with the one checked above.
+#[test]
+fn program_transitions_match() {
+    use BountyStatus::*;
+    let all = [Open, Claimed, Delivered, Completed, Disputed, Cancelled];
+    for from in all {
+        for to in all {
+            assert_eq!(
+                from == to || from.can_transition_to(to),
+                is_transition(from, to),
+                "{from:?} -> {to:?}"
+            );
+        }
+        assert_eq!(from.is_terminal(), !is_live(from), "{from:?}");
+    }
+}
+
+/// `Bounty::transition` itself refuses every step outside the table, with the
+/// bounty left untouched. Handlers check the status they expect first, so no
+/// instruction reaches this guard.
+#[test]
+fn transition_rejects_steps_outside_the_lifecycle() {
+    use BountyStatus::*;
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let (mut bounty, mut history) = runtime.block_on(async {
+        let mut env = Env::new().await;
+        let client = env.client().await;
+        let address = env.open_bounty(&client, 1_000).await;
+        let bounty: Bounty = env.fetch(&address).await;
+        let history: BountyHistory = env.fetch(&pda::history(&address)).await;
+        (bounty, history)
+    });
+    let actor = Pubkey::new_unique();
+    let total = history.total;
+    let all = [Open, Claimed, Delivered, Completed, Disputed, Cancelled];
+    for from in all {
+        for to in all.into_iter().filter(|to| !from.can_transition_to(*to)) {
+            bounty.status = from;
+            let error = bounty.transition(&mut history, to, actor).unwrap_err();
+            assert!(
+                matches!(
+                    &error,
+                    anchor_lang::error::Error::AnchorError(error)
+                        if error.error_code_number
+                            == u32::from(BountyError::InvalidStatusTransition)
+                ),
+                "{from:?} -> {to:?}: {error:?}"
+            );
+            assert_eq!(bounty.status, from);
+            assert_ne!(bounty.last_actor, actor);
+        }
+    }
+    assert_eq!(history.total, total);
+}
@@ -0,0 +1,318 @@
+//! Subcontracts funded by the agent or carved out of the parent payout.
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::Pubkey;
+use bounty_escrow::instruction as escrow_ix;
+use bounty_escrow::{BountyError, UNSKILLED};
+use clawedwork_client::accounts::{Agent, Bounty, BountyIndexPage, ClientState};
+use clawedwork_client::status::BountyStatus;
+use clawedwork_client::{escrow, pda};
+use clawedwork_tests::*;
+use solana_program_test::BanksClientError;
+use solana_sdk::signature::Signer;
+
+/// Registered agent that can also post bounties of their own.
+async fn contractor(env: &mut Env) -> User {
+    let user = env.user(STARTING_USDC).await;
+    env.register(&user, 0, None).await.unwrap();
+    let ix = escrow::init_client(&user.pubkey(), None);
+    env.send(&[ix], &[&user.keypair]).await.unwrap();
+    user
+}
+
+fn subcontract_args(
+    budget: u64,
+    deadline: i64,
+    fund_from_payout: bool,
+) -> escrow_ix::CreateSubcontract {
+    escrow_ix::CreateSubcontract {
+        metadata_uri: URI.to_string(),
+        metadata_hash: HASH,
+        budget,
+        deadline,
+        skills: 0,
+        require_skill_match: false,
+        fund_from_payout,
+    }
+}
+
+async fn try_subcontract(
+    env: &mut Env,
+    parent: &Pubkey,
+    agent: &User,
+    budget: u64,
+    fund_from_payout: bool,
+) -> Result<Pubkey, BanksClientError> {
+    let state: Bounty = env.fetch(parent).await;
+    let client_state: ClientState = env.fetch(&pda::client(&agent.pubkey())).await;
+    let deadline = env.now().await + 3600;
+    let index_pages = env.index_pages(0).await;
+    let ix = escrow::create_subcontract(
+        parent,
+        &state,
+        &agent.pubkey(),
+        client_state.bounty_count,
+        (!fund_from_payout).then_some(agent.token),
+        &index_pages,
+        subcontract_args(budget, deadline, fund_from_payout),
+    );
+    env.send(&[ix], &[&agent.keypair]).await?;
+    Ok(pda::bounty(&agent.pubkey(), client_state.bounty_count))
+}
+
+async fn fund(env: &mut Env, parent: &Pubkey, child: &Pubkey) -> TxResult {
+    let parent_state: Bounty = env.fetch(parent).await;
+    let child_state: Bounty = env.fetch(child).await;
+    let ix = escrow::fund_subcontract(parent, &parent_state, child, &child_state);
+    env.send(&[ix], &[]).await
+}
+
+#[tokio::test]
+async fn payout_funded_subcontract_is_paid_after_parent() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = contractor(&mut env).await;
+    let helper = env.agent(0).await;
+    let parent = env.open_bounty(&client, 1_000).await;
+    env.claim(&parent, &agent).await.unwrap();
+
+    assert_error(
+        try_subcontract(&mut env, &parent, &agent, 1_001, true).await,
+        BountyError::ExceedsParentBudget,
+    );
+    let child = try_subcontract(&mut env, &parent, &agent, 400, true)
+        .await
+        .unwrap();
+    assert_error(
+        try_subcontract(&mut env, &parent, &agent, 601, true).await,
+        BountyError::ExceedsParentBudget,
+    );
+    let state: Bounty = env.fetch(&parent).await;
+    assert_eq!(state.reserved_for_children, 400);
+    assert_eq!(state.child_count, 1);
+    let child_state: Bounty = env.fetch(&child).await;
+    assert_eq!(child_state.parent, parent);
+    assert_eq!(child_state.client, agent.pubkey());
+    assert!(child_state.awaiting_parent_funds);
+    assert_eq!(env.balance(&child_state.vault).await, 0);
+
+    env.claim(&child, &helper).await.unwrap();
+    env.submit(&child, &helper).await.unwrap();
+    assert_error(
+        env.approve(&child, &agent).await,
+        BountyError::SubcontractNotFunded,
+    );
+    assert_error(
+        fund(&mut env, &parent, &child).await,
+        BountyError::NotCompleted,
+    );
+
+    // The parent payout holds back the reserved share
+    env.submit(&parent, &agent).await.unwrap();
+    env.approve(&parent, &client).await.unwrap();
+    assert_eq!(env.balance(&agent.token).await, STARTING_USDC + 600);
+    assert_eq!(env.balance(&state.vault).await, 400);
+
+    fund(&mut env, &parent, &child).await.unwrap();
+    assert_error(
+        fund(&mut env, &parent, &child).await,
+        BountyError::SubcontractAlreadyFunded,
+    );
+    let state: Bounty = env.fetch(&parent).await;
+    assert_eq!(state.reserved_for_children, 0);
+    assert_eq!(env.balance(&state.vault).await, 0);
+
+    env.approve(&child, &agent).await.unwrap();
+    assert_eq!(env.balance(&helper.token).await, 400);
+    assert_eq!(env.balance(&agent.token).await, STARTING_USDC + 600);
+}
+
+#[tokio::test]
+async fn self_funded_subcontract_escrows_agent_tokens() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = contractor(&mut env).await;
+    let helper = env.agent(0).await;
+    let parent = env.open_bounty(&client, 1_000).await;
+
+    assert_error(
+        try_subcontract(&mut env, &parent, &agent, 100, false).await,
+        BountyError::NotAssignedAgent,
+    );
+    env.claim(&parent, &agent).await.unwrap();
+
+    let state: Bounty = env.fetch(&parent).await;
+    let client_state: ClientState = env.fetch(&pda::client(&agent.pubkey())).await;
+    let deadline = env.now().await + 3600;
+    let index_pages = env.index_pages(0).await;
+    let ix = escrow::create_subcontract(
+        &parent,
+        &state,
+        &agent.pubkey(),
+        client_state.bounty_count,
+        None,
+        &index_pages,
+        subcontract_args(100, deadline, false),
+    );
+    assert_error(
+        env.send(&[ix], &[&agent.keypair]).await,
+        BountyError::MissingTokenAccount,
+    );
+
+    // Self-funded children may exceed the parent budget
+    let child = try_subcontract(&mut env, &parent, &agent, 2_000, false)
+        .await
+        .unwrap();
+    let child_state: Bounty = env.fetch(&child).await;
+    assert!(!child_state.awaiting_parent_funds);
+    assert_eq!(env.balance(&child_state.vault).await, 2_000);
+    assert_eq!(env.balance(&agent.token).await, STARTING_USDC - 2_000);
+    let state: Bounty = env.fetch(&parent).await;
+    assert_eq!(state.reserved_for_children, 0);
+    assert_error(
+        fund(&mut env, &parent, &child).await,
+        BountyError::SubcontractAlreadyFunded,
+    );
+
+    env.claim(&child, &helper).await.unwrap();
+    env.submit(&child, &helper).await.unwrap();
+    env.approve(&child, &agent).await.unwrap();
+    assert_eq!(env.balance(&helper.token).await, 2_000);
+
+    env.submit(&parent, &agent).await.unwrap();
+    assert_error(
+        try_subcontract(&mut env, &parent, &agent, 100, false).await,
+        BountyError::NotClaimed,
+    );
+}
+
+#[tokio::test]
+async fn cancelled_subcontract_returns_reservation() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = contractor(&mut env).await;
+    let parent = env.open_bounty(&client, 1_000).await;
+    let other = env.open_bounty(&client, 1_000).await;
+    env.claim(&parent, &agent).await.unwrap();
+    env.claim(&other, &agent).await.unwrap();
+    let first = try_subcontract(&mut env, &parent, &agent, 300, true)
+        .await
+        .unwrap();
+    let second = try_subcontract(&mut env, &parent, &agent, 300, true)
+        .await
+        .unwrap();
+    let unrelated = try_subcontract(&mut env, &other, &agent, 300, true)
+        .await
+        .unwrap();
+
+    // Children only draw on their own parent
+    let parent_state: Bounty = env.fetch(&parent).await;
+    let child_state: Bounty = env.fetch(&unrelated).await;
+    let ix = escrow::fund_subcontract(&parent, &parent_state, &unrelated, &child_state);
+    assert_error(env.send(&[ix], &[]).await, BountyError::ParentMismatch);
+    let first_state: Bounty = env.fetch(&first).await;
+    let mut ix = escrow::cancel_bounty(&first, &first_state, &agent.token);
+    ix.accounts
+        .iter_mut()
+        .filter(|meta| meta.pubkey == parent)
+        .for_each(|meta| meta.pubkey = other);
+    assert_error(
+        env.send(&[ix], &[&agent.keypair]).await,
+        BountyError::ParentMismatch,
+    );
+
+    let ix = escrow::cancel_bounty(&first, &first_state, &agent.token);
+    env.send(&[ix], &[&agent.keypair]).await.unwrap();
+    let parent_state: Bounty = env.fetch(&parent).await;
+    assert_eq!(parent_state.reserved_for_children, 300);
+    assert_eq!(env.balance(&agent.token).await, STARTING_USDC);
+
+    // Once the parent pays out, the reservation must go to the child
+    env.submit(&parent, &agent).await.unwrap();
+    env.approve(&parent, &client).await.unwrap();
+    assert_eq!(env.balance(&agent.token).await, STARTING_USDC + 700);
+    let second_state: Bounty = env.fetch(&second).await;
+    let ix = escrow::cancel_bounty(&second, &second_state, &agent.token);
+    assert_error(
+        env.send(std::slice::from_ref(&ix), &[&agent.keypair]).await,
+        BountyError::FundSubcontractFirst,
+    );
+    fund(&mut env, &parent, &second).await.unwrap();
+    env.send(&[ix], &[&agent.keypair]).await.unwrap();
+    assert_eq!(env.balance(&agent.token).await, STARTING_USDC + 1_000);
+}
+
+#[tokio::test]
+async fn dispute_lost_by_agent_cancels_unfunded_subcontracts() {
+    let mut env = Env::new().await;
+    let client = env.client().await;
+    let agent = contractor(&mut env).await;
+    let helper = env.agent(0).await;
+    let parent = env.open_bounty(&client, 1_000).await;
+    env.claim(&parent, &agent).await.unwrap();
+    let open_child = try_subcontract(&mut env, &parent, &agent, 300, true)
+        .await
+        .unwrap();
+    let claimed_child = try_subcontract(&mut env, &parent, &agent, 200, true)
+        .await
+        .unwrap();
+    env.claim(&claimed_child, &helper).await.unwrap();
+    env.submit(&claimed_child, &helper).await.unwrap();
+
+    let ix = escrow::dispute_bounty(&parent, &client.pubkey());
+    env.send(&[ix], &[&client.keypair]).await.unwrap();
+    let state: Bounty = env.fetch(&parent).await;
+    let open_state: Bounty = env.fetch(&open_child).await;
+    let open_page = pda::index_page(BountyStatus::Open, UNSKILLED, open_state.index_pages[0]);
+    let claimed_state: Bounty = env.fetch(&claimed_child).await;
+    let admin = env.admin.pubkey();
+    let payout = env.payout_accounts(&parent).await;
+    let client_before = env.balance(&client.token).await;
+
+    // Every reserved child must be listed
+    let ix = escrow::resolve_dispute(
+        &parent,
+        &state,
+        &admin,
+        &client.token,
+        &payout,
+        &[(open_child, open_state.clone())],
+        0,
+    );
+    assert_error(
+        env.send_as_admin(&[ix]).await,
+        BountyError::UnfundedSubcontractsMismatch,
+    );
+
+    let ix = escrow::resolve_dispute(
+        &parent,
+        &state,
+        &admin,
+        &client.token,
+        &payout,
+        &[(open_child, open_state), (claimed_child, claimed_state)],
+        0,
+    );
+    env.send_as_admin(&[ix]).await.unwrap();
+
+    // The client gets the reservations back and the agent nothing
+    assert_eq!(env.balance(&client.token).await, client_before + 1_000);
+    assert_eq!(env.balance(&agent.token).await, STARTING_USDC);
+    let state: Bounty = env.fetch(&parent).await;
+    assert_eq!(state.status, BountyStatus::Cancelled);
+    assert_eq!(state.reserved_for_children, 0);
+    for child in [open_child, claimed_child] {
+        let child_state: Bounty = env.fetch(&child).await;
+        assert_eq!(child_state.status, BountyStatus::Cancelled);
+        assert!(!child_state.awaiting_parent_funds);
+        assert!(!env.exists(&child_state.vault).await);
+        assert_error(
+            fund(&mut env, &parent, &child).await,
+            ErrorCode::AccountNotInitialized,
+        );
+    }
+    let page: BountyIndexPage = env.fetch(&open_page).await;
+    assert!(!page.bounties.contains(&open_child));
+    let profile: Agent = env.fetch(&helper.agent()).await;
+    assert_eq!(profile.active_claims, 0);
+}